@@ -0,0 +1,394 @@
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote, quote_spanned};
+use syn::{
+    meta::ParseNestedMeta, parse_macro_input, spanned::Spanned, Data, DeriveInput, Error, Fields,
+    Ident, LitStr, Result,
+};
+
+/// Derives `ha_mqtt_discovery::HaDevice` for a struct representing a physical device.
+///
+/// Struct attributes, all optional: `#[ha(base_topic = "...", identifier = "...", name = "...", manufacturer = "...", model = "...", sw_version = "...", hw_version = "...")]`.
+///
+/// Field attributes:
+/// - `#[ha(identifier)]`: the field holds the device identifier, it takes precedence over the struct `identifier` attribute.
+/// - `#[ha(sensor, device_class = "...", unit = "...", state_class = "...", name = "...", icon = "...")]`: the field value is the state of a sensor.
+/// - `#[ha(binary_sensor, device_class = "...", name = "...", icon = "...")]`: the boolean field is the state of a binary sensor.
+/// - `#[ha(switch, device_class = "...", name = "...", icon = "...", on_command = "method")]`: the boolean field is the state of a switch, `method` is called with the new value when a command is received.
+///
+/// The `unit` must be a known unit of measurement symbol, eg. `°C`, an unknown unit fails the compilation.
+///
+/// Entity names can be translated with `HaDevice::localized_entities`, the field name being the translation key.
+#[proc_macro_derive(HaDevice, attributes(ha))]
+pub fn derive_ha_device(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+#[derive(Default)]
+struct DeviceAttributes {
+    base_topic: Option<LitStr>,
+    identifier: Option<LitStr>,
+    name: Option<LitStr>,
+    manufacturer: Option<LitStr>,
+    model: Option<LitStr>,
+    sw_version: Option<LitStr>,
+    hw_version: Option<LitStr>,
+}
+
+#[derive(PartialEq)]
+enum Kind {
+    Identifier,
+    Sensor,
+    BinarySensor,
+    Switch,
+}
+
+struct EntityField {
+    ident: Ident,
+    kind: Kind,
+    device_class: Option<LitStr>,
+    unit: Option<LitStr>,
+    state_class: Option<LitStr>,
+    name: Option<LitStr>,
+    icon: Option<LitStr>,
+    on_command: Option<LitStr>,
+}
+
+fn expand(input: DeriveInput) -> Result<TokenStream2> {
+    let device_attributes = parse_device_attributes(&input)?;
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new(
+            input.span(),
+            "HaDevice can only be derived for structs",
+        ));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(Error::new(
+            input.span(),
+            "HaDevice can only be derived for structs with named fields",
+        ));
+    };
+    let mut entity_fields = vec![];
+    for field in &fields.named {
+        for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("ha")) {
+            let ident = field.ident.clone().expect("named field");
+            entity_fields.push(parse_entity_field(ident, attr)?);
+        }
+    }
+
+    let identifier = match entity_fields
+        .iter()
+        .find(|field| field.kind == Kind::Identifier)
+    {
+        Some(field) => {
+            let ident = &field.ident;
+            quote!(self.#ident.to_string())
+        }
+        None => match &device_attributes.identifier {
+            Some(identifier) => quote!(#identifier.to_string()),
+            None => {
+                return Err(Error::new(
+                    input.span(),
+                    "a device identifier is required, either with a `#[ha(identifier)]` field or a `#[ha(identifier = \"...\")]` struct attribute",
+                ))
+            }
+        },
+    };
+    let base_topic = match &device_attributes.base_topic {
+        Some(base_topic) => quote!(format!("{}/{}", #base_topic, #identifier)),
+        None => identifier.clone(),
+    };
+
+    let device_information = device_information(&device_attributes, &identifier);
+    let entities = entity_fields
+        .iter()
+        .filter(|field| field.kind != Kind::Identifier)
+        .map(entity)
+        .collect::<Result<Vec<_>>>()?;
+    let states = entity_fields
+        .iter()
+        .filter(|field| field.kind != Kind::Identifier)
+        .map(state);
+    let commands = entity_fields
+        .iter()
+        .filter(|field| field.kind == Kind::Switch)
+        .map(command);
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::ha_mqtt_discovery::HaDevice for #ident #ty_generics #where_clause {
+            fn device_information(&self) -> ::ha_mqtt_discovery::mqtt::common::Device {
+                #device_information
+            }
+
+            fn entities(&self) -> ::std::vec::Vec<::ha_mqtt_discovery::Entity> {
+                self.localized_entities(&::ha_mqtt_discovery::locale::DefaultNames)
+            }
+
+            fn localized_entities(
+                &self,
+                names: &dyn ::ha_mqtt_discovery::locale::NameProvider,
+            ) -> ::std::vec::Vec<::ha_mqtt_discovery::Entity> {
+                let identifier = #identifier;
+                let base_topic = #base_topic;
+                let origin = ::ha_mqtt_discovery::mqtt::common::Origin::new(env!("CARGO_PKG_NAME"))
+                    .with_sw_version(env!("CARGO_PKG_VERSION"));
+                let device = self.device_information();
+                vec![#(#entities),*]
+            }
+
+            fn states(&self) -> ::std::vec::Vec<(::std::string::String, ::std::string::String)> {
+                let base_topic = #base_topic;
+                vec![#(#states),*]
+            }
+
+            fn handle_command(&mut self, topic: &str, payload: &str) -> bool {
+                let base_topic = #base_topic;
+                #(#commands)*
+                false
+            }
+        }
+    })
+}
+
+fn parse_device_attributes(input: &DeriveInput) -> Result<DeviceAttributes> {
+    let mut attributes = DeviceAttributes::default();
+    for attr in input.attrs.iter().filter(|attr| attr.path().is_ident("ha")) {
+        attr.parse_nested_meta(|meta| {
+            let value = Some(meta.value()?.parse::<LitStr>()?);
+            if meta.path.is_ident("base_topic") {
+                attributes.base_topic = value;
+            } else if meta.path.is_ident("identifier") {
+                attributes.identifier = value;
+            } else if meta.path.is_ident("name") {
+                attributes.name = value;
+            } else if meta.path.is_ident("manufacturer") {
+                attributes.manufacturer = value;
+            } else if meta.path.is_ident("model") {
+                attributes.model = value;
+            } else if meta.path.is_ident("sw_version") {
+                attributes.sw_version = value;
+            } else if meta.path.is_ident("hw_version") {
+                attributes.hw_version = value;
+            } else {
+                return Err(meta.error("unsupported device attribute"));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(attributes)
+}
+
+fn parse_entity_field(ident: Ident, attr: &syn::Attribute) -> Result<EntityField> {
+    let mut kind = None;
+    let mut field = EntityField {
+        ident,
+        kind: Kind::Identifier,
+        device_class: None,
+        unit: None,
+        state_class: None,
+        name: None,
+        icon: None,
+        on_command: None,
+    };
+    attr.parse_nested_meta(|meta| {
+        let parse_kind = |kind: &mut Option<Kind>, value: Kind, meta: &ParseNestedMeta| {
+            if kind.replace(value).is_some() {
+                return Err(meta.error("a field can only be mapped to a single entity"));
+            }
+            Ok(())
+        };
+        if meta.path.is_ident("identifier") {
+            parse_kind(&mut kind, Kind::Identifier, &meta)
+        } else if meta.path.is_ident("sensor") {
+            parse_kind(&mut kind, Kind::Sensor, &meta)
+        } else if meta.path.is_ident("binary_sensor") {
+            parse_kind(&mut kind, Kind::BinarySensor, &meta)
+        } else if meta.path.is_ident("switch") {
+            parse_kind(&mut kind, Kind::Switch, &meta)
+        } else {
+            let value = Some(meta.value()?.parse::<LitStr>()?);
+            if meta.path.is_ident("device_class") {
+                field.device_class = value;
+            } else if meta.path.is_ident("unit") {
+                field.unit = value;
+            } else if meta.path.is_ident("state_class") {
+                field.state_class = value;
+            } else if meta.path.is_ident("name") {
+                field.name = value;
+            } else if meta.path.is_ident("icon") {
+                field.icon = value;
+            } else if meta.path.is_ident("on_command") {
+                field.on_command = value;
+            } else {
+                return Err(meta.error("unsupported entity attribute"));
+            }
+            Ok(())
+        }
+    })?;
+    field.kind = kind.ok_or_else(|| {
+        Error::new(
+            attr.span(),
+            "expected one of `identifier`, `sensor`, `binary_sensor` or `switch`",
+        )
+    })?;
+    Ok(field)
+}
+
+fn device_information(attributes: &DeviceAttributes, identifier: &TokenStream2) -> TokenStream2 {
+    let optional_setters = [
+        ("name", &attributes.name),
+        ("manufacturer", &attributes.manufacturer),
+        ("model", &attributes.model),
+        ("sw_version", &attributes.sw_version),
+        ("hw_version", &attributes.hw_version),
+    ]
+    .into_iter()
+    .filter_map(|(setter, value)| {
+        let setter = Ident::new(setter, Span::call_site());
+        value.as_ref().map(|value| quote!(.#setter(#value)))
+    });
+    quote! {
+        ::ha_mqtt_discovery::mqtt::common::Device::default()
+            .add_identifier(#identifier)
+            #(#optional_setters)*
+    }
+}
+
+fn entity(field: &EntityField) -> Result<TokenStream2> {
+    let ident = &field.ident;
+    let field_name = ident.to_string();
+    let name = match &field.name {
+        Some(name) => quote!(#name),
+        None => {
+            let name = display_name(&field_name);
+            quote!(#name)
+        }
+    };
+    let (entity_type, device_class_type) = match field.kind {
+        Kind::Sensor => (quote!(sensor::Sensor), "SensorDeviceClass"),
+        Kind::BinarySensor => (
+            quote!(binary_sensor::BinarySensor),
+            "BinarySensorDeviceClass",
+        ),
+        Kind::Switch => (quote!(switch::Switch), "SwitchDeviceClass"),
+        Kind::Identifier => unreachable!("identifier fields aren't entities"),
+    };
+    let mut setters = vec![];
+    if let Some(device_class) = &field.device_class {
+        let device_class_type = Ident::new(device_class_type, Span::call_site());
+        let variant = pascal_case_ident(device_class);
+        setters.push(quote!(.device_class(::ha_mqtt_discovery::mqtt::device_classes::#device_class_type::#variant)));
+    }
+    if let Some(unit) = &field.unit {
+        if field.kind != Kind::Sensor {
+            return Err(Error::new(unit.span(), "only sensors have a unit"));
+        }
+        // the unit is checked by a constant, an unknown unit fails the compilation
+        let message = format!("`{}` should be a known unit of measurement", unit.value())
+            .replace('{', "{{")
+            .replace('}', "}}");
+        let check = quote_spanned!(unit.span()=>
+            const _: () = assert!(::ha_mqtt_discovery::mqtt::units::Unit::is_known_symbol(#unit), #message);
+        );
+        setters.push(quote!(.unit_of_measurement({
+            #check
+            ::ha_mqtt_discovery::mqtt::units::Unit::custom(#unit)
+        })));
+    }
+    if let Some(state_class) = &field.state_class {
+        if field.kind != Kind::Sensor {
+            return Err(Error::new(
+                state_class.span(),
+                "only sensors have a state class",
+            ));
+        }
+        let variant = pascal_case_ident(state_class);
+        setters.push(
+            quote!(.state_class(::ha_mqtt_discovery::mqtt::common::SensorStateClass::#variant)),
+        );
+    }
+    if let Some(icon) = &field.icon {
+        setters.push(quote!(.icon(#icon)));
+    }
+    if field.kind == Kind::Switch {
+        setters.push(quote!(.command_topic(format!("{}/{}/set", base_topic, #field_name))));
+    } else if let Some(on_command) = &field.on_command {
+        return Err(Error::new(
+            on_command.span(),
+            "only switches receive commands",
+        ));
+    }
+    Ok(quote! {
+        ::ha_mqtt_discovery::mqtt::#entity_type::builder()
+            .origin(origin.clone())
+            .device(device.clone())
+            .unique_id(format!("{}_{}", identifier, #field_name))
+            .name(names.name(#field_name).unwrap_or_else(|| #name.to_string()))
+            .state_topic(format!("{}/{}", base_topic, #field_name))
+            #(#setters)*
+            .build()
+            .into()
+    })
+}
+
+fn state(field: &EntityField) -> TokenStream2 {
+    let ident = &field.ident;
+    let field_name = ident.to_string();
+    let payload = match field.kind {
+        Kind::Sensor => quote!(self.#ident.to_string()),
+        _ => quote!(if self.#ident { "ON" } else { "OFF" }.to_string()),
+    };
+    quote!((format!("{}/{}", base_topic, #field_name), #payload))
+}
+
+fn command(field: &EntityField) -> TokenStream2 {
+    let ident = &field.ident;
+    let field_name = ident.to_string();
+    let hook = field.on_command.as_ref().map(|on_command| {
+        let method = format_ident!("{}", on_command.value(), span = on_command.span());
+        quote!(self.#method(self.#ident);)
+    });
+    quote! {
+        if topic == format!("{}/{}/set", base_topic, #field_name) {
+            match payload {
+                "ON" => self.#ident = true,
+                "OFF" => self.#ident = false,
+                _ => return true,
+            }
+            #hook
+            return true;
+        }
+    }
+}
+
+fn display_name(field_name: &str) -> String {
+    let name = field_name.replace('_', " ");
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => name,
+    }
+}
+
+fn pascal_case_ident(value: &LitStr) -> Ident {
+    let pascal_case: String = value
+        .value()
+        .split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first
+                    .to_uppercase()
+                    .chain(chars.flat_map(char::to_lowercase))
+                    .collect(),
+                None => String::new(),
+            }
+        })
+        .collect();
+    Ident::new(&pascal_case, value.span())
+}
@@ -32,6 +32,7 @@ export const abbreviations = {
 'cmd_on_tpl':          'command_on_template',
 'cmd_t':               'command_topic',
 'cmd_tpl':             'command_template',
+'cmps':                'components',
 'cod_arm_req':         'code_arm_required',
 'cod_dis_req':         'code_disarm_required',
 'cod_trig_req':        'code_trigger_required',
@@ -109,6 +110,7 @@ export const abbreviations = {
 'osc_cmd_tpl':         'oscillation_command_template',
 'osc_stat_t':          'oscillation_state_topic',
 'osc_val_tpl':         'oscillation_value_template',
+'p':                   'platform',
 'pct_cmd_t':           'percentage_command_topic',
 'pct_cmd_tpl':         'percentage_command_template',
 'pct_stat_t':          'percentage_state_topic',
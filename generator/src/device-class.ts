@@ -3,11 +3,15 @@ import { readFileSync } from "fs";
 type EnumValue = {
     value: string,
     description: string,
+    // the generic device class, serialized as null
+    generic: boolean,
 }
 
 type DeviceClassesEnumModel = {
     name: string,
-    values: EnumValue[]
+    values: EnumValue[],
+    // whether the enum has a generic device class
+    generic: boolean,
 }
 
 export function extractDeviceClassesEnums(name: string, docFile: string): DeviceClassesEnumModel {
@@ -20,14 +24,17 @@ export function extractDeviceClassesEnums(name: string, docFile: string): Device
   const enumValues = docEnumValuesParagraph.split("\n").map((line) => {
     const match = /- (?<name>.*): (?<description>.*)/gm.exec(line);
     if (match?.groups) {
+      const value = match.groups.name.replace(/[^\w]/g,'');
       return {
-        value: match.groups.name.replace(/[^\w]/g,''),
+        value: value,
         description: match.groups.description,
+        generic: value === "None",
       }
     } else{return null;}
   }).filter((value) => !!value);
   return {
     name: name,
     values: enumValues,
+    generic: enumValues.some((value) => value.generic),
   }
 }
@@ -16,7 +16,7 @@ const IGNORED_ATTRS = [
 
 type FieldAttributes = {
   description: string;
-  required: boolean;
+  required: boolean | "exclusive";
   type:
     | "template"
     | "string"
@@ -29,8 +29,9 @@ type FieldAttributes = {
     | ["string", "list"];
   rustType?: string;
   enumValues?: {[name: string]: string}
-  import?: string;
+  import?: string | string[];
   useInto?: boolean;
+  useDecimal?: boolean;
   iterable?: boolean;
   rustSafeName?: string;
 
@@ -42,6 +43,7 @@ type MqttEntity = {
   entityDoc: string;
   imports: Set<string>;
   properties: object;
+  hasRequiredProperties: boolean;
 };
 
 export function generateMqttEntityModel(
@@ -57,15 +59,17 @@ export function generateMqttEntityModel(
     const modelDescriptor = YAML.parse(modelDescriptorYaml!![1]);
     const entries = Object.entries(modelDescriptor)
       .filter(([name, attrs]) => !IGNORED_ATTRS.includes(name));
+    const entityName = name;
     for (const [name, attrs] of entries) {
-      appendRustType(name, attrs as FieldAttributes);
+      appendRustType(entityName, name, attrs as FieldAttributes);
     }
 
     return {
       entityName: name,
       entityDoc: docContent,
-      imports: new Set(entries.map(([name, attrs]) => attrs.import).filter(importInstruction => !!importInstruction)),
+      imports: new Set(entries.flatMap(([name, attrs]) => attrs.import ?? [])),
       properties: Object.fromEntries(entries),
+      hasRequiredProperties: entries.some(([name, attrs]) => (attrs as FieldAttributes).required),
     };
   } catch (e) {
     console.error(modelDescriptorYaml!![1]);
@@ -73,7 +77,11 @@ export function generateMqttEntityModel(
   }
 }
 
-function appendRustType(name: string, attrs: FieldAttributes) {
+function appendRustType(entityName: string, name: string, attrs: FieldAttributes) {
+  // exclusive attributes are mutually exclusive alternatives, none of them is required on its own
+  if (attrs.required === "exclusive") {
+    attrs.required = false;
+  }
   if (name === "type") {
     attrs.rustSafeName = `r#${name}`;
   } else {
@@ -88,7 +96,8 @@ function appendRustType(name: string, attrs: FieldAttributes) {
       break;
     case "float":
       attrs.rustType = "Decimal";
-      attrs.import = "pub use rust_decimal::Decimal"
+      attrs.useDecimal = true;
+      attrs.import = ["pub use rust_decimal::Decimal", "use crate::decimal::{DecimalError, IntoDecimal, TryIntoDecimal}"];
       break;
     case "integer":
       attrs.rustType = "i32";
@@ -110,10 +119,13 @@ function appendRustType(name: string, attrs: FieldAttributes) {
         attrs.useInto = true;
       }
   }
+  if (name === "topic" || name.endsWith("_topic")) {
+    attrs.rustType = "Topic";
+  }
   switch (name) {
     case "device_class":
       const entityName = new RegExp(
-        "/integrations/(?<name>[^/]*)/#device-class"
+        "/integrations/(?<name>[^/]*)/(#device-class(es)?)?\\)"
       ).exec(attrs.description)?.groups.name;
       if (entityName) {
         const deviceClassType = `${toPascalCase(entityName)}DeviceClass`;
@@ -137,5 +149,19 @@ function appendRustType(name: string, attrs: FieldAttributes) {
       attrs.rustType = "TemperatureUnit";
       attrs.import = `use super::common::TemperatureUnit`;
       break;
+    case "mode":
+      if (entityName === "number") {
+        attrs.rustType = "NumberMode";
+        attrs.useInto = false;
+        attrs.import = `use super::common::NumberMode`;
+      }
+      break;
+    case "supported_features":
+      if (entityName === "alarm_control_panel") {
+        attrs.rustType = "AlarmFeature";
+        attrs.useInto = false;
+        attrs.import = `use super::common::AlarmFeature`;
+      }
+      break;
   }
 }
@@ -1,6 +1,6 @@
 import { readFileSync, writeFileSync, readdirSync } from "fs";
 import Handlebars from "handlebars";
-import { allAbbreviations } from "./abbretiations";
+import { abbreviations, allAbbreviations, device, origin } from "./abbretiations";
 import { extractDeviceClassesEnums } from "./device-class";
 import { generateMqttEntityModel } from "./entity";
 import { toPascalCase } from "./strings";
@@ -23,6 +23,7 @@ const ENTITIES = [
   "lawn_mower",
   //"light",
   "lock",
+  "notify",
   "number",
   "scene",
   "select",
@@ -44,6 +45,16 @@ Handlebars.registerHelper("abbreviation", (name: string) => {
   return new Handlebars.SafeString(abbreviation ? abbreviation[0] : name);
 });
 
+// the long names are accepted when parsing configurations, eg. the `configuration.yaml` entries
+Handlebars.registerHelper("alias", (name: string) => {
+  const abbreviation = Object.entries(allAbbreviations).find(
+    ([shortName, fullName]) => name === fullName
+  );
+  return new Handlebars.SafeString(
+    abbreviation && abbreviation[0] !== name ? `, alias = "${name}"` : ""
+  );
+});
+
 Handlebars.registerHelper("comment", (text: string) => {
   return text?.replaceAll("\n", "\n/// ");
 });
@@ -79,6 +90,17 @@ const template = readFileSync(
 const output = Handlebars.compile(template)(enumsModels);
 writeFileSync(`${BASEDIR}/src/mqtt/device_classes.rs`, output);
 
+// generate abbreviations.rs
+const templateAbbreviations = readFileSync(
+  `${BASEDIR}/generator/src/rust_abbreviations.mustache`
+).toString();
+const outputAbbreviations = Handlebars.compile(templateAbbreviations)({
+  abbreviations,
+  device,
+  origin,
+});
+writeFileSync(`${BASEDIR}/src/mqtt/abbreviations.rs`, outputAbbreviations);
+
 // generate mod.rs
 const templateMod = readFileSync(
   `${BASEDIR}/generator/src/rust_mod.mustache`
@@ -0,0 +1,62 @@
+use serde_derive::{Deserialize, Serialize};
+
+use crate::variant::variant_name;
+
+/// The states an alarm control panel can report on its `state_topic`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlarmState {
+    Disarmed,
+    ArmedHome,
+    ArmedAway,
+    ArmedNight,
+    ArmedVacation,
+    ArmedCustomBypass,
+    Pending,
+    Triggered,
+    Arming,
+    Disarming,
+}
+
+impl AlarmState {
+    /// The state published on the `state_topic`, eg. `armed_home`.
+    pub fn as_str(&self) -> &'static str {
+        variant_name(self)
+    }
+}
+
+#[cfg(all(test, feature = "client"))]
+mod tests {
+    use serde_json::json;
+
+    use crate::{
+        mqtt::{alarm_control_panel::AlarmControlPanel, common::AlarmFeature},
+        testing::MockClient,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn can_publish_alarm_states() {
+        let client = MockClient::default();
+        let ha = client.ha();
+        let panel = AlarmControlPanel::new("home/alarm/set", "home/alarm")
+            .supported_features(vec![AlarmFeature::ArmHome, AlarmFeature::ArmCustomBypass]);
+
+        ha.publish_alarm_state(&panel, AlarmState::ArmedCustomBypass)
+            .await
+            .unwrap();
+
+        client.assert_published("home/alarm", "armed_custom_bypass");
+        assert_eq!(
+            serde_json::to_value(&panel).unwrap()["sup_feat"],
+            json!(["arm_home", "arm_custom_bypass"])
+        );
+        assert!(serde_json::from_value::<AlarmControlPanel>(json!({
+            "cmd_t": "home/alarm/set",
+            "stat_t": "home/alarm",
+            "sup_feat": ["arm_homee"]
+        }))
+        .is_err());
+    }
+}
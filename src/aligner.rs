@@ -0,0 +1,236 @@
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{anyhow, Result};
+use rumqttc::v5::AsyncClient;
+
+use crate::{client::MqttPublisher, Entity, HomeAssistantMqtt};
+
+/// The longest sleep between two checks of the wall clock, so a tick missed during a suspend is caught up quickly.
+const MAX_SLEEP: Duration = Duration::from_secs(1);
+
+/// How the values recorded between two ticks are combined into the published state.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Aggregation {
+    /// The last recorded value.
+    Last,
+    /// The average of the recorded values.
+    Mean,
+    /// The lowest recorded value.
+    Min,
+    /// The highest recorded value.
+    Max,
+    /// The sum of the recorded values, eg. for energy counted since the previous tick.
+    Sum,
+}
+
+#[derive(Clone, Debug)]
+struct Accumulator {
+    aggregation: Aggregation,
+    values: Vec<f64>,
+}
+
+impl Accumulator {
+    fn aggregate(&self) -> Option<f64> {
+        let values = &self.values;
+        let last = *values.last()?;
+        Some(match self.aggregation {
+            Aggregation::Last => last,
+            Aggregation::Mean => values.iter().sum::<f64>() / values.len() as f64,
+            Aggregation::Min => values.iter().copied().fold(f64::INFINITY, f64::min),
+            Aggregation::Max => values.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+            Aggregation::Sum => values.iter().sum(),
+        })
+    }
+}
+
+/// Publishes the measurements of sensors on wall clock aligned intervals, eg. at the top of every minute, for
+/// cleaner Home Assistant long-term statistics.
+///
+/// The values are recorded as they are measured and published at each tick, clones share the same values:
+///
+/// ```no_run
+/// # #[cfg(feature = "entity-sensor")]
+/// # async fn example(home_assistant: ha_mqtt_discovery::HomeAssistantMqtt) -> anyhow::Result<()> {
+/// # use std::time::Duration;
+/// # use ha_mqtt_discovery::{
+/// #     aligner::{Aggregation, StatisticsAligner},
+/// #     mqtt::sensor::Sensor,
+/// #     Entity,
+/// # };
+/// # let power_sensor = Entity::from(Sensor::new("home/power"));
+/// let aligner = StatisticsAligner::new(home_assistant, Duration::from_secs(60));
+/// aligner.register(&power_sensor, Aggregation::Mean)?;
+/// tokio::spawn({
+///     let aligner = aligner.clone();
+///     async move { aligner.run().await }
+/// });
+/// aligner.record("home/power", 1250.0);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct StatisticsAligner<C: MqttPublisher = AsyncClient> {
+    home_assistant: HomeAssistantMqtt<C>,
+    interval: Duration,
+    sensors: Arc<Mutex<BTreeMap<String, Accumulator>>>,
+}
+
+impl<C: MqttPublisher> StatisticsAligner<C> {
+    /// An aligner publishing at every multiple of the interval since the Unix epoch, the interval must not be zero.
+    pub fn new(home_assistant: HomeAssistantMqtt<C>, interval: Duration) -> Self {
+        Self {
+            home_assistant,
+            interval,
+            sensors: Arc::new(Mutex::new(BTreeMap::new())),
+        }
+    }
+
+    /// Publishes the values recorded for the entity on its state topic.
+    pub fn register(&self, entity: &Entity, aggregation: Aggregation) -> Result<()> {
+        let topic = entity
+            .resolved_state_topic()
+            .ok_or(anyhow!("{} entity has no state topic", entity.platform()))?;
+        self.register_topic(topic, aggregation);
+        Ok(())
+    }
+
+    /// Publishes the values recorded for the state topic.
+    pub fn register_topic<S: Into<String>>(&self, topic: S, aggregation: Aggregation) {
+        self.sensors.lock().unwrap().insert(
+            topic.into(),
+            Accumulator {
+                aggregation,
+                values: vec![],
+            },
+        );
+    }
+
+    /// Records a measurement to publish at the next tick.
+    ///
+    /// Returns `false` when the topic isn't registered.
+    pub fn record(&self, topic: &str, value: f64) -> bool {
+        match self.sensors.lock().unwrap().get_mut(topic) {
+            Some(accumulator) => {
+                accumulator.values.push(value);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Publishes the values recorded since the previous tick, sensors without new values are left untouched.
+    ///
+    /// The values are cleared once published: when a publication fails, the values of the sensors not published
+    /// yet are kept and aggregated with the values of the next tick.
+    ///
+    /// Returns the number of published states.
+    pub async fn flush(&self) -> Result<usize> {
+        let states: Vec<(String, usize, f64)> = self
+            .sensors
+            .lock()
+            .unwrap()
+            .iter()
+            .filter_map(|(topic, accumulator)| {
+                Some((
+                    topic.clone(),
+                    accumulator.values.len(),
+                    accumulator.aggregate()?,
+                ))
+            })
+            .collect();
+        for (topic, count, value) in &states {
+            self.home_assistant
+                .publish_state(topic, value.to_string())
+                .await?;
+            // the values recorded during the publication are kept for the next tick
+            if let Some(accumulator) = self.sensors.lock().unwrap().get_mut(topic) {
+                let count = (*count).min(accumulator.values.len());
+                accumulator.values.drain(..count);
+            }
+        }
+        Ok(states.len())
+    }
+
+    /// Publishes the recorded values at every tick, until a publication fails.
+    ///
+    /// The wall clock is checked at least every second: after a suspend or a long reconnection the missed ticks
+    /// are caught up with a single publication and the next ticks stay aligned.
+    pub async fn run(&self) -> Result<()> {
+        let mut tick = next_aligned_tick(SystemTime::now(), self.interval);
+        loop {
+            let now = SystemTime::now();
+            match tick.duration_since(now) {
+                Ok(remaining) if !remaining.is_zero() => {
+                    tokio::time::sleep(remaining.min(MAX_SLEEP)).await;
+                }
+                _ => {
+                    self.flush().await?;
+                    tick = next_aligned_tick(now, self.interval);
+                }
+            }
+        }
+    }
+}
+
+/// The first multiple of the interval since the Unix epoch strictly after the given time.
+pub fn next_aligned_tick(now: SystemTime, interval: Duration) -> SystemTime {
+    let interval = interval.as_millis().max(1);
+    let elapsed = now
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+    let next = (elapsed / interval + 1) * interval;
+    UNIX_EPOCH + Duration::from_millis(next as u64)
+}
+
+#[cfg(all(test, feature = "entity-sensor"))]
+mod tests {
+    use crate::{mqtt::sensor::Sensor, testing::MockClient};
+
+    use super::*;
+
+    #[test]
+    fn can_align_ticks_on_the_wall_clock() {
+        let at = |seconds: u64| UNIX_EPOCH + Duration::from_secs(seconds);
+        let minute = Duration::from_secs(60);
+        assert_eq!(next_aligned_tick(at(3_601), minute), at(3_660));
+        assert_eq!(next_aligned_tick(at(3_660), minute), at(3_720));
+        assert_eq!(
+            next_aligned_tick(at(3_601), Duration::from_secs(3_600)),
+            at(7_200)
+        );
+    }
+
+    #[tokio::test]
+    async fn can_publish_aggregated_measurements() {
+        let client = MockClient::default();
+        let aligner = StatisticsAligner::new(client.ha(), Duration::from_secs(60));
+        aligner
+            .register(&Sensor::new("home/power").into(), Aggregation::Mean)
+            .unwrap();
+        aligner.register_topic("home/energy", Aggregation::Sum);
+
+        assert!(aligner.record("home/power", 100.0));
+        assert!(aligner.record("home/power", 200.0));
+        assert!(aligner.record("home/energy", 0.5));
+        assert!(aligner.record("home/energy", 0.25));
+        assert!(!aligner.record("home/unknown", 1.0));
+
+        assert_eq!(aligner.flush().await.unwrap(), 2);
+        client.assert_published("home/power", "150");
+        client.assert_published("home/energy", "0.75");
+        assert_eq!(aligner.flush().await.unwrap(), 0);
+
+        client.set_failing(true);
+        assert!(aligner.record("home/power", 300.0));
+        assert!(aligner.flush().await.is_err());
+        client.set_failing(false);
+        assert!(aligner.record("home/power", 500.0));
+        assert_eq!(aligner.flush().await.unwrap(), 1);
+        client.assert_published("home/power", "400");
+    }
+}
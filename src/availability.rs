@@ -0,0 +1,260 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use futures::{Stream, StreamExt};
+use rumqttc::v5::{
+    mqttbytes::{v5::LastWill, QoS},
+    AsyncClient,
+};
+use tokio::sync::watch;
+
+use crate::{
+    client::{publish_traced, PublishOptions},
+    mqtt::common::{Availability, AvailabilityCheck},
+};
+
+/// The payloads representing the available and unavailable states, `online` and `offline` by default.
+///
+/// Configure them once and share them between the `AvailabilityManager`, the last will and the entities
+/// (see `HomeAssistantMqtt::with_availability_payloads`).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AvailabilityPayloads {
+    pub online: String,
+    pub offline: String,
+}
+
+impl Default for AvailabilityPayloads {
+    fn default() -> Self {
+        Self {
+            online: "online".to_string(),
+            offline: "offline".to_string(),
+        }
+    }
+}
+
+impl AvailabilityPayloads {
+    pub fn new<S: Into<String>>(online: S, offline: S) -> Self {
+        Self {
+            online: online.into(),
+            offline: offline.into(),
+        }
+    }
+
+    /// The payload of the given state.
+    pub fn payload(&self, available: bool) -> &str {
+        if available {
+            &self.online
+        } else {
+            &self.offline
+        }
+    }
+
+    /// An availability check of the topic, the payloads are only set when they differ from the Home Assistant defaults.
+    pub fn check<S: Into<String>>(&self, topic: S) -> AvailabilityCheck {
+        self.apply(AvailabilityCheck::topic(topic))
+    }
+
+    /// An availability of the entity following the topic.
+    pub fn availability<S: Into<String>>(&self, topic: S) -> Availability {
+        Availability::single(self.check(topic))
+    }
+
+    /// Sets the payloads the check doesn't define yet, when they differ from the Home Assistant defaults.
+    pub fn apply(&self, mut check: AvailabilityCheck) -> AvailabilityCheck {
+        let defaults = AvailabilityPayloads::default();
+        if check.payload_available.is_none() && self.online != defaults.online {
+            check.payload_available = Some(self.online.clone());
+        }
+        if check.payload_not_available.is_none() && self.offline != defaults.offline {
+            check.payload_not_available = Some(self.offline.clone());
+        }
+        check
+    }
+
+    /// The retained last will publishing the unavailable payload on the topic.
+    pub fn last_will<S: Into<String>>(&self, topic: S) -> LastWill {
+        LastWill::new(
+            topic,
+            self.offline.as_bytes().to_vec(),
+            QoS::AtLeastOnce,
+            true,
+            None,
+        )
+    }
+}
+
+/// Publishes the availability of a bridge on a shared availability topic, driven by an external health signal.
+///
+/// The same topic should be used as the client last will (see `AvailabilityManager::last_will`) so the
+/// availability also reflects the MQTT connectivity.
+#[derive(Clone)]
+pub struct AvailabilityManager {
+    client: AsyncClient,
+    topic: String,
+    payloads: AvailabilityPayloads,
+}
+
+impl AvailabilityManager {
+    /// An availability manager publishing the default `online` and `offline` payloads on the given topic.
+    pub fn new<S: Into<String>>(client: AsyncClient, topic: S) -> Self {
+        Self {
+            client,
+            topic: topic.into(),
+            payloads: AvailabilityPayloads::default(),
+        }
+    }
+
+    /// The payloads that represent the available and unavailable states.
+    pub fn with_payloads<S: Into<String>>(
+        self,
+        payload_available: S,
+        payload_not_available: S,
+    ) -> Self {
+        self.with_availability_payloads(AvailabilityPayloads::new(
+            payload_available,
+            payload_not_available,
+        ))
+    }
+
+    /// The payloads that represent the available and unavailable states.
+    pub fn with_availability_payloads(mut self, payloads: AvailabilityPayloads) -> Self {
+        self.payloads = payloads;
+        self
+    }
+
+    /// The payloads that represent the available and unavailable states.
+    pub fn payloads(&self) -> &AvailabilityPayloads {
+        &self.payloads
+    }
+
+    /// The availability topic.
+    pub fn topic(&self) -> &str {
+        &self.topic
+    }
+
+    /// The availability check entities should use to follow this availability topic.
+    pub fn availability_check(&self) -> AvailabilityCheck {
+        self.payloads.check(&self.topic)
+    }
+
+    /// The last will to register on the MQTT connection so the broker marks the bridge unavailable when the connection is lost.
+    pub fn last_will(&self) -> LastWill {
+        self.payloads.last_will(&self.topic)
+    }
+
+    /// Publishes the availability state.
+    pub async fn set_available(&self, available: bool) -> Result<()> {
+        let payload = self.payloads.payload(available).as_bytes().to_vec();
+        Ok(publish_traced(
+            &self.client,
+            self.topic.clone(),
+            QoS::AtLeastOnce,
+            true,
+            payload,
+            PublishOptions::default(),
+        )
+        .await?)
+    }
+
+    /// Publishes the availability each time the health signal changes, until the signal ends.
+    pub async fn follow<S: Stream<Item = bool>>(&self, signal: S) -> Result<()> {
+        let mut signal = std::pin::pin!(signal);
+        let mut last_state = None;
+        while let Some(available) = signal.next().await {
+            if last_state != Some(available) {
+                self.set_available(available).await?;
+                last_state = Some(available);
+            }
+        }
+        Ok(())
+    }
+
+    /// Publishes the availability each time the watched health value changes, until the sender is dropped.
+    ///
+    /// The current value is published immediately.
+    pub async fn follow_watch(&self, mut signal: watch::Receiver<bool>) -> Result<()> {
+        loop {
+            let available = *signal.borrow_and_update();
+            self.set_available(available).await?;
+            if signal.changed().await.is_err() {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Calls the health check at a regular interval and publishes the availability each time the result changes.
+    ///
+    /// This can be tied to a watchdog, eg. `sd_notify` WATCHDOG=1 notifications sent when the check succeeds.
+    pub async fn check_periodically<F>(&self, period: Duration, mut check: F) -> Result<()>
+    where
+        F: FnMut() -> bool,
+    {
+        let mut interval = tokio::time::interval(period);
+        let mut last_state = None;
+        loop {
+            interval.tick().await;
+            let available = check();
+            if last_state != Some(available) {
+                self.set_available(available).await?;
+                last_state = Some(available);
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "entity-sensor"))]
+mod tests {
+    use rumqttc::v5::MqttOptions;
+    use serde_json::Value;
+
+    use crate::{mqtt::sensor::Sensor, testing::MockClient, Entity, HomeAssistantMqtt};
+
+    use super::*;
+
+    #[test]
+    fn can_build_entity_availability_checks() {
+        let (client, _) = AsyncClient::new(MqttOptions::new("test", "localhost", 1883), 10);
+        let availability = AvailabilityManager::new(client, "bridge/availability");
+
+        assert_eq!(
+            availability.availability_check(),
+            AvailabilityCheck::topic("bridge/availability")
+        );
+        assert_eq!(
+            availability.with_payloads("1", "0").availability_check(),
+            AvailabilityCheck::topic("bridge/availability")
+                .payload_available("1")
+                .payload_not_available("0")
+        );
+    }
+
+    #[tokio::test]
+    async fn can_share_availability_payloads() {
+        let client = MockClient::default();
+        let payloads = AvailabilityPayloads::new("1", "0");
+        let registry = HomeAssistantMqtt::new(client.clone(), "homeassistant")
+            .with_availability_payloads(payloads.clone());
+
+        registry
+            .publish_entity(Entity::from(
+                Sensor::new("home/temperature")
+                    .unique_id("temperature")
+                    .availability(Availability::single_topic("home/availability")),
+            ))
+            .await
+            .unwrap();
+
+        let message = client
+            .last_published("homeassistant/sensor/temperature/config")
+            .unwrap();
+        let configuration: Value = serde_json::from_slice(&message.payload).unwrap();
+        assert_eq!(
+            configuration["avty"],
+            serde_json::json!([{"t": "home/availability", "pl_avail": "1", "pl_not_avail": "0"}])
+        );
+        assert_eq!(
+            payloads.last_will("home/availability").message,
+            "0".as_bytes()
+        );
+    }
+}
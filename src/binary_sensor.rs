@@ -0,0 +1,38 @@
+use crate::mqtt::binary_sensor::BinarySensor;
+
+impl BinarySensor {
+    /// The payload of the state, according to the `payload_on` and `payload_off` payloads of the binary sensor.
+    pub fn state_payload(&self, on: bool) -> &str {
+        if on {
+            self.payload_on.as_deref().unwrap_or("ON")
+        } else {
+            self.payload_off.as_deref().unwrap_or("OFF")
+        }
+    }
+}
+
+#[cfg(all(test, feature = "client"))]
+mod tests {
+    use crate::{testing::MockClient, HomeAssistantMqtt};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn can_publish_binary_sensor_states() {
+        let client = MockClient::default();
+        let ha = HomeAssistantMqtt::new(client.clone(), "homeassistant");
+        let door = BinarySensor::new("~/contact")
+            .topic_prefix("home/door")
+            .payload_on("open");
+
+        ha.publish_binary_sensor_state(&door, true).await.unwrap();
+        assert_eq!(
+            client
+                .last_published("home/door/contact")
+                .unwrap()
+                .payload_str(),
+            "open"
+        );
+        assert_eq!(door.state_payload(false), "OFF");
+    }
+}
@@ -0,0 +1,151 @@
+use anyhow::Result;
+use futures::executor::block_on;
+use serde::Serialize;
+
+use crate::{
+    availability::AvailabilityPayloads,
+    client::{MqttClient, MqttPublisher},
+    device::HaDevice,
+    discovery::DiscoveryOptions,
+    error::PublishError,
+    Entity,
+};
+
+/// A blocking `HomeAssistantMqtt`, for the applications running without an async runtime.
+///
+/// It publishes through a sync `rumqttc::v5::Client` (default) or `rumqttc::Client`, whose `Connection` must be
+/// iterated on another thread for the messages to be sent.
+///
+/// ```no_run
+/// # fn main() -> anyhow::Result<()> {
+/// # #[cfg(feature = "entity-sensor")] {
+/// # use ha_mqtt_discovery::{blocking, mqtt::sensor::Sensor, v5::MqttOptions};
+/// let (client, mut connection) = rumqttc::v5::Client::new(MqttOptions::new("garage", "localhost", 1883), 10);
+/// std::thread::spawn(move || for _ in connection.iter() {});
+/// let home_assistant = blocking::HomeAssistantMqtt::new(client, "homeassistant");
+/// home_assistant.publish_entity(Sensor::new("garage/temperature").unique_id("garage_temperature").into())?;
+/// home_assistant.publish_state("garage/temperature", "21.5")?;
+/// # }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct HomeAssistantMqtt<C: MqttPublisher = rumqttc::v5::Client> {
+    inner: crate::HomeAssistantMqtt<C>,
+}
+
+impl<C: MqttPublisher> HomeAssistantMqtt<C> {
+    pub fn new<S: Into<String>>(client: C, discovery_prefix: S) -> Self {
+        Self::from(crate::HomeAssistantMqtt::new(client, discovery_prefix))
+    }
+
+    /// ID of the node providing the entities, inserted as the `<node_id>` level of the discovery topics.
+    pub fn with_node_id<S: Into<String>>(self, node_id: S) -> Self {
+        Self::from(self.inner.with_node_id(node_id))
+    }
+
+    /// The availability payloads of the bridge, set on the availability checks of the published entities which don't
+    /// define their own payloads.
+    pub fn with_availability_payloads(self, payloads: AvailabilityPayloads) -> Self {
+        Self::from(self.inner.with_availability_payloads(payloads))
+    }
+
+    /// The QoS, retain flag and expiry of the discovery configuration messages.
+    pub fn with_discovery_options(self, discovery_options: DiscoveryOptions) -> Self {
+        Self::from(self.inner.with_discovery_options(discovery_options))
+    }
+
+    /// Adds a MQTT v5 user property to every discovery configuration and state published, see
+    /// `HomeAssistantMqtt::with_user_property`.
+    pub fn with_user_property<N: Into<String>, V: Into<String>>(self, name: N, value: V) -> Self {
+        Self::from(self.inner.with_user_property(name, value))
+    }
+
+    /// The async publisher, for the features not exposed by the blocking API.
+    pub fn inner(&self) -> &crate::HomeAssistantMqtt<C> {
+        &self.inner
+    }
+
+    /// Publishes the discovery configuration of the entity, see `HomeAssistantMqtt::publish_entity`.
+    pub fn publish_entity(&self, entity: Entity) -> Result<(), PublishError> {
+        block_on(self.inner.publish_entity(entity))
+    }
+
+    /// Publishes the discovery configuration of every entity of the device.
+    pub fn publish_device<D: HaDevice>(&self, device: &D) -> Result<()> {
+        block_on(self.inner.publish_device_entities(device))
+    }
+
+    /// Publishes the current state of every entity of the device.
+    pub fn publish_device_states<D: HaDevice>(&self, device: &D) -> Result<()> {
+        block_on(self.inner.publish_device_states(device))
+    }
+
+    /// Removes the discovery configuration published on the topic, Home Assistant then deletes the entity.
+    pub fn remove_discovery(&self, topic: &str) -> Result<(), PublishError> {
+        block_on(self.inner.remove_discovery(topic))
+    }
+
+    /// Publishes a retained state.
+    ///
+    /// Returns `false` when the state deduplication is enabled and the state was skipped because it's identical to the last one.
+    pub fn publish_state<P: Into<Vec<u8>>>(&self, topic: &str, payload: P) -> Result<bool> {
+        block_on(self.inner.publish_state(topic, payload))
+    }
+
+    /// Publishes a retained JSON payload.
+    pub fn publish_data<S: Serialize>(
+        &self,
+        topic: &str,
+        payload: &S,
+        message_expiry_interval: Option<u32>,
+    ) -> Result<()> {
+        block_on(
+            self.inner
+                .publish_data(topic, payload, message_expiry_interval),
+        )
+    }
+}
+
+impl<C: MqttClient> HomeAssistantMqtt<C> {
+    /// Subscribes to the command topic of every entity of the device.
+    pub fn subscribe_device_commands<D: HaDevice>(&self, device: &D) -> Result<()> {
+        block_on(self.inner.subscribe_device_commands(device))
+    }
+}
+
+impl<C: MqttPublisher> From<crate::HomeAssistantMqtt<C>> for HomeAssistantMqtt<C> {
+    fn from(inner: crate::HomeAssistantMqtt<C>) -> Self {
+        Self { inner }
+    }
+}
+
+#[cfg(all(test, feature = "entity-sensor"))]
+mod tests {
+    use rumqttc::v5::MqttOptions;
+
+    use crate::mqtt::sensor::Sensor;
+
+    use super::*;
+
+    #[test]
+    fn can_publish_without_a_runtime() {
+        let (client, _connection) =
+            rumqttc::v5::Client::new(MqttOptions::new("test", "localhost", 1883), 10);
+        let home_assistant = HomeAssistantMqtt::new(client, "homeassistant");
+
+        assert!(home_assistant
+            .publish_entity(
+                Sensor::new("home/temperature")
+                    .unique_id("temperature")
+                    .into()
+            )
+            .is_ok());
+        assert!(home_assistant
+            .publish_state("home/temperature", "21.5")
+            .unwrap());
+        assert!(home_assistant
+            .publish_data("home/attributes", &serde_json::json!({"battery": 80}), None)
+            .is_ok());
+    }
+}
@@ -0,0 +1,368 @@
+use std::{pin::pin, time::Duration};
+
+use anyhow::Result;
+use futures::future::{select, Either};
+use rumqttc::v5::{mqttbytes::v5::Packet, AsyncClient, Event, EventLoop, MqttOptions};
+
+use crate::{
+    availability::{AvailabilityManager, AvailabilityPayloads},
+    command::{Command, CommandRouter},
+    mqtt::common::Availability,
+    registry::{DiscoveryRegistry, MemoryStore, RegistryStore},
+    status::{HomeAssistantStatus, StatusWatcher},
+    topic::DEFAULT_DISCOVERY_PREFIX,
+    Entity, HomeAssistantMqtt,
+};
+
+type PendingCommand = (Entity, Box<dyn Fn(Command<'_>) + Send + Sync>);
+
+/// A complete Home Assistant bridge: it owns the MQTT connection, announces the entities and routes their commands.
+///
+/// On every (re)connection and every Home Assistant birth message (`online` on `<discovery_prefix>/status`), the
+/// bridge publishes its availability and the discovery configuration of its entities, then removes the entities
+/// declared by the previous run only. The entities without availability checks follow the bridge availability.
+///
+/// ```no_run
+/// # #[cfg(all(feature = "entity-sensor", feature = "entity-switch"))]
+/// # async fn example() -> anyhow::Result<()> {
+/// # use ha_mqtt_discovery::{
+/// #     bridge::Bridge,
+/// #     mqtt::{sensor::Sensor, switch::Switch},
+/// #     registry::JsonFileStore,
+/// #     v5::MqttOptions,
+/// # };
+/// # let door = Switch::new("garage/door/set").unique_id("garage_door");
+/// let mut bridge = Bridge::builder(MqttOptions::new("garage", "localhost", 1883))
+///     .store(JsonFileStore::new("discovery.json"))
+///     .entity(Sensor::new("garage/temperature").unique_id("garage_temperature"))
+///     .on_command(door.clone(), |command| println!("{:?}", command.payload_str()))
+///     .build()?;
+/// let publisher = bridge.publisher();
+/// tokio::spawn(async move { publisher.publish_state("garage/temperature", "21.5").await });
+/// bridge.run().await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct Bridge<S: RegistryStore = MemoryStore> {
+    client: AsyncClient,
+    eventloop: EventLoop,
+    home_assistant: HomeAssistantMqtt,
+    availability: AvailabilityManager,
+    registry: DiscoveryRegistry<S>,
+    router: CommandRouter,
+    entities: Vec<Entity>,
+    status: StatusWatcher,
+    reconnect_delay: Duration,
+}
+
+impl Bridge {
+    /// Starts the configuration of a bridge connecting with the given options.
+    ///
+    /// The last will is overwritten to mark the bridge unavailable when the connection is lost.
+    pub fn builder(mqtt_options: MqttOptions) -> BridgeBuilder {
+        BridgeBuilder {
+            mqtt_options,
+            capacity: 10,
+            discovery_prefix: DEFAULT_DISCOVERY_PREFIX.to_string(),
+            node_id: None,
+            availability_topic: None,
+            availability_payloads: AvailabilityPayloads::default(),
+            store: MemoryStore::default(),
+            entities: vec![],
+            commands: vec![],
+            reconnect_delay: Duration::from_secs(5),
+        }
+    }
+}
+
+impl<S: RegistryStore> Bridge<S> {
+    /// A publisher sharing the connection of the bridge, to publish states from other tasks.
+    pub fn publisher(&self) -> HomeAssistantMqtt {
+        self.home_assistant.clone()
+    }
+
+    /// The manager of the bridge availability.
+    pub fn availability(&self) -> &AvailabilityManager {
+        &self.availability
+    }
+
+    /// The entities announced by the bridge.
+    pub fn entities(&self) -> &[Entity] {
+        &self.entities
+    }
+
+    /// Polls the MQTT connection until a command or an announcement fails.
+    ///
+    /// After a connection error, the connection is polled again once the reconnection delay elapsed, which reconnects
+    /// to the broker.
+    pub async fn run(&mut self) -> Result<()> {
+        loop {
+            let event = poll(&mut self.eventloop, self.reconnect_delay).await;
+            let mut announce = self.handle_event(&event)?;
+            while announce {
+                announce = false;
+                let Bridge {
+                    client,
+                    eventloop,
+                    home_assistant,
+                    availability,
+                    registry,
+                    router,
+                    entities,
+                    status,
+                    reconnect_delay,
+                } = self;
+                // the event loop must be polled while the messages are queued, the birth messages and
+                // reconnections received in the meantime trigger another announcement
+                let announcement = pin!(announce_entities(
+                    home_assistant,
+                    availability,
+                    registry,
+                    entities
+                ));
+                let polling = pin!(async {
+                    loop {
+                        let event = poll(eventloop, *reconnect_delay).await;
+                        if handle_event(client, router, status, &event)? {
+                            announce = true;
+                        }
+                    }
+                });
+                match select(announcement, polling).await {
+                    Either::Left((result, _)) => result?,
+                    Either::Right((result, _)) => return result,
+                }
+            }
+        }
+    }
+
+    fn handle_event(&self, event: &Event) -> Result<bool> {
+        handle_event(&self.client, &self.router, &self.status, event)
+    }
+}
+
+/// Polls the event loop until an event is received, waiting for the reconnection delay after each connection error.
+async fn poll(eventloop: &mut EventLoop, reconnect_delay: Duration) -> Event {
+    loop {
+        match eventloop.poll().await {
+            Ok(event) => return event,
+            Err(_error) => {
+                #[cfg(feature = "tracing")]
+                tracing::warn!(error = %_error, "connection error, reconnecting");
+                tokio::time::sleep(reconnect_delay).await;
+            }
+        }
+    }
+}
+
+/// Dispatches the commands and returns whether the entities should be announced.
+fn handle_event(
+    client: &AsyncClient,
+    router: &CommandRouter,
+    status: &StatusWatcher,
+    event: &Event,
+) -> Result<bool> {
+    router.handle_event(client, event)?;
+    let status = status.handle_event(client, event)?;
+    Ok(matches!(event, Event::Incoming(Packet::ConnAck(_)))
+        || status == Some(HomeAssistantStatus::Online))
+}
+
+async fn announce_entities<S: RegistryStore>(
+    home_assistant: &HomeAssistantMqtt,
+    availability: &AvailabilityManager,
+    registry: &mut DiscoveryRegistry<S>,
+    entities: &[Entity],
+) -> Result<()> {
+    availability.set_available(true).await?;
+    for entity in entities {
+        registry.publish(home_assistant, entity.clone()).await?;
+    }
+    registry.sync(home_assistant).await?;
+    Ok(())
+}
+
+/// The configuration of a `Bridge`.
+pub struct BridgeBuilder<S: RegistryStore = MemoryStore> {
+    mqtt_options: MqttOptions,
+    capacity: usize,
+    discovery_prefix: String,
+    node_id: Option<String>,
+    availability_topic: Option<String>,
+    availability_payloads: AvailabilityPayloads,
+    store: S,
+    entities: Vec<Entity>,
+    commands: Vec<PendingCommand>,
+    reconnect_delay: Duration,
+}
+
+impl<S: RegistryStore> BridgeBuilder<S> {
+    /// The capacity of the request channel of the MQTT client, 10 by default.
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// The discovery prefix, `homeassistant` by default.
+    pub fn discovery_prefix<T: Into<String>>(mut self, discovery_prefix: T) -> Self {
+        self.discovery_prefix = discovery_prefix.into();
+        self
+    }
+
+    /// The `<node_id>` level of the discovery topics.
+    pub fn node_id<T: Into<String>>(mut self, node_id: T) -> Self {
+        self.node_id = Some(node_id.into());
+        self
+    }
+
+    /// The availability topic of the bridge, `<client_id>/availability` by default.
+    pub fn availability_topic<T: Into<String>>(mut self, availability_topic: T) -> Self {
+        self.availability_topic = Some(availability_topic.into());
+        self
+    }
+
+    /// The payloads of the bridge availability, `online` and `offline` by default.
+    pub fn availability_payloads(mut self, availability_payloads: AvailabilityPayloads) -> Self {
+        self.availability_payloads = availability_payloads;
+        self
+    }
+
+    /// The delay before polling the connection again after a connection error, 5 seconds by default.
+    pub fn reconnect_delay(mut self, reconnect_delay: Duration) -> Self {
+        self.reconnect_delay = reconnect_delay;
+        self
+    }
+
+    /// Where the discovery configurations published by a run are saved, to remove the stale ones on the next run.
+    ///
+    /// The entries are kept in memory by default.
+    pub fn store<T: RegistryStore>(self, store: T) -> BridgeBuilder<T> {
+        BridgeBuilder {
+            mqtt_options: self.mqtt_options,
+            capacity: self.capacity,
+            discovery_prefix: self.discovery_prefix,
+            node_id: self.node_id,
+            availability_topic: self.availability_topic,
+            availability_payloads: self.availability_payloads,
+            store,
+            entities: self.entities,
+            commands: self.commands,
+            reconnect_delay: self.reconnect_delay,
+        }
+    }
+
+    /// Announces an entity.
+    pub fn entity<E: Into<Entity>>(mut self, entity: E) -> Self {
+        self.entities.push(entity.into());
+        self
+    }
+
+    /// Announces an entity and handles the commands sent to its command topic.
+    pub fn on_command<E, F>(mut self, entity: E, handler: F) -> Self
+    where
+        E: Into<Entity>,
+        F: Fn(Command<'_>) + Send + Sync + 'static,
+    {
+        let entity = entity.into();
+        self.entities.push(entity.clone());
+        self.commands.push((entity, Box::new(handler)));
+        self
+    }
+
+    /// Creates the MQTT client and the bridge subsystems.
+    ///
+    /// Fails when the discovery prefix is invalid, the store can't be loaded or an entity handling commands has no
+    /// command topic.
+    pub fn build(mut self) -> Result<Bridge<S>> {
+        let availability_topic = self
+            .availability_topic
+            .unwrap_or_else(|| format!("{}/availability", self.mqtt_options.client_id()));
+        self.mqtt_options
+            .set_last_will(self.availability_payloads.last_will(&availability_topic));
+        let (client, eventloop) = AsyncClient::new(self.mqtt_options, self.capacity);
+
+        let mut home_assistant =
+            HomeAssistantMqtt::try_new(client.clone(), &self.discovery_prefix)?
+                .with_availability_payloads(self.availability_payloads.clone());
+        if let Some(node_id) = self.node_id {
+            home_assistant = home_assistant.with_node_id(node_id);
+        }
+        let availability = AvailabilityManager::new(client.clone(), availability_topic)
+            .with_availability_payloads(self.availability_payloads);
+        let mut router = CommandRouter::default();
+        for (entity, handler) in self.commands {
+            router.on_command(&entity, handler)?;
+        }
+        let entities = self
+            .entities
+            .into_iter()
+            .map(|mut entity| {
+                if entity.availability().availability.is_empty() {
+                    entity
+                        .set_availability(Availability::single(availability.availability_check()));
+                }
+                entity
+            })
+            .collect();
+
+        Ok(Bridge {
+            client,
+            eventloop,
+            home_assistant,
+            availability,
+            registry: DiscoveryRegistry::new(self.store)?,
+            router,
+            entities,
+            status: StatusWatcher::new(self.discovery_prefix),
+            reconnect_delay: self.reconnect_delay,
+        })
+    }
+}
+
+#[cfg(all(test, feature = "entity-sensor", feature = "entity-switch"))]
+mod tests {
+    use crate::mqtt::{common::AvailabilityCheck, sensor::Sensor, switch::Switch};
+
+    use super::*;
+
+    #[test]
+    fn can_build_bridges() {
+        let bridge = Bridge::builder(MqttOptions::new("garage", "localhost", 1883))
+            .entity(Sensor::new("garage/temperature").unique_id("temperature"))
+            .on_command(Switch::new("garage/door/set").unique_id("door"), |_| {})
+            .build()
+            .unwrap();
+
+        assert_eq!(bridge.entities().len(), 2);
+        assert_eq!(
+            bridge.entities()[0].availability().availability,
+            vec![AvailabilityCheck::topic("garage/availability")]
+        );
+        assert_eq!(
+            bridge.eventloop.options.last_will(),
+            Some(AvailabilityPayloads::default().last_will("garage/availability"))
+        );
+
+        assert!(
+            Bridge::builder(MqttOptions::new("garage", "localhost", 1883))
+                .on_command(Sensor::new("garage/temperature"), |_| {})
+                .build()
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn can_keep_polling_after_connection_errors() {
+        // nothing listens on the port 1, every connection attempt fails
+        let mut bridge = Bridge::builder(MqttOptions::new("garage", "127.0.0.1", 1))
+            .reconnect_delay(Duration::from_millis(10))
+            .build()
+            .unwrap();
+
+        assert!(
+            tokio::time::timeout(Duration::from_millis(200), bridge.run())
+                .await
+                .is_err()
+        );
+    }
+}
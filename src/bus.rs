@@ -0,0 +1,232 @@
+use anyhow::Result;
+#[cfg(feature = "client")]
+use rumqttc::v5::mqttbytes::QoS;
+#[cfg(feature = "client")]
+use serde::de::DeserializeOwned;
+use serde_derive::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[cfg(feature = "entity-binary_sensor")]
+use crate::mqtt::binary_sensor::BinarySensor;
+#[cfg(feature = "entity-button")]
+use crate::mqtt::button::Button;
+#[cfg(feature = "entity-number")]
+use crate::mqtt::number::Number;
+#[cfg(feature = "entity-sensor")]
+use crate::mqtt::sensor::Sensor;
+#[cfg(feature = "entity-switch")]
+use crate::mqtt::switch::Switch;
+use crate::topic::Topic;
+#[cfg(feature = "client")]
+use crate::{
+    client::{publish_traced, MqttPublisher, PublishOptions},
+    command::Command,
+};
+
+/// The JSON message carried on the device bus topics: the addressed `component` and its `payload`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Envelope<C = String, P = Value> {
+    pub component: C,
+    pub payload: P,
+}
+
+/// The component name as a JSON string, which is also a valid Jinja string literal.
+fn string_literal(component: &str) -> String {
+    serde_json::to_string(component).expect("string is serializable")
+}
+
+/// A single command/state topic pair shared by every entity of a device.
+///
+/// The entity configurations built by the bus extract their own state from the envelope with a `value_template` and
+/// wrap their commands into the envelope with a `command_template`:
+///
+/// ```no_run
+/// # #[cfg(all(feature = "client", feature = "entity-sensor"))]
+/// # async fn example(client: ha_mqtt_discovery::v5::AsyncClient) -> anyhow::Result<()> {
+/// # use ha_mqtt_discovery::bus::DeviceBus;
+/// let bus = DeviceBus::new("home/thermostat");
+/// let temperature = bus.sensor("temperature").unique_id("thermostat_temperature");
+/// bus.publish_state(&client, "temperature", &21.5).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct DeviceBus {
+    command_topic: Topic,
+    state_topic: Topic,
+}
+
+impl DeviceBus {
+    /// A bus receiving the commands on `<base_topic>/command` and publishing the states on `<base_topic>/state`.
+    pub fn new<T: Into<Topic>>(base_topic: T) -> Self {
+        let base_topic = base_topic.into();
+        Self {
+            command_topic: base_topic.join("command"),
+            state_topic: base_topic.join("state"),
+        }
+    }
+
+    /// A bus using the given command and state topics.
+    pub fn with_topics<T: Into<Topic>>(command_topic: T, state_topic: T) -> Self {
+        Self {
+            command_topic: command_topic.into(),
+            state_topic: state_topic.into(),
+        }
+    }
+
+    pub fn command_topic(&self) -> &Topic {
+        &self.command_topic
+    }
+
+    pub fn state_topic(&self) -> &Topic {
+        &self.state_topic
+    }
+
+    /// Template extracting the state of the component from the state envelope, other components keep their state.
+    pub fn value_template(&self, component: &str) -> String {
+        let component = string_literal(component);
+        format!("{{% if value_json.component == {component} %}}{{{{ value_json.payload }}}}{{% else %}}{{{{ this.state }}}}{{% endif %}}")
+    }
+
+    /// Template wrapping the command value into an envelope addressed to the component.
+    pub fn command_template(&self, component: &str) -> String {
+        let component = string_literal(component);
+        format!("{{\"component\": {component}, \"payload\": {{{{ value | tojson }}}}}}")
+    }
+
+    /// The envelope addressed to the component, serialized as JSON.
+    pub fn envelope<P: serde::Serialize>(&self, component: &str, payload: &P) -> Result<String> {
+        Ok(serde_json::to_string(&Envelope { component, payload })?)
+    }
+
+    /// A sensor reading its state from the bus.
+    #[cfg(feature = "entity-sensor")]
+    pub fn sensor(&self, component: &str) -> Sensor {
+        Sensor::new(self.state_topic.clone()).value_template(self.value_template(component))
+    }
+
+    /// A binary sensor reading its state from the bus.
+    #[cfg(feature = "entity-binary_sensor")]
+    pub fn binary_sensor(&self, component: &str) -> BinarySensor {
+        BinarySensor::new(self.state_topic.clone()).value_template(self.value_template(component))
+    }
+
+    /// A switch reading its `ON`/`OFF` state from the bus and sending its commands through the bus.
+    ///
+    /// Switches don't support command templates, the `ON` and `OFF` envelopes are set as command payloads.
+    #[cfg(feature = "entity-switch")]
+    pub fn switch(&self, component: &str) -> Switch {
+        Switch::new(self.command_topic.clone())
+            .state_topic(self.state_topic.clone())
+            .value_template(self.value_template(component))
+            .payload_on(
+                self.envelope(component, &"ON")
+                    .expect("string is serializable"),
+            )
+            .payload_off(
+                self.envelope(component, &"OFF")
+                    .expect("string is serializable"),
+            )
+            .state_on("ON")
+            .state_off("OFF")
+    }
+
+    /// A number reading its state from the bus and sending its commands through the bus.
+    #[cfg(feature = "entity-number")]
+    pub fn number(&self, component: &str) -> Number {
+        Number::new(self.command_topic.clone())
+            .command_template(self.command_template(component))
+            .state_topic(self.state_topic.clone())
+            .value_template(self.value_template(component))
+    }
+
+    /// A button sending its press command through the bus.
+    #[cfg(feature = "entity-button")]
+    pub fn button(&self, component: &str) -> Button {
+        Button::new(self.command_topic.clone()).command_template(self.command_template(component))
+    }
+
+    /// Decodes a command received on the bus command topic, the component and the payload types can be any deserializable type.
+    #[cfg(feature = "client")]
+    pub fn decode_command<C, P>(&self, command: &Command<'_>) -> Result<Envelope<C, P>>
+    where
+        C: DeserializeOwned,
+        P: DeserializeOwned,
+    {
+        Ok(serde_json::from_slice(command.payload)?)
+    }
+
+    /// Publishes the state of a component on the bus state topic.
+    #[cfg(feature = "client")]
+    pub async fn publish_state<C: MqttPublisher, P: serde::Serialize>(
+        &self,
+        client: &C,
+        component: &str,
+        payload: &P,
+    ) -> Result<()> {
+        let envelope = self.envelope(component, payload)?;
+        publish_traced(
+            client,
+            self.state_topic.to_string(),
+            QoS::AtLeastOnce,
+            false,
+            envelope.into_bytes(),
+            PublishOptions {
+                content_type: Some("application/json".to_string()),
+                ..Default::default()
+            },
+        )
+        .await?;
+        Ok(())
+    }
+}
+
+#[cfg(all(
+    test,
+    feature = "client",
+    feature = "entity-number",
+    feature = "entity-switch"
+))]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn can_route_entities_through_the_bus() {
+        let bus = DeviceBus::new("home/thermostat/");
+
+        let number = bus.number("target");
+        assert_eq!(number.command_topic, "home/thermostat/command");
+        assert_eq!(
+            number.command_template.as_deref(),
+            Some(r#"{"component": "target", "payload": {{ value | tojson }}}"#)
+        );
+        assert_eq!(
+            number.value_template.as_deref(),
+            Some(
+                r#"{% if value_json.component == "target" %}{{ value_json.payload }}{% else %}{{ this.state }}{% endif %}"#
+            )
+        );
+        let command_template = bus.command_template("e\u{301}clairage \"salon\"");
+        let envelope: Value =
+            serde_json::from_str(&command_template.replace("{{ value | tojson }}", "1")).unwrap();
+        assert_eq!(envelope["component"], "e\u{301}clairage \"salon\"");
+        assert_eq!(
+            bus.switch("heater").payload_on.as_deref(),
+            Some(r#"{"component":"heater","payload":"ON"}"#)
+        );
+
+        let command = Command {
+            topic: "home/thermostat/command",
+            payload: br#"{"component": "target", "payload": 19.5}"#,
+        };
+        assert_eq!(
+            bus.decode_command::<String, Value>(&command).unwrap(),
+            Envelope {
+                component: "target".to_string(),
+                payload: json!(19.5)
+            }
+        );
+    }
+}
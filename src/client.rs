@@ -0,0 +1,284 @@
+use std::future::Future;
+
+use rumqttc::v5::mqttbytes::{v5::PublishProperties, QoS};
+
+/// Message properties only supported by MQTT v5, they are dropped by MQTT v3.1.1 clients.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PublishOptions {
+    /// Lifetime of the message in seconds, the broker discards retained messages once expired.
+    pub message_expiry_interval: Option<u32>,
+    /// Content type of the payload, eg. `application/json`.
+    pub content_type: Option<String>,
+    /// Topic of the response to a request message.
+    pub response_topic: Option<String>,
+    /// Application defined name/value pairs, brokers may route or filter messages on them.
+    pub user_properties: Vec<(String, String)>,
+}
+
+/// The publish layer used by `HomeAssistantMqtt`, implemented for both MQTT v5 and MQTT v3.1.1 `rumqttc` clients.
+///
+/// Implement it to plug in another MQTT client, or use `testing::MockClient` to unit test an integration without a broker.
+pub trait MqttPublisher: Clone + Send + Sync {
+    type Error: std::error::Error + Send + Sync + 'static;
+
+    /// Publishes a message, the MQTT v5 properties are dropped when the protocol doesn't support them.
+    fn publish(
+        &self,
+        topic: String,
+        qos: QoS,
+        retain: bool,
+        payload: Vec<u8>,
+        options: PublishOptions,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+}
+
+/// A publisher which can also subscribe to topics.
+pub trait MqttClient: MqttPublisher {
+    /// Subscribes to a topic filter.
+    fn subscribe(
+        &self,
+        filter: String,
+        qos: QoS,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+}
+
+impl MqttPublisher for rumqttc::v5::AsyncClient {
+    type Error = rumqttc::v5::ClientError;
+
+    async fn publish(
+        &self,
+        topic: String,
+        qos: QoS,
+        retain: bool,
+        payload: Vec<u8>,
+        options: PublishOptions,
+    ) -> Result<(), Self::Error> {
+        let properties = PublishProperties {
+            message_expiry_interval: options.message_expiry_interval,
+            content_type: options.content_type,
+            response_topic: options.response_topic,
+            user_properties: options.user_properties,
+            ..Default::default()
+        };
+        self.publish_with_properties(topic, qos, retain, payload, properties)
+            .await
+    }
+}
+
+impl MqttClient for rumqttc::v5::AsyncClient {
+    async fn subscribe(&self, filter: String, qos: QoS) -> Result<(), Self::Error> {
+        rumqttc::v5::AsyncClient::subscribe(self, filter, qos).await
+    }
+}
+
+impl MqttPublisher for rumqttc::AsyncClient {
+    type Error = rumqttc::ClientError;
+
+    async fn publish(
+        &self,
+        topic: String,
+        qos: QoS,
+        retain: bool,
+        payload: Vec<u8>,
+        _options: PublishOptions,
+    ) -> Result<(), Self::Error> {
+        rumqttc::AsyncClient::publish(self, topic, v3_qos(qos), retain, payload).await
+    }
+}
+
+impl MqttClient for rumqttc::AsyncClient {
+    async fn subscribe(&self, filter: String, qos: QoS) -> Result<(), Self::Error> {
+        rumqttc::AsyncClient::subscribe(self, filter, v3_qos(qos)).await
+    }
+}
+
+impl MqttPublisher for rumqttc::v5::Client {
+    type Error = rumqttc::v5::ClientError;
+
+    /// Blocks until the request is queued, see `blocking::HomeAssistantMqtt`.
+    async fn publish(
+        &self,
+        topic: String,
+        qos: QoS,
+        retain: bool,
+        payload: Vec<u8>,
+        options: PublishOptions,
+    ) -> Result<(), Self::Error> {
+        let properties = PublishProperties {
+            message_expiry_interval: options.message_expiry_interval,
+            content_type: options.content_type,
+            response_topic: options.response_topic,
+            user_properties: options.user_properties,
+            ..Default::default()
+        };
+        self.publish_with_properties(topic, qos, retain, payload, properties)
+    }
+}
+
+impl MqttClient for rumqttc::v5::Client {
+    async fn subscribe(&self, filter: String, qos: QoS) -> Result<(), Self::Error> {
+        rumqttc::v5::Client::subscribe(self, filter, qos)
+    }
+}
+
+impl MqttPublisher for rumqttc::Client {
+    type Error = rumqttc::ClientError;
+
+    /// Blocks until the request is queued, see `blocking::HomeAssistantMqtt`.
+    async fn publish(
+        &self,
+        topic: String,
+        qos: QoS,
+        retain: bool,
+        payload: Vec<u8>,
+        _options: PublishOptions,
+    ) -> Result<(), Self::Error> {
+        rumqttc::Client::publish(self, topic, v3_qos(qos), retain, payload)
+    }
+}
+
+impl MqttClient for rumqttc::Client {
+    async fn subscribe(&self, filter: String, qos: QoS) -> Result<(), Self::Error> {
+        rumqttc::Client::subscribe(self, filter, v3_qos(qos))
+    }
+}
+
+/// Publishes a message through the client, within a `mqtt_publish` span recording the topic, payload size, QoS and
+/// retain flag with the `tracing` feature.
+pub(crate) async fn publish_traced<C: MqttPublisher>(
+    client: &C,
+    topic: String,
+    qos: QoS,
+    retain: bool,
+    payload: Vec<u8>,
+    options: PublishOptions,
+) -> Result<(), C::Error> {
+    #[cfg(feature = "tracing")]
+    {
+        use tracing::Instrument;
+
+        let span = tracing::debug_span!(
+            "mqtt_publish",
+            topic = %topic,
+            size = payload.len(),
+            qos = qos as u8,
+            retain
+        );
+        let result = client
+            .publish(topic, qos, retain, payload, options)
+            .instrument(span.clone())
+            .await;
+        let _entered = span.enter();
+        match &result {
+            Ok(()) => tracing::debug!("message published"),
+            Err(error) => tracing::warn!(%error, "message not published"),
+        }
+        result
+    }
+    #[cfg(not(feature = "tracing"))]
+    client.publish(topic, qos, retain, payload, options).await
+}
+
+fn v3_qos(qos: QoS) -> rumqttc::QoS {
+    match qos {
+        QoS::AtMostOnce => rumqttc::QoS::AtMostOnce,
+        QoS::AtLeastOnce => rumqttc::QoS::AtLeastOnce,
+        QoS::ExactlyOnce => rumqttc::QoS::ExactlyOnce,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[tokio::test]
+    #[cfg(feature = "entity-sensor")]
+    async fn can_publish_with_a_v3_client() {
+        use rumqttc::MqttOptions;
+
+        use crate::{mqtt::sensor::Sensor, Entity, HomeAssistantMqtt};
+
+        let (client, _eventloop) =
+            rumqttc::AsyncClient::new(MqttOptions::new("test", "localhost", 1883), 10);
+        let registry = HomeAssistantMqtt::new(client, "homeassistant");
+
+        let published = registry
+            .publish_entity(Entity::from(
+                Sensor::new("home/temperature").unique_id("temperature"),
+            ))
+            .await;
+
+        assert!(published.is_ok());
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn can_trace_published_messages() {
+        use std::sync::{Arc, Mutex};
+
+        use tracing::{
+            field::{Field, Visit},
+            span::{Attributes, Id, Record},
+            Event, Metadata, Subscriber,
+        };
+
+        use super::*;
+        use crate::testing::MockClient;
+
+        /// Records the fields of the spans and the messages of the events.
+        #[derive(Clone, Default)]
+        struct Recorder(Arc<Mutex<Vec<String>>>);
+
+        impl Visit for Recorder {
+            fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+                self.0.lock().unwrap().push(format!("{field}={value:?}"));
+            }
+        }
+
+        impl Subscriber for Recorder {
+            fn enabled(&self, _: &Metadata<'_>) -> bool {
+                true
+            }
+
+            fn new_span(&self, span: &Attributes<'_>) -> Id {
+                span.record(&mut self.clone());
+                Id::from_u64(1)
+            }
+
+            fn record(&self, _: &Id, _: &Record<'_>) {}
+
+            fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+            fn event(&self, event: &Event<'_>) {
+                event.record(&mut self.clone());
+            }
+
+            fn enter(&self, _: &Id) {}
+
+            fn exit(&self, _: &Id) {}
+        }
+
+        let recorder = Recorder::default();
+        tracing::subscriber::with_default(recorder.clone(), || {
+            futures::executor::block_on(publish_traced(
+                &MockClient::default(),
+                "home/temperature".to_string(),
+                QoS::AtLeastOnce,
+                true,
+                b"21.5".to_vec(),
+                PublishOptions::default(),
+            ))
+            .unwrap();
+        });
+
+        let records = recorder.0.lock().unwrap().clone();
+        assert_eq!(
+            records,
+            vec![
+                "topic=home/temperature",
+                "size=4",
+                "qos=1",
+                "retain=true",
+                "message=message published"
+            ]
+        );
+    }
+}
@@ -0,0 +1,607 @@
+use std::str::FromStr;
+
+use rust_decimal::Decimal;
+use serde::de::value::StrDeserializer;
+use serde_derive::{Deserialize, Serialize};
+
+use crate::{
+    decimal::{DecimalError, IntoDecimal, TryIntoDecimal},
+    mqtt::climate::Climate,
+    topic::{resolve, Topic},
+    variant::variant_name,
+};
+
+/// The operation mode of a climate device.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HvacMode {
+    Auto,
+    Off,
+    Cool,
+    Heat,
+    Dry,
+    FanOnly,
+}
+
+/// What a climate device is currently doing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HvacAction {
+    Off,
+    Heating,
+    Cooling,
+    Drying,
+    Idle,
+    Fan,
+    Preheating,
+    Defrosting,
+}
+
+/// What a climate device is currently doing, published on the `action_topic`.
+pub type ClimateAction = HvacAction;
+
+impl HvacMode {
+    /// The mode published on the `mode_state_topic` and received on the `mode_command_topic`, eg. `fan_only`.
+    pub fn as_str(&self) -> &'static str {
+        variant_name(self)
+    }
+}
+
+impl FromStr for HvacMode {
+    type Err = serde::de::value::Error;
+
+    fn from_str(mode: &str) -> Result<Self, Self::Err> {
+        serde::Deserialize::deserialize(StrDeserializer::new(mode))
+    }
+}
+
+impl HvacAction {
+    /// The action published on the `action_topic`, eg. `heating`.
+    pub fn as_str(&self) -> &'static str {
+        variant_name(self)
+    }
+}
+
+/// A fan mode of a climate device, the modes Home Assistant translates or any other mode.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FanMode {
+    On,
+    Off,
+    Auto,
+    Low,
+    Medium,
+    High,
+    Middle,
+    Focus,
+    Diffuse,
+    Custom(String),
+}
+
+impl FanMode {
+    pub fn as_str(&self) -> &str {
+        match self {
+            FanMode::On => "on",
+            FanMode::Off => "off",
+            FanMode::Auto => "auto",
+            FanMode::Low => "low",
+            FanMode::Medium => "medium",
+            FanMode::High => "high",
+            FanMode::Middle => "middle",
+            FanMode::Focus => "focus",
+            FanMode::Diffuse => "diffuse",
+            FanMode::Custom(mode) => mode,
+        }
+    }
+}
+
+/// A swing mode of a climate device, the modes Home Assistant translates or any other mode.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SwingMode {
+    On,
+    Off,
+    Both,
+    Vertical,
+    Horizontal,
+    Custom(String),
+}
+
+impl SwingMode {
+    pub fn as_str(&self) -> &str {
+        match self {
+            SwingMode::On => "on",
+            SwingMode::Off => "off",
+            SwingMode::Both => "both",
+            SwingMode::Vertical => "vertical",
+            SwingMode::Horizontal => "horizontal",
+            SwingMode::Custom(mode) => mode,
+        }
+    }
+}
+
+impl From<HvacMode> for String {
+    fn from(mode: HvacMode) -> Self {
+        mode.as_str().to_string()
+    }
+}
+
+impl From<HvacAction> for String {
+    fn from(action: HvacAction) -> Self {
+        action.as_str().to_string()
+    }
+}
+
+impl From<FanMode> for String {
+    fn from(mode: FanMode) -> Self {
+        match mode {
+            FanMode::Custom(mode) => mode,
+            mode => mode.as_str().to_string(),
+        }
+    }
+}
+
+impl From<SwingMode> for String {
+    fn from(mode: SwingMode) -> Self {
+        match mode {
+            SwingMode::Custom(mode) => mode,
+            mode => mode.as_str().to_string(),
+        }
+    }
+}
+
+/// The runtime state of a climate entity.
+///
+/// It's published as a single JSON payload on the topics extracting the values with a template, like the ones
+/// configured by `Climate::json_state_topic`, and as plain values on the other state topics.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ClimateState {
+    /// The current temperature measured by the device.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current_temperature: Option<Decimal>,
+    /// The target temperature.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<Decimal>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mode: Option<HvacMode>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub action: Option<HvacAction>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fan_mode: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preset_mode: Option<String>,
+    /// The current humidity measured by the device.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current_humidity: Option<Decimal>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_humidity: Option<Decimal>,
+    /// The lower target temperature, of the devices with a target temperature range.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_temp_low: Option<Decimal>,
+    /// The upper target temperature, of the devices with a target temperature range.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_temp_high: Option<Decimal>,
+}
+
+impl ClimateState {
+    pub fn current_temperature<D: IntoDecimal>(mut self, current_temperature: D) -> Self {
+        self.current_temperature = Some(current_temperature.into_decimal());
+        self
+    }
+
+    /// Same as [`Self::current_temperature`] for the floats, fails when the value is NaN, infinite or out of the `Decimal` range.
+    pub fn try_current_temperature<D: TryIntoDecimal>(
+        self,
+        current_temperature: D,
+    ) -> Result<Self, DecimalError> {
+        Ok(self.current_temperature(current_temperature.try_into_decimal()?))
+    }
+
+    pub fn temperature<D: IntoDecimal>(mut self, temperature: D) -> Self {
+        self.temperature = Some(temperature.into_decimal());
+        self
+    }
+
+    /// Same as [`Self::temperature`] for the floats, fails when the value is NaN, infinite or out of the `Decimal` range.
+    pub fn try_temperature<D: TryIntoDecimal>(self, temperature: D) -> Result<Self, DecimalError> {
+        Ok(self.temperature(temperature.try_into_decimal()?))
+    }
+
+    pub fn mode(mut self, mode: HvacMode) -> Self {
+        self.mode = Some(mode);
+        self
+    }
+
+    pub fn action(mut self, action: HvacAction) -> Self {
+        self.action = Some(action);
+        self
+    }
+
+    pub fn fan_mode<S: Into<String>>(mut self, fan_mode: S) -> Self {
+        self.fan_mode = Some(fan_mode.into());
+        self
+    }
+
+    pub fn preset_mode<S: Into<String>>(mut self, preset_mode: S) -> Self {
+        self.preset_mode = Some(preset_mode.into());
+        self
+    }
+
+    pub fn current_humidity<D: IntoDecimal>(mut self, current_humidity: D) -> Self {
+        self.current_humidity = Some(current_humidity.into_decimal());
+        self
+    }
+
+    /// Same as [`Self::current_humidity`] for the floats, fails when the value is NaN, infinite or out of the `Decimal` range.
+    pub fn try_current_humidity<D: TryIntoDecimal>(
+        self,
+        current_humidity: D,
+    ) -> Result<Self, DecimalError> {
+        Ok(self.current_humidity(current_humidity.try_into_decimal()?))
+    }
+
+    pub fn target_humidity<D: IntoDecimal>(mut self, target_humidity: D) -> Self {
+        self.target_humidity = Some(target_humidity.into_decimal());
+        self
+    }
+
+    /// Same as [`Self::target_humidity`] for the floats, fails when the value is NaN, infinite or out of the `Decimal` range.
+    pub fn try_target_humidity<D: TryIntoDecimal>(
+        self,
+        target_humidity: D,
+    ) -> Result<Self, DecimalError> {
+        Ok(self.target_humidity(target_humidity.try_into_decimal()?))
+    }
+
+    pub fn target_temp_low<D: IntoDecimal>(mut self, target_temp_low: D) -> Self {
+        self.target_temp_low = Some(target_temp_low.into_decimal());
+        self
+    }
+
+    /// Same as [`Self::target_temp_low`] for the floats, fails when the value is NaN, infinite or out of the `Decimal` range.
+    pub fn try_target_temp_low<D: TryIntoDecimal>(
+        self,
+        target_temp_low: D,
+    ) -> Result<Self, DecimalError> {
+        Ok(self.target_temp_low(target_temp_low.try_into_decimal()?))
+    }
+
+    pub fn target_temp_high<D: IntoDecimal>(mut self, target_temp_high: D) -> Self {
+        self.target_temp_high = Some(target_temp_high.into_decimal());
+        self
+    }
+
+    /// Same as [`Self::target_temp_high`] for the floats, fails when the value is NaN, infinite or out of the `Decimal` range.
+    pub fn try_target_temp_high<D: TryIntoDecimal>(
+        self,
+        target_temp_high: D,
+    ) -> Result<Self, DecimalError> {
+        Ok(self.target_temp_high(target_temp_high.try_into_decimal()?))
+    }
+}
+
+/// The target values of a climate entity, see `HomeAssistantMqtt::set_climate_targets`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ClimateTargets {
+    pub temperature: Option<Decimal>,
+    pub temperature_low: Option<Decimal>,
+    pub temperature_high: Option<Decimal>,
+    pub humidity: Option<Decimal>,
+}
+
+impl ClimateTargets {
+    pub fn temperature<D: IntoDecimal>(mut self, temperature: D) -> Self {
+        self.temperature = Some(temperature.into_decimal());
+        self
+    }
+
+    /// Same as [`Self::temperature`] for the floats, fails when the value is NaN, infinite or out of the `Decimal` range.
+    pub fn try_temperature<D: TryIntoDecimal>(self, temperature: D) -> Result<Self, DecimalError> {
+        Ok(self.temperature(temperature.try_into_decimal()?))
+    }
+
+    /// The target temperature range, of the devices configuring the `temperature_low_*` and `temperature_high_*`
+    /// topics.
+    pub fn temperature_range<L: IntoDecimal, H: IntoDecimal>(mut self, low: L, high: H) -> Self {
+        self.temperature_low = Some(low.into_decimal());
+        self.temperature_high = Some(high.into_decimal());
+        self
+    }
+
+    /// Same as [`Self::temperature_range`] for the floats, fails when a value is NaN, infinite or out of the `Decimal`
+    /// range.
+    pub fn try_temperature_range<L: TryIntoDecimal, H: TryIntoDecimal>(
+        self,
+        low: L,
+        high: H,
+    ) -> Result<Self, DecimalError> {
+        Ok(self.temperature_range(low.try_into_decimal()?, high.try_into_decimal()?))
+    }
+
+    pub fn humidity<D: IntoDecimal>(mut self, humidity: D) -> Self {
+        self.humidity = Some(humidity.into_decimal());
+        self
+    }
+
+    /// Same as [`Self::humidity`] for the floats, fails when the value is NaN, infinite or out of the `Decimal` range.
+    pub fn try_humidity<D: TryIntoDecimal>(self, humidity: D) -> Result<Self, DecimalError> {
+        Ok(self.humidity(humidity.try_into_decimal()?))
+    }
+}
+
+impl From<&ClimateTargets> for ClimateState {
+    fn from(targets: &ClimateTargets) -> Self {
+        ClimateState {
+            temperature: targets.temperature,
+            target_temp_low: targets.temperature_low,
+            target_temp_high: targets.temperature_high,
+            target_humidity: targets.humidity,
+            ..Default::default()
+        }
+    }
+}
+
+/// A command sent by Home Assistant to one of the command topics of a climate entity.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ClimateCommand {
+    Temperature(Decimal),
+    TemperatureLow(Decimal),
+    TemperatureHigh(Decimal),
+    TargetHumidity(Decimal),
+    Mode(HvacMode),
+    FanMode(String),
+    SwingMode(String),
+    PresetMode(String),
+    /// Sent to the `power_command_topic`, `true` for the `payload_on` payload.
+    Power(bool),
+}
+
+impl Climate {
+    /// Reads every value of the `ClimateState` JSON payload published on the topic.
+    pub fn json_state_topic<T: Into<Topic>>(mut self, topic: T) -> Self {
+        let topic: Topic = topic.into();
+        let template = |attribute: &str| Some(format!("{{{{ value_json.{attribute} }}}}"));
+        self.current_temperature_topic = Some(topic.clone());
+        self.current_temperature_template = template("current_temperature");
+        self.temperature_state_topic = Some(topic.clone());
+        self.temperature_state_template = template("temperature");
+        self.mode_state_topic = Some(topic.clone());
+        self.mode_state_template = template("mode");
+        self.action_topic = Some(topic.clone());
+        self.action_template = template("action");
+        self.fan_mode_state_topic = Some(topic.clone());
+        self.fan_mode_state_template = template("fan_mode");
+        self.preset_mode_state_topic = Some(topic.clone());
+        self.preset_mode_value_template = template("preset_mode");
+        self.current_humidity_topic = Some(topic.clone());
+        self.current_humidity_template = template("current_humidity");
+        self.target_humidity_state_topic = Some(topic);
+        self.target_humidity_state_template = template("target_humidity");
+        self
+    }
+
+    /// The command of a payload received on one of the command topics, `None` for the other topics and the invalid
+    /// payloads.
+    ///
+    /// The payloads rendered by a `*_command_template` aren't recognized.
+    pub fn command(&self, topic: &str, payload: &str) -> Option<ClimateCommand> {
+        let is = |command_topic: &Option<Topic>| {
+            command_topic.as_ref().is_some_and(|command_topic| {
+                let command_topic = resolve(command_topic, self.topic_prefix.as_deref());
+                command_topic.as_str() == topic
+            })
+        };
+        let decimal = || payload.trim().parse::<Decimal>().ok();
+        if is(&self.temperature_command_topic) {
+            decimal().map(ClimateCommand::Temperature)
+        } else if is(&self.temperature_low_command_topic) {
+            decimal().map(ClimateCommand::TemperatureLow)
+        } else if is(&self.temperature_high_command_topic) {
+            decimal().map(ClimateCommand::TemperatureHigh)
+        } else if is(&self.target_humidity_command_topic) {
+            decimal().map(ClimateCommand::TargetHumidity)
+        } else if is(&self.mode_command_topic) {
+            payload.parse().ok().map(ClimateCommand::Mode)
+        } else if is(&self.fan_mode_command_topic) {
+            Some(ClimateCommand::FanMode(payload.to_string()))
+        } else if is(&self.swing_mode_command_topic) {
+            Some(ClimateCommand::SwingMode(payload.to_string()))
+        } else if is(&self.preset_mode_command_topic) {
+            Some(ClimateCommand::PresetMode(payload.to_string()))
+        } else if is(&self.power_command_topic) {
+            if payload == self.payload_on.as_deref().unwrap_or("ON") {
+                Some(ClimateCommand::Power(true))
+            } else if payload == self.payload_off.as_deref().unwrap_or("OFF") {
+                Some(ClimateCommand::Power(false))
+            } else {
+                None
+            }
+        } else {
+            None
+        }
+    }
+
+    /// The `(state_topic, template, state_attribute)` of every state value the entity reads.
+    #[cfg(feature = "client")]
+    pub(crate) fn state_bindings(&self) -> [(Option<&Topic>, Option<&str>, &'static str); 10] {
+        [
+            (
+                self.current_temperature_topic.as_ref(),
+                self.current_temperature_template.as_deref(),
+                "current_temperature",
+            ),
+            (
+                self.temperature_state_topic.as_ref(),
+                self.temperature_state_template.as_deref(),
+                "temperature",
+            ),
+            (
+                self.mode_state_topic.as_ref(),
+                self.mode_state_template.as_deref(),
+                "mode",
+            ),
+            (
+                self.action_topic.as_ref(),
+                self.action_template.as_deref(),
+                "action",
+            ),
+            (
+                self.fan_mode_state_topic.as_ref(),
+                self.fan_mode_state_template.as_deref(),
+                "fan_mode",
+            ),
+            (
+                self.preset_mode_state_topic.as_ref(),
+                self.preset_mode_value_template.as_deref(),
+                "preset_mode",
+            ),
+            (
+                self.current_humidity_topic.as_ref(),
+                self.current_humidity_template.as_deref(),
+                "current_humidity",
+            ),
+            (
+                self.target_humidity_state_topic.as_ref(),
+                self.target_humidity_state_template.as_deref(),
+                "target_humidity",
+            ),
+            (
+                self.temperature_low_state_topic.as_ref(),
+                self.temperature_low_state_template.as_deref(),
+                "target_temp_low",
+            ),
+            (
+                self.temperature_high_state_topic.as_ref(),
+                self.temperature_high_state_template.as_deref(),
+                "target_temp_high",
+            ),
+        ]
+    }
+}
+
+#[cfg(all(test, feature = "client"))]
+mod tests {
+    use rust_decimal_macros::dec;
+    use serde_json::json;
+
+    use crate::testing::MockClient;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn can_publish_climate_states() {
+        let client = MockClient::default();
+        let ha = client.ha();
+        let mut climate = Climate::default()
+            .topic_prefix("home/heater")
+            .json_state_topic("~/state")
+            .mode_state_topic("~/mode");
+        climate.mode_state_template = None;
+
+        ha.publish_climate_state(
+            &climate,
+            &ClimateState::default()
+                .current_temperature(19)
+                .temperature(21)
+                .mode(HvacMode::Heat)
+                .action(HvacAction::Heating),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(client.published().len(), 2);
+        client.assert_published_json(
+            "home/heater/state",
+            json!({
+                "current_temperature": 19.0,
+                "temperature": 21.0,
+                "mode": "heat",
+                "action": "heating"
+            }),
+        );
+        client.assert_published("home/heater/mode", "heat");
+        assert_eq!(
+            climate.current_temperature_template.as_deref(),
+            Some("{{ value_json.current_temperature }}")
+        );
+    }
+
+    #[tokio::test]
+    async fn can_set_climate_targets_and_parse_commands() {
+        let client = MockClient::default();
+        let ha = client.ha();
+        let climate = Climate::default()
+            .topic_prefix("home/ac")
+            .temperature_low_command_topic("~/low/set")
+            .temperature_low_state_topic("~/low")
+            .temperature_high_command_topic("~/high/set")
+            .temperature_high_state_topic("~/high")
+            .target_humidity_state_topic("~/humidity")
+            .target_humidity_state_template("{{ value_json.target_humidity }}")
+            .mode_command_topic("~/mode/set")
+            .power_command_topic("~/power/set");
+
+        ha.set_climate_targets(
+            &climate,
+            &ClimateTargets::default()
+                .temperature_range(19, Decimal::new(245, 1))
+                .humidity(45),
+        )
+        .await
+        .unwrap();
+
+        client.assert_published("home/ac/low", "19.0");
+        client.assert_published("home/ac/high", "24.5");
+        client.assert_published_json(
+            "home/ac/humidity",
+            json!({"target_temp_low": 19.0, "target_temp_high": 24.5, "target_humidity": 45.0}),
+        );
+
+        assert_eq!(
+            climate.command("home/ac/low/set", "18.5"),
+            Some(ClimateCommand::TemperatureLow(Decimal::new(185, 1)))
+        );
+        assert_eq!(
+            climate.command("home/ac/mode/set", "fan_only"),
+            Some(ClimateCommand::Mode(HvacMode::FanOnly))
+        );
+        assert_eq!(
+            climate.command("home/ac/power/set", "OFF"),
+            Some(ClimateCommand::Power(false))
+        );
+        assert_eq!(climate.command("home/ac/low/set", "warm"), None);
+        assert_eq!(climate.command("home/ac/mode/set", "sleep"), None);
+        assert_eq!(climate.command("home/ac/low", "18"), None);
+    }
+
+    #[test]
+    fn can_configure_climate_modes() {
+        let climate = Climate::default()
+            .modes(vec![HvacMode::Off, HvacMode::Heat, HvacMode::FanOnly])
+            .fan_modes(vec![FanMode::Auto, FanMode::Custom("turbo".to_string())])
+            .swing_modes(vec![SwingMode::On, SwingMode::Off]);
+
+        let attributes = serde_json::to_value(&climate).unwrap();
+        assert_eq!(attributes["modes"], json!(["off", "heat", "fan_only"]));
+        assert_eq!(attributes["fan_modes"], json!(["auto", "turbo"]));
+        assert_eq!(attributes["swing_modes"], json!(["on", "off"]));
+        assert_eq!(
+            ClimateState::default().fan_mode(FanMode::High).fan_mode,
+            Some("high".to_string())
+        );
+    }
+
+    #[test]
+    fn can_configure_float_temperatures() {
+        let climate = Climate::default()
+            .try_min_temp(16.5)
+            .and_then(|climate| climate.try_temp_step(0.5))
+            .unwrap();
+        assert_eq!(climate.min_temp, Some(dec!(16.5)));
+        assert_eq!(climate.temp_step, Some(dec!(0.5)));
+        assert_eq!(
+            ClimateTargets::default().try_temperature_range(19.5, 23.0),
+            Ok(ClimateTargets::default().temperature_range(dec!(19.5), 23))
+        );
+        assert!(Climate::default().try_max_temp(f64::INFINITY).is_err());
+        assert!(ClimateState::default().try_temperature(f32::NAN).is_err());
+    }
+}
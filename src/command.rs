@@ -0,0 +1,367 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    str::Utf8Error,
+    sync::Arc,
+};
+
+use anyhow::{anyhow, Result};
+use rumqttc::v5::{
+    mqttbytes::{
+        v5::{Filter, Packet, Publish},
+        QoS,
+    },
+    AsyncClient, Event, EventLoop,
+};
+
+use crate::Entity;
+
+/// A command message received on a subscribed topic.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Command<'a> {
+    /// The topic the command was received on.
+    pub topic: &'a str,
+    /// The raw command payload.
+    pub payload: &'a [u8],
+}
+
+impl Command<'_> {
+    /// The command payload as an UTF-8 string.
+    pub fn payload_str(&self) -> Result<&str, Utf8Error> {
+        std::str::from_utf8(self.payload)
+    }
+}
+
+type CommandHandler = Arc<dyn Fn(Command<'_>) + Send + Sync>;
+
+#[derive(Clone)]
+struct Route {
+    filter: String,
+    qos: QoS,
+    handler: CommandHandler,
+}
+
+/// Subscribes to entity command topics and dispatches the received commands to the registered handlers.
+///
+/// ```no_run
+/// # #[cfg(feature = "entity-switch")]
+/// # async fn example(
+/// #     client: ha_mqtt_discovery::v5::AsyncClient,
+/// #     mut eventloop: ha_mqtt_discovery::v5::EventLoop,
+/// # ) -> anyhow::Result<()> {
+/// # use ha_mqtt_discovery::{command::CommandRouter, mqtt::switch::Switch, Entity};
+/// # let switch = Entity::from(Switch::new("garage/door/set"));
+/// let mut router = CommandRouter::default();
+/// router.on_command(&switch, |command| println!("{:?}", command.payload_str()))?;
+/// router.run(&client, &mut eventloop).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Default)]
+pub struct CommandRouter {
+    routes: Vec<Route>,
+    trie: RouteTrie,
+    wildcard_threshold: Option<usize>,
+}
+
+impl CommandRouter {
+    /// Subscribes to `<prefix>/#` instead of the individual topics when at least `threshold` topic filters share a common prefix.
+    ///
+    /// This keeps the number of subscriptions under the broker limits and speeds up the resubscription on reconnect,
+    /// the messages received for unregistered topics are ignored.
+    pub fn with_wildcard_threshold(mut self, threshold: usize) -> Self {
+        self.wildcard_threshold = Some(threshold);
+        self
+    }
+
+    /// Registers a handler for the commands sent to the entity command topic.
+    ///
+    /// The subscription uses the entity `qos`, or QoS 0 which is the Home Assistant default.
+    pub fn on_command<F>(&mut self, entity: &Entity, handler: F) -> Result<()>
+    where
+        F: Fn(Command<'_>) + Send + Sync + 'static,
+    {
+        let command_topic = entity
+            .command_topic()
+            .ok_or(anyhow!("{} entity has no command topic", entity.platform()))?;
+        let topic = entity.resolve_topic(command_topic);
+        let qos = entity.qos().map(QoS::from).unwrap_or(QoS::AtMostOnce);
+        self.on_topic(topic, qos, handler);
+        Ok(())
+    }
+
+    /// Registers a handler for the messages received on a topic filter, wildcards `+` and `#` are supported.
+    pub fn on_topic<S, F>(&mut self, filter: S, qos: QoS, handler: F)
+    where
+        S: Into<String>,
+        F: Fn(Command<'_>) + Send + Sync + 'static,
+    {
+        let filter: String = filter.into();
+        self.trie.insert(&filter, self.routes.len());
+        self.routes.push(Route {
+            filter,
+            qos,
+            handler: Arc::new(handler),
+        });
+    }
+
+    /// The topic filters to subscribe to, with the highest QoS requested for each filter.
+    pub fn filters(&self) -> Vec<Filter> {
+        if let Some(threshold) = self.wildcard_threshold {
+            let mut tree = FilterTree::default();
+            for route in &self.routes {
+                tree.insert(&route.filter, route.qos);
+            }
+            return tree.compact(None, threshold);
+        }
+        let mut filters: Vec<Filter> = vec![];
+        for route in &self.routes {
+            match filters.iter_mut().find(|f| f.path == route.filter) {
+                Some(filter) => filter.qos = max_qos(Some(filter.qos), route.qos),
+                None => filters.push(Filter::new(route.filter.clone(), route.qos)),
+            }
+        }
+        filters
+    }
+
+    /// Subscribes to every registered topic filter.
+    pub async fn subscribe(&self, client: &AsyncClient) -> Result<()> {
+        let filters = self.filters();
+        if !filters.is_empty() {
+            client.subscribe_many(filters).await?;
+        }
+        Ok(())
+    }
+
+    /// Calls the handlers registered for the topic of the message.
+    ///
+    /// Returns `true` when at least one handler was called.
+    pub fn dispatch(&self, publish: &Publish) -> bool {
+        let Ok(topic) = std::str::from_utf8(&publish.topic) else {
+            return false;
+        };
+        let command = Command {
+            topic,
+            payload: &publish.payload,
+        };
+        let levels: Vec<&str> = topic.split('/').collect();
+        let mut matched = vec![];
+        self.trie.matches(&levels, &mut matched);
+        matched.sort_unstable();
+        matched.dedup();
+        for index in &matched {
+            (self.routes[*index].handler)(command);
+        }
+        !matched.is_empty()
+    }
+
+    /// Handles an event from the MQTT event loop: incoming messages are dispatched and the subscriptions are renewed on (re)connection.
+    pub fn handle_event(&self, client: &AsyncClient, event: &Event) -> Result<()> {
+        match event {
+            Event::Incoming(Packet::Publish(publish)) => {
+                self.dispatch(publish);
+            }
+            Event::Incoming(Packet::ConnAck(_)) => {
+                let filters = self.filters();
+                if !filters.is_empty() {
+                    client.try_subscribe_many(filters)?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Polls the MQTT event loop and dispatches the received commands until a connection error occurs.
+    pub async fn run(&self, client: &AsyncClient, eventloop: &mut EventLoop) -> Result<()> {
+        loop {
+            let event = eventloop.poll().await?;
+            self.handle_event(client, &event)?;
+        }
+    }
+}
+
+/// The registered routes indexed by topic filter levels.
+#[derive(Clone, Default)]
+struct RouteTrie {
+    /// Routes whose filter ends at this level.
+    routes: Vec<usize>,
+    /// Routes whose filter ends with a `#` wildcard after this level.
+    multi_level: Vec<usize>,
+    children: HashMap<String, RouteTrie>,
+}
+
+impl RouteTrie {
+    fn insert(&mut self, filter: &str, route: usize) {
+        let mut node = self;
+        for level in filter.split('/') {
+            if level == "#" {
+                node.multi_level.push(route);
+                return;
+            }
+            node = node.children.entry(level.to_string()).or_default();
+        }
+        node.routes.push(route);
+    }
+
+    fn matches(&self, levels: &[&str], matched: &mut Vec<usize>) {
+        matched.extend(&self.multi_level);
+        match levels.split_first() {
+            None => matched.extend(&self.routes),
+            Some((level, remaining_levels)) => {
+                if let Some(child) = self.children.get(*level) {
+                    child.matches(remaining_levels, matched);
+                }
+                if let Some(child) = self.children.get("+") {
+                    child.matches(remaining_levels, matched);
+                }
+            }
+        }
+    }
+}
+
+/// The topic filters to subscribe to, indexed by levels.
+#[derive(Default)]
+struct FilterTree {
+    qos: Option<QoS>,
+    children: BTreeMap<String, FilterTree>,
+}
+
+impl FilterTree {
+    fn insert(&mut self, filter: &str, qos: QoS) {
+        let mut node = self;
+        for level in filter.split('/') {
+            node = node.children.entry(level.to_string()).or_default();
+        }
+        node.qos = Some(max_qos(node.qos, qos));
+    }
+
+    /// The filters of the subtree, replaced with a single `<path>/#` filter when there are at least `threshold` of them.
+    fn compact(&self, path: Option<&str>, threshold: usize) -> Vec<Filter> {
+        let mut filters = vec![];
+        if let (Some(path), Some(qos)) = (path, self.qos) {
+            filters.push(Filter::new(path, qos));
+        }
+        for (level, child) in &self.children {
+            let child_path = match path {
+                Some(path) => format!("{path}/{level}"),
+                None => level.to_string(),
+            };
+            filters.extend(child.compact(Some(&child_path), threshold));
+        }
+        match path {
+            Some(path) if filters.len() >= threshold.max(2) => {
+                let qos = filters.iter().fold(QoS::AtMostOnce, |qos, filter| {
+                    max_qos(Some(qos), filter.qos)
+                });
+                vec![Filter::new(format!("{path}/#"), qos)]
+            }
+            _ => filters,
+        }
+    }
+}
+
+fn max_qos(current: Option<QoS>, qos: QoS) -> QoS {
+    match current {
+        Some(current) if (current as u8) > (qos as u8) => current,
+        _ => qos,
+    }
+}
+
+/// Whether a topic matches an MQTT topic filter, supporting the `+` and `#` wildcards.
+pub fn topic_matches(filter: &str, topic: &str) -> bool {
+    let mut filter_levels = filter.split('/');
+    let mut topic_levels = topic.split('/');
+    loop {
+        match (filter_levels.next(), topic_levels.next()) {
+            (Some("#"), _) => return true,
+            (Some("+"), Some(_)) => continue,
+            (Some(filter_level), Some(topic_level)) if filter_level == topic_level => continue,
+            (None, None) => return true,
+            _ => return false,
+        }
+    }
+}
+
+#[cfg(all(test, feature = "entity-switch"))]
+mod tests {
+    use std::sync::Mutex;
+
+    use crate::mqtt::{common::Qos, switch::Switch};
+
+    use super::*;
+
+    #[test]
+    fn can_match_topic_filters() {
+        assert!(topic_matches("home/switch/set", "home/switch/set"));
+        assert!(topic_matches("home/+/set", "home/switch/set"));
+        assert!(topic_matches("home/#", "home/switch/set"));
+        assert!(topic_matches("#", "home"));
+        assert!(!topic_matches("home/+/set", "home/switch/state"));
+        assert!(!topic_matches("home/switch", "home/switch/set"));
+        assert!(!topic_matches("home/switch/set", "home/switch"));
+    }
+
+    #[test]
+    fn can_dispatch_entity_commands() {
+        let received = Arc::new(Mutex::new(vec![]));
+        let mut router = CommandRouter::default();
+        let switch = Entity::from(
+            Switch::new("~/set")
+                .topic_prefix("home/heater")
+                .qos(Qos::AtLeastOnce),
+        );
+        let handler_received = received.clone();
+        router
+            .on_command(&switch, move |command| {
+                handler_received
+                    .lock()
+                    .unwrap()
+                    .push(command.payload_str().unwrap().to_string())
+            })
+            .unwrap();
+
+        assert_eq!(
+            router.filters(),
+            vec![Filter::new("home/heater/set", QoS::AtLeastOnce)]
+        );
+        assert!(router.dispatch(&Publish::new(
+            "home/heater/set",
+            QoS::AtLeastOnce,
+            "ON",
+            None
+        )));
+        assert!(!router.dispatch(&Publish::new(
+            "home/heater/state",
+            QoS::AtLeastOnce,
+            "ON",
+            None
+        )));
+        assert_eq!(*received.lock().unwrap(), vec!["ON"]);
+    }
+
+    #[test]
+    fn can_subscribe_with_wildcards() {
+        let mut router = CommandRouter::default().with_wildcard_threshold(2);
+        router.on_topic("home/heater/set", QoS::AtMostOnce, |_| {});
+        router.on_topic("home/fan/set", QoS::AtLeastOnce, |_| {});
+        router.on_topic("garden/pump/set", QoS::AtMostOnce, |_| {});
+        router.on_topic("garden/+/mode/set", QoS::AtMostOnce, |_| {});
+        router.on_topic("garage/door/set", QoS::AtMostOnce, |_| {});
+
+        assert_eq!(
+            router.filters(),
+            vec![
+                Filter::new("garage/door/set", QoS::AtMostOnce),
+                Filter::new("garden/#", QoS::AtMostOnce),
+                Filter::new("home/#", QoS::AtLeastOnce),
+            ]
+        );
+        assert!(router.dispatch(&Publish::new(
+            "garden/pool/mode/set",
+            QoS::AtMostOnce,
+            "eco",
+            None
+        )));
+        assert!(!router.dispatch(&Publish::new("home/light/set", QoS::AtMostOnce, "ON", None)));
+    }
+}
@@ -0,0 +1,811 @@
+use std::collections::{BTreeMap, HashSet};
+
+use anyhow::{anyhow, bail, Result};
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+
+use crate::{
+    client::{MqttPublisher, PublishOptions},
+    error::PublishError,
+    mqtt::common::{Device, Origin},
+    slug::{slugify, SlugOptions},
+    validation::ValidationError,
+    Entity, HomeAssistantMqtt,
+};
+
+/// The attributes which can be set once at the device level for all the components.
+const SHARED_ATTRIBUTES: [&str; 4] = ["avty", "avty_mode", "stat_t", "qos"];
+
+/// The entities of a device published together with device based discovery, on
+/// `<discovery_prefix>/device/[<node_id>/]<object_id>/config`.
+///
+/// Each entity is published as a component of the `cmps` map under a key chosen by the caller, the shared
+/// device and origin information are only sent once.
+///
+/// ```no_run
+/// # #[cfg(all(feature = "entity-sensor", feature = "entity-switch"))]
+/// # async fn example(ha: ha_mqtt_discovery::HomeAssistantMqtt) -> anyhow::Result<()> {
+/// # use ha_mqtt_discovery::{
+/// #     components::DeviceComponents,
+/// #     mqtt::{common::Device, sensor::Sensor, switch::Switch},
+/// # };
+/// let mut components = DeviceComponents::new("heater", Device::default().add_identifier("heater"))
+///     .component("temperature", Sensor::new("heater/temperature").unique_id("heater_temperature"));
+/// ha.publish_device_components(&components).await?;
+/// components.add_component(&ha, "switch", Switch::new("heater/set").unique_id("heater_switch")).await?;
+/// components.remove_component(&ha, "temperature").await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct DeviceComponents {
+    object_id: String,
+    device: Device,
+    origin: Origin,
+    components: BTreeMap<String, Entity>,
+    removed_components: BTreeMap<String, String>,
+    share_attributes: bool,
+    auto_suffix: bool,
+}
+
+impl DeviceComponents {
+    /// The ID of the device must only consist of characters from the character class [a-zA-Z0-9_-] (alphanumerics, underscore and hyphen).
+    pub fn new<S: Into<String>>(object_id: S, device: Device) -> Self {
+        Self {
+            object_id: object_id.into(),
+            device,
+            origin: Origin::default(),
+            components: BTreeMap::new(),
+            removed_components: BTreeMap::new(),
+            share_attributes: false,
+            auto_suffix: false,
+        }
+    }
+
+    /// Groups entities announced one by one as the components of their device.
+    ///
+    /// The components are keyed by the slug of their `unique_id` and share their common availability, state topic
+    /// and QoS at the device level. The object ID is the slug of the first identifier or connection of the device.
+    pub fn from_entities(
+        device: Device,
+        origin: Origin,
+        entities: Vec<Entity>,
+    ) -> Result<Self, PublishError> {
+        let object_id = device
+            .identifiers
+            .first()
+            .or(device.connections.first().map(|c| &c.identifier))
+            .map(|identifier| slugify(identifier))
+            .ok_or(PublishError::EmptyDeviceIdentifiers)?;
+        let mut components = Self::new(object_id, device)
+            .origin(origin)
+            .share_attributes(true);
+        let mut keys = HashSet::new();
+        for entity in entities {
+            let key = match (entity.unique_id(), &entity) {
+                (Some(unique_id), _) => unique_id.to_string(),
+                #[cfg(feature = "entity-device_trigger")]
+                (None, Entity::DeviceTrigger(trigger)) => trigger.discovery_object_id(),
+                (None, _) => return Err(PublishError::MissingUniqueId),
+            };
+            let key = SlugOptions::default().slugify_unique(&key, &mut keys);
+            if let Some(error) = components.collision(&key, &entity) {
+                return Err(error);
+            }
+            components.insert(key, entity);
+        }
+        Ok(components)
+    }
+
+    /// Whether the availability, state topic and QoS shared by every component are only published once at the
+    /// device level, `false` by default.
+    pub fn share_attributes(mut self, share_attributes: bool) -> Self {
+        self.share_attributes = share_attributes;
+        self
+    }
+
+    /// Whether the keys, `unique_id` and `object_id` already used by another component are suffixed with `_2`, `_3`...
+    /// when a component is added, `false` by default.
+    ///
+    /// Set it before adding the components.
+    pub fn auto_suffix(mut self, auto_suffix: bool) -> Self {
+        self.auto_suffix = auto_suffix;
+        self
+    }
+
+    /// Information about the application that supplies the device.
+    pub fn origin(mut self, origin: Origin) -> Self {
+        self.origin = origin;
+        self
+    }
+
+    /// Adds or replaces the component published under the key.
+    ///
+    /// Home Assistant rejects the components sharing the `unique_id` or `object_id` of another component: the
+    /// publication then fails with `PublishError::DuplicateComponent`. See `try_component` to handle the collisions
+    /// right away.
+    pub fn component<S: Into<String>, E: Into<Entity>>(mut self, key: S, entity: E) -> Self {
+        let (key, entity) = self.deduplicate(key.into(), entity.into());
+        self.insert(key, entity);
+        self
+    }
+
+    /// Adds a component, fails when its key, `unique_id` or `object_id` is already used by another component and
+    /// the auto-suffixing is disabled.
+    pub fn try_component<S: Into<String>, E: Into<Entity>>(
+        mut self,
+        key: S,
+        entity: E,
+    ) -> Result<Self, PublishError> {
+        let (key, entity) = self.deduplicate(key.into(), entity.into());
+        if self.components.contains_key(&key) {
+            return Err(PublishError::DuplicateComponent {
+                attribute: "key",
+                value: key,
+            });
+        }
+        if let Some(error) = self.collision(&key, &entity) {
+            return Err(error);
+        }
+        self.insert(key, entity);
+        Ok(self)
+    }
+
+    /// The `<object_id>` of the device discovery topic.
+    pub fn object_id(&self) -> &str {
+        &self.object_id
+    }
+
+    /// The components of the device by key.
+    pub fn components(&self) -> &BTreeMap<String, Entity> {
+        &self.components
+    }
+
+    /// Adds or replaces the component published under the key, then publishes the device configuration again.
+    /// Returns the key of the component, suffixed when the auto-suffixing is enabled and the key is already used.
+    ///
+    /// Fails without publishing when the `unique_id` or `object_id` of the component is already used by another
+    /// component and the auto-suffixing is disabled.
+    pub async fn add_component<C, S, E>(
+        &mut self,
+        ha: &HomeAssistantMqtt<C>,
+        key: S,
+        entity: E,
+    ) -> Result<String, PublishError>
+    where
+        C: MqttPublisher,
+        S: Into<String>,
+        E: Into<Entity>,
+    {
+        let (key, entity) = self.deduplicate(key.into(), entity.into());
+        if let Some(error) = self.collision(&key, &entity) {
+            return Err(error);
+        }
+        self.insert(key.clone(), entity);
+        self.publish(ha).await?;
+        Ok(key)
+    }
+
+    /// Removes the component published under the key, then publishes the device configuration again.
+    ///
+    /// Home Assistant deletes the entity when the component is published with its platform only, this marker
+    /// is dropped once published.
+    pub async fn remove_component<C: MqttPublisher>(
+        &mut self,
+        ha: &HomeAssistantMqtt<C>,
+        key: &str,
+    ) -> Result<Option<Entity>, PublishError> {
+        let Some(entity) = self.components.remove(key) else {
+            return Ok(None);
+        };
+        self.removed_components
+            .insert(key.to_string(), entity.platform().to_string());
+        self.publish(ha).await?;
+        Ok(Some(entity))
+    }
+
+    async fn publish<C: MqttPublisher>(
+        &mut self,
+        ha: &HomeAssistantMqtt<C>,
+    ) -> Result<(), PublishError> {
+        ha.publish_device_components(self).await?;
+        self.removed_components.clear();
+        Ok(())
+    }
+
+    /// Checks the device and every component against the Home Assistant rules, returns every violated rule.
+    ///
+    /// The device must be identified, and the components must have distinct `unique_id` and `object_id`.
+    pub fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        let mut errors = vec![];
+        if !self.device.is_identified() {
+            errors.push(ValidationError::MissingDeviceIdentifiers);
+        }
+        for (key, entity) in &self.components {
+            let component_error = |error| ValidationError::Component {
+                key: key.clone(),
+                error: Box::new(error),
+            };
+            if entity.unique_id().is_none() && entity.platform() != "device_automation" {
+                errors.push(component_error(ValidationError::MissingAttribute {
+                    attribute: "unique_id",
+                    required_by: "cmps",
+                }));
+            }
+            if let Err(violations) = entity.validate() {
+                errors.extend(violations.into_iter().map(component_error));
+            }
+        }
+        let mut unique_ids = HashSet::new();
+        let mut object_ids = HashSet::new();
+        for entity in self.components.values() {
+            if let Some(unique_id) = entity.unique_id().filter(|id| !unique_ids.insert(*id)) {
+                errors.push(ValidationError::DuplicateComponent {
+                    attribute: "unique_id",
+                    value: unique_id.to_string(),
+                });
+            }
+            if let Some(object_id) = entity.object_id().filter(|id| !object_ids.insert(*id)) {
+                errors.push(ValidationError::DuplicateComponent {
+                    attribute: "object_id",
+                    value: object_id.to_string(),
+                });
+            }
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// The error of a component sharing its `unique_id` or `object_id` with a component published under another key.
+    fn collision(&self, key: &str, entity: &Entity) -> Option<PublishError> {
+        let others = self
+            .components
+            .iter()
+            .filter(|(other_key, _)| *other_key != key)
+            .map(|(_, other)| other);
+        for other in others {
+            if let Some(unique_id) = entity
+                .unique_id()
+                .filter(|id| other.unique_id() == Some(id))
+            {
+                return Some(PublishError::DuplicateComponent {
+                    attribute: "unique_id",
+                    value: unique_id.to_string(),
+                });
+            }
+            if let Some(object_id) = entity
+                .object_id()
+                .filter(|id| other.object_id() == Some(id))
+            {
+                return Some(PublishError::DuplicateComponent {
+                    attribute: "object_id",
+                    value: object_id.to_string(),
+                });
+            }
+        }
+        None
+    }
+
+    /// Suffixes the key, `unique_id` and `object_id` already used by another component when the auto-suffixing is
+    /// enabled.
+    fn deduplicate(&self, key: String, mut entity: Entity) -> (String, Entity) {
+        if !self.auto_suffix {
+            return (key, entity);
+        }
+        let key = suffixed(&key, |candidate| self.components.contains_key(candidate));
+        let others = || self.components.values();
+        if let Some(unique_id) = entity.unique_id_mut().and_then(Option::as_mut) {
+            *unique_id = suffixed(unique_id, |candidate| {
+                others().any(|other| other.unique_id() == Some(candidate))
+            });
+        }
+        if let Some(object_id) = entity.object_id_mut().and_then(Option::as_mut) {
+            *object_id = suffixed(object_id, |candidate| {
+                others().any(|other| other.object_id() == Some(candidate))
+            });
+        }
+        (key, entity)
+    }
+
+    fn insert(&mut self, key: String, entity: Entity) {
+        self.removed_components.remove(&key);
+        self.components.insert(key, entity);
+    }
+
+    /// The device discovery configuration, with the `dev`, `o` and `cmps` attributes.
+    pub(crate) fn payload<F>(&self, entity_attributes: F) -> Result<Value, PublishError>
+    where
+        F: Fn(&Entity) -> Result<Value, serde_json::Error>,
+    {
+        if !self.device.is_identified() {
+            return Err(PublishError::EmptyDeviceIdentifiers);
+        }
+        let mut components = Map::new();
+        for (key, entity) in &self.components {
+            if entity.unique_id().is_none() && entity.platform() != "device_automation" {
+                return Err(PublishError::MissingUniqueId);
+            }
+            if let Some(error) = self.collision(key, entity) {
+                return Err(error);
+            }
+            let mut attributes = entity_attributes(entity)?;
+            if let Value::Object(attributes) = &mut attributes {
+                attributes.remove("dev");
+                attributes.remove("o");
+                attributes.insert("p".to_string(), Value::from(entity.platform()));
+            }
+            components.insert(key.clone(), attributes);
+        }
+        let shared = if self.share_attributes {
+            shared_attributes(&mut components)
+        } else {
+            Map::new()
+        };
+        for (key, platform) in &self.removed_components {
+            components.insert(key.clone(), json!({ "p": platform }));
+        }
+        let mut payload = shared;
+        payload.insert("dev".to_string(), json!(self.device));
+        payload.insert("o".to_string(), json!(self.origin));
+        payload.insert("cmps".to_string(), Value::Object(components));
+        Ok(Value::Object(payload))
+    }
+}
+
+/// The value, suffixed with `_2`, `_3`... until it isn't taken.
+fn suffixed(value: &str, taken: impl Fn(&str) -> bool) -> String {
+    let mut candidate = value.to_string();
+    let mut index = 1;
+    while taken(&candidate) {
+        index += 1;
+        candidate = format!("{value}_{index}");
+    }
+    candidate
+}
+
+/// Publishes the states of the components sharing one state topic as a single JSON document.
+///
+/// Each component reads its field of the document with a `{{ value_json.<field> }}` value template, the document
+/// keeps the last value of every field so each publication carries the complete state.
+///
+/// ```no_run
+/// # #[cfg(feature = "entity-sensor")]
+/// # async fn example(
+/// #     ha: ha_mqtt_discovery::HomeAssistantMqtt,
+/// #     device: ha_mqtt_discovery::mqtt::common::Device,
+/// # ) -> anyhow::Result<()> {
+/// # use ha_mqtt_discovery::{
+/// #     components::{DeviceComponents, DeviceRuntime},
+/// #     mqtt::sensor::Sensor,
+/// # };
+/// let components = DeviceComponents::new("heater", device)
+///     .component("temperature", Sensor::new("heater/state").value_template(DeviceRuntime::value_template("temp")))
+///     .component("power", Sensor::new("heater/state").value_template(DeviceRuntime::value_template("power")));
+/// let mut runtime = DeviceRuntime::new(&components, "heater/state")?;
+/// runtime.set("temperature", 21.5)?.set("power", 1200)?;
+/// runtime.publish(&ha).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct DeviceRuntime {
+    state_topic: String,
+    fields: BTreeMap<String, String>,
+    document: Map<String, Value>,
+}
+
+impl DeviceRuntime {
+    /// Routes the components whose state topic is the given one, they must read their state with a
+    /// `{{ value_json.<field> }}` value template.
+    pub fn new<S: Into<String>>(components: &DeviceComponents, state_topic: S) -> Result<Self> {
+        let state_topic = state_topic.into();
+        let mut fields = BTreeMap::new();
+        for (key, entity) in &components.components {
+            if entity.resolved_state_topic().as_deref() != Some(state_topic.as_str()) {
+                continue;
+            }
+            let attributes = entity.get_attributes()?;
+            let field = attributes
+                .get("val_tpl")
+                .and_then(Value::as_str)
+                .and_then(value_json_field)
+                .ok_or(anyhow!(
+                    "component {key} should read its state with a {{{{ value_json.<field> }}}} template"
+                ))?;
+            fields.insert(key.clone(), field.to_string());
+        }
+        if fields.is_empty() {
+            bail!("no component reads its state from {state_topic}");
+        }
+        Ok(Self {
+            state_topic,
+            fields,
+            document: Map::new(),
+        })
+    }
+
+    /// The value template reading the field of the state document.
+    pub fn value_template(field: &str) -> String {
+        format!("{{{{ value_json.{field} }}}}")
+    }
+
+    /// The shared state topic.
+    pub fn state_topic(&self) -> &str {
+        &self.state_topic
+    }
+
+    /// The field of the state document read by each component, by component key.
+    pub fn fields(&self) -> &BTreeMap<String, String> {
+        &self.fields
+    }
+
+    /// Sets the state of the component, it's published with the next document.
+    pub fn set<V: Serialize>(&mut self, key: &str, value: V) -> Result<&mut Self> {
+        let field = self.fields.get(key).ok_or(anyhow!(
+            "no component {key} reads its state from {}",
+            self.state_topic
+        ))?;
+        self.document
+            .insert(field.clone(), serde_json::to_value(value)?);
+        Ok(self)
+    }
+
+    /// The state document with the last value of every component.
+    pub fn payload(&self) -> Value {
+        Value::Object(self.document.clone())
+    }
+
+    /// Publishes the retained state document, returns `false` when it was skipped by the state deduplication.
+    pub async fn publish<C: MqttPublisher>(&self, ha: &HomeAssistantMqtt<C>) -> Result<bool> {
+        let options = PublishOptions {
+            content_type: Some("application/json".to_string()),
+            ..Default::default()
+        };
+        ha.publish_with_retain(&self.state_topic, self.payload().to_string(), true, options)
+            .await
+    }
+}
+
+/// The field read by a `{{ value_json.<field> }}` template.
+fn value_json_field(template: &str) -> Option<&str> {
+    let field = template
+        .trim()
+        .strip_prefix("{{")?
+        .strip_suffix("}}")?
+        .trim()
+        .strip_prefix("value_json.")?;
+    let valid = !field.is_empty() && field.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    valid.then_some(field)
+}
+
+/// Removes the attributes set to the same value in every component and returns them, the empty availabilities
+/// aren't shared.
+fn shared_attributes(components: &mut Map<String, Value>) -> Map<String, Value> {
+    let mut shared = Map::new();
+    if components.len() < 2 {
+        return shared;
+    }
+    for attribute in SHARED_ATTRIBUTES {
+        let mut values = components.values().map(|c| c.get(attribute));
+        let Some(Some(value)) = values.next() else {
+            continue;
+        };
+        if value.as_array().is_some_and(Vec::is_empty)
+            || value.as_str().is_some_and(|value| value.contains('~'))
+            || !values.all(|other| other == Some(value))
+        {
+            continue;
+        }
+        shared.insert(attribute.to_string(), value.clone());
+    }
+    if !shared.contains_key("avty") {
+        shared.remove("avty_mode");
+    }
+    for attributes in components.values_mut().filter_map(Value::as_object_mut) {
+        for attribute in shared.keys() {
+            attributes.remove(attribute);
+        }
+    }
+    shared
+}
+
+#[cfg(all(test, feature = "entity-sensor"))]
+mod tests {
+    #[cfg(feature = "entity-switch")]
+    use serde_json::json;
+
+    #[cfg(feature = "entity-switch")]
+    use crate::mqtt::{common::Qos, switch::Switch};
+    use crate::{
+        mqtt::{common::Availability, sensor::Sensor},
+        testing::MockClient,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    #[cfg(feature = "entity-switch")]
+    async fn can_update_device_components_incrementally() {
+        let client = MockClient::default();
+        let ha = HomeAssistantMqtt::new(client.clone(), "homeassistant");
+        let mut components =
+            DeviceComponents::new("heater", Device::default().add_identifier("heater"))
+                .origin(Origin::new("bridge"))
+                .component(
+                    "temperature",
+                    Sensor::new("heater/temperature").unique_id("heater_temperature"),
+                );
+
+        components
+            .add_component(
+                &ha,
+                "switch",
+                Switch::new("heater/set").unique_id("heater_switch"),
+            )
+            .await
+            .unwrap();
+        components
+            .remove_component(&ha, "temperature")
+            .await
+            .unwrap();
+
+        let published = client.published();
+        assert_eq!(published[0].topic, "homeassistant/device/heater/config");
+        let payload =
+            |index: usize| -> Value { serde_json::from_slice(&published[index].payload).unwrap() };
+        assert_eq!(
+            payload(0),
+            json!({
+                "dev": {"ids": ["heater"]},
+                "o": {"name": "bridge"},
+                "cmps": {
+                    "switch": {
+                        "p": "switch",
+                        "cmd_t": "heater/set",
+                        "uniq_id": "heater_switch",
+                        "avty_mode": "all",
+                        "avty": []
+                    },
+                    "temperature": {
+                        "p": "sensor",
+                        "stat_t": "heater/temperature",
+                        "uniq_id": "heater_temperature",
+                        "avty_mode": "all",
+                        "avty": []
+                    }
+                }
+            })
+        );
+        assert_eq!(payload(1)["cmps"]["temperature"], json!({"p": "sensor"}));
+        assert_eq!(
+            components.components().keys().collect::<Vec<_>>(),
+            vec!["switch"]
+        );
+        assert!(components.payload(Entity::get_attributes).unwrap()["cmps"]
+            .get("temperature")
+            .is_none());
+    }
+
+    #[test]
+    fn can_detect_component_collisions() {
+        let device = || Device::default().add_identifier("heater");
+        let sensor = || Sensor::new("heater/temperature").unique_id("heater_temperature");
+
+        let components = DeviceComponents::new("heater", device())
+            .try_component("temperature", sensor())
+            .unwrap();
+        assert!(matches!(
+            components
+                .clone()
+                .try_component("temperature", sensor().unique_id("other")),
+            Err(PublishError::DuplicateComponent {
+                attribute: "key",
+                ..
+            })
+        ));
+        assert!(matches!(
+            components.clone().try_component("other", sensor()),
+            Err(PublishError::DuplicateComponent {
+                attribute: "unique_id",
+                ..
+            })
+        ));
+
+        let components = DeviceComponents::new("heater", device())
+            .auto_suffix(true)
+            .component("temperature", sensor().object_id("temperature"))
+            .try_component("temperature", sensor().object_id("temperature"))
+            .unwrap();
+        let suffixed = &components.components()["temperature_2"];
+        assert_eq!(suffixed.unique_id(), Some("heater_temperature_2"));
+        assert_eq!(suffixed.object_id(), Some("temperature_2"));
+        assert!(components.payload(Entity::get_attributes).is_ok());
+    }
+
+    #[tokio::test]
+    async fn can_validate_device_components() {
+        let sensor = || Sensor::new("heater/temperature").unique_id("heater_temperature");
+        let components = DeviceComponents::new("heater", Device::default().name("Heater"))
+            .component(
+                "temperature",
+                sensor().availability(Availability::default().expire_after(0)),
+            )
+            .component("other", sensor())
+            .component("power", Sensor::new("heater/power"));
+
+        assert_eq!(
+            components.validate(),
+            Err(vec![
+                ValidationError::MissingDeviceIdentifiers,
+                ValidationError::Component {
+                    key: "power".to_string(),
+                    error: Box::new(ValidationError::MissingAttribute {
+                        attribute: "unique_id",
+                        required_by: "cmps"
+                    })
+                },
+                ValidationError::Component {
+                    key: "temperature".to_string(),
+                    error: Box::new(ValidationError::InvalidExpireAfter)
+                },
+                ValidationError::DuplicateComponent {
+                    attribute: "unique_id",
+                    value: "heater_temperature".to_string()
+                },
+            ])
+        );
+
+        let ha = HomeAssistantMqtt::new(MockClient::default(), "homeassistant");
+        let components =
+            DeviceComponents::new("heater", Device::default().add_identifier("heater")).component(
+                "temperature",
+                sensor().availability(Availability::default().expire_after(0)),
+            );
+        assert!(matches!(
+            ha.publish_device_components(&components).await,
+            Err(PublishError::Invalid(errors)) if errors.len() == 1
+        ));
+    }
+
+    #[tokio::test]
+    async fn can_add_components_with_auto_suffix() {
+        let ha = HomeAssistantMqtt::new(MockClient::default(), "homeassistant");
+        let device = || Device::default().add_identifier("heater");
+        let sensor = || Sensor::new("heater/temperature").unique_id("heater_temperature");
+
+        let mut components = DeviceComponents::new("heater", device())
+            .auto_suffix(true)
+            .component("temperature", sensor());
+        let key = components
+            .add_component(&ha, "temperature", sensor())
+            .await
+            .unwrap();
+        assert_eq!(key, "temperature_2");
+        assert_eq!(
+            components.components()["temperature_2"].unique_id(),
+            Some("heater_temperature_2")
+        );
+
+        let mut components =
+            DeviceComponents::new("heater", device()).component("temperature", sensor());
+        assert!(matches!(
+            components.add_component(&ha, "other", sensor()).await,
+            Err(PublishError::DuplicateComponent {
+                attribute: "unique_id",
+                ..
+            })
+        ));
+        assert!(!components.components().contains_key("other"));
+
+        let components = DeviceComponents::new("heater", device())
+            .component("temperature", sensor())
+            .component("other", sensor());
+        assert!(matches!(
+            components.payload(Entity::get_attributes),
+            Err(PublishError::DuplicateComponent { .. })
+        ));
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "entity-switch")]
+    async fn can_publish_shared_state_documents() {
+        let client = MockClient::default();
+        let ha = HomeAssistantMqtt::new(client.clone(), "homeassistant");
+        let components =
+            DeviceComponents::new("heater", Device::default().add_identifier("heater"))
+                .component(
+                    "temperature",
+                    Sensor::new("heater/state")
+                        .unique_id("heater_temperature")
+                        .value_template(DeviceRuntime::value_template("temp")),
+                )
+                .component(
+                    "switch",
+                    Switch::new("heater/set")
+                        .unique_id("heater_switch")
+                        .state_topic("heater/state")
+                        .value_template("{{value_json.on}}"),
+                )
+                .component(
+                    "power",
+                    Sensor::new("heater/power").unique_id("heater_power"),
+                );
+
+        let mut runtime = DeviceRuntime::new(&components, "heater/state").unwrap();
+        assert_eq!(runtime.fields().len(), 2);
+        runtime
+            .set("temperature", 21.5)
+            .unwrap()
+            .set("switch", "ON")
+            .unwrap();
+        assert!(runtime.set("power", 1200).is_err());
+        assert!(runtime.publish(&ha).await.unwrap());
+        runtime.set("temperature", 22).unwrap();
+        runtime.publish(&ha).await.unwrap();
+
+        let published = client.last_published("heater/state").unwrap();
+        assert_eq!(
+            serde_json::from_slice::<Value>(&published.payload).unwrap(),
+            json!({"temp": 22, "on": "ON"})
+        );
+        assert!(published.retain);
+        assert!(DeviceRuntime::new(&components, "heater/power").is_err());
+        assert!(DeviceRuntime::new(&components, "heater/other").is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "entity-switch")]
+    fn can_group_entities_by_device() {
+        let availability = Availability::single("heater/availability");
+        let components = DeviceComponents::from_entities(
+            Device::default().add_identifier("Heater 01"),
+            Origin::new("bridge"),
+            vec![
+                Sensor::new("heater/state")
+                    .unique_id("heater_temperature")
+                    .availability(availability.clone())
+                    .qos(Qos::AtLeastOnce)
+                    .into(),
+                Switch::new("heater/set")
+                    .unique_id("heater_switch")
+                    .state_topic("heater/state")
+                    .availability(availability)
+                    .into(),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(components.object_id(), "heater_01");
+        assert_eq!(
+            components.payload(Entity::get_attributes).unwrap(),
+            json!({
+                "dev": {"ids": ["Heater 01"]},
+                "o": {"name": "bridge"},
+                "avty": [{"t": "heater/availability"}],
+                "avty_mode": "all",
+                "stat_t": "heater/state",
+                "cmps": {
+                    "heater_switch": {
+                        "p": "switch",
+                        "cmd_t": "heater/set",
+                        "uniq_id": "heater_switch"
+                    },
+                    "heater_temperature": {
+                        "p": "sensor",
+                        "uniq_id": "heater_temperature",
+                        "qos": "1"
+                    }
+                }
+            })
+        );
+        assert!(DeviceComponents::from_entities(
+            Device::default().add_identifier("heater"),
+            Origin::new("bridge"),
+            vec![Sensor::new("heater/state").into()],
+        )
+        .is_err());
+    }
+}
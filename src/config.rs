@@ -0,0 +1,329 @@
+use std::path::Path;
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde_derive::Deserialize;
+use serde_json::Value;
+
+#[cfg(feature = "client")]
+use crate::components::DeviceComponents;
+use crate::{
+    mqtt::common::{Device, Origin},
+    Entity,
+};
+
+/// Entities defined in a YAML file, so that their names, icons or device classes can be changed without
+/// recompiling the application publishing them.
+///
+/// The entities are listed with their `platform` and their attributes under their full names, like in
+/// `configuration.yaml`. The `device` and `origin` are applied to the entities which don't set their own:
+///
+/// ```yaml
+/// device:
+///   name: Garage
+///   identifiers: [garage]
+/// origin:
+///   name: garage-bridge
+/// entities:
+///   - platform: sensor
+///     name: Temperature
+///     unique_id: garage_temperature
+///     state_topic: ${BASE_TOPIC:-garage}/temperature
+///     device_class: temperature
+///   - platform: switch
+///     name: Door
+///     unique_id: garage_door
+///     command_topic: ${BASE_TOPIC:-garage}/door/set
+/// ```
+///
+/// The `${NAME}` and `${NAME:-default}` references to environment variables are replaced in the values once the file
+/// is parsed, so the variables can hold any character and the references in the comments are ignored. The default
+/// applies when the variable is unset or empty, and `$$` stands for a single `$`. An interpolated value reading as a
+/// number or a boolean is typed as such, like an unquoted YAML scalar.
+#[derive(Clone)]
+pub struct EntitiesConfig {
+    pub device: Option<Device>,
+    pub origin: Option<Origin>,
+    pub entities: Vec<Entity>,
+}
+
+#[derive(Deserialize)]
+struct EntitiesFile {
+    device: Option<Device>,
+    origin: Option<Origin>,
+    #[serde(default)]
+    entities: Vec<Value>,
+}
+
+impl EntitiesConfig {
+    /// Loads the entities of a YAML file, interpolating the environment variables of the process.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let yaml = std::fs::read_to_string(path)
+            .with_context(|| format!("can't read entities file '{}'", path.display()))?;
+        Self::from_yaml(&yaml)
+            .with_context(|| format!("invalid entities file '{}'", path.display()))
+    }
+
+    /// Parses the entities of a YAML document, interpolating the environment variables of the process.
+    pub fn from_yaml(yaml: &str) -> Result<Self> {
+        Self::from_yaml_with(yaml, |name| std::env::var(name).ok())
+    }
+
+    /// Parses the entities of a YAML document, interpolating the variables resolved by `lookup`.
+    ///
+    /// Every entity is checked with `Entity::validate`, the errors of all the entities are reported together.
+    pub fn from_yaml_with<F>(yaml: &str, lookup: F) -> Result<Self>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let mut document = serde_yaml::from_str(yaml)?;
+        interpolate_values(&mut document, &lookup)?;
+        let file: EntitiesFile = serde_json::from_value(document)?;
+        let mut entities = Vec::with_capacity(file.entities.len());
+        let mut errors = vec![];
+        for (index, attributes) in file.entities.into_iter().enumerate() {
+            let mut entity =
+                parse_entity(attributes).with_context(|| format!("entities[{index}]"))?;
+            if let Some(device) = &file.device {
+                if *entity.device() == Device::default() {
+                    entity.set_device(device.clone());
+                }
+            }
+            if let Some(origin) = &file.origin {
+                if *entity.origin() == Origin::default() {
+                    entity.set_origin(origin.clone());
+                }
+            }
+            if let Err(violations) = entity.validate() {
+                errors.extend(violations.into_iter().map(|violation| {
+                    format!("entities[{index}] ({}): {violation}", entity.platform())
+                }));
+            }
+            entities.push(entity);
+        }
+        if !errors.is_empty() {
+            bail!("invalid entities:\n  {}", errors.join("\n  "));
+        }
+        Ok(Self {
+            device: file.device,
+            origin: file.origin,
+            entities,
+        })
+    }
+
+    /// Groups the entities as the components of the `device` of the file, see `DeviceComponents::from_entities`.
+    #[cfg(feature = "client")]
+    pub fn device_components(self) -> Result<DeviceComponents> {
+        let device = self
+            .device
+            .ok_or_else(|| anyhow!("the entities file has no device"))?;
+        Ok(DeviceComponents::from_entities(
+            device,
+            self.origin.unwrap_or_default(),
+            self.entities,
+        )?)
+    }
+}
+
+/// Parses an entity of the `entities` list, the other attributes than `platform` are the entity attributes.
+fn parse_entity(mut attributes: Value) -> Result<Entity> {
+    let platform = match attributes.as_object_mut().map(|a| a.remove("platform")) {
+        Some(Some(Value::String(platform))) => platform,
+        Some(_) => bail!("the entity has no platform"),
+        None => bail!("expected the entity attributes, got '{attributes}'"),
+    };
+    Entity::from_platform(&platform, attributes)
+}
+
+/// Interpolates the string values of the parsed document, the keys are left as is.
+fn interpolate_values<F>(value: &mut Value, lookup: &F) -> Result<()>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    match value {
+        Value::String(text) if text.contains('$') => {
+            let interpolated = interpolate(text, lookup)?;
+            *value = if text.contains("${") {
+                scalar(interpolated)
+            } else {
+                Value::String(interpolated)
+            };
+        }
+        Value::Array(values) => {
+            for value in values {
+                interpolate_values(value, lookup)?;
+            }
+        }
+        Value::Object(attributes) => {
+            for value in attributes.values_mut() {
+                interpolate_values(value, lookup)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Types an interpolated value as a number or a boolean when it reads as one, like an unquoted YAML scalar.
+fn scalar(text: String) -> Value {
+    match serde_yaml::from_str(&text) {
+        Ok(value @ (Value::Number(_) | Value::Bool(_))) => value,
+        _ => Value::String(text),
+    }
+}
+
+/// Replaces the `${NAME}` and `${NAME:-default}` references with the values resolved by `lookup`.
+fn interpolate<F>(text: &str, lookup: &F) -> Result<String>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    let mut interpolated = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('$') {
+        interpolated.push_str(&rest[..start]);
+        rest = &rest[start + 1..];
+        if let Some(after) = rest.strip_prefix('$') {
+            interpolated.push('$');
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix('{') {
+            let end = after
+                .find('}')
+                .ok_or_else(|| anyhow!("unterminated variable reference '${{{after}'"))?;
+            let (name, default) = match after[..end].split_once(":-") {
+                Some((name, default)) => (name, Some(default)),
+                None => (&after[..end], None),
+            };
+            if name.is_empty() {
+                bail!("empty variable reference '${{{}}}'", &after[..end]);
+            }
+            match (lookup(name), default) {
+                (Some(value), Some(default)) if value.is_empty() => interpolated.push_str(default),
+                (Some(value), _) => interpolated.push_str(&value),
+                (None, Some(default)) => interpolated.push_str(default),
+                (None, None) => bail!("environment variable '{name}' is not set"),
+            }
+            rest = &after[end + 1..];
+        } else {
+            interpolated.push('$');
+        }
+    }
+    interpolated.push_str(rest);
+    Ok(interpolated)
+}
+
+#[cfg(all(test, feature = "entity-sensor", feature = "entity-switch"))]
+mod tests {
+    use crate::mqtt::{common::Availability, device_classes::SensorDeviceClass, sensor::Sensor};
+
+    use super::*;
+
+    const ENTITIES: &str = r#"
+        device:
+          name: Garage
+          identifiers: [garage]
+        origin:
+          name: garage-bridge
+        entities:
+          - platform: sensor
+            name: Temperature
+            unique_id: garage_temperature
+            state_topic: ${BASE_TOPIC}/temperature
+            device_class: temperature
+            expire_after: ${EXPIRE_AFTER:-600}
+          - platform: switch
+            name: Door $$1
+            unique_id: garage_door
+            command_topic: ${BASE_TOPIC}/door/set
+    "#;
+
+    #[test]
+    fn can_load_entities_config() {
+        let lookup = |name: &str| (name == "BASE_TOPIC").then(|| "home/garage".to_string());
+        let config = EntitiesConfig::from_yaml_with(ENTITIES, lookup).unwrap();
+
+        let Entity::Sensor(sensor) = &config.entities[0] else {
+            panic!("expected a sensor");
+        };
+        assert_eq!(
+            *sensor,
+            Sensor::new("home/garage/temperature")
+                .name("Temperature")
+                .unique_id("garage_temperature")
+                .device_class(SensorDeviceClass::Temperature)
+                .availability(Availability::default().expire_after(600))
+                .device(Device::default().name("Garage").add_identifier("garage"))
+                .origin(Origin::new("garage-bridge"))
+        );
+        assert_eq!(config.entities[1].name(), Some("Door $1"));
+        assert_eq!(
+            config.entities[1].command_topic(),
+            Some("home/garage/door/set")
+        );
+
+        #[cfg(feature = "client")]
+        {
+            let components = config.device_components().unwrap();
+            assert_eq!(components.object_id(), "garage");
+            assert_eq!(components.components().len(), 2);
+        }
+
+        let error = EntitiesConfig::from_yaml_with(ENTITIES, |_| None)
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.to_string(),
+            "environment variable 'BASE_TOPIC' is not set"
+        );
+    }
+
+    #[test]
+    fn can_interpolate_any_value() {
+        let lookup = |name: &str| match name {
+            "NAME" => Some("Door: left # main\nentrance".to_string()),
+            "TOPIC" => Some("home/${HOME}".to_string()),
+            _ => None,
+        };
+        let config = EntitiesConfig::from_yaml_with(
+            r#"
+                # the ${UNSET} references of the comments are ignored
+                entities:
+                  - platform: switch
+                    name: ${NAME}
+                    command_topic: "${TOPIC}/set" # ${UNSET}
+            "#,
+            lookup,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.entities[0].name(),
+            Some("Door: left # main\nentrance")
+        );
+        assert_eq!(config.entities[0].command_topic(), Some("home/${HOME}/set"));
+    }
+
+    #[test]
+    fn can_validate_entities_config() {
+        let error = EntitiesConfig::from_yaml_with(
+            r#"
+                device:
+                  name: Garage
+                entities:
+                  - platform: number
+                    command_topic: garage/number
+                    min: 10
+                    max: 0
+            "#,
+            |_| None,
+        )
+        .err()
+        .unwrap();
+        assert_eq!(
+            error.to_string(),
+            "invalid entities:\n  \
+             entities[0] (number): 'device' only works when 'unique_id' is set\n  \
+             entities[0] (number): at least one of 'identifiers' or 'connections' must be present to identify the device\n  \
+             entities[0] (number): 'min' 10 must be lower than 'max' 0"
+        );
+    }
+}
@@ -0,0 +1,387 @@
+use std::time::Duration;
+
+use anyhow::{bail, Result};
+use rumqttc::{
+    v5::{
+        mqttbytes::v5::{LastWill, Packet, Publish},
+        AsyncClient, ConnectionError, Event, EventLoop, MqttOptions,
+    },
+    Transport,
+};
+use tokio::sync::mpsc::{self, Receiver, Sender};
+
+use crate::{
+    availability::AvailabilityPayloads,
+    status::{HomeAssistantStatus, StatusWatcher},
+    topic::DEFAULT_DISCOVERY_PREFIX,
+    HomeAssistantMqtt,
+};
+
+/// How the connection to the broker is secured.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Tls {
+    /// TLS verified with the root certificates of the platform.
+    PlatformRoots,
+    /// TLS verified with the given PEM encoded CA, with an optional PEM encoded client certificate and key.
+    CustomCa {
+        ca: Vec<u8>,
+        client_auth: Option<(Vec<u8>, Vec<u8>)>,
+    },
+}
+
+/// The connection settings of `HomeAssistantMqtt::connect`.
+///
+/// ```no_run
+/// # fn main() -> anyhow::Result<()> {
+/// # use ha_mqtt_discovery::{
+/// #     availability::AvailabilityPayloads,
+/// #     connect::{ConnectOptions, Tls},
+/// #     HomeAssistantMqtt,
+/// # };
+/// let options = ConnectOptions::new("broker.local", "garage")
+///     .credentials("garage", "secret")
+///     .tls(Tls::PlatformRoots)
+///     .availability_last_will("garage/availability", AvailabilityPayloads::default());
+/// let (home_assistant, mut incoming) = HomeAssistantMqtt::connect(options)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct ConnectOptions {
+    host: String,
+    port: Option<u16>,
+    client_id: String,
+    credentials: Option<(String, String)>,
+    tls: Option<Tls>,
+    keep_alive: Duration,
+    last_will: Option<LastWill>,
+    capacity: usize,
+    incoming_capacity: usize,
+    reconnect_delay: Duration,
+    discovery_prefix: String,
+    republish_discovery: bool,
+}
+
+impl ConnectOptions {
+    pub fn new<H: Into<String>, C: Into<String>>(host: H, client_id: C) -> Self {
+        Self {
+            host: host.into(),
+            port: None,
+            client_id: client_id.into(),
+            credentials: None,
+            tls: None,
+            keep_alive: Duration::from_secs(60),
+            last_will: None,
+            capacity: 10,
+            incoming_capacity: 100,
+            reconnect_delay: Duration::from_secs(5),
+            discovery_prefix: DEFAULT_DISCOVERY_PREFIX.to_string(),
+            republish_discovery: true,
+        }
+    }
+
+    /// The port of the broker, 1883 by default or 8883 with TLS.
+    pub fn port(mut self, port: u16) -> Self {
+        self.port = Some(port);
+        self
+    }
+
+    /// The username and password of the client.
+    pub fn credentials<U: Into<String>, P: Into<String>>(
+        mut self,
+        username: U,
+        password: P,
+    ) -> Self {
+        self.credentials = Some((username.into(), password.into()));
+        self
+    }
+
+    /// Secures the connection with TLS.
+    pub fn tls(mut self, tls: Tls) -> Self {
+        self.tls = Some(tls);
+        self
+    }
+
+    /// The keep-alive interval, 60 seconds by default.
+    pub fn keep_alive(mut self, keep_alive: Duration) -> Self {
+        self.keep_alive = keep_alive;
+        self
+    }
+
+    /// The message published by the broker when the connection is lost.
+    pub fn last_will(mut self, last_will: LastWill) -> Self {
+        self.last_will = Some(last_will);
+        self
+    }
+
+    /// Marks the availability topic unavailable when the connection is lost.
+    pub fn availability_last_will<T: AsRef<str>>(
+        self,
+        availability_topic: T,
+        payloads: AvailabilityPayloads,
+    ) -> Self {
+        self.last_will(payloads.last_will(availability_topic.as_ref()))
+    }
+
+    /// The capacity of the request channel of the MQTT client, 10 by default.
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// The number of received messages buffered until `IncomingMessages::recv`, 100 by default.
+    ///
+    /// Once the buffer is full, the event loop waits for the messages to be received: a slow consumer delays the
+    /// publications and the keep-alive pings of the client.
+    pub fn incoming_capacity(mut self, incoming_capacity: usize) -> Self {
+        self.incoming_capacity = incoming_capacity;
+        self
+    }
+
+    /// The delay before reconnecting after a connection error, 5 seconds by default.
+    pub fn reconnect_delay(mut self, reconnect_delay: Duration) -> Self {
+        self.reconnect_delay = reconnect_delay;
+        self
+    }
+
+    /// The discovery prefix, `homeassistant` by default.
+    pub fn discovery_prefix<T: Into<String>>(mut self, discovery_prefix: T) -> Self {
+        self.discovery_prefix = discovery_prefix.into();
+        self
+    }
+
+    /// Whether the published discovery configurations are published again on every reconnection and Home Assistant
+    /// birth message, `true` by default.
+    ///
+    /// Brokers without persistence lose the retained configurations when they restart.
+    pub fn republish_discovery(mut self, republish_discovery: bool) -> Self {
+        self.republish_discovery = republish_discovery;
+        self
+    }
+
+    /// The `rumqttc` options matching these settings.
+    pub fn mqtt_options(&self) -> Result<MqttOptions> {
+        if self.host.is_empty() {
+            bail!("the broker host is empty");
+        }
+        if self.client_id.is_empty() || self.client_id.starts_with(char::is_whitespace) {
+            bail!("invalid client id {:?}", self.client_id);
+        }
+        let default_port = if self.tls.is_some() { 8883 } else { 1883 };
+        let mut options = MqttOptions::new(
+            &self.client_id,
+            &self.host,
+            self.port.unwrap_or(default_port),
+        );
+        options.set_keep_alive(self.keep_alive);
+        if let Some((username, password)) = &self.credentials {
+            options.set_credentials(username, password);
+        }
+        if let Some(last_will) = &self.last_will {
+            options.set_last_will(last_will.clone());
+        }
+        match &self.tls {
+            None => {}
+            Some(Tls::PlatformRoots) => {
+                options.set_transport(Transport::tls_with_default_config());
+            }
+            Some(Tls::CustomCa { ca, client_auth }) => {
+                options.set_transport(Transport::tls(ca.clone(), client_auth.clone(), None));
+            }
+        }
+        Ok(options)
+    }
+}
+
+/// The messages received on the subscribed topics, returned by `HomeAssistantMqtt::connect`.
+///
+/// Up to `ConnectOptions::incoming_capacity` messages are buffered until they are received, dropping the handle
+/// discards them.
+pub struct IncomingMessages {
+    receiver: Receiver<Publish>,
+}
+
+impl IncomingMessages {
+    /// The next message, `None` once the event loop stopped because every clone of the client was dropped.
+    pub async fn recv(&mut self) -> Option<Publish> {
+        self.receiver.recv().await
+    }
+}
+
+impl HomeAssistantMqtt<AsyncClient> {
+    /// Connects to the broker and spawns its event loop on the current tokio runtime.
+    ///
+    /// The event loop reconnects after the connection errors and stops once every clone of the client is dropped. Unless
+    /// disabled, it publishes the discovery configurations again on every reconnection and Home Assistant birth message.
+    ///
+    /// The topics subscribed by `subscribe_device_commands` are subscribed again when the broker didn't keep the
+    /// session, the other subscriptions of the client must be made again by the caller.
+    pub fn connect(options: ConnectOptions) -> Result<(Self, IncomingMessages)> {
+        let mqtt_options = options.mqtt_options()?;
+        let (client, eventloop) = AsyncClient::new(mqtt_options, options.capacity);
+        let (sender, receiver) = mpsc::channel(options.incoming_capacity);
+        let home_assistant = HomeAssistantMqtt::try_new(client, &options.discovery_prefix)?;
+        let republisher = options
+            .republish_discovery
+            .then(|| Republisher::new(home_assistant.clone()));
+        tokio::spawn(run_eventloop(
+            eventloop,
+            sender,
+            home_assistant.clone(),
+            republisher,
+            options.reconnect_delay,
+        ));
+        Ok((home_assistant, IncomingMessages { receiver }))
+    }
+}
+
+/// Publishes the discovery configurations again when they may have been lost.
+struct Republisher {
+    home_assistant: HomeAssistantMqtt,
+    status: StatusWatcher,
+    connected: bool,
+}
+
+impl Republisher {
+    fn new(home_assistant: HomeAssistantMqtt) -> Self {
+        Self {
+            status: StatusWatcher::new(home_assistant.discovery_prefix.clone()),
+            home_assistant,
+            connected: false,
+        }
+    }
+
+    /// Returns whether the configurations are published again.
+    fn handle_event(&mut self, event: &Event) -> bool {
+        let reconnected = matches!(event, Event::Incoming(Packet::ConnAck(_)))
+            && std::mem::replace(&mut self.connected, true);
+        let status = self
+            .status
+            .handle_event(&self.home_assistant.client, event)
+            .ok()
+            .flatten();
+        if reconnected || status == Some(HomeAssistantStatus::Online) {
+            // the event loop must keep being polled while the configurations are queued
+            let home_assistant = self.home_assistant.clone();
+            tokio::spawn(async move { home_assistant.republish_discovery().await });
+            return true;
+        }
+        false
+    }
+}
+
+async fn run_eventloop(
+    mut eventloop: EventLoop,
+    sender: Sender<Publish>,
+    home_assistant: HomeAssistantMqtt,
+    mut republisher: Option<Republisher>,
+    reconnect_delay: Duration,
+) {
+    loop {
+        match eventloop.poll().await {
+            Ok(event) => {
+                if let Some(republisher) = &mut republisher {
+                    republisher.handle_event(&event);
+                }
+                match event {
+                    Event::Incoming(Packet::ConnAck(connack)) if !connack.session_present => {
+                        let home_assistant = home_assistant.clone();
+                        tokio::spawn(async move { home_assistant.resubscribe().await });
+                    }
+                    Event::Incoming(Packet::Publish(publish)) => {
+                        // the handle may have been dropped, the client still needs the event loop
+                        let _ = sender.send(publish).await;
+                    }
+                    _ => {}
+                }
+            }
+            Err(ConnectionError::RequestsDone) => return,
+            Err(_) => tokio::time::sleep(reconnect_delay).await,
+        }
+    }
+}
+
+#[cfg(all(test, feature = "entity-sensor"))]
+mod tests {
+    use rumqttc::v5::mqttbytes::{
+        v5::{ConnAck, ConnectReturnCode},
+        QoS,
+    };
+
+    use crate::{mqtt::sensor::Sensor, Entity};
+
+    use super::*;
+
+    #[test]
+    fn can_build_mqtt_options() {
+        let options = ConnectOptions::new("broker.local", "garage")
+            .credentials("garage", "secret")
+            .tls(Tls::CustomCa {
+                ca: b"-----BEGIN CERTIFICATE-----".to_vec(),
+                client_auth: None,
+            })
+            .keep_alive(Duration::from_secs(30))
+            .availability_last_will("garage/availability", AvailabilityPayloads::default());
+
+        let mqtt_options = options.mqtt_options().unwrap();
+        assert_eq!(
+            mqtt_options.broker_address(),
+            ("broker.local".to_string(), 8883)
+        );
+        assert_eq!(
+            mqtt_options.credentials(),
+            Some(("garage".to_string(), "secret".to_string()))
+        );
+        assert_eq!(mqtt_options.keep_alive(), Duration::from_secs(30));
+        assert_eq!(
+            mqtt_options.last_will(),
+            Some(AvailabilityPayloads::default().last_will("garage/availability"))
+        );
+        assert!(matches!(mqtt_options.transport(), Transport::Tls(_)));
+        assert!(ConnectOptions::new("", "garage").mqtt_options().is_err());
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a broker on localhost:1883"]
+    async fn can_connect_with_options() {
+        let (home_assistant, _incoming) =
+            HomeAssistantMqtt::connect(ConnectOptions::new("localhost", "garage")).unwrap();
+        assert!(home_assistant
+            .publish_entity(Entity::from(
+                Sensor::new("garage/temperature").unique_id("temperature")
+            ))
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn can_republish_discovery_on_reconnection() {
+        let (client, _eventloop) =
+            AsyncClient::new(MqttOptions::new("garage", "localhost", 1883), 10);
+        let mut republisher =
+            Republisher::new(HomeAssistantMqtt::try_new(client, "homeassistant/").unwrap());
+        let home_assistant = republisher.home_assistant.clone();
+        let sensor = Entity::from(Sensor::new("garage/temperature").unique_id("temperature"));
+        home_assistant.publish_entity(sensor).await.unwrap();
+        assert_eq!(
+            home_assistant.published_discovery_topics(),
+            vec!["homeassistant/sensor/temperature/config"]
+        );
+        assert_eq!(home_assistant.republish_discovery().await.unwrap(), 1);
+
+        let connack = Event::Incoming(Packet::ConnAck(ConnAck {
+            session_present: false,
+            code: ConnectReturnCode::Success,
+            properties: None,
+        }));
+        assert!(!republisher.handle_event(&connack));
+        assert!(republisher.handle_event(&connack));
+        let birth = Publish::new("homeassistant/status", QoS::AtLeastOnce, "online", None);
+        assert!(republisher.handle_event(&Event::Incoming(Packet::Publish(birth))));
+        home_assistant
+            .remove_discovery("homeassistant/sensor/temperature/config")
+            .await
+            .unwrap();
+        assert!(home_assistant.published_discovery_topics().is_empty());
+    }
+}
@@ -0,0 +1,312 @@
+use std::fmt::Display;
+
+use rust_decimal::Decimal;
+
+use crate::mqtt::units::{
+    ConcentrationUnit, DataRateUnit, DataUnit, EnergyUnit, FrequencyUnit, LengthUnit, MassUnit,
+    PowerUnit, PressureUnit, SpeedUnit, TempUnit, TimeUnit, Unit, VolumeFlowRateUnit, VolumeUnit,
+};
+
+/// The reason why a value can't be converted between two units.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ConversionError {
+    /// The units don't measure the same quantity, eg. `°C` and `W`.
+    IncompatibleUnits { from: Unit, to: Unit },
+    /// The converted value exceeds the `Decimal` range.
+    Overflow {
+        value: Decimal,
+        from: Unit,
+        to: Unit,
+    },
+}
+
+impl Display for ConversionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConversionError::IncompatibleUnits { from, to } => {
+                write!(f, "'{from}' can't be converted to '{to}'")
+            }
+            ConversionError::Overflow { value, from, to } => {
+                write!(f, "{value} {from} overflows when converted to '{to}'")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConversionError {}
+
+/// A unit expressed in the base unit of its quantity: `base = (value + offset) * numerator / denominator`.
+struct Scale {
+    base: &'static str,
+    numerator: Decimal,
+    denominator: Decimal,
+    offset: Decimal,
+}
+
+impl Scale {
+    fn new(base: &'static str, numerator: Decimal) -> Self {
+        Scale {
+            base,
+            numerator,
+            denominator: Decimal::ONE,
+            offset: Decimal::ZERO,
+        }
+    }
+
+    fn ratio(base: &'static str, numerator: Decimal, denominator: Decimal) -> Self {
+        Scale {
+            denominator,
+            ..Scale::new(base, numerator)
+        }
+    }
+
+    fn offset(mut self, offset: Decimal) -> Self {
+        self.offset = offset;
+        self
+    }
+}
+
+/// `mantissa * 10^-scale`
+fn dec(mantissa: i64, scale: u32) -> Decimal {
+    Decimal::new(mantissa, scale)
+}
+
+impl Unit {
+    /// Converts a value between two units measuring the same quantity, eg. from `°F` to `°C`.
+    ///
+    /// Months and years are the average Gregorian ones, gallons and fluid ounces the US customary ones.
+    ///
+    /// ```
+    /// # use ha_mqtt_discovery::mqtt::units::{TempUnit, Unit};
+    /// # use rust_decimal::Decimal;
+    /// let celsius = Unit::convert(Decimal::from(212), TempUnit::TempFahrenheit, TempUnit::Celsius);
+    /// assert_eq!(celsius, Ok(Decimal::from(100)));
+    /// ```
+    pub fn convert(
+        value: Decimal,
+        from: impl Into<Unit>,
+        to: impl Into<Unit>,
+    ) -> Result<Decimal, ConversionError> {
+        let (from, to) = (from.into(), to.into());
+        if from == to {
+            return Ok(value);
+        }
+        let (Some(source), Some(target)) = (from.scale(), to.scale()) else {
+            return Err(ConversionError::IncompatibleUnits { from, to });
+        };
+        if source.base != target.base {
+            return Err(ConversionError::IncompatibleUnits { from, to });
+        }
+        // the multiplications come first to keep the precision of the exact factors
+        (value + source.offset)
+            .checked_mul(source.numerator)
+            .and_then(|base| base.checked_mul(target.denominator))
+            .and_then(|base| base.checked_div(source.denominator * target.numerator))
+            .map(|converted| (converted - target.offset).normalize())
+            .ok_or(ConversionError::Overflow { value, from, to })
+    }
+
+    fn scale(&self) -> Option<Scale> {
+        let scale = match *self {
+            Unit::Power(unit) => match unit {
+                PowerUnit::Watt => Scale::new("W", Decimal::ONE),
+                PowerUnit::KiloWatt => Scale::new("W", Decimal::ONE_THOUSAND),
+            },
+            Unit::Energy(unit) => match unit {
+                EnergyUnit::WattHour => Scale::new("Wh", Decimal::ONE),
+                EnergyUnit::KiloWattHour => Scale::new("Wh", Decimal::ONE_THOUSAND),
+            },
+            Unit::Temperature(unit) => match unit {
+                TempUnit::TempKelvin => Scale::new("K", Decimal::ONE),
+                TempUnit::Celsius => Scale::new("K", Decimal::ONE).offset(dec(27315, 2)),
+                TempUnit::TempFahrenheit => {
+                    Scale::ratio("K", dec(5, 0), dec(9, 0)).offset(dec(45967, 2))
+                }
+            },
+            Unit::Time(unit) => match unit {
+                TimeUnit::Microseconds => Scale::new("s", dec(1, 6)),
+                TimeUnit::Milliseconds => Scale::new("s", dec(1, 3)),
+                TimeUnit::Seconds => Scale::new("s", Decimal::ONE),
+                TimeUnit::Minutes => Scale::new("s", dec(60, 0)),
+                TimeUnit::Hours => Scale::new("s", dec(3_600, 0)),
+                TimeUnit::Days => Scale::new("s", dec(86_400, 0)),
+                TimeUnit::Weeks => Scale::new("s", dec(604_800, 0)),
+                TimeUnit::Months => Scale::new("s", dec(2_629_746, 0)),
+                TimeUnit::Years => Scale::new("s", dec(31_556_952, 0)),
+            },
+            Unit::Length(unit) => match unit {
+                LengthUnit::Millimeters => Scale::new("m", dec(1, 3)),
+                LengthUnit::Centimeters => Scale::new("m", dec(1, 2)),
+                LengthUnit::Meters => Scale::new("m", Decimal::ONE),
+                LengthUnit::Kilometers => Scale::new("m", Decimal::ONE_THOUSAND),
+                LengthUnit::Inches => Scale::new("m", dec(254, 4)),
+                LengthUnit::Feet => Scale::new("m", dec(3_048, 4)),
+                LengthUnit::Yard => Scale::new("m", dec(9_144, 4)),
+                LengthUnit::Miles => Scale::new("m", dec(1_609_344, 3)),
+            },
+            Unit::Frequency(unit) => match unit {
+                FrequencyUnit::Hertz => Scale::new("Hz", Decimal::ONE),
+                FrequencyUnit::GigaHertz => Scale::new("Hz", dec(1_000_000_000, 0)),
+            },
+            Unit::Pressure(unit) => match unit {
+                PressureUnit::Pa => Scale::new("Pa", Decimal::ONE),
+                PressureUnit::HPa => Scale::new("Pa", Decimal::ONE_HUNDRED),
+                PressureUnit::Bar => Scale::new("Pa", dec(100_000, 0)),
+                PressureUnit::MBar => Scale::new("Pa", Decimal::ONE_HUNDRED),
+                PressureUnit::InHg => Scale::new("Pa", dec(3_386_389, 3)),
+                PressureUnit::Psi => Scale::new("Pa", dec(6_894_757_293_168, 9)),
+            },
+            Unit::Volume(unit) => match unit {
+                VolumeUnit::Liters => Scale::new("m³", dec(1, 3)),
+                VolumeUnit::Milliliters => Scale::new("m³", dec(1, 6)),
+                VolumeUnit::CubicMeters => Scale::new("m³", Decimal::ONE),
+                VolumeUnit::CubicFeet => Scale::new("m³", dec(28_316_846_592, 12)),
+                VolumeUnit::Gallons => Scale::new("m³", dec(3_785_411_784, 12)),
+                VolumeUnit::FluidOunce => Scale::new("m³", dec(295_735_295_625, 16)),
+            },
+            Unit::VolumeFlowRate(unit) => match unit {
+                VolumeFlowRateUnit::CubicMetersPerHour => Scale::new("m³/h", Decimal::ONE),
+                VolumeFlowRateUnit::CubicFeetPerMinute => {
+                    Scale::new("m³/h", dec(28_316_846_592 * 60, 12))
+                }
+            },
+            Unit::Mass(unit) => match unit {
+                MassUnit::Micrograms => Scale::new("g", dec(1, 6)),
+                MassUnit::Milligrams => Scale::new("g", dec(1, 3)),
+                MassUnit::Grams => Scale::new("g", Decimal::ONE),
+                MassUnit::Kilograms => Scale::new("g", Decimal::ONE_THOUSAND),
+                MassUnit::Ounces => Scale::new("g", dec(28_349_523_125, 9)),
+                MassUnit::Pounds => Scale::new("g", dec(45_359_237, 5)),
+            },
+            Unit::Speed(unit) => match unit {
+                SpeedUnit::MetersPerSecond => Scale::new("m/s", Decimal::ONE),
+                SpeedUnit::MillimetersPerDay => Scale::ratio("m/s", dec(1, 3), dec(86_400, 0)),
+                SpeedUnit::InchesPerDay => Scale::ratio("m/s", dec(254, 4), dec(86_400, 0)),
+                SpeedUnit::InchesPerHour => Scale::ratio("m/s", dec(254, 4), dec(3_600, 0)),
+                SpeedUnit::KilometersPerHour => {
+                    Scale::ratio("m/s", Decimal::ONE_THOUSAND, dec(3_600, 0))
+                }
+                SpeedUnit::MilesPerHour => Scale::ratio("m/s", dec(1_609_344, 3), dec(3_600, 0)),
+            },
+            Unit::Concentration(unit) => match unit {
+                ConcentrationUnit::MicrogramsPerCubicMeter => Scale::new("µg/m³", Decimal::ONE),
+                ConcentrationUnit::MilligramsPerCubicMeter => {
+                    Scale::new("µg/m³", Decimal::ONE_THOUSAND)
+                }
+                ConcentrationUnit::PartsPerMillion => Scale::new("ppb", Decimal::ONE_THOUSAND),
+                ConcentrationUnit::PartsPerBillion => Scale::new("ppb", Decimal::ONE),
+                ConcentrationUnit::PartsPerCubicMeter => return None,
+            },
+            Unit::Data(unit) => Scale::new("bit", data_bits(unit)),
+            Unit::DataRateUnit(unit) => Scale::new(
+                "bit/s",
+                data_bits(match unit {
+                    DataRateUnit::BitsPerSecond => DataUnit::Bits,
+                    DataRateUnit::KilobitsPerSecond => DataUnit::Kilobits,
+                    DataRateUnit::MegabitsPerSecond => DataUnit::Megabits,
+                    DataRateUnit::GigabitsPerSecond => DataUnit::Gigabits,
+                    DataRateUnit::BytesPerSecond => DataUnit::Bytes,
+                    DataRateUnit::KilobytesPerSecond => DataUnit::Kilobytes,
+                    DataRateUnit::MegabytesPerSecond => DataUnit::Megabytes,
+                    DataRateUnit::GigabytesPerSecond => DataUnit::Gigabytes,
+                    DataRateUnit::KibibytesPerSecond => DataUnit::Kibibytes,
+                    DataRateUnit::MebibytesPerSecond => DataUnit::Mebibytes,
+                    DataRateUnit::GibibytesPerSecond => DataUnit::Gibibytes,
+                }),
+            ),
+            // the other families have a single unit, or units of distinct quantities
+            _ => return None,
+        };
+        Some(scale)
+    }
+}
+
+/// The number of bits of a data unit.
+fn data_bits(unit: DataUnit) -> Decimal {
+    let (bits, multiple, power): (i128, i128, u32) = match unit {
+        DataUnit::Bits => (1, 1000, 0),
+        DataUnit::Kilobits => (1, 1000, 1),
+        DataUnit::Megabits => (1, 1000, 2),
+        DataUnit::Gigabits => (1, 1000, 3),
+        DataUnit::Bytes => (8, 1000, 0),
+        DataUnit::Kilobytes => (8, 1000, 1),
+        DataUnit::Megabytes => (8, 1000, 2),
+        DataUnit::Gigabytes => (8, 1000, 3),
+        DataUnit::Terabytes => (8, 1000, 4),
+        DataUnit::Petabytes => (8, 1000, 5),
+        DataUnit::Exabytes => (8, 1000, 6),
+        DataUnit::Zettabytes => (8, 1000, 7),
+        DataUnit::Yottabytes => (8, 1000, 8),
+        DataUnit::Kibibytes => (8, 1024, 1),
+        DataUnit::Mebibytes => (8, 1024, 2),
+        DataUnit::Gibibytes => (8, 1024, 3),
+        DataUnit::Tebibytes => (8, 1024, 4),
+        DataUnit::Pebibytes => (8, 1024, 5),
+        DataUnit::Exbibytes => (8, 1024, 6),
+        DataUnit::Zebibytes => (8, 1024, 7),
+        DataUnit::Yobibytes => (8, 1024, 8),
+    };
+    Decimal::from_i128_with_scale(bits * multiple.pow(power), 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mqtt::units::ElectricalUnit;
+
+    use super::*;
+
+    #[test]
+    fn can_convert_units() {
+        let convert = |value: i64, from: Unit, to: Unit| Unit::convert(value.into(), from, to);
+
+        assert_eq!(
+            convert(
+                -40,
+                TempUnit::Celsius.into(),
+                TempUnit::TempFahrenheit.into()
+            ),
+            Ok(dec(-40, 0))
+        );
+        assert_eq!(
+            convert(0, TempUnit::Celsius.into(), TempUnit::TempKelvin.into()),
+            Ok(dec(27315, 2))
+        );
+        assert_eq!(
+            convert(1_500, PowerUnit::Watt.into(), PowerUnit::KiloWatt.into()),
+            Ok(dec(15, 1))
+        );
+        assert_eq!(
+            convert(
+                36,
+                SpeedUnit::KilometersPerHour.into(),
+                SpeedUnit::MetersPerSecond.into()
+            ),
+            Ok(dec(10, 0))
+        );
+        assert_eq!(
+            convert(1, DataUnit::Kibibytes.into(), DataUnit::Bits.into()),
+            Ok(dec(8_192, 0))
+        );
+        assert_eq!(
+            convert(1, PressureUnit::Bar.into(), PressureUnit::HPa.into()),
+            Ok(dec(1_000, 0))
+        );
+        assert_eq!(
+            convert(
+                5,
+                ElectricalUnit::VoltAmpere.into(),
+                ElectricalUnit::VoltAmpere.into()
+            ),
+            Ok(dec(5, 0))
+        );
+
+        let error = convert(1, TempUnit::Celsius.into(), PowerUnit::Watt.into()).unwrap_err();
+        assert_eq!(error.to_string(), "'°C' can't be converted to 'W'");
+        assert!(convert(
+            1,
+            ConcentrationUnit::PartsPerMillion.into(),
+            ConcentrationUnit::MilligramsPerCubicMeter.into()
+        )
+        .is_err());
+    }
+}
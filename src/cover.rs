@@ -0,0 +1,107 @@
+use serde_json::json;
+
+use crate::mqtt::cover::Cover;
+
+/// The state of a cover, published on its `state_topic`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CoverState {
+    Open,
+    Opening,
+    Closed,
+    Closing,
+    Stopped,
+}
+
+impl Cover {
+    /// The payload of the state, according to the `state_*` payloads of the cover.
+    pub fn state_payload(&self, state: &CoverState) -> &str {
+        let (payload, default) = match state {
+            CoverState::Open => (&self.state_open, "open"),
+            CoverState::Opening => (&self.state_opening, "opening"),
+            CoverState::Closed => (&self.state_closed, "closed"),
+            CoverState::Closing => (&self.state_closing, "closing"),
+            CoverState::Stopped => (&self.state_stopped, "stopped"),
+        };
+        payload.as_deref().unwrap_or(default)
+    }
+
+    /// The payload of a position in percent, from 0 (closed) to 100 (open), scaled between `position_closed` and
+    /// `position_open`.
+    ///
+    /// The position is published as `{"position": <value>}` when a `position_template` is configured, the template
+    /// is expected to extract `value_json.position`.
+    pub fn position_payload(&self, percent: u8) -> String {
+        let position = scale(
+            percent,
+            self.position_closed.unwrap_or(0),
+            self.position_open.unwrap_or(100),
+        );
+        match self.position_template {
+            Some(_) => json!({ "position": position }).to_string(),
+            None => position.to_string(),
+        }
+    }
+
+    /// The payload of a tilt in percent, scaled between `tilt_min` and `tilt_max`.
+    ///
+    /// The tilt is published as `{"tilt": <value>}` when a `tilt_status_template` is configured, the template is
+    /// expected to extract `value_json.tilt`.
+    pub fn tilt_payload(&self, percent: u8) -> String {
+        let tilt = scale(
+            percent,
+            self.tilt_min.unwrap_or(0),
+            self.tilt_max.unwrap_or(100),
+        );
+        match self.tilt_status_template {
+            Some(_) => json!({ "tilt": tilt }).to_string(),
+            None => tilt.to_string(),
+        }
+    }
+}
+
+/// Scales a percentage, clamped to 100, between the values at 0% and at 100%.
+fn scale(percent: u8, min: i32, max: i32) -> i32 {
+    let ratio = f64::from(percent.min(100)) / 100.0;
+    (f64::from(min) + f64::from(max - min) * ratio).round() as i32
+}
+
+#[cfg(all(test, feature = "client"))]
+mod tests {
+    use crate::{testing::MockClient, HomeAssistantMqtt};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn can_publish_cover_positions() {
+        let client = MockClient::default();
+        let ha = HomeAssistantMqtt::new(client.clone(), "homeassistant");
+        let cover = Cover::builder()
+            .command_topic("home/blind/set")
+            .build()
+            .state_topic("home/blind/state")
+            .state_open("OPENED")
+            .position_topic("home/blind/position")
+            .position_closed(255)
+            .position_open(0)
+            .tilt_status_topic("home/blind/tilt")
+            .tilt_status_template("{{ value_json.tilt }}")
+            .tilt_max(180);
+
+        ha.publish_cover_state(&cover, &CoverState::Open)
+            .await
+            .unwrap();
+        ha.publish_cover_position(&cover, 25).await.unwrap();
+        ha.publish_cover_tilt(&cover, 150).await.unwrap();
+
+        let payload = |topic| {
+            client
+                .last_published(topic)
+                .unwrap()
+                .payload_str()
+                .to_string()
+        };
+        assert_eq!(payload("home/blind/state"), "OPENED");
+        assert_eq!(payload("home/blind/position"), "191");
+        assert_eq!(payload("home/blind/tilt"), r#"{"tilt":180}"#);
+    }
+}
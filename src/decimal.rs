@@ -0,0 +1,138 @@
+use std::fmt::{self, Display, Formatter};
+
+use rust_decimal::{prelude::FromPrimitive, Decimal};
+
+/// A number accepted by the numeric attributes, so that the integers can be passed without converting them into
+/// `Decimal` first. The floats may not have a `Decimal` value, they're passed to the fallible `try_*` setters.
+///
+/// ```
+/// # fn main() -> Result<(), ha_mqtt_discovery::decimal::DecimalError> {
+/// # #[cfg(feature = "entity-number")] {
+/// # use ha_mqtt_discovery::{decimal::rounded, mqtt::number::Number};
+/// let number = Number::new("home/volume/set")
+///     .min(0)
+///     .try_max(1.5)?
+///     .try_step(rounded(1.0 / 3.0, 2))?;
+/// assert!(Number::new("home/volume/set").try_max(f64::NAN).is_err());
+/// assert_eq!(number.step.unwrap().to_string(), "0.33");
+/// # }
+/// # Ok(())
+/// # }
+/// ```
+pub trait IntoDecimal {
+    fn into_decimal(self) -> Decimal;
+}
+
+impl IntoDecimal for Decimal {
+    fn into_decimal(self) -> Decimal {
+        self
+    }
+}
+
+macro_rules! impl_into_decimal_for_integers {
+    ($($integer:ty),*) => {
+        $(
+            impl IntoDecimal for $integer {
+                fn into_decimal(self) -> Decimal {
+                    Decimal::from(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_into_decimal_for_integers!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+/// A float which is NaN, infinite or exceeds the `Decimal` range.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DecimalError {
+    pub value: f64,
+}
+
+impl Display for DecimalError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} can't be converted to a Decimal", self.value)
+    }
+}
+
+impl std::error::Error for DecimalError {}
+
+/// A number which may not have a `Decimal` value, like the floats.
+///
+/// The floats are converted to their shortest decimal representation, eg. `0.1` and not
+/// `0.1000000000000000055511151231`, use `rounded` to limit the number of decimal places.
+pub trait TryIntoDecimal {
+    fn try_into_decimal(self) -> Result<Decimal, DecimalError>;
+}
+
+impl<D: IntoDecimal> TryIntoDecimal for D {
+    fn try_into_decimal(self) -> Result<Decimal, DecimalError> {
+        Ok(self.into_decimal())
+    }
+}
+
+impl TryIntoDecimal for f64 {
+    fn try_into_decimal(self) -> Result<Decimal, DecimalError> {
+        Decimal::from_f64(self)
+            .map(|decimal| decimal.normalize())
+            .ok_or(DecimalError { value: self })
+    }
+}
+
+impl TryIntoDecimal for f32 {
+    fn try_into_decimal(self) -> Result<Decimal, DecimalError> {
+        Decimal::from_f32(self)
+            .map(|decimal| decimal.normalize())
+            .ok_or(DecimalError {
+                value: f64::from(self),
+            })
+    }
+}
+
+/// A float rounded to a number of decimal places, see `rounded`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Rounded {
+    value: f64,
+    decimal_places: u32,
+}
+
+/// The float rounded half to even to the decimal places when converted into a `Decimal`.
+pub fn rounded(value: f64, decimal_places: u32) -> Rounded {
+    Rounded {
+        value,
+        decimal_places,
+    }
+}
+
+impl TryIntoDecimal for Rounded {
+    fn try_into_decimal(self) -> Result<Decimal, DecimalError> {
+        Ok(self
+            .value
+            .try_into_decimal()?
+            .round_dp(self.decimal_places)
+            .normalize())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    #[test]
+    fn can_convert_numbers_into_decimals() {
+        assert_eq!(0.1.try_into_decimal().unwrap().to_string(), "0.1");
+        assert_eq!(21.5f32.try_into_decimal(), Ok(dec!(21.5)));
+        assert_eq!(30.0.try_into_decimal().unwrap().to_string(), "30");
+        assert_eq!(7u8.into_decimal(), dec!(7));
+        assert_eq!(rounded(2.0 / 3.0, 3).try_into_decimal(), Ok(dec!(0.667)));
+        assert_eq!(dec!(1.50).into_decimal().to_string(), "1.50");
+        assert_eq!(
+            f64::NAN.try_into_decimal().unwrap_err().to_string(),
+            "NaN can't be converted to a Decimal"
+        );
+        assert!(f64::INFINITY.try_into_decimal().is_err());
+        assert!(rounded(1e30, 2).try_into_decimal().is_err());
+    }
+}
@@ -0,0 +1,51 @@
+use crate::{locale::NameProvider, mqtt::common::Device, Entity};
+
+/// A physical device exposed to Home Assistant as a set of entities.
+///
+/// This trait is usually implemented with `#[derive(HaDevice)]` (requires the `derive` feature):
+///
+/// ```
+/// # #[cfg(all(feature = "derive", feature = "entity-sensor", feature = "entity-switch"))]
+/// # mod example {
+/// # use ha_mqtt_discovery::HaDevice;
+/// #[derive(HaDevice)]
+/// #[ha(base_topic = "home", name = "Barometer", manufacturer = "Awesome corp")]
+/// struct Barometer {
+///     #[ha(identifier)]
+///     serial_number: String,
+///     #[ha(sensor, device_class = "temperature", unit = "°C", state_class = "measurement")]
+///     temperature: f32,
+///     #[ha(switch, name = "Heater", on_command = "heater_switched")]
+///     heater: bool,
+/// }
+/// #
+/// # impl Barometer {
+/// #     fn heater_switched(&mut self, _on: bool) {}
+/// # }
+/// # }
+/// ```
+///
+/// Each entity publishes its state on `[<base_topic>/]<identifier>/<field>` and, for switches,
+/// receives its commands on `[<base_topic>/]<identifier>/<field>/set`.
+pub trait HaDevice {
+    /// Information about the device all the entities are a part of.
+    fn device_information(&self) -> Device;
+
+    /// The discovery configuration of every entity of the device.
+    fn entities(&self) -> Vec<Entity>;
+
+    /// The discovery configuration of every entity of the device, with names translated by the given provider.
+    ///
+    /// Derived devices look up the translations by field name, other implementations keep the default names.
+    fn localized_entities(&self, _names: &dyn NameProvider) -> Vec<Entity> {
+        self.entities()
+    }
+
+    /// The current state of every entity as `(state_topic, payload)` pairs.
+    fn states(&self) -> Vec<(String, String)>;
+
+    /// Applies a command payload received on the given topic.
+    ///
+    /// Returns `false` when the topic isn't the command topic of one of the device entities.
+    fn handle_command(&mut self, topic: &str, payload: &str) -> bool;
+}
@@ -0,0 +1,511 @@
+use std::collections::HashSet;
+
+use anyhow::Result;
+use rumqttc::v5::{
+    mqttbytes::{
+        v5::{Filter, Packet, Publish},
+        QoS,
+    },
+    AsyncClient, Event,
+};
+use serde_json::{json, Value};
+
+use crate::{
+    mqtt::abbreviations::{self, expand_device, expand_origin},
+    Entity,
+};
+
+/// The components of a discovery topic: `<discovery_prefix>/<component>/[<node_id>/]<object_id>/config`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DiscoveryTopic {
+    /// One of the supported MQTT integrations, eg. `binary_sensor`.
+    pub component: String,
+    /// ID of the node providing the topic, if any.
+    pub node_id: Option<String>,
+    /// The ID of the device.
+    pub object_id: String,
+}
+
+impl DiscoveryTopic {
+    /// Parses a discovery topic, returns `None` if the topic isn't a discovery topic under the given prefix.
+    pub fn parse(discovery_prefix: &str, topic: &str) -> Option<Self> {
+        let discovery_prefix = discovery_prefix.trim_end_matches('/');
+        let levels: Vec<&str> = topic
+            .strip_prefix(discovery_prefix)?
+            .strip_prefix('/')?
+            .strip_suffix("/config")?
+            .split('/')
+            .collect();
+        match levels[..] {
+            [component, object_id] => Some(DiscoveryTopic {
+                component: component.to_string(),
+                node_id: None,
+                object_id: object_id.to_string(),
+            }),
+            [component, node_id, object_id] => Some(DiscoveryTopic {
+                component: component.to_string(),
+                node_id: Some(node_id.to_string()),
+                object_id: object_id.to_string(),
+            }),
+            _ => None,
+        }
+    }
+}
+
+const ONE_WEEK_SECONDS: u32 = 60 * 60 * 24 * 7;
+
+/// How discovery configurations are published.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DiscoveryOptions {
+    /// Defaults to QoS 1.
+    pub qos: QoS,
+    /// Defaults to `true` so Home Assistant discovers the entities when it (re)starts.
+    pub retain: bool,
+    /// Lifetime of the configuration in seconds, defaults to one week. `None` keeps it forever.
+    pub message_expiry_interval: Option<u32>,
+    /// Maximum size of a configuration in bytes, the larger configurations aren't published. Unlimited by default.
+    pub max_payload_size: Option<usize>,
+    /// Removes the attributes set to their Home Assistant default value to shrink the configurations, defaults to
+    /// `false`.
+    pub strip_defaults: bool,
+    /// Publishes the attributes with their full names, eg. `state_topic` instead of `stat_t`, which is easier to
+    /// read when debugging with `mosquitto_sub`. Defaults to `false`.
+    pub full_names: bool,
+}
+
+impl Default for DiscoveryOptions {
+    fn default() -> Self {
+        Self {
+            qos: QoS::AtLeastOnce,
+            retain: true,
+            message_expiry_interval: Some(ONE_WEEK_SECONDS),
+            max_payload_size: None,
+            strip_defaults: false,
+            full_names: false,
+        }
+    }
+}
+
+impl DiscoveryOptions {
+    pub fn qos(mut self, qos: QoS) -> Self {
+        self.qos = qos;
+        self
+    }
+
+    pub fn retain(mut self, retain: bool) -> Self {
+        self.retain = retain;
+        self
+    }
+
+    pub fn message_expiry_interval(mut self, message_expiry_interval: Option<u32>) -> Self {
+        self.message_expiry_interval = message_expiry_interval;
+        self
+    }
+
+    /// Brokers often limit the size of the messages, eg. 64 KB.
+    pub fn max_payload_size(mut self, max_payload_size: usize) -> Self {
+        self.max_payload_size = Some(max_payload_size);
+        self
+    }
+
+    pub fn strip_defaults(mut self, strip_defaults: bool) -> Self {
+        self.strip_defaults = strip_defaults;
+        self
+    }
+
+    pub fn full_names(mut self, full_names: bool) -> Self {
+        self.full_names = full_names;
+        self
+    }
+}
+
+/// Removes the attributes of a discovery configuration set to their Home Assistant default value, including the
+/// attributes of its availability checks and of its device components.
+pub(crate) fn strip_defaults(payload: &mut Value) {
+    let Some(attributes) = payload.as_object_mut() else {
+        return;
+    };
+    for check in attributes
+        .get_mut("avty")
+        .and_then(Value::as_array_mut)
+        .into_iter()
+        .flatten()
+    {
+        strip_defaults(check);
+    }
+    for component in attributes
+        .get_mut("cmps")
+        .and_then(Value::as_object_mut)
+        .into_iter()
+        .flat_map(|components| components.values_mut())
+    {
+        strip_defaults(component);
+    }
+    attributes.retain(|key, value| !is_default(key, value));
+}
+
+fn is_default(key: &str, value: &Value) -> bool {
+    match (key, value) {
+        ("avty" | "dev", value) => {
+            value.as_array().is_some_and(Vec::is_empty)
+                || value.as_object().is_some_and(|object| object.is_empty())
+        }
+        ("o", Value::Object(origin)) => {
+            origin.is_empty() || (origin.len() == 1 && origin.get("name") == Some(&json!("")))
+        }
+        ("avty_mode", value) => value == "latest",
+        ("qos", value) => value == "0" || value == 0,
+        ("ret" | "frc_upd", value) => value == false,
+        ("en", value) => value == true,
+        ("enc", value) => value == "utf-8",
+        ("pl_on", value) => value == "ON",
+        ("pl_off", value) => value == "OFF",
+        ("pl_avail", value) => value == "online",
+        ("pl_not_avail", value) => value == "offline",
+        _ => false,
+    }
+}
+
+/// Replaces the abbreviated attribute names of a discovery configuration with their full names, including the
+/// attributes of its device, origin, availability checks and device components.
+pub(crate) fn expand_names(payload: &mut Value) {
+    let Some(attributes) = payload.as_object_mut() else {
+        return;
+    };
+    for check in attributes
+        .get_mut("avty")
+        .and_then(Value::as_array_mut)
+        .into_iter()
+        .flatten()
+    {
+        expand_keys(check, abbreviations::expand);
+    }
+    if let Some(device) = attributes.get_mut("dev") {
+        expand_keys(device, expand_device);
+    }
+    if let Some(origin) = attributes.get_mut("o") {
+        expand_keys(origin, expand_origin);
+    }
+    for component in attributes
+        .get_mut("cmps")
+        .and_then(Value::as_object_mut)
+        .into_iter()
+        .flat_map(|components| components.values_mut())
+    {
+        expand_names(component);
+    }
+    expand_keys(payload, abbreviations::expand);
+}
+
+fn expand_keys(value: &mut Value, expand: fn(&str) -> Option<&'static str>) {
+    let Value::Object(attributes) = value else {
+        return;
+    };
+    *attributes = std::mem::take(attributes)
+        .into_iter()
+        .map(|(key, value)| match expand(&key) {
+            Some(name) => (name.to_string(), value),
+            None => (key, value),
+        })
+        .collect();
+}
+
+/// A change of the discovery configurations published on the broker.
+///
+/// The `entity` is the configuration parsed by `Entity::from_discovery`, `None` when it isn't the configuration of an
+/// enabled platform, eg. a device discovery configuration.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DiscoveryEvent {
+    /// A discovery configuration was published for a new entity.
+    Created {
+        topic: DiscoveryTopic,
+        configuration: Value,
+        entity: Option<Entity>,
+    },
+    /// The discovery configuration of a known entity was published again.
+    Updated {
+        topic: DiscoveryTopic,
+        configuration: Value,
+        entity: Option<Entity>,
+    },
+    /// The discovery configuration of an entity was removed with an empty payload.
+    Removed { topic: DiscoveryTopic },
+}
+
+/// Tracks the discovery configurations published under a discovery prefix.
+#[derive(Clone, Debug)]
+pub struct DiscoveryWatcher {
+    discovery_prefix: String,
+    known_topics: HashSet<DiscoveryTopic>,
+}
+
+impl DiscoveryWatcher {
+    pub fn new<S: Into<String>>(discovery_prefix: S) -> Self {
+        let discovery_prefix: String = discovery_prefix.into();
+        Self {
+            discovery_prefix: discovery_prefix.trim_end_matches('/').to_string(),
+            known_topics: HashSet::new(),
+        }
+    }
+
+    /// The topic filters matching every discovery topic.
+    pub fn filters(&self) -> Vec<Filter> {
+        let prefix = &self.discovery_prefix;
+        vec![
+            Filter::new(format!("{prefix}/+/+/config"), QoS::AtLeastOnce),
+            Filter::new(format!("{prefix}/+/+/+/config"), QoS::AtLeastOnce),
+        ]
+    }
+
+    /// Converts a received message into a discovery event.
+    ///
+    /// Returns `None` for messages which aren't published on a discovery topic or don't carry a JSON payload.
+    pub fn handle_publish(&mut self, publish: &Publish) -> Option<DiscoveryEvent> {
+        let raw_topic = std::str::from_utf8(&publish.topic).ok()?;
+        let topic = DiscoveryTopic::parse(&self.discovery_prefix, raw_topic)?;
+        if publish.payload.is_empty() {
+            return self
+                .known_topics
+                .remove(&topic)
+                .then_some(DiscoveryEvent::Removed { topic });
+        }
+        let configuration = serde_json::from_slice(&publish.payload).ok()?;
+        let entity = Entity::from_discovery(raw_topic, &publish.payload).ok();
+        if self.known_topics.insert(topic.clone()) {
+            Some(DiscoveryEvent::Created {
+                topic,
+                configuration,
+                entity,
+            })
+        } else {
+            Some(DiscoveryEvent::Updated {
+                topic,
+                configuration,
+                entity,
+            })
+        }
+    }
+
+    /// Handles an event from the MQTT event loop: discovery topics are subscribed on (re)connection.
+    pub fn handle_event(
+        &mut self,
+        client: &AsyncClient,
+        event: &Event,
+    ) -> Result<Option<DiscoveryEvent>> {
+        match event {
+            Event::Incoming(Packet::Publish(publish)) => Ok(self.handle_publish(publish)),
+            Event::Incoming(Packet::ConnAck(_)) => {
+                client.try_subscribe_many(self.filters())?;
+                Ok(None)
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "entity-sensor"))]
+mod tests {
+    use serde_json::json;
+
+    #[cfg(feature = "entity-switch")]
+    use crate::{
+        error::PublishError,
+        mqtt::{common::Qos, switch::Switch},
+    };
+    use crate::{
+        mqtt::{
+            common::{Availability, Device},
+            sensor::Sensor,
+        },
+        testing::MockClient,
+        HomeAssistantMqtt,
+    };
+
+    use super::*;
+
+    fn publish(topic: &str, payload: &str) -> Publish {
+        Publish::new(topic, QoS::AtLeastOnce, payload.to_string(), None)
+    }
+
+    #[test]
+    fn can_strip_the_default_availability_mode() {
+        let mut payload = json!({"avty_mode": "all", "avty": [{"t": "a"}, {"t": "b"}]});
+        strip_defaults(&mut payload);
+        assert_eq!(payload["avty_mode"], "all");
+
+        let mut payload = json!({"avty_mode": "latest", "avty": [{"t": "a"}, {"t": "b"}]});
+        strip_defaults(&mut payload);
+        assert_eq!(payload, json!({"avty": [{"t": "a"}, {"t": "b"}]}));
+    }
+
+    #[test]
+    fn can_parse_discovery_topics() {
+        assert_eq!(
+            DiscoveryTopic::parse("homeassistant/", "homeassistant/sensor/node/temp/config"),
+            Some(DiscoveryTopic {
+                component: "sensor".to_string(),
+                node_id: Some("node".to_string()),
+                object_id: "temp".to_string(),
+            })
+        );
+        assert_eq!(
+            DiscoveryTopic::parse("homeassistant", "homeassistant/sensor/temp/config"),
+            Some(DiscoveryTopic {
+                component: "sensor".to_string(),
+                node_id: None,
+                object_id: "temp".to_string(),
+            })
+        );
+        assert_eq!(
+            DiscoveryTopic::parse("homeassistant", "homeassistant/sensor/temp/state"),
+            None
+        );
+        assert_eq!(
+            DiscoveryTopic::parse("homeassistant", "other/sensor/temp/config"),
+            None
+        );
+    }
+
+    #[test]
+    fn can_map_abbreviations() {
+        assert_eq!(abbreviations::abbreviate("state_topic"), Some("stat_t"));
+        assert_eq!(abbreviations::expand("stat_t"), Some("state_topic"));
+        assert_eq!(abbreviations::expand("state_topic"), None);
+        assert_eq!(expand_device("ids"), Some("identifiers"));
+        assert_eq!(abbreviations::abbreviate_origin("support_url"), Some("url"));
+
+        let mut payload = json!({
+            "o": {"name": "bridge", "sw": "1.0"},
+            "cmps": {"temperature": {"p": "sensor", "stat_t": "home/temperature"}}
+        });
+        expand_names(&mut payload);
+        assert_eq!(
+            payload,
+            json!({
+                "origin": {"name": "bridge", "sw_version": "1.0"},
+                "components": {"temperature": {"platform": "sensor", "state_topic": "home/temperature"}}
+            })
+        );
+    }
+
+    #[test]
+    fn can_track_discovery_configurations() {
+        let mut watcher = DiscoveryWatcher::new("homeassistant");
+        let topic = DiscoveryTopic::parse("homeassistant", "homeassistant/sensor/temp/config");
+
+        assert_eq!(
+            watcher.handle_publish(&publish(
+                "homeassistant/sensor/temp/config",
+                "{\"name\":\"a\",\"stat_t\":\"home/temp\"}"
+            )),
+            Some(DiscoveryEvent::Created {
+                topic: topic.clone().unwrap(),
+                configuration: json!({"name": "a", "stat_t": "home/temp"}),
+                entity: Some(Sensor::new("home/temp").name("a").into())
+            })
+        );
+        assert_eq!(
+            watcher.handle_publish(&publish(
+                "homeassistant/sensor/temp/config",
+                "{\"name\":\"b\"}"
+            )),
+            Some(DiscoveryEvent::Updated {
+                topic: topic.clone().unwrap(),
+                configuration: json!({"name": "b"}),
+                entity: None
+            })
+        );
+        assert_eq!(
+            watcher.handle_publish(&publish("homeassistant/sensor/temp/config", "")),
+            Some(DiscoveryEvent::Removed {
+                topic: topic.unwrap()
+            })
+        );
+        assert_eq!(
+            watcher.handle_publish(&publish("homeassistant/sensor/temp/config", "")),
+            None
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "entity-switch")]
+    fn can_shrink_discovery_payloads() {
+        let switch = Entity::from(
+            Switch::new("home/heater/set")
+                .unique_id("heater")
+                .qos(Qos::AtMostOnce)
+                .payload_on("ON")
+                .payload_off("1")
+                .availability(Availability::single_topic("home/availability")),
+        );
+        let ha = HomeAssistantMqtt::new(MockClient::default(), "homeassistant")
+            .with_discovery_options(DiscoveryOptions::default().strip_defaults(true));
+
+        let (_, payload) = ha.discovery_message(&switch).unwrap();
+        assert_eq!(
+            payload,
+            r#"{"avty":[{"t":"home/availability"}],"avty_mode":"all","cmd_t":"home/heater/set","pl_off":"1","uniq_id":"heater"}"#
+        );
+        assert!(matches!(
+            ha.with_discovery_options(DiscoveryOptions::default().max_payload_size(64))
+                .discovery_message(&switch),
+            Err(PublishError::PayloadTooLarge { limit: 64, .. })
+        ));
+    }
+
+    #[test]
+    fn can_publish_full_attribute_names() {
+        let sensor = Entity::from(
+            Sensor::new("home/temperature")
+                .unique_id("temperature")
+                .device(Device::default().add_identifier("thermometer"))
+                .availability(Availability::single_topic("home/availability")),
+        );
+        let ha = HomeAssistantMqtt::new(MockClient::default(), "homeassistant")
+            .with_discovery_options(
+                DiscoveryOptions::default()
+                    .strip_defaults(true)
+                    .full_names(true),
+            );
+
+        let (_, payload) = ha.discovery_message(&sensor).unwrap();
+        assert_eq!(
+            serde_json::from_str::<Value>(&payload).unwrap(),
+            serde_json::json!({
+                "availability": [{"topic": "home/availability"}],
+                "availability_mode": "all",
+                "device": {"identifiers": ["thermometer"]},
+                "state_topic": "home/temperature",
+                "unique_id": "temperature"
+            })
+        );
+        let parsed: Sensor = serde_json::from_str(&payload).unwrap();
+        assert_eq!(parsed.unique_id.as_deref(), Some("temperature"));
+    }
+
+    #[tokio::test]
+    async fn can_configure_discovery_messages() {
+        let client = MockClient::default();
+        let registry = HomeAssistantMqtt::new(client.clone(), "homeassistant")
+            .with_discovery_options(
+                DiscoveryOptions::default()
+                    .qos(QoS::ExactlyOnce)
+                    .retain(false)
+                    .message_expiry_interval(None),
+            );
+
+        registry
+            .publish_entity(Entity::from(
+                Sensor::new("home/temperature").unique_id("temperature"),
+            ))
+            .await
+            .unwrap();
+
+        let message = client
+            .last_published("homeassistant/sensor/temperature/config")
+            .unwrap();
+        assert_eq!(message.qos, QoS::ExactlyOnce);
+        assert!(!message.retain);
+        assert_eq!(message.options.message_expiry_interval, None);
+    }
+}
@@ -0,0 +1,105 @@
+use std::fmt::Display;
+
+use crate::{
+    topic::{InvalidTopic, TopicError},
+    validation::ValidationError,
+};
+
+/// The reason why a discovery configuration can't be published.
+#[derive(Debug)]
+pub enum PublishError {
+    /// The entity has no `unique_id`, it is required to build the discovery topic.
+    MissingUniqueId,
+    /// The entity device has neither identifiers nor connections.
+    EmptyDeviceIdentifiers,
+    /// The configuration has no origin name and the `OriginPolicy::Strict` policy is enforced.
+    MissingOrigin,
+    /// The node ID contains characters outside of [a-zA-Z0-9_-].
+    InvalidNodeId(String),
+    /// The discovery prefix isn't a valid topic, see `topic::normalize_discovery_prefix`.
+    InvalidDiscoveryPrefix {
+        discovery_prefix: String,
+        error: TopicError,
+    },
+    /// A topic attribute of the discovery configuration is rejected.
+    InvalidTopic(InvalidTopic),
+    /// The discovery configuration can't be serialized.
+    Serialization(serde_json::Error),
+    /// The serialized discovery configuration exceeds `DiscoveryOptions::max_payload_size`.
+    PayloadTooLarge { size: usize, limit: usize },
+    /// Two components of a device share the same key, `unique_id` or `object_id`.
+    DuplicateComponent {
+        attribute: &'static str,
+        value: String,
+    },
+    /// The configuration violates Home Assistant rules, see `Entity::validate` and `DeviceComponents::validate`.
+    Invalid(Vec<ValidationError>),
+    /// The MQTT client failed to send the message.
+    Client(Box<dyn std::error::Error + Send + Sync>),
+}
+
+impl Display for PublishError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PublishError::MissingUniqueId => {
+                write!(f, "entity configuration should have an attribute 'uniq_id'")
+            }
+            PublishError::EmptyDeviceIdentifiers => write!(
+                f,
+                "device should have at least one of 'identifiers' or 'connections'"
+            ),
+            PublishError::MissingOrigin => {
+                write!(f, "configuration should have an origin with a 'name'")
+            }
+            PublishError::InvalidNodeId(node_id) => write!(
+                f,
+                "node_id '{node_id}' should only consist of characters [a-zA-Z0-9_-]"
+            ),
+            PublishError::InvalidDiscoveryPrefix {
+                discovery_prefix,
+                error,
+            } => write!(f, "invalid discovery prefix '{discovery_prefix}': {error}"),
+            PublishError::InvalidTopic(error) => error.fmt(f),
+            PublishError::Serialization(error) => {
+                write!(f, "entity configuration can't be serialized: {error}")
+            }
+            PublishError::PayloadTooLarge { size, limit } => write!(
+                f,
+                "configuration of {size} bytes exceeds the limit of {limit} bytes"
+            ),
+            PublishError::DuplicateComponent { attribute, value } => write!(
+                f,
+                "device components should have distinct '{attribute}', '{value}' is used more than once"
+            ),
+            PublishError::Invalid(errors) => {
+                let errors: Vec<String> = errors.iter().map(ToString::to_string).collect();
+                write!(f, "invalid configuration: {}", errors.join(", "))
+            }
+            PublishError::Client(error) => write!(f, "MQTT client error: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for PublishError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            PublishError::InvalidDiscoveryPrefix { error, .. } => Some(error),
+            PublishError::InvalidTopic(error) => Some(error),
+            PublishError::Serialization(error) => Some(error),
+            PublishError::Client(error) => Some(error.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl From<InvalidTopic> for PublishError {
+    fn from(error: InvalidTopic) -> Self {
+        PublishError::InvalidTopic(error)
+    }
+}
+
+impl From<serde_json::Error> for PublishError {
+    fn from(error: serde_json::Error) -> Self {
+        PublishError::Serialization(error)
+    }
+}
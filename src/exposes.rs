@@ -0,0 +1,347 @@
+use std::fmt::Display;
+
+use rust_decimal::Decimal;
+use serde_derive::Deserialize;
+use serde_json::Value;
+
+use crate::{
+    components::DeviceComponents,
+    mqtt::{
+        binary_sensor::BinarySensor, common::Device, number::Number, select::Select,
+        sensor::Sensor, switch::Switch, text::Text, units::Unit,
+    },
+    Entity,
+};
+
+/// The `access` bit set when the property is published on the device topic.
+const ACCESS_STATE: u8 = 1;
+/// The `access` bit set when the property can be set through the `/set` topic.
+const ACCESS_SET: u8 = 2;
+
+/// A capability of a zigbee2mqtt device, as listed in the `exposes` of its definition.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct Expose {
+    #[serde(rename = "type")]
+    pub expose_type: String,
+    pub name: Option<String>,
+    pub label: Option<String>,
+    pub property: Option<String>,
+    /// A bitmask of 1 (published), 2 (settable) and 4 (gettable).
+    #[serde(default)]
+    pub access: u8,
+    pub unit: Option<String>,
+    pub value_on: Option<Value>,
+    pub value_off: Option<Value>,
+    pub value_min: Option<Decimal>,
+    pub value_max: Option<Decimal>,
+    pub value_step: Option<Decimal>,
+    #[serde(default)]
+    pub values: Vec<String>,
+    /// The nested capabilities of the composite types, eg. `switch`.
+    #[serde(default)]
+    pub features: Vec<Expose>,
+}
+
+/// An expose which can't be converted into entities.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ExposeError {
+    /// The type of the expose isn't converted, eg. `light`.
+    Unsupported { expose_type: String },
+    /// The expose lacks an attribute required by its type.
+    MissingAttribute {
+        expose_type: String,
+        attribute: &'static str,
+    },
+}
+
+impl Display for ExposeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExposeError::Unsupported { expose_type } => {
+                write!(f, "'{expose_type}' exposes are not supported")
+            }
+            ExposeError::MissingAttribute {
+                expose_type,
+                attribute,
+            } => write!(f, "'{attribute}' is required by '{expose_type}' exposes"),
+        }
+    }
+}
+
+impl std::error::Error for ExposeError {}
+
+/// Converts the exposes of a zigbee2mqtt device into the entities reading its JSON state on `<device_topic>` and
+/// writing its properties on `<device_topic>/set`, like the zigbee2mqtt discovery does.
+///
+/// The `binary`, `numeric`, `enum` and `text` exposes become binary sensors, sensors, switches, numbers, selects and
+/// texts depending on whether they can be set, the `switch` exposes are converted feature by feature. The units
+/// unknown to Home Assistant are dropped.
+///
+/// ```no_run
+/// # use ha_mqtt_discovery::{
+/// #     exposes::{Expose, ExposesImporter},
+/// #     mqtt::common::Device,
+/// #     HomeAssistantMqtt,
+/// # };
+/// # async fn example(ha: HomeAssistantMqtt, definition: serde_json::Value) -> anyhow::Result<()> {
+/// let exposes: Vec<Expose> = serde_json::from_value(definition["exposes"].clone())?;
+/// let importer = ExposesImporter::new(Device::default().add_identifier("0x00158d0001"), "zigbee2mqtt/door");
+/// ha.publish_device_components(&importer.components(&exposes)?).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct ExposesImporter {
+    device: Device,
+    device_topic: String,
+}
+
+impl ExposesImporter {
+    pub fn new<S: Into<String>>(device: Device, device_topic: S) -> Self {
+        Self {
+            device,
+            device_topic: device_topic.into(),
+        }
+    }
+
+    /// The entities of an expose keyed by property, with a `unique_id` derived from the device identifier and the
+    /// property, see `Entity::derive_unique_id`.
+    pub fn entities(&self, expose: &Expose) -> Result<Vec<(String, Entity)>, ExposeError> {
+        if expose.expose_type == "switch" {
+            let mut entities = vec![];
+            for feature in &expose.features {
+                entities.extend(self.entities(feature)?);
+            }
+            return Ok(entities);
+        }
+        if !matches!(
+            expose.expose_type.as_str(),
+            "binary" | "numeric" | "enum" | "text"
+        ) {
+            return Err(ExposeError::Unsupported {
+                expose_type: expose.expose_type.clone(),
+            });
+        }
+        let property = expose
+            .property
+            .as_deref()
+            .ok_or_else(|| missing(expose, "property"))?;
+        let settable = expose.access & ACCESS_SET != 0;
+        let unique_id = Entity::derive_unique_id(&self.device, property);
+        let state_topic = (expose.access & ACCESS_STATE != 0).then(|| self.device_topic.clone());
+        let command_topic = format!("{}/set", self.device_topic);
+        let value_template = format!("{{{{ value_json.{property} }}}}");
+        let name = expose
+            .label
+            .clone()
+            .or(expose.name.clone())
+            .unwrap_or(property.to_string());
+        let unit = expose
+            .unit
+            .as_deref()
+            .and_then(|unit| unit.parse::<Unit>().ok());
+
+        let entity: Entity = match expose.expose_type.as_str() {
+            "binary" => {
+                let value_on = expose
+                    .value_on
+                    .as_ref()
+                    .ok_or_else(|| missing(expose, "value_on"))?;
+                let value_off = expose
+                    .value_off
+                    .as_ref()
+                    .ok_or_else(|| missing(expose, "value_off"))?;
+                if settable {
+                    let command =
+                        |value: &Value| serde_json::json!({ property: value }).to_string();
+                    let mut switch = Switch::new(command_topic)
+                        .payload_on(command(value_on))
+                        .payload_off(command(value_off))
+                        .state_on(rendered(value_on))
+                        .state_off(rendered(value_off));
+                    if let Some(state_topic) = state_topic {
+                        switch = switch
+                            .state_topic(state_topic)
+                            .value_template(value_template);
+                    }
+                    switch.unique_id(unique_id).name(name).into()
+                } else {
+                    BinarySensor::new(self.device_topic.clone())
+                        .value_template(value_template)
+                        .payload_on(rendered(value_on))
+                        .payload_off(rendered(value_off))
+                        .unique_id(unique_id)
+                        .name(name)
+                        .into()
+                }
+            }
+            "numeric" if settable => {
+                let mut number = Number::new(command_topic)
+                    .command_template(format!("{{\"{property}\": {{{{ value }}}}}}"));
+                if let Some(state_topic) = state_topic {
+                    number = number
+                        .state_topic(state_topic)
+                        .value_template(value_template);
+                }
+                if let Some(min) = expose.value_min {
+                    number = number.min(min);
+                }
+                if let Some(max) = expose.value_max {
+                    number = number.max(max);
+                }
+                if let Some(step) = expose.value_step {
+                    number = number.step(step);
+                }
+                if let Some(unit) = unit {
+                    number = number.unit_of_measurement(unit);
+                }
+                number.unique_id(unique_id).name(name).into()
+            }
+            "enum" if settable => {
+                let mut select = Select::new(command_topic, expose.values.clone())
+                    .command_template(format!("{{\"{property}\": \"{{{{ value }}}}\"}}"));
+                if let Some(state_topic) = state_topic {
+                    select = select
+                        .state_topic(state_topic)
+                        .value_template(value_template);
+                }
+                select.unique_id(unique_id).name(name).into()
+            }
+            "text" if settable => {
+                let mut text = Text::new(command_topic)
+                    .command_template(format!("{{\"{property}\": \"{{{{ value }}}}\"}}"));
+                if let Some(state_topic) = state_topic {
+                    text = text.state_topic(state_topic).value_template(value_template);
+                }
+                text.unique_id(unique_id).name(name).into()
+            }
+            // the read-only numeric, enum and text exposes
+            _ => {
+                let mut sensor = Sensor::new(self.device_topic.clone())
+                    .value_template(value_template)
+                    .unique_id(unique_id)
+                    .name(name);
+                if let Some(unit) = unit {
+                    sensor = sensor.unit_of_measurement(unit);
+                }
+                sensor.into()
+            }
+        };
+        Ok(vec![(property.to_string(), entity)])
+    }
+
+    /// The components of the device, the unsupported exposes are skipped.
+    ///
+    /// The object ID is the slug of the first identifier or connection of the device.
+    pub fn components(&self, exposes: &[Expose]) -> Result<DeviceComponents, ExposeError> {
+        let object_id = Entity::derive_unique_id(&self.device, "");
+        let mut components = DeviceComponents::new(object_id, self.device.clone())
+            .share_attributes(true)
+            .auto_suffix(true);
+        for expose in exposes {
+            match self.entities(expose) {
+                Ok(entities) => {
+                    for (key, entity) in entities {
+                        components = components.component(key, entity);
+                    }
+                }
+                Err(ExposeError::Unsupported { .. }) => {}
+                Err(error) => return Err(error),
+            }
+        }
+        Ok(components)
+    }
+}
+
+fn missing(expose: &Expose, attribute: &'static str) -> ExposeError {
+    ExposeError::MissingAttribute {
+        expose_type: expose.expose_type.clone(),
+        attribute,
+    }
+}
+
+/// The value as rendered by a Home Assistant template, eg. `True` for `true`.
+fn rendered(value: &Value) -> String {
+    match value {
+        Value::String(value) => value.clone(),
+        Value::Bool(true) => "True".to_string(),
+        Value::Bool(false) => "False".to_string(),
+        value => value.to_string(),
+    }
+}
+
+#[cfg(all(test, feature = "client"))]
+mod tests {
+    use serde_json::json;
+
+    use crate::{testing::MockClient, HomeAssistantMqtt};
+
+    use super::*;
+
+    #[test]
+    fn can_import_exposes() {
+        let exposes: Vec<Expose> = serde_json::from_value(json!([
+            {"type": "switch", "features": [
+                {"type": "binary", "name": "state", "property": "state", "access": 7,
+                 "value_on": "ON", "value_off": "OFF", "value_toggle": "TOGGLE"}
+            ]},
+            {"type": "binary", "name": "contact", "label": "Contact", "property": "contact", "access": 1,
+             "value_on": false, "value_off": true},
+            {"type": "numeric", "name": "temperature", "property": "temperature", "access": 1, "unit": "°C"},
+            {"type": "numeric", "name": "countdown", "property": "countdown", "access": 7,
+             "value_min": 0, "value_max": 43200, "unit": "s"},
+            {"type": "enum", "name": "power_on_behavior", "property": "power_on_behavior", "access": 7,
+             "values": ["off", "on", "previous"]},
+            {"type": "light", "features": []}
+        ]))
+        .unwrap();
+        let importer = ExposesImporter::new(
+            Device::default().add_identifier("0x00158d0001"),
+            "zigbee2mqtt/plug",
+        );
+
+        let components = importer.components(&exposes).unwrap();
+        assert_eq!(
+            components.components().keys().collect::<Vec<_>>(),
+            [
+                "contact",
+                "countdown",
+                "power_on_behavior",
+                "state",
+                "temperature"
+            ]
+        );
+        let (_, payload) = HomeAssistantMqtt::new(MockClient::default(), "homeassistant")
+            .device_discovery_message(&components)
+            .unwrap();
+        let payload: Value = serde_json::from_str(&payload).unwrap();
+        let cmps = &payload["cmps"];
+        assert_eq!(payload["stat_t"], "zigbee2mqtt/plug");
+        assert_eq!(cmps["state"]["p"], "switch");
+        assert_eq!(cmps["state"]["pl_on"], r#"{"state":"ON"}"#);
+        assert_eq!(cmps["state"]["stat_on"], "ON");
+        assert_eq!(cmps["contact"]["p"], "binary_sensor");
+        assert_eq!(cmps["contact"]["name"], "Contact");
+        assert_eq!(cmps["contact"]["pl_on"], "False");
+        assert_eq!(cmps["temperature"]["unit_of_meas"], "°C");
+        assert_eq!(cmps["countdown"]["p"], "number");
+        assert_eq!(cmps["countdown"]["max"], 43200.0);
+        assert_eq!(
+            cmps["countdown"]["cmd_tpl"],
+            r#"{"countdown": {{ value }}}"#
+        );
+        assert_eq!(cmps["power_on_behavior"]["p"], "select");
+        assert_eq!(
+            cmps["power_on_behavior"]["uniq_id"],
+            "0x00158d0001_power_on_behavior"
+        );
+
+        assert!(matches!(
+            importer.entities(&exposes[5]),
+            Err(ExposeError::Unsupported { expose_type }) if expose_type == "light"
+        ));
+        assert!(importer
+            .entities(&serde_json::from_value(json!({"type": "numeric", "access": 1})).unwrap())
+            .is_err());
+    }
+}
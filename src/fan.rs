@@ -0,0 +1,299 @@
+use std::ops::RangeInclusive;
+
+use anyhow::{anyhow, Result};
+use serde_derive::{Deserialize, Serialize};
+
+use crate::{
+    mqtt::fan::Fan,
+    topic::{resolve, Topic},
+    variant::variant_name,
+};
+
+/// The direction of a fan, published on its `direction_state_topic`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FanDirection {
+    Forward,
+    Reverse,
+}
+
+impl FanDirection {
+    /// The direction published on the `direction_state_topic`, eg. `forward`.
+    pub fn as_str(&self) -> &'static str {
+        variant_name(self)
+    }
+}
+
+/// The state of a fan, published on its state topics by `HomeAssistantMqtt::publish_fan_state`.
+///
+/// The values left to `None` aren't published.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FanState {
+    pub on: Option<bool>,
+    /// The speed in percent, converted into a speed of the `speed_range_min..=speed_range_max` range.
+    pub percentage: Option<u8>,
+    pub preset_mode: Option<String>,
+    pub oscillating: Option<bool>,
+    pub direction: Option<FanDirection>,
+}
+
+impl FanState {
+    pub fn on(mut self, on: bool) -> Self {
+        self.on = Some(on);
+        self
+    }
+
+    pub fn percentage(mut self, percentage: u8) -> Self {
+        self.percentage = Some(percentage);
+        self
+    }
+
+    pub fn preset_mode<S: Into<String>>(mut self, preset_mode: S) -> Self {
+        self.preset_mode = Some(preset_mode.into());
+        self
+    }
+
+    pub fn oscillating(mut self, oscillating: bool) -> Self {
+        self.oscillating = Some(oscillating);
+        self
+    }
+
+    pub fn direction(mut self, direction: FanDirection) -> Self {
+        self.direction = Some(direction);
+        self
+    }
+}
+
+/// A command sent by Home Assistant to a fan.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FanCommand {
+    On,
+    Off,
+    /// The speed requested on the `percentage_command_topic`, Home Assistant already converted the percentage into
+    /// a speed of the `speed_range_min..=speed_range_max` range, 0 turns the fan off.
+    Speed(i32),
+    /// One of the `preset_modes` of the fan.
+    PresetMode(String),
+    Oscillate(bool),
+    Direction(FanDirection),
+}
+
+impl Fan {
+    /// The speeds of the device, `speed_range_min..=speed_range_max`, `1..=100` by default.
+    pub fn speed_range(&self) -> RangeInclusive<i32> {
+        self.speed_range_min.unwrap_or(1)..=self.speed_range_max.unwrap_or(100)
+    }
+
+    /// The speed of the device matching a percentage, rounded up like Home Assistant does before publishing a
+    /// percentage command: 0% is the speed below `speed_range_min`, 100% is `speed_range_max`.
+    pub fn percentage_to_speed(&self, percentage: u8) -> Result<i32> {
+        if percentage > 100 {
+            return Err(anyhow!("fan percentage {percentage} is greater than 100"));
+        }
+        let (offset, speeds) = self.speed_steps()?;
+        // ceil(speeds * percentage / 100) with integers
+        Ok(offset + (speeds * i32::from(percentage) + 99) / 100)
+    }
+
+    /// The percentage of a speed of the device, rounded down like Home Assistant does when it receives a speed on the
+    /// `percentage_state_topic`.
+    pub fn speed_to_percentage(&self, speed: i32) -> Result<u8> {
+        let (offset, speeds) = self.speed_steps()?;
+        let range = offset..=*self.speed_range().end();
+        if !range.contains(&speed) {
+            return Err(anyhow!(
+                "fan speed {speed} is outside of {}..={}",
+                range.start(),
+                range.end()
+            ));
+        }
+        Ok(((speed - offset) * 100 / speeds) as u8)
+    }
+
+    /// The speed below `speed_range_min`, meaning off, and the number of speeds of the device.
+    fn speed_steps(&self) -> Result<(i32, i32)> {
+        let range = self.speed_range();
+        if range.start() < &1 || range.is_empty() {
+            return Err(anyhow!(
+                "fan speed range {}..={} should start at 1 or more and not be empty",
+                range.start(),
+                range.end()
+            ));
+        }
+        Ok((range.start() - 1, range.end() - range.start() + 1))
+    }
+
+    /// The payload of the on/off state, according to `payload_on` and `payload_off`.
+    pub fn state_payload(&self, on: bool) -> &str {
+        if on {
+            self.payload_on.as_deref().unwrap_or("ON")
+        } else {
+            self.payload_off.as_deref().unwrap_or("OFF")
+        }
+    }
+
+    /// The payload of the oscillation state, according to `payload_oscillation_on` and `payload_oscillation_off`.
+    pub fn oscillation_payload(&self, oscillating: bool) -> &str {
+        if oscillating {
+            self.payload_oscillation_on
+                .as_deref()
+                .unwrap_or("oscillate_on")
+        } else {
+            self.payload_oscillation_off
+                .as_deref()
+                .unwrap_or("oscillate_off")
+        }
+    }
+
+    /// The command of a payload received on one of the command topics of the fan, `None` when the topic isn't one of
+    /// them or the payload isn't recognized.
+    ///
+    /// The payloads rendered by the command templates aren't recognized.
+    pub fn command(&self, topic: &str, payload: &str) -> Option<FanCommand> {
+        if resolve(&self.command_topic, self.topic_prefix.as_deref()).as_str() == topic {
+            return match payload {
+                _ if payload == self.state_payload(true) => Some(FanCommand::On),
+                _ if payload == self.state_payload(false) => Some(FanCommand::Off),
+                _ => None,
+            };
+        }
+        if self.is_command_topic(&self.percentage_command_topic, topic) {
+            let speed = payload.trim().parse().ok()?;
+            let range = self.speed_range().start() - 1..=*self.speed_range().end();
+            return range.contains(&speed).then_some(FanCommand::Speed(speed));
+        }
+        if self.is_command_topic(&self.preset_mode_command_topic, topic) {
+            let preset_modes = self.preset_modes.as_deref().unwrap_or_default();
+            return preset_modes
+                .iter()
+                .any(|preset_mode| preset_mode == payload)
+                .then(|| FanCommand::PresetMode(payload.to_string()));
+        }
+        if self.is_command_topic(&self.oscillation_command_topic, topic) {
+            return match payload {
+                _ if payload == self.oscillation_payload(true) => Some(FanCommand::Oscillate(true)),
+                _ if payload == self.oscillation_payload(false) => {
+                    Some(FanCommand::Oscillate(false))
+                }
+                _ => None,
+            };
+        }
+        if self.is_command_topic(&self.direction_command_topic, topic) {
+            return match payload {
+                "forward" => Some(FanCommand::Direction(FanDirection::Forward)),
+                "reverse" => Some(FanCommand::Direction(FanDirection::Reverse)),
+                _ => None,
+            };
+        }
+        None
+    }
+
+    fn is_command_topic(&self, command_topic: &Option<Topic>, topic: &str) -> bool {
+        command_topic.as_ref().is_some_and(|command_topic| {
+            resolve(command_topic, self.topic_prefix.as_deref()).as_str() == topic
+        })
+    }
+}
+
+#[cfg(all(test, feature = "client"))]
+mod tests {
+    use crate::{testing::MockClient, HomeAssistantMqtt};
+
+    use super::*;
+
+    #[test]
+    fn can_convert_fan_speeds() {
+        let fan = Fan::new("~/set")
+            .topic_prefix("home/fan")
+            .speed_range_min(1)
+            .speed_range_max(3);
+
+        assert_eq!(fan.percentage_to_speed(0).unwrap(), 0);
+        assert_eq!(fan.percentage_to_speed(1).unwrap(), 1);
+        assert_eq!(fan.percentage_to_speed(33).unwrap(), 1);
+        assert_eq!(fan.percentage_to_speed(34).unwrap(), 2);
+        assert_eq!(fan.percentage_to_speed(66).unwrap(), 2);
+        assert_eq!(fan.percentage_to_speed(67).unwrap(), 3);
+        assert_eq!(fan.percentage_to_speed(100).unwrap(), 3);
+        assert!(fan.percentage_to_speed(101).is_err());
+        assert_eq!(fan.speed_to_percentage(0).unwrap(), 0);
+        assert_eq!(fan.speed_to_percentage(1).unwrap(), 33);
+        assert_eq!(fan.speed_to_percentage(2).unwrap(), 66);
+        assert_eq!(fan.speed_to_percentage(3).unwrap(), 100);
+        assert!(fan.speed_to_percentage(4).is_err());
+        for percentage in 0..=100 {
+            let speed = fan.percentage_to_speed(percentage).unwrap();
+            assert_eq!(
+                fan.percentage_to_speed(fan.speed_to_percentage(speed).unwrap())
+                    .unwrap(),
+                speed
+            );
+        }
+
+        let shifted = Fan::new("home/fan/set")
+            .speed_range_min(10)
+            .speed_range_max(12);
+        assert_eq!(shifted.percentage_to_speed(0).unwrap(), 9);
+        assert_eq!(shifted.percentage_to_speed(50).unwrap(), 11);
+        assert_eq!(shifted.speed_to_percentage(12).unwrap(), 100);
+        assert!(Fan::new("home/fan/set")
+            .speed_range_min(0)
+            .percentage_to_speed(50)
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn can_drive_fans() {
+        let client = MockClient::default();
+        let ha = HomeAssistantMqtt::new(client.clone(), "homeassistant");
+        let fan = Fan::new("~/set")
+            .topic_prefix("home/fan")
+            .state_topic("~/state")
+            .percentage_state_topic("~/speed")
+            .percentage_command_topic("~/speed/set")
+            .preset_mode_state_topic("~/preset")
+            .preset_mode_command_topic("~/preset/set")
+            .preset_modes(vec!["auto".to_string()])
+            .oscillation_command_topic("~/oscillation/set")
+            .speed_range_max(3);
+
+        ha.publish_fan_state(
+            &fan,
+            &FanState::default()
+                .on(true)
+                .percentage(50)
+                .preset_mode("auto")
+                .oscillating(true),
+        )
+        .await
+        .unwrap();
+        let payload = |topic| {
+            client
+                .last_published(topic)
+                .unwrap()
+                .payload_str()
+                .to_string()
+        };
+        assert_eq!(payload("home/fan/state"), "ON");
+        assert_eq!(payload("home/fan/speed"), "2");
+        assert_eq!(payload("home/fan/preset"), "auto");
+        assert_eq!(client.published().len(), 3);
+
+        assert_eq!(fan.command("home/fan/set", "OFF"), Some(FanCommand::Off));
+        assert_eq!(
+            fan.command("home/fan/speed/set", "3"),
+            Some(FanCommand::Speed(3))
+        );
+        assert_eq!(fan.command("home/fan/speed/set", "4"), None);
+        assert_eq!(
+            fan.command("home/fan/preset/set", "auto"),
+            Some(FanCommand::PresetMode("auto".to_string()))
+        );
+        assert_eq!(fan.command("home/fan/preset/set", "eco"), None);
+        assert_eq!(
+            fan.command("home/fan/oscillation/set", "oscillate_off"),
+            Some(FanCommand::Oscillate(false))
+        );
+        assert_eq!(fan.command("home/fan/other", "ON"), None);
+    }
+}
@@ -0,0 +1,1738 @@
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, BTreeSet},
+    sync::{Arc, Mutex},
+};
+
+use anyhow::{anyhow, bail, Result};
+use futures::{stream, Stream};
+use rumqttc::v5::{mqttbytes::QoS::AtLeastOnce, AsyncClient, EventLoop};
+use serde::Serialize;
+use serde_json::Value;
+
+#[cfg(feature = "entity-alarm_control_panel")]
+use crate::alarm_control_panel::AlarmState;
+use crate::availability::AvailabilityPayloads;
+use crate::client::{publish_traced, MqttClient, MqttPublisher, PublishOptions};
+#[cfg(feature = "entity-climate")]
+use crate::climate::{ClimateState, ClimateTargets};
+use crate::components::DeviceComponents;
+#[cfg(feature = "entity-cover")]
+use crate::cover::CoverState;
+use crate::discovery::{self, DiscoveryEvent, DiscoveryOptions, DiscoveryWatcher};
+use crate::error::PublishError;
+#[cfg(feature = "entity-fan")]
+use crate::fan::FanState;
+use crate::hook::PublishHook;
+#[cfg(feature = "entity-humidifier")]
+use crate::humidifier::HumidifierAction;
+#[cfg(feature = "entity-camera")]
+use crate::image::image_content_type;
+#[cfg(any(feature = "entity-camera", feature = "entity-image"))]
+use crate::image::image_payload;
+#[cfg(feature = "entity-image")]
+use crate::image::ImageData;
+#[cfg(feature = "entity-lawn_mower")]
+use crate::lawn_mower::LawnMowerActivity;
+use crate::locale::NameProvider;
+#[cfg(feature = "entity-lock")]
+use crate::lock::LockState;
+#[cfg(feature = "entity-alarm_control_panel")]
+use crate::mqtt::alarm_control_panel::AlarmControlPanel;
+#[cfg(feature = "entity-binary_sensor")]
+use crate::mqtt::binary_sensor::BinarySensor;
+#[cfg(feature = "entity-camera")]
+use crate::mqtt::camera::Camera;
+#[cfg(feature = "entity-climate")]
+use crate::mqtt::climate::Climate;
+use crate::mqtt::common::{Device, Origin};
+#[cfg(feature = "entity-cover")]
+use crate::mqtt::cover::Cover;
+#[cfg(feature = "entity-event")]
+use crate::mqtt::event::Event;
+#[cfg(feature = "entity-fan")]
+use crate::mqtt::fan::Fan;
+#[cfg(feature = "entity-humidifier")]
+use crate::mqtt::humidifier::Humidifier;
+#[cfg(feature = "entity-image")]
+use crate::mqtt::image::Image;
+#[cfg(feature = "entity-lawn_mower")]
+use crate::mqtt::lawn_mower::LawnMower;
+#[cfg(feature = "entity-lock")]
+use crate::mqtt::lock::Lock;
+#[cfg(feature = "entity-siren")]
+use crate::mqtt::siren::Siren;
+#[cfg(feature = "entity-switch")]
+use crate::mqtt::switch::Switch;
+#[cfg(feature = "entity-tag")]
+use crate::mqtt::tag::Tag;
+#[cfg(feature = "entity-text")]
+use crate::mqtt::text::Text;
+#[cfg(feature = "entity-update")]
+use crate::mqtt::update::Update;
+#[cfg(feature = "entity-vacuum")]
+use crate::mqtt::vacuum::Vacuum;
+#[cfg(feature = "entity-valve")]
+use crate::mqtt::valve::Valve;
+use crate::origin::OriginPolicy;
+#[cfg(feature = "entity-siren")]
+use crate::siren::SirenCommand;
+use crate::state::{StateCache, StateMetrics};
+use crate::status::{HomeAssistantStatus, StatusWatcher};
+#[cfg(feature = "entity-cover")]
+use crate::topic::Topic;
+use crate::topic::{normalize_discovery_prefix, validate_payload_topics};
+#[cfg(feature = "entity-update")]
+use crate::update::UpdateState;
+#[cfg(feature = "entity-vacuum")]
+use crate::vacuum::VacuumState;
+#[cfg(feature = "entity-valve")]
+use crate::valve::ValveState;
+use crate::{device::HaDevice, Entity};
+
+/// Publishes discovery configurations through an MQTT v5 (default) or MQTT v3.1.1 `rumqttc` client.
+#[derive(Clone)]
+pub struct HomeAssistantMqtt<C: MqttPublisher = AsyncClient> {
+    pub(crate) client: C,
+    pub(crate) discovery_prefix: String,
+    node_id: Option<String>,
+    discovery_options: DiscoveryOptions,
+    state_cache: Option<StateCache>,
+    discovery_cache: StateCache,
+    discovery_payloads: Arc<Mutex<BTreeMap<String, Vec<u8>>>>,
+    subscriptions: Arc<Mutex<BTreeSet<String>>>,
+    availability_payloads: Option<AvailabilityPayloads>,
+    hooks: Vec<Arc<dyn PublishHook>>,
+    origin_policy: OriginPolicy,
+    default_device: Option<Device>,
+    user_properties: Vec<(String, String)>,
+}
+
+impl<C: MqttPublisher> HomeAssistantMqtt<C> {
+    /// The trailing slashes of the discovery prefix are removed, see `try_new` to also validate it.
+    pub fn new<S: Into<String>>(client: C, discovery_prefix: S) -> Self {
+        let discovery_prefix: String = discovery_prefix.into();
+        Self {
+            client,
+            discovery_prefix: discovery_prefix.trim_end_matches('/').to_string(),
+            node_id: None,
+            discovery_options: DiscoveryOptions::default(),
+            state_cache: None,
+            discovery_cache: StateCache::default(),
+            discovery_payloads: Arc::default(),
+            subscriptions: Arc::default(),
+            availability_payloads: None,
+            hooks: vec![],
+            origin_policy: OriginPolicy::default(),
+            default_device: None,
+            user_properties: vec![],
+        }
+    }
+
+    /// Fails when the discovery prefix is empty or contains a wildcard, see `topic::normalize_discovery_prefix`.
+    pub fn try_new<S: AsRef<str>>(client: C, discovery_prefix: S) -> Result<Self, PublishError> {
+        let discovery_prefix = discovery_prefix.as_ref();
+        normalize_discovery_prefix(discovery_prefix)
+            .map(|normalized| Self::new(client, normalized))
+            .map_err(|error| PublishError::InvalidDiscoveryPrefix {
+                discovery_prefix: discovery_prefix.to_string(),
+                error,
+            })
+    }
+
+    /// ID of the node providing the entities, inserted as the `<node_id>` level of the discovery topics.
+    ///
+    /// The ID of the node must only consist of characters from the character class [a-zA-Z0-9_-] (alphanumerics, underscore and hyphen).
+    pub fn with_node_id<S: Into<String>>(mut self, node_id: S) -> Self {
+        self.node_id = Some(node_id.into());
+        self
+    }
+
+    /// Skips the retained states identical to the last state published on the same topic.
+    ///
+    /// The cache is shared by the clones of this instance, `force_state` bypasses it.
+    pub fn with_state_deduplication(mut self) -> Self {
+        self.state_cache = Some(StateCache::default());
+        self
+    }
+
+    /// Counters of the published and skipped states, `None` unless the state deduplication is enabled.
+    pub fn state_metrics(&self) -> Option<StateMetrics> {
+        self.state_cache.as_ref().map(StateCache::metrics)
+    }
+
+    /// The availability payloads of the bridge, set on the availability checks of the published entities which don't
+    /// define their own payloads.
+    pub fn with_availability_payloads(mut self, payloads: AvailabilityPayloads) -> Self {
+        self.availability_payloads = Some(payloads);
+        self
+    }
+
+    /// Registers callbacks called around the publication of every discovery configuration, in registration order.
+    pub fn with_hook<H: PublishHook + 'static>(mut self, hook: H) -> Self {
+        self.hooks.push(Arc::new(hook));
+        self
+    }
+
+    /// How the configurations without origin information are handled, they are published as they are by default.
+    pub fn with_origin_policy(mut self, origin_policy: OriginPolicy) -> Self {
+        self.origin_policy = origin_policy;
+        self
+    }
+
+    /// The origin set on the published configurations without origin information, same as an
+    /// `OriginPolicy::Inject` policy.
+    pub fn with_default_origin(self, origin: Origin) -> Self {
+        self.with_origin_policy(OriginPolicy::Inject(origin))
+    }
+
+    /// The device set on the published entities without device information, for the bridges exposing a single device.
+    ///
+    /// The entities must still have a `unique_id` to be attached to the device.
+    pub fn with_default_device(mut self, device: Device) -> Self {
+        self.default_device = Some(device);
+        self
+    }
+
+    /// Adds a MQTT v5 user property to every discovery configuration and state published by this instance, eg.
+    /// `origin=my-bridge` to filter the messages broker-side.
+    ///
+    /// The user properties of the `PublishOptions` of a call are sent after these ones.
+    pub fn with_user_property<N: Into<String>, V: Into<String>>(
+        mut self,
+        name: N,
+        value: V,
+    ) -> Self {
+        self.user_properties.push((name.into(), value.into()));
+        self
+    }
+
+    /// The QoS, retain flag and expiry of the discovery configuration messages.
+    pub fn with_discovery_options(mut self, discovery_options: DiscoveryOptions) -> Self {
+        self.discovery_options = discovery_options;
+        self
+    }
+
+    /// The discovery topic needs to follow a specific format:
+    /// `<discovery_prefix>/<component>/[<node_id>/]<object_id>/config`
+    ///
+    /// - `<discovery_prefix>`: The Discovery Prefix defaults to homeassistant. This prefix can be changed.
+    /// - `<component>`: One of the supported MQTT integrations, eg. binary_sensor.
+    /// - `<node_id>` (Optional): ID of the node providing the topic, this is not used by Home Assistant but may be used to structure the MQTT topic. The ID of the node must only consist of characters from the character class [a-zA-Z0-9_-] (alphanumerics, underscore and hyphen).
+    /// - `<object_id>`: The ID of the device. This is only to allow for separate topics for each device and is not used for the entity_id. The ID of the device must only consist of characters from the character class [a-zA-Z0-9_-] (alphanumerics, underscore and hyphen).
+    ///
+    /// The `<node_id>` level can be used by clients to only subscribe to their own (command) topics by using one wildcard topic like <discovery_prefix>/+/<node_id>/+/set.
+    ///
+    /// Best practice for entities with a unique_id is to set `<object_id>` to unique_id and omit the `<node_id>`.
+    pub async fn publish_entity(&self, entity: Entity) -> Result<(), PublishError> {
+        self.publish_entity_with_options(entity, PublishOptions::default())
+            .await
+    }
+
+    /// Publishes the discovery configuration of the entity with MQTT v5 properties, eg. user properties a broker
+    /// routes on.
+    ///
+    /// The set properties override the `application/json` content type and the message expiry interval of the
+    /// `DiscoveryOptions`. `republish_discovery` publishes the configuration again without them.
+    pub async fn publish_entity_with_options(
+        &self,
+        entity: Entity,
+        options: PublishOptions,
+    ) -> Result<(), PublishError> {
+        let (topic, payload) = self.discovery_message(&entity)?;
+        self.publish_discovery(topic.clone(), payload.into_bytes(), options)
+            .await?;
+        for hook in &self.hooks {
+            hook.after_publish(&topic, &entity);
+        }
+        Ok(())
+    }
+
+    /// Publishes the discovery configuration of the entity unless the same configuration was the last one published
+    /// on its discovery topic by this instance or one of its clones. Returns whether the configuration was published.
+    ///
+    /// Home Assistant reloads an entity each time its configuration is published, bridges announcing their entities
+    /// on every reconnection should use it to avoid the churn.
+    pub async fn publish_entity_if_changed(&self, entity: Entity) -> Result<bool, PublishError> {
+        let (topic, payload) = self.discovery_message(&entity)?;
+        if !self.discovery_cache.has_changed(&topic, payload.as_bytes()) {
+            self.discovery_cache.record_skipped();
+            return Ok(false);
+        }
+        self.publish_discovery(
+            topic.clone(),
+            payload.into_bytes(),
+            PublishOptions::default(),
+        )
+        .await?;
+        for hook in &self.hooks {
+            hook.after_publish(&topic, &entity);
+        }
+        Ok(true)
+    }
+
+    /// Forgets the configurations published so far, `publish_entity_if_changed` then publishes every configuration
+    /// again. Needed when the broker lost its retained messages, like a broker without persistence restarting.
+    pub fn clear_discovery_cache(&self) {
+        self.discovery_cache.clear();
+    }
+
+    /// The discovery topics published by this instance or one of its clones and not removed since.
+    pub fn published_discovery_topics(&self) -> Vec<String> {
+        self.discovery_payloads
+            .lock()
+            .unwrap()
+            .keys()
+            .cloned()
+            .collect()
+    }
+
+    /// Publishes again every discovery configuration published by this instance or one of its clones and not removed
+    /// since, eg. after the broker lost its retained messages. Returns the number of configurations published.
+    ///
+    /// `HomeAssistantMqtt::connect` calls it on every reconnection and Home Assistant birth message.
+    pub async fn republish_discovery(&self) -> Result<usize, PublishError> {
+        let payloads = self.discovery_payloads.lock().unwrap().clone();
+        for (topic, payload) in &payloads {
+            self.publish_discovery(topic.clone(), payload.clone(), PublishOptions::default())
+                .await?;
+        }
+        Ok(payloads.len())
+    }
+
+    /// The discovery topic and the JSON discovery configuration of the entity, as published by `publish_entity`.
+    ///
+    /// Fails with `PublishError::Invalid` when the entity breaks the rules checked by `Entity::validate`.
+    pub fn discovery_message(&self, entity: &Entity) -> Result<(String, String), PublishError> {
+        let mut entity = self.prepare_entity(entity);
+        if entity.origin().name.is_empty() {
+            let mut origin = entity.origin().clone();
+            self.origin_policy.apply(&mut origin)?;
+            entity.to_mut().set_origin(origin);
+        }
+        let entity = entity.as_ref();
+        let component = entity.platform();
+        let attributes = entity.get_attributes()?;
+        validate_payload_topics(&attributes)?;
+        let device = entity.device();
+        if *device != Device::default() && !device.is_identified() {
+            return Err(PublishError::EmptyDeviceIdentifiers);
+        }
+        let object_id = match entity {
+            #[cfg(feature = "entity-device_trigger")]
+            Entity::DeviceTrigger(trigger) => trigger.discovery_object_id(),
+            _ => entity
+                .unique_id()
+                .ok_or(PublishError::MissingUniqueId)?
+                .to_string(),
+        };
+        entity.validate().map_err(PublishError::Invalid)?;
+        let topic = self.discovery_topic(component, &object_id)?;
+        Ok((topic, self.encode_discovery_payload(attributes)?))
+    }
+
+    /// Serializes a discovery configuration according to the discovery options.
+    fn encode_discovery_payload(&self, mut payload: Value) -> Result<String, PublishError> {
+        if self.discovery_options.strip_defaults {
+            discovery::strip_defaults(&mut payload);
+        }
+        if self.discovery_options.full_names {
+            discovery::expand_names(&mut payload);
+        }
+        let payload = serde_json::ser::to_string(&payload)?;
+        match self.discovery_options.max_payload_size {
+            Some(limit) if payload.len() > limit => Err(PublishError::PayloadTooLarge {
+                size: payload.len(),
+                limit,
+            }),
+            _ => Ok(payload),
+        }
+    }
+
+    /// The entity as published: with the default device, the bridge availability payloads and the changes of the
+    /// hooks.
+    fn prepare_entity<'a>(&self, entity: &'a Entity) -> Cow<'a, Entity> {
+        let default_device = self
+            .default_device
+            .as_ref()
+            .filter(|_| *entity.device() == Device::default());
+        if default_device.is_none() && self.availability_payloads.is_none() && self.hooks.is_empty()
+        {
+            return Cow::Borrowed(entity);
+        }
+        let mut entity = entity.clone();
+        if let Some(device) = default_device {
+            entity.set_device(device.clone());
+        }
+        if let Some(payloads) = &self.availability_payloads {
+            let availability = entity.availability_mut();
+            availability.availability = availability
+                .availability
+                .drain(..)
+                .map(|check| payloads.apply(check))
+                .collect();
+        }
+        for hook in &self.hooks {
+            hook.before_publish(&mut entity);
+        }
+        Cow::Owned(entity)
+    }
+
+    /// The device discovery topic `<discovery_prefix>/device/[<node_id>/]<object_id>/config` and the JSON configuration
+    /// of every component of the device, as published by `publish_device_components`.
+    ///
+    /// Fails with `PublishError::Invalid` when the device breaks the rules checked by `DeviceComponents::validate`.
+    pub fn device_discovery_message(
+        &self,
+        components: &DeviceComponents,
+    ) -> Result<(String, String), PublishError> {
+        let mut payload =
+            components.payload(|entity| self.prepare_entity(entity).get_attributes())?;
+        if self.origin_policy != OriginPolicy::Ignore {
+            let mut origin: Origin = match payload.get("o") {
+                Some(origin) => serde_json::from_value(origin.clone())?,
+                None => Origin::default(),
+            };
+            self.origin_policy.apply(&mut origin)?;
+            payload["o"] = serde_json::to_value(origin)?;
+        }
+        validate_payload_topics(&payload)?;
+        components.validate().map_err(PublishError::Invalid)?;
+        let topic = self.discovery_topic("device", components.object_id())?;
+        Ok((topic, self.encode_discovery_payload(payload)?))
+    }
+
+    /// Publishes the configuration of every component of a device in a single device discovery message.
+    pub async fn publish_device_components(
+        &self,
+        components: &DeviceComponents,
+    ) -> Result<(), PublishError> {
+        let (topic, payload) = self.device_discovery_message(components)?;
+        self.publish_discovery(
+            topic.clone(),
+            payload.into_bytes(),
+            PublishOptions::default(),
+        )
+        .await?;
+        for entity in components.components().values() {
+            for hook in &self.hooks {
+                hook.after_publish(&topic, entity);
+            }
+        }
+        Ok(())
+    }
+
+    fn discovery_topic(&self, component: &str, object_id: &str) -> Result<String, PublishError> {
+        let prefix = &self.discovery_prefix;
+        match &self.node_id {
+            Some(node_id) => {
+                if !is_valid_topic_id(node_id) {
+                    return Err(PublishError::InvalidNodeId(node_id.clone()));
+                }
+                Ok(format!("{prefix}/{component}/{node_id}/{object_id}/config"))
+            }
+            None => Ok(format!("{prefix}/{component}/{object_id}/config")),
+        }
+    }
+
+    /// Removes the discovery configuration published on the topic, Home Assistant then deletes the entity.
+    ///
+    /// The empty payload is always retained so it also clears the retained configuration.
+    pub async fn remove_discovery(&self, topic: &str) -> Result<(), PublishError> {
+        self.discovery_payloads.lock().unwrap().remove(topic);
+        self.discovery_cache.forget(topic);
+        publish_traced(
+            &self.client,
+            topic.to_string(),
+            self.discovery_options.qos,
+            true,
+            vec![],
+            self.tagged(PublishOptions::default()),
+        )
+        .await
+        .map_err(|error| PublishError::Client(Box::new(error)))
+    }
+
+    async fn publish_discovery(
+        &self,
+        topic: String,
+        payload: Vec<u8>,
+        overrides: PublishOptions,
+    ) -> Result<(), PublishError> {
+        let discovery_options = &self.discovery_options;
+        let options = PublishOptions {
+            message_expiry_interval: overrides
+                .message_expiry_interval
+                .or(discovery_options.message_expiry_interval),
+            content_type: overrides
+                .content_type
+                .or(Some("application/json".to_string())),
+            ..overrides
+        };
+        publish_traced(
+            &self.client,
+            topic.clone(),
+            discovery_options.qos,
+            discovery_options.retain,
+            payload.clone(),
+            self.tagged(options),
+        )
+        .await
+        .map_err(|error| PublishError::Client(Box::new(error)))?;
+        self.discovery_cache.record_published(&topic, &payload);
+        self.discovery_payloads
+            .lock()
+            .unwrap()
+            .insert(topic, payload);
+        Ok(())
+    }
+
+    /// Publishes the discovery configuration of every entity of the device.
+    pub async fn publish_device_entities<D: HaDevice>(&self, device: &D) -> Result<()> {
+        for entity in device.entities() {
+            self.publish_entity(entity).await?;
+        }
+        Ok(())
+    }
+
+    /// Publishes the discovery configuration of every entity of the device, with names translated by the given provider.
+    pub async fn publish_localized_device_entities<D: HaDevice>(
+        &self,
+        device: &D,
+        names: &dyn NameProvider,
+    ) -> Result<()> {
+        for entity in device.localized_entities(names) {
+            self.publish_entity(entity).await?;
+        }
+        Ok(())
+    }
+
+    /// Publishes the current state of every entity of the device.
+    pub async fn publish_device_states<D: HaDevice>(&self, device: &D) -> Result<()> {
+        for (topic, payload) in device.states() {
+            self.publish_state(&topic, payload).await?;
+        }
+        Ok(())
+    }
+
+    /// Publishes a retained state.
+    ///
+    /// Returns `false` when the state deduplication is enabled and the state was skipped because it's identical to the last one.
+    pub async fn publish_state<P: Into<Vec<u8>>>(&self, topic: &str, payload: P) -> Result<bool> {
+        self.publish_retained(topic, payload.into(), PublishOptions::default(), false)
+            .await
+    }
+
+    /// Publishes a state with the given retain flag.
+    ///
+    /// Event-like payloads, such as button presses, scanned tags or events, shouldn't be retained: the broker would
+    /// replay them to Home Assistant on every reconnection. Only the retained states are deduplicated.
+    pub async fn publish_with_retain<P: Into<Vec<u8>>>(
+        &self,
+        topic: &str,
+        payload: P,
+        retain: bool,
+        options: PublishOptions,
+    ) -> Result<bool> {
+        self.publish_message(topic, payload.into(), options, retain, false)
+            .await
+    }
+
+    /// Publishes a state on the state topic of the entity, retained according to `Entity::retains_state`.
+    pub async fn publish_entity_state<P: Into<Vec<u8>>>(
+        &self,
+        entity: &Entity,
+        payload: P,
+    ) -> Result<bool> {
+        let topic = entity
+            .state_topic()
+            .ok_or(anyhow!("{} entity has no state topic", entity.platform()))?;
+        let topic = entity.resolve_topic(topic);
+        self.publish_with_retain(
+            &topic,
+            payload,
+            entity.retains_state(),
+            PublishOptions::default(),
+        )
+        .await
+    }
+
+    /// Publishes the attributes of the entity on its JSON attributes topic, retained according to
+    /// `Entity::retains_state`.
+    ///
+    /// Home Assistant only accepts a JSON object, the other JSON values are rejected.
+    pub async fn publish_attributes<A: Serialize>(
+        &self,
+        entity: &Entity,
+        attributes: &A,
+    ) -> Result<bool> {
+        let topic = entity.resolved_json_attributes_topic().ok_or(anyhow!(
+            "{} entity has no json attributes topic",
+            entity.platform()
+        ))?;
+        let attributes = serde_json::to_value(attributes)?;
+        if !attributes.is_object() {
+            bail!("json attributes should be an object, got {attributes}");
+        }
+        let options = PublishOptions {
+            content_type: Some("application/json".to_string()),
+            ..Default::default()
+        };
+        self.publish_with_retain(
+            topic.as_ref(),
+            attributes.to_string(),
+            entity.retains_state(),
+            options,
+        )
+        .await
+    }
+
+    /// Publishes a retained state even if it's identical to the last one.
+    pub async fn force_state<P: Into<Vec<u8>>>(&self, topic: &str, payload: P) -> Result<()> {
+        self.publish_retained(topic, payload.into(), PublishOptions::default(), true)
+            .await?;
+        Ok(())
+    }
+
+    /// Publishes the JSON state of an update entity on its state topic.
+    #[cfg(feature = "entity-update")]
+    pub async fn publish_update_state(&self, update: &Update, state: &UpdateState) -> Result<()> {
+        let topic = update
+            .state_topic
+            .as_ref()
+            .ok_or(anyhow!("update entity has no state topic"))?;
+        let topic = crate::topic::resolve(topic, update.topic_prefix.as_deref());
+        self.publish_state(&topic, serde_json::to_vec(state)?)
+            .await?;
+        Ok(())
+    }
+
+    /// Publishes the JSON state of a vacuum entity on its state topic.
+    #[cfg(feature = "entity-vacuum")]
+    pub async fn publish_vacuum_state(&self, vacuum: &Vacuum, state: &VacuumState) -> Result<()> {
+        let topic = vacuum
+            .state_topic
+            .as_ref()
+            .ok_or(anyhow!("vacuum entity has no state topic"))?;
+        let topic = crate::topic::resolve(topic, vacuum.topic_prefix.as_deref());
+        self.publish_state(&topic, serde_json::to_vec(state)?)
+            .await?;
+        Ok(())
+    }
+
+    /// Publishes the state of an alarm control panel entity on its state topic.
+    #[cfg(feature = "entity-alarm_control_panel")]
+    pub async fn publish_alarm_state(
+        &self,
+        alarm_control_panel: &AlarmControlPanel,
+        state: AlarmState,
+    ) -> Result<()> {
+        let topic = crate::topic::resolve(
+            &alarm_control_panel.state_topic,
+            alarm_control_panel.topic_prefix.as_deref(),
+        );
+        self.publish_state(&topic, state.as_str()).await?;
+        Ok(())
+    }
+
+    /// Publishes the state of a cover entity on its state topic, see `Cover::state_payload`.
+    #[cfg(feature = "entity-cover")]
+    pub async fn publish_cover_state(&self, cover: &Cover, state: &CoverState) -> Result<()> {
+        let topic = cover
+            .state_topic
+            .as_ref()
+            .ok_or(anyhow!("cover entity has no state topic"))?;
+        self.publish_cover_topic(cover, topic, cover.state_payload(state))
+            .await
+    }
+
+    /// Publishes a position in percent of a cover entity on its position topic, see `Cover::position_payload`.
+    #[cfg(feature = "entity-cover")]
+    pub async fn publish_cover_position(&self, cover: &Cover, percent: u8) -> Result<()> {
+        let topic = cover
+            .position_topic
+            .as_ref()
+            .ok_or(anyhow!("cover entity has no position topic"))?;
+        self.publish_cover_topic(cover, topic, cover.position_payload(percent))
+            .await
+    }
+
+    /// Publishes a tilt in percent of a cover entity on its tilt status topic, see `Cover::tilt_payload`.
+    #[cfg(feature = "entity-cover")]
+    pub async fn publish_cover_tilt(&self, cover: &Cover, percent: u8) -> Result<()> {
+        let topic = cover
+            .tilt_status_topic
+            .as_ref()
+            .ok_or(anyhow!("cover entity has no tilt status topic"))?;
+        self.publish_cover_topic(cover, topic, cover.tilt_payload(percent))
+            .await
+    }
+
+    #[cfg(feature = "entity-cover")]
+    async fn publish_cover_topic<P: Into<Vec<u8>>>(
+        &self,
+        cover: &Cover,
+        topic: &Topic,
+        payload: P,
+    ) -> Result<()> {
+        let topic = crate::topic::resolve(topic, cover.topic_prefix.as_deref());
+        self.publish_state(&topic, payload).await?;
+        Ok(())
+    }
+
+    /// Publishes the value of a text entity on its state topic, once checked with `Text::check_value`.
+    #[cfg(feature = "entity-text")]
+    pub async fn publish_text_state(&self, text: &Text, value: &str) -> Result<()> {
+        text.check_value(value)?;
+        let topic = text
+            .state_topic
+            .as_ref()
+            .ok_or(anyhow!("text entity has no state topic"))?;
+        let topic = crate::topic::resolve(topic, text.topic_prefix.as_deref());
+        self.publish_state(&topic, value).await?;
+        Ok(())
+    }
+
+    /// Sends a command to a siren entity on its command topic, see `Siren::command_payload`.
+    #[cfg(feature = "entity-siren")]
+    pub async fn publish_siren_command(&self, siren: &Siren, command: &SirenCommand) -> Result<()> {
+        let payload = siren.command_payload(command)?;
+        let topic = siren
+            .command_topic
+            .as_ref()
+            .ok_or(anyhow!("siren entity has no command topic"))?;
+        let topic = crate::topic::resolve(topic, siren.topic_prefix.as_deref());
+        publish_traced(
+            &self.client,
+            topic.to_string(),
+            AtLeastOnce,
+            false,
+            payload.into_bytes(),
+            self.tagged(PublishOptions::default()),
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Publishes the JSON state of a siren entity on its state topic, see `Siren::command_payload`.
+    #[cfg(feature = "entity-siren")]
+    pub async fn publish_siren_state(&self, siren: &Siren, state: &SirenCommand) -> Result<()> {
+        let payload = siren.command_payload(state)?;
+        let topic = siren
+            .state_topic
+            .as_ref()
+            .ok_or(anyhow!("siren entity has no state topic"))?;
+        let topic = crate::topic::resolve(topic, siren.topic_prefix.as_deref());
+        self.publish_state(&topic, payload).await?;
+        Ok(())
+    }
+
+    /// Publishes a retained image on the topic of a camera entity, base64 encoded when its `image_encoding` is `b64`.
+    ///
+    /// The content type property is guessed from the image signature.
+    #[cfg(feature = "entity-camera")]
+    pub async fn publish_camera_image(&self, camera: &Camera, bytes: &[u8]) -> Result<()> {
+        let payload = image_payload(bytes, camera.image_encoding.as_deref())?;
+        let topic = crate::topic::resolve(&camera.topic, camera.topic_prefix.as_deref());
+        let options = PublishOptions {
+            content_type: image_content_type(bytes).map(str::to_string),
+            ..Default::default()
+        };
+        self.publish_retained(&topic, payload, options, false)
+            .await?;
+        Ok(())
+    }
+
+    /// Publishes a retained image of an image entity: the data on its image topic, base64 encoded when its
+    /// `image_encoding` is `b64`, or the URL on its URL topic.
+    ///
+    /// The content type property of the data is the `content_type` of the entity, `image/jpeg` by default.
+    #[cfg(feature = "entity-image")]
+    pub async fn publish_image(&self, image: &Image, data: ImageData<'_>) -> Result<()> {
+        let (topic, payload, content_type) = match data {
+            ImageData::Bytes(bytes) => (
+                image
+                    .image_topic
+                    .as_ref()
+                    .ok_or(anyhow!("image entity has no image topic"))?,
+                image_payload(bytes, image.image_encoding.as_deref())?,
+                Some(image.content_type.as_deref().unwrap_or("image/jpeg")),
+            ),
+            ImageData::Url(url) => (
+                image
+                    .url_topic
+                    .as_ref()
+                    .ok_or(anyhow!("image entity has no URL topic"))?,
+                url.as_bytes().to_vec(),
+                None,
+            ),
+        };
+        let topic = crate::topic::resolve(topic, image.topic_prefix.as_deref());
+        let options = PublishOptions {
+            content_type: content_type.map(str::to_string),
+            ..Default::default()
+        };
+        self.publish_retained(&topic, payload, options, false)
+            .await?;
+        Ok(())
+    }
+
+    /// Publishes the activity of a lawn mower entity on its activity state topic.
+    #[cfg(feature = "entity-lawn_mower")]
+    pub async fn publish_lawn_mower_activity(
+        &self,
+        lawn_mower: &LawnMower,
+        activity: LawnMowerActivity,
+    ) -> Result<()> {
+        let topic = lawn_mower
+            .activity_state_topic
+            .as_ref()
+            .ok_or(anyhow!("lawn mower entity has no activity state topic"))?;
+        let topic = crate::topic::resolve(topic, lawn_mower.topic_prefix.as_deref());
+        self.publish_state(&topic, activity.as_str()).await?;
+        Ok(())
+    }
+
+    /// Publishes the state of a fan entity on its state topics, the percentage is converted into a speed of the
+    /// `speed_range_min..=speed_range_max` range.
+    ///
+    /// The values without a configured topic are skipped.
+    #[cfg(feature = "entity-fan")]
+    pub async fn publish_fan_state(&self, fan: &Fan, state: &FanState) -> Result<()> {
+        let percentage = state
+            .percentage
+            .map(|percentage| fan.percentage_to_speed(percentage))
+            .transpose()?;
+        let values = [
+            (
+                &fan.state_topic,
+                state.on.map(|on| fan.state_payload(on).to_string()),
+            ),
+            (
+                &fan.percentage_state_topic,
+                percentage.map(|speed| speed.to_string()),
+            ),
+            (&fan.preset_mode_state_topic, state.preset_mode.clone()),
+            (
+                &fan.oscillation_state_topic,
+                state
+                    .oscillating
+                    .map(|oscillating| fan.oscillation_payload(oscillating).to_string()),
+            ),
+            (
+                &fan.direction_state_topic,
+                state
+                    .direction
+                    .map(|direction| direction.as_str().to_string()),
+            ),
+        ];
+        for (topic, payload) in values {
+            if let (Some(topic), Some(payload)) = (topic, payload) {
+                self.publish_state(
+                    &crate::topic::resolve(topic, fan.topic_prefix.as_deref()),
+                    payload,
+                )
+                .await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Publishes the current action of a humidifier entity on its action topic.
+    #[cfg(feature = "entity-humidifier")]
+    pub async fn publish_humidifier_action(
+        &self,
+        humidifier: &Humidifier,
+        action: HumidifierAction,
+    ) -> Result<()> {
+        let topic = humidifier
+            .action_topic
+            .as_ref()
+            .ok_or(anyhow!("humidifier entity has no action topic"))?;
+        let topic = crate::topic::resolve(topic, humidifier.topic_prefix.as_deref());
+        self.publish_state(&topic, action.as_str()).await?;
+        Ok(())
+    }
+
+    /// Publishes the state of a switch entity on its state topic, see `Switch::state_payload`.
+    #[cfg(feature = "entity-switch")]
+    pub async fn publish_switch_state(&self, switch: &Switch, on: bool) -> Result<()> {
+        let topic = switch
+            .state_topic
+            .as_ref()
+            .ok_or(anyhow!("switch entity has no state topic"))?;
+        let topic = crate::topic::resolve(topic, switch.topic_prefix.as_deref());
+        self.publish_state(&topic, switch.state_payload(on)).await?;
+        Ok(())
+    }
+
+    /// Publishes the state of a binary sensor entity on its state topic, see `BinarySensor::state_payload`.
+    #[cfg(feature = "entity-binary_sensor")]
+    pub async fn publish_binary_sensor_state(
+        &self,
+        binary_sensor: &BinarySensor,
+        on: bool,
+    ) -> Result<()> {
+        let topic = crate::topic::resolve(
+            &binary_sensor.state_topic,
+            binary_sensor.topic_prefix.as_deref(),
+        );
+        self.publish_state(&topic, binary_sensor.state_payload(on))
+            .await?;
+        Ok(())
+    }
+
+    /// Publishes the state of a lock entity on its state topic, see `Lock::state_payload`.
+    #[cfg(feature = "entity-lock")]
+    pub async fn publish_lock_state(&self, lock: &Lock, state: LockState) -> Result<()> {
+        let topic = lock
+            .state_topic
+            .as_ref()
+            .ok_or(anyhow!("lock entity has no state topic"))?;
+        let topic = crate::topic::resolve(topic, lock.topic_prefix.as_deref());
+        self.publish_state(&topic, lock.state_payload(state))
+            .await?;
+        Ok(())
+    }
+
+    /// Publishes the state of a valve entity on its state topic, see `Valve::state_payload`.
+    #[cfg(feature = "entity-valve")]
+    pub async fn publish_valve_state(&self, valve: &Valve, state: &ValveState) -> Result<()> {
+        let topic = valve
+            .state_topic
+            .as_ref()
+            .ok_or(anyhow!("valve entity has no state topic"))?;
+        let topic = crate::topic::resolve(topic, valve.topic_prefix.as_deref());
+        self.publish_state(&topic, valve.state_payload(state)?)
+            .await?;
+        Ok(())
+    }
+
+    /// Publishes an event of an event entity on its state topic.
+    ///
+    /// The `event_type` must be one of the entity `event_types`, Home Assistant drops the other events. The
+    /// attributes must serialize to a JSON object, or to `null` when the event has no attributes.
+    #[cfg(feature = "entity-event")]
+    pub async fn publish_event<A: Serialize>(
+        &self,
+        event: &Event,
+        event_type: &str,
+        attributes: A,
+    ) -> Result<()> {
+        if !event.event_types.iter().any(|t| t == event_type) {
+            return Err(anyhow!(
+                "event type '{event_type}' is not one of the entity event types {:?}",
+                event.event_types
+            ));
+        }
+        let mut payload = match serde_json::to_value(attributes)? {
+            Value::Object(attributes) => attributes,
+            Value::Null => serde_json::Map::new(),
+            _ => return Err(anyhow!("event attributes should be a JSON object")),
+        };
+        payload.insert("event_type".to_string(), event_type.into());
+        let topic = crate::topic::resolve(&event.state_topic, event.topic_prefix.as_deref());
+        publish_traced(
+            &self.client,
+            topic.to_string(),
+            AtLeastOnce,
+            false,
+            serde_json::to_vec(&payload)?,
+            self.tagged(PublishOptions::default()),
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Publishes the ID of a scanned tag on the topic of a tag scanner.
+    ///
+    /// The ID is published as is, the `value_template` of the scanner should be unset or extract the ID from it.
+    #[cfg(feature = "entity-tag")]
+    pub async fn publish_tag_scan(&self, tag: &Tag, tag_id: &str) -> Result<()> {
+        if tag_id.is_empty() {
+            return Err(anyhow!("tag ID is empty"));
+        }
+        let topic = crate::topic::resolve(&tag.topic, tag.topic_prefix.as_deref());
+        topic
+            .validate()
+            .map_err(|error| anyhow!("invalid tag scanner topic: {error}"))?;
+        publish_traced(
+            &self.client,
+            topic.to_string(),
+            AtLeastOnce,
+            false,
+            tag_id.as_bytes().to_vec(),
+            self.tagged(PublishOptions::default()),
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Publishes the state of a climate entity on its state topics.
+    ///
+    /// The topics extracting the value with a template receive the whole JSON state, once per topic, the other
+    /// topics receive the plain value. The values without a configured topic are skipped.
+    #[cfg(feature = "entity-climate")]
+    pub async fn publish_climate_state(
+        &self,
+        climate: &Climate,
+        state: &ClimateState,
+    ) -> Result<()> {
+        let json_state = serde_json::to_value(state)?;
+        let mut json_topics = BTreeSet::new();
+        for (topic, template, attribute) in climate.state_bindings() {
+            let (Some(topic), Some(value)) = (topic, json_state.get(attribute)) else {
+                continue;
+            };
+            let topic = crate::topic::resolve(topic, climate.topic_prefix.as_deref());
+            match (template, value) {
+                (Some(_), _) => {
+                    if json_topics.insert(topic.clone()) {
+                        self.publish_state(&topic, serde_json::to_vec(&json_state)?)
+                            .await?;
+                    }
+                }
+                (None, Value::String(value)) => {
+                    self.publish_state(&topic, value.as_str()).await?;
+                }
+                (None, value) => {
+                    self.publish_state(&topic, value.to_string()).await?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Publishes the target temperatures and humidity of a climate entity on their state topics, see
+    /// `publish_climate_state`.
+    ///
+    /// The state topics reading a JSON payload with a template receive a payload with the targets only, publish the
+    /// whole `ClimateState` with `publish_climate_state` to report the other values.
+    #[cfg(feature = "entity-climate")]
+    pub async fn set_climate_targets(
+        &self,
+        climate: &Climate,
+        targets: &ClimateTargets,
+    ) -> Result<()> {
+        self.publish_climate_state(climate, &ClimateState::from(targets))
+            .await
+    }
+
+    /// The options with the user properties of this instance first.
+    pub(crate) fn tagged(&self, mut options: PublishOptions) -> PublishOptions {
+        if !self.user_properties.is_empty() {
+            let user_properties = std::mem::take(&mut options.user_properties);
+            options.user_properties = self.user_properties.clone();
+            options.user_properties.extend(user_properties);
+        }
+        options
+    }
+
+    async fn publish_retained(
+        &self,
+        topic: &str,
+        payload: Vec<u8>,
+        options: PublishOptions,
+        force: bool,
+    ) -> Result<bool> {
+        self.publish_message(topic, payload, options, true, force)
+            .await
+    }
+
+    /// Publishes a state, the retained ones are deduplicated.
+    async fn publish_message(
+        &self,
+        topic: &str,
+        payload: Vec<u8>,
+        options: PublishOptions,
+        retain: bool,
+        force: bool,
+    ) -> Result<bool> {
+        let state_cache = self.state_cache.as_ref().filter(|_| retain);
+        if let Some(state_cache) = state_cache {
+            if !force && !state_cache.has_changed(topic, &payload) {
+                state_cache.record_skipped();
+                return Ok(false);
+            }
+        }
+        // the payload is only recorded once published, a failed publication is retried whatever the payload
+        let recorded_payload = state_cache.map(|_| payload.clone());
+        publish_traced(
+            &self.client,
+            topic.to_string(),
+            AtLeastOnce,
+            retain,
+            payload,
+            self.tagged(options),
+        )
+        .await?;
+        if let (Some(state_cache), Some(payload)) = (state_cache, recorded_payload) {
+            state_cache.record_published(topic, &payload);
+        }
+        Ok(true)
+    }
+
+    pub async fn publish_data<S: Serialize>(
+        &self,
+        topic: &str,
+        payload: &S,
+        message_expiry_interval: Option<u32>,
+    ) -> Result<()> {
+        let payload = serde_json::ser::to_string(payload)?;
+        let options = PublishOptions {
+            message_expiry_interval,
+            content_type: Some("application/json".to_string()),
+            ..Default::default()
+        };
+        self.publish_retained(topic, payload.into_bytes(), options, false)
+            .await?;
+        Ok(())
+    }
+
+    /// Publishes a retained payload as is, with the given MQTT v5 properties.
+    pub async fn publish_raw<P: Into<Vec<u8>>>(
+        &self,
+        topic: &str,
+        payload: P,
+        options: PublishOptions,
+    ) -> Result<()> {
+        self.publish_retained(topic, payload.into(), options, false)
+            .await?;
+        Ok(())
+    }
+
+    /// Publishes a retained plain text payload, like the `ON`, `home` or `21.5` states most entities expect.
+    ///
+    /// The content type is `text/plain` unless another one is given.
+    pub async fn publish_string(
+        &self,
+        topic: &str,
+        payload: &str,
+        mut options: PublishOptions,
+    ) -> Result<()> {
+        options
+            .content_type
+            .get_or_insert_with(|| "text/plain".to_string());
+        self.publish_raw(topic, payload, options).await
+    }
+}
+
+impl<C: MqttClient> HomeAssistantMqtt<C> {
+    /// Subscribes to the command topic of every entity of the device.
+    ///
+    /// Received commands should then be handed over to `HaDevice::handle_command`. The topics are recorded for
+    /// `resubscribe`.
+    pub async fn subscribe_device_commands<D: HaDevice>(&self, device: &D) -> Result<()> {
+        for entity in device.entities() {
+            if let Some(command_topic) = entity.command_topic() {
+                self.client
+                    .subscribe(command_topic.to_string(), AtLeastOnce)
+                    .await?;
+                self.subscriptions
+                    .lock()
+                    .unwrap()
+                    .insert(command_topic.to_string());
+            }
+        }
+        Ok(())
+    }
+
+    /// Subscribes again to every topic subscribed by `subscribe_device_commands` on this instance or one of its clones,
+    /// eg. after the broker lost the session. Returns the number of topics subscribed.
+    ///
+    /// `HomeAssistantMqtt::connect` calls it on every connection without a session present.
+    pub async fn resubscribe(&self) -> Result<usize> {
+        let topics = self.subscriptions.lock().unwrap().clone();
+        for topic in &topics {
+            self.client.subscribe(topic.clone(), AtLeastOnce).await?;
+        }
+        Ok(topics.len())
+    }
+}
+
+impl HomeAssistantMqtt<AsyncClient> {
+    /// Watches the discovery configurations published under the discovery prefix.
+    ///
+    /// The stream takes over the MQTT event loop: it subscribes to the discovery topics on every (re)connection and yields
+    /// an event each time a discovery configuration is created, updated or removed. Connection errors are yielded as well,
+    /// polling the stream again attempts to reconnect.
+    pub fn watch_discovery(
+        &self,
+        eventloop: EventLoop,
+    ) -> impl Stream<Item = Result<DiscoveryEvent>> {
+        let watcher = DiscoveryWatcher::new(self.discovery_prefix.clone());
+        stream::unfold(
+            (self.client.clone(), eventloop, watcher),
+            |(client, mut eventloop, mut watcher)| async move {
+                loop {
+                    let event = match eventloop.poll().await {
+                        Ok(event) => watcher.handle_event(&client, &event),
+                        Err(error) => Err(error.into()),
+                    };
+                    match event {
+                        Ok(None) => continue,
+                        Ok(Some(event)) => return Some((Ok(event), (client, eventloop, watcher))),
+                        Err(error) => return Some((Err(error), (client, eventloop, watcher))),
+                    }
+                }
+            },
+        )
+    }
+
+    /// Watches the status of Home Assistant, to publish the discovery configurations again when it restarts as
+    /// recommended by the Home Assistant documentation.
+    ///
+    /// The stream takes over the MQTT event loop: it subscribes to `<discovery_prefix>/status` on every
+    /// (re)connection and yields each received status. Connection errors are yielded as well, polling the stream
+    /// again attempts to reconnect.
+    ///
+    /// ```no_run
+    /// # use std::pin::pin;
+    /// # use futures::StreamExt;
+    /// # use ha_mqtt_discovery::{status::HomeAssistantStatus, v5::EventLoop, Entity, HomeAssistantMqtt};
+    /// # async fn example(ha: HomeAssistantMqtt, eventloop: EventLoop, sensor: Entity) -> anyhow::Result<()> {
+    /// let mut statuses = pin!(ha.watch_status(eventloop));
+    /// while let Some(status) = statuses.next().await {
+    ///     if status? == HomeAssistantStatus::Online {
+    ///         ha.publish_entity(sensor.clone()).await?;
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn watch_status(
+        &self,
+        eventloop: EventLoop,
+    ) -> impl Stream<Item = Result<HomeAssistantStatus>> {
+        let watcher = StatusWatcher::new(self.discovery_prefix.clone());
+        stream::unfold(
+            (self.client.clone(), eventloop, watcher),
+            |(client, mut eventloop, watcher)| async move {
+                loop {
+                    let status = match eventloop.poll().await {
+                        Ok(event) => watcher.handle_event(&client, &event),
+                        Err(error) => Err(error.into()),
+                    };
+                    match status {
+                        Ok(None) => continue,
+                        Ok(Some(status)) => {
+                            return Some((Ok(status), (client, eventloop, watcher)))
+                        }
+                        Err(error) => return Some((Err(error), (client, eventloop, watcher))),
+                    }
+                }
+            },
+        )
+    }
+}
+
+/// Whether the ID only consists of characters from the character class [a-zA-Z0-9_-].
+pub(crate) fn is_valid_topic_id(id: &str) -> bool {
+    !id.is_empty()
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+#[cfg(all(test, feature = "entity-sensor"))]
+mod tests {
+    use crate::{
+        mqtt::{common::Availability, sensor::Sensor},
+        testing::MockClient,
+        topic::TopicError,
+        validation::ValidationError,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    #[cfg(feature = "entity-switch")]
+    async fn can_retry_a_failed_state_publication() {
+        let client = MockClient::default();
+        let ha = HomeAssistantMqtt::new(client.clone(), "homeassistant").with_state_deduplication();
+
+        client.set_failing(true);
+        assert!(ha.publish_state("home/temperature", "21.5").await.is_err());
+        client.set_failing(false);
+        assert!(ha.publish_state("home/temperature", "21.5").await.unwrap());
+
+        assert_eq!(
+            client
+                .last_published("home/temperature")
+                .unwrap()
+                .payload_str(),
+            "21.5"
+        );
+    }
+
+    #[cfg(feature = "entity-switch")]
+    struct Garage;
+
+    #[cfg(feature = "entity-switch")]
+    impl HaDevice for Garage {
+        fn device_information(&self) -> Device {
+            Device::default().name("Garage").add_identifier("garage")
+        }
+
+        fn entities(&self) -> Vec<Entity> {
+            vec![
+                Sensor::new("garage/temperature")
+                    .unique_id("temperature")
+                    .into(),
+                Switch::new("garage/door/set").unique_id("door").into(),
+            ]
+        }
+
+        fn states(&self) -> Vec<(String, String)> {
+            vec![]
+        }
+
+        fn handle_command(&mut self, _topic: &str, _payload: &str) -> bool {
+            false
+        }
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "entity-switch")]
+    async fn can_resubscribe_to_device_commands() {
+        let client = MockClient::default();
+        let ha = HomeAssistantMqtt::new(client.clone(), "homeassistant");
+        ha.subscribe_device_commands(&Garage).await.unwrap();
+        client.clear();
+
+        assert_eq!(ha.clone().resubscribe().await.unwrap(), 1);
+        assert_eq!(
+            client.subscriptions(),
+            vec![("garage/door/set".to_string(), AtLeastOnce)]
+        );
+    }
+
+    #[tokio::test]
+    async fn can_publish_a_removed_discovery_again() {
+        let client = MockClient::default();
+        let ha = HomeAssistantMqtt::new(client.clone(), "homeassistant");
+        let sensor = Sensor::new("home/temperature").unique_id("temperature");
+
+        assert!(ha
+            .publish_entity_if_changed(sensor.clone().into())
+            .await
+            .unwrap());
+        ha.remove_discovery("homeassistant/sensor/temperature/config")
+            .await
+            .unwrap();
+        assert!(ha.publish_entity_if_changed(sensor.into()).await.unwrap());
+
+        let configuration = client
+            .last_published("homeassistant/sensor/temperature/config")
+            .unwrap();
+        assert!(!configuration.payload.is_empty());
+        assert_eq!(client.published().len(), 3);
+    }
+
+    #[test]
+    fn can_validate_topic_ids() {
+        assert!(is_valid_topic_id("bridge_01-a"));
+        assert!(!is_valid_topic_id(""));
+        assert!(!is_valid_topic_id("bridge/01"));
+        assert!(!is_valid_topic_id("bridge+"));
+    }
+
+    #[tokio::test]
+    async fn can_publish_to_a_mock_client() {
+        let client = MockClient::default();
+        let registry = HomeAssistantMqtt::new(client.clone(), "homeassistant");
+
+        registry
+            .publish_entity(Entity::from(
+                Sensor::new("home/temperature").unique_id("temperature"),
+            ))
+            .await
+            .unwrap();
+
+        let message = client
+            .last_published("homeassistant/sensor/temperature/config")
+            .unwrap();
+        assert!(message.retain);
+        assert_eq!(message.options.message_expiry_interval, Some(604800));
+        assert_eq!(
+            serde_json::from_str::<Value>(message.payload_str()).unwrap()["stat_t"],
+            "home/temperature"
+        );
+    }
+
+    #[test]
+    fn can_validate_discovery_prefixes() {
+        let client = MockClient::default();
+        let (topic, _) = HomeAssistantMqtt::try_new(client.clone(), "homeassistant//")
+            .unwrap()
+            .discovery_message(&Entity::from(
+                Sensor::new("home/temperature").unique_id("temperature"),
+            ))
+            .unwrap();
+        assert_eq!(topic, "homeassistant/sensor/temperature/config");
+
+        assert!(matches!(
+            HomeAssistantMqtt::try_new(client.clone(), "home/#"),
+            Err(PublishError::InvalidDiscoveryPrefix {
+                error: TopicError::Wildcard,
+                ..
+            })
+        ));
+        assert!(matches!(
+            HomeAssistantMqtt::try_new(client, ""),
+            Err(PublishError::InvalidDiscoveryPrefix {
+                error: TopicError::Empty,
+                ..
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn can_reject_invalid_entities() {
+        let client = MockClient::default();
+        let ha = HomeAssistantMqtt::new(client.clone(), "homeassistant");
+        let sensor = Sensor::new("home/temperature")
+            .unique_id("temperature")
+            .availability(Availability::default().expire_after(0));
+
+        let error = ha.publish_entity(sensor.into()).await.err().unwrap();
+        assert!(matches!(
+            &error,
+            PublishError::Invalid(errors) if errors == &[ValidationError::InvalidExpireAfter]
+        ));
+        assert_eq!(
+            error.to_string(),
+            "invalid configuration: 'expire_after' must be greater than 0"
+        );
+        assert!(client.published().is_empty());
+    }
+
+    #[tokio::test]
+    async fn can_tag_messages_with_user_properties() {
+        let client = MockClient::default();
+        let ha = HomeAssistantMqtt::new(client.clone(), "homeassistant")
+            .with_user_property("origin", "my-bridge")
+            .with_user_property("version", "1.2.3");
+        let tags = |name: &str, value: &str| {
+            vec![
+                ("origin".to_string(), "my-bridge".to_string()),
+                ("version".to_string(), "1.2.3".to_string()),
+                (name.to_string(), value.to_string()),
+            ]
+        };
+
+        ha.publish_entity_with_options(
+            Entity::from(Sensor::new("home/temperature").unique_id("temperature")),
+            PublishOptions {
+                user_properties: vec![("kind".to_string(), "discovery".to_string())],
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        ha.publish_string(
+            "home/temperature",
+            "21.5",
+            PublishOptions {
+                user_properties: vec![("kind".to_string(), "state".to_string())],
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        ha.publish_state("home/humidity", "40").await.unwrap();
+
+        assert_eq!(
+            client
+                .last_published("homeassistant/sensor/temperature/config")
+                .unwrap()
+                .options
+                .user_properties,
+            tags("kind", "discovery")
+        );
+        assert_eq!(
+            client
+                .last_published("home/temperature")
+                .unwrap()
+                .options
+                .user_properties,
+            tags("kind", "state")
+        );
+        assert_eq!(
+            client
+                .last_published("home/humidity")
+                .unwrap()
+                .options
+                .user_properties
+                .len(),
+            2
+        );
+    }
+
+    #[tokio::test]
+    async fn can_override_discovery_publish_properties() {
+        let client = MockClient::default();
+        let registry = HomeAssistantMqtt::new(client.clone(), "homeassistant");
+
+        registry
+            .publish_entity_with_options(
+                Entity::from(Sensor::new("home/temperature").unique_id("temperature")),
+                PublishOptions {
+                    response_topic: Some("home/replies".to_string()),
+                    user_properties: vec![("site".to_string(), "garage".to_string())],
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        let options = client
+            .last_published("homeassistant/sensor/temperature/config")
+            .unwrap()
+            .options;
+        assert_eq!(options.content_type.as_deref(), Some("application/json"));
+        assert_eq!(options.response_topic.as_deref(), Some("home/replies"));
+        assert_eq!(
+            options.user_properties,
+            vec![("site".to_string(), "garage".to_string())]
+        );
+    }
+
+    #[tokio::test]
+    async fn can_inject_default_device_and_origin() {
+        let client = MockClient::default();
+        let registry = HomeAssistantMqtt::new(client.clone(), "homeassistant")
+            .with_default_device(Device::default().name("Meter").add_identifier("meter"))
+            .with_default_origin(Origin::new("meter-bridge"));
+
+        registry
+            .publish_entity(Entity::from(Sensor::new("home/power").unique_id("power")))
+            .await
+            .unwrap();
+        registry
+            .publish_entity(Entity::from(
+                Sensor::new("home/temperature")
+                    .unique_id("temperature")
+                    .device(Device::default().add_identifier("thermometer"))
+                    .origin(Origin::new("thermometer-bridge")),
+            ))
+            .await
+            .unwrap();
+
+        let power: Value = serde_json::from_slice(
+            &client
+                .last_published("homeassistant/sensor/power/config")
+                .unwrap()
+                .payload,
+        )
+        .unwrap();
+        assert_eq!(
+            power["dev"],
+            serde_json::json!({"name": "Meter", "ids": ["meter"]})
+        );
+        assert_eq!(power["o"]["name"], "meter-bridge");
+        let temperature: Value = serde_json::from_slice(
+            &client
+                .last_published("homeassistant/sensor/temperature/config")
+                .unwrap()
+                .payload,
+        )
+        .unwrap();
+        assert_eq!(
+            temperature["dev"]["ids"],
+            serde_json::json!(["thermometer"])
+        );
+        assert_eq!(temperature["o"]["name"], "thermometer-bridge");
+    }
+
+    #[tokio::test]
+    async fn can_skip_identical_states() {
+        let client = MockClient::default();
+        let registry =
+            HomeAssistantMqtt::new(client.clone(), "homeassistant").with_state_deduplication();
+
+        assert!(registry
+            .publish_state("home/temperature", "21.5")
+            .await
+            .unwrap());
+        assert!(!registry
+            .publish_state("home/temperature", "21.5")
+            .await
+            .unwrap());
+        assert!(registry
+            .publish_state("home/temperature", "22")
+            .await
+            .unwrap());
+        registry
+            .force_state("home/temperature", "22")
+            .await
+            .unwrap();
+
+        assert_eq!(client.published().len(), 3);
+        assert_eq!(
+            registry.state_metrics(),
+            Some(StateMetrics {
+                published: 3,
+                skipped: 1
+            })
+        );
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "entity-event")]
+    async fn can_publish_events() {
+        let client = MockClient::default();
+        let registry = HomeAssistantMqtt::new(client.clone(), "homeassistant");
+        let event = Event::new(vec!["press", "hold"], "home/doorbell");
+
+        registry
+            .publish_event(&event, "press", serde_json::json!({ "button": "front" }))
+            .await
+            .unwrap();
+        registry.publish_event(&event, "hold", ()).await.unwrap();
+        assert!(registry.publish_event(&event, "release", ()).await.is_err());
+        assert!(registry.publish_event(&event, "press", 42).await.is_err());
+
+        let published = client.published();
+        assert_eq!(published.len(), 2);
+        assert_eq!(
+            published[0].payload_str(),
+            r#"{"button":"front","event_type":"press"}"#
+        );
+        assert_eq!(published[1].payload_str(), r#"{"event_type":"hold"}"#);
+        assert!(!published[1].retain);
+    }
+
+    #[tokio::test]
+    async fn can_publish_json_attributes() {
+        let client = MockClient::default();
+        let registry = HomeAssistantMqtt::new(client.clone(), "homeassistant");
+        let sensor = Entity::from(
+            Sensor::new("~/state")
+                .topic_prefix("home/meter")
+                .json_attributes_topic("~/attributes"),
+        );
+
+        assert!(registry
+            .publish_attributes(&sensor, &serde_json::json!({ "serial": "A42" }))
+            .await
+            .unwrap());
+        assert!(registry.publish_attributes(&sensor, &[1, 2]).await.is_err());
+        assert!(registry.publish_attributes(&sensor, &"A42").await.is_err());
+        assert!(registry
+            .publish_attributes(&Entity::from(Sensor::new("home/temperature")), &())
+            .await
+            .is_err());
+
+        let published = client.published();
+        assert_eq!(published.len(), 1);
+        assert_eq!(published[0].topic, "home/meter/attributes");
+        assert_eq!(published[0].payload_str(), r#"{"serial":"A42"}"#);
+        assert!(published[0].retain);
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "entity-tag")]
+    async fn can_publish_tag_scans() {
+        let client = MockClient::default();
+        let registry = HomeAssistantMqtt::new(client.clone(), "homeassistant");
+
+        registry
+            .publish_tag_scan(&Tag::new("~/scanned").topic_prefix("home/reader"), "0x1234")
+            .await
+            .unwrap();
+        assert!(registry
+            .publish_tag_scan(&Tag::new(""), "0x1234")
+            .await
+            .is_err());
+        assert!(registry
+            .publish_tag_scan(&Tag::new("home/reader/scanned"), "")
+            .await
+            .is_err());
+
+        let published = client.published();
+        assert_eq!(published.len(), 1);
+        assert_eq!(published[0].topic, "home/reader/scanned");
+        assert_eq!(published[0].payload_str(), "0x1234");
+    }
+
+    #[tokio::test]
+    async fn can_publish_plain_payloads() {
+        let client = MockClient::default();
+        let ha = HomeAssistantMqtt::new(client.clone(), "homeassistant");
+
+        ha.publish_string("home/presence", "home", PublishOptions::default())
+            .await
+            .unwrap();
+        ha.publish_raw("home/raw", vec![0x01, 0x02], PublishOptions::default())
+            .await
+            .unwrap();
+        ha.publish_data("home/json", &"home", None).await.unwrap();
+
+        let published = client.published();
+        assert_eq!(published[0].payload_str(), "home");
+        assert_eq!(
+            published[0].options.content_type.as_deref(),
+            Some("text/plain")
+        );
+        assert!(published[0].retain);
+        assert_eq!(published[1].payload, vec![0x01, 0x02]);
+        assert_eq!(published[1].options.content_type, None);
+        assert_eq!(published[2].payload_str(), "\"home\"");
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "entity-event")]
+    async fn can_choose_the_retain_flag() {
+        let client = MockClient::default();
+        let ha = HomeAssistantMqtt::new(client.clone(), "homeassistant").with_state_deduplication();
+
+        let event = Entity::from(Event::new(vec!["press"], "home/doorbell"));
+        ha.publish_entity_state(&event, r#"{"event_type":"press"}"#)
+            .await
+            .unwrap();
+        ha.publish_entity_state(&event, r#"{"event_type":"press"}"#)
+            .await
+            .unwrap();
+        ha.publish_entity_state(&Sensor::new("home/temperature").into(), "21")
+            .await
+            .unwrap();
+        ha.publish_with_retain("home/button", "PRESS", false, PublishOptions::default())
+            .await
+            .unwrap();
+
+        let published = client.published();
+        assert_eq!(published.len(), 4);
+        assert!(!published[0].retain && !published[1].retain);
+        assert!(published[2].retain);
+        assert!(!published[3].retain);
+    }
+
+    #[tokio::test]
+    async fn can_skip_unchanged_discovery_configurations() {
+        let client = MockClient::default();
+        let ha = HomeAssistantMqtt::new(client.clone(), "homeassistant");
+        let sensor = Sensor::new("home/temperature").unique_id("temperature");
+
+        assert!(ha
+            .publish_entity_if_changed(sensor.clone().into())
+            .await
+            .unwrap());
+        assert!(!ha
+            .clone()
+            .publish_entity_if_changed(sensor.clone().into())
+            .await
+            .unwrap());
+        assert!(ha
+            .publish_entity_if_changed(sensor.clone().name("Temperature").into())
+            .await
+            .unwrap());
+        ha.clear_discovery_cache();
+        assert!(ha
+            .publish_entity_if_changed(sensor.name("Temperature").into())
+            .await
+            .unwrap());
+
+        assert_eq!(client.published().len(), 3);
+    }
+}
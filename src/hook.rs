@@ -0,0 +1,83 @@
+use crate::Entity;
+
+/// Callbacks around the publication of discovery configurations, registered with `HomeAssistantMqtt::with_hook`.
+///
+/// The hooks also apply to the entities published through a `DiscoveryRegistry` or as `DeviceComponents`.
+///
+/// ```
+/// # use ha_mqtt_discovery::{hook::PublishHook, Entity};
+/// struct BuildInfo;
+///
+/// impl PublishHook for BuildInfo {
+///     fn before_publish(&self, entity: &mut Entity) {
+///         entity.origin_mut().sw_version = option_env!("BUILD_HASH").map(str::to_string);
+///     }
+/// }
+///
+/// # #[cfg(feature = "client")]
+/// # fn example(client: ha_mqtt_discovery::v5::AsyncClient) {
+/// # use ha_mqtt_discovery::HomeAssistantMqtt;
+/// let ha = HomeAssistantMqtt::new(client, "homeassistant").with_hook(BuildInfo);
+/// # }
+/// ```
+pub trait PublishHook: Send + Sync {
+    /// Called before the entity is serialized, the changes only apply to the published configuration.
+    fn before_publish(&self, _entity: &mut Entity) {}
+
+    /// Called once the discovery configuration of the entity is published on the topic.
+    fn after_publish(&self, _topic: &str, _entity: &Entity) {}
+}
+
+#[cfg(all(test, feature = "client", feature = "entity-sensor"))]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use crate::{
+        mqtt::{common::Origin, sensor::Sensor},
+        testing::MockClient,
+        HomeAssistantMqtt,
+    };
+
+    use super::*;
+
+    #[derive(Default)]
+    struct Audit {
+        published: Mutex<Vec<String>>,
+    }
+
+    impl PublishHook for Arc<Audit> {
+        fn before_publish(&self, entity: &mut Entity) {
+            entity.set_origin(Origin::new("bridge").with_sw_version("abc123"));
+        }
+
+        fn after_publish(&self, topic: &str, _entity: &Entity) {
+            self.published.lock().unwrap().push(topic.to_string());
+        }
+    }
+
+    #[tokio::test]
+    async fn can_hook_into_publications() {
+        let client = MockClient::default();
+        let audit = Arc::new(Audit::default());
+        let ha = HomeAssistantMqtt::new(client.clone(), "homeassistant").with_hook(audit.clone());
+
+        ha.publish_entity(
+            Sensor::new("home/temperature")
+                .unique_id("temperature")
+                .into(),
+        )
+        .await
+        .unwrap();
+
+        let message = client
+            .last_published("homeassistant/sensor/temperature/config")
+            .unwrap();
+        assert!(message
+            .payload_str()
+            .contains(r#""o":{"name":"bridge","sw":"abc123"}"#));
+        assert_eq!(
+            *audit.published.lock().unwrap(),
+            vec!["homeassistant/sensor/temperature/config"]
+        );
+    }
+}
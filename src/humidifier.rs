@@ -0,0 +1,73 @@
+use serde_derive::{Deserialize, Serialize};
+
+use crate::{mqtt::humidifier::Humidifier, topic::Topic, variant::variant_name};
+
+/// What a humidifier is currently doing, published on the `action_topic`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HumidifierAction {
+    Off,
+    Humidifying,
+    Drying,
+    Idle,
+}
+
+impl HumidifierAction {
+    /// The action published on the `action_topic`, eg. `humidifying`.
+    pub fn as_str(&self) -> &'static str {
+        variant_name(self)
+    }
+}
+
+impl Humidifier {
+    /// Sets the `modes` and the `mode_command_topic` receiving them, which must be configured together.
+    pub fn mode_control<T: Into<Topic>, M: Into<String>>(
+        self,
+        mode_command_topic: T,
+        modes: Vec<M>,
+    ) -> Self {
+        self.mode_command_topic(mode_command_topic).modes(modes)
+    }
+}
+
+#[cfg(all(test, feature = "client"))]
+mod tests {
+    use crate::{testing::MockClient, validation::ValidationError, Entity};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn can_publish_humidifier_actions() {
+        let client = MockClient::default();
+        let ha = client.ha();
+        let humidifier = Humidifier::new("~/set", "~/humidity/set")
+            .topic_prefix("home/humidifier")
+            .action_topic("~/action")
+            .mode_control("~/mode/set", vec!["eco", "boost"]);
+
+        ha.publish_humidifier_action(&humidifier, HumidifierAction::Drying)
+            .await
+            .unwrap();
+        client.assert_published("home/humidifier/action", "drying");
+        assert_eq!(Entity::from(humidifier).validate(), Ok(()));
+
+        assert_eq!(
+            Entity::from(
+                Humidifier::new("home/humidifier/set", "home/humidifier/humidity/set")
+                    .mode_command_topic("home/humidifier/mode/set")
+            )
+            .validate(),
+            Err(vec![ValidationError::MissingAttribute {
+                attribute: "modes",
+                required_by: "mode_command_topic"
+            }])
+        );
+        assert!(ha
+            .publish_humidifier_action(
+                &Humidifier::new("home/humidifier/set", "home/humidifier/humidity/set"),
+                HumidifierAction::Idle
+            )
+            .await
+            .is_err());
+    }
+}
@@ -0,0 +1,106 @@
+#[cfg(feature = "client")]
+use anyhow::{anyhow, Result};
+#[cfg(feature = "client")]
+use base64::{engine::general_purpose::STANDARD, Engine};
+
+/// The maximum size of an MQTT payload, in bytes. Brokers are often configured with a lower limit, like the
+/// `message_size_limit` of Mosquitto, and `rumqttc` with the `max_packet_size` of its options.
+pub const MAX_PAYLOAD_SIZE: usize = 268_435_455;
+
+/// The image of an image entity: its data published on the `image_topic` or its URL published on the `url_topic`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImageData<'a> {
+    Bytes(&'a [u8]),
+    Url(&'a str),
+}
+
+/// The payload of the image data, base64 encoded when the `image_encoding` of the entity is `b64`.
+#[cfg(feature = "client")]
+pub(crate) fn image_payload(bytes: &[u8], image_encoding: Option<&str>) -> Result<Vec<u8>> {
+    let payload = match image_encoding {
+        Some("b64") => STANDARD.encode(bytes).into_bytes(),
+        Some(encoding) if !encoding.is_empty() => {
+            return Err(anyhow!("unsupported image encoding '{encoding}'"));
+        }
+        _ => bytes.to_vec(),
+    };
+    if payload.len() > MAX_PAYLOAD_SIZE {
+        return Err(anyhow!(
+            "image payload of {} bytes exceeds the MQTT limit of {MAX_PAYLOAD_SIZE} bytes",
+            payload.len()
+        ));
+    }
+    Ok(payload)
+}
+
+/// The content type of the common image formats, guessed from their signature.
+pub fn image_content_type(bytes: &[u8]) -> Option<&'static str> {
+    match bytes {
+        [0xFF, 0xD8, 0xFF, ..] => Some("image/jpeg"),
+        [0x89, b'P', b'N', b'G', ..] => Some("image/png"),
+        [b'G', b'I', b'F', b'8', ..] => Some("image/gif"),
+        [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'E', b'B', b'P', ..] => Some("image/webp"),
+        [b'B', b'M', ..] => Some("image/bmp"),
+        _ => None,
+    }
+}
+
+#[cfg(all(
+    test,
+    feature = "client",
+    feature = "entity-camera",
+    feature = "entity-image"
+))]
+mod tests {
+    use crate::{
+        mqtt::{camera::Camera, image::Image},
+        testing::MockClient,
+        HomeAssistantMqtt,
+    };
+
+    use super::*;
+
+    const PNG: &[u8] = &[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+
+    #[tokio::test]
+    async fn can_publish_images() {
+        let client = MockClient::default();
+        let ha = HomeAssistantMqtt::new(client.clone(), "homeassistant");
+        let camera = Camera::new("home/camera").image_encoding("b64");
+        let image = Image::default()
+            .image_topic("home/image")
+            .url_topic("home/image/url");
+
+        ha.publish_camera_image(&camera, PNG).await.unwrap();
+        ha.publish_image(&image, ImageData::Bytes(PNG))
+            .await
+            .unwrap();
+        ha.publish_image(&image, ImageData::Url("http://camera.local/snapshot.png"))
+            .await
+            .unwrap();
+
+        let camera_image = client.last_published("home/camera").unwrap();
+        assert_eq!(camera_image.payload_str(), "iVBORw0KGgo=");
+        assert_eq!(
+            camera_image.options.content_type.as_deref(),
+            Some("image/png")
+        );
+        let image_data = client.last_published("home/image").unwrap();
+        assert_eq!(image_data.payload, PNG);
+        assert_eq!(
+            image_data.options.content_type.as_deref(),
+            Some("image/jpeg")
+        );
+        assert_eq!(
+            client
+                .last_published("home/image/url")
+                .unwrap()
+                .payload_str(),
+            "http://camera.local/snapshot.png"
+        );
+        assert!(ha
+            .publish_image(&Image::default(), ImageData::Bytes(PNG))
+            .await
+            .is_err());
+    }
+}
@@ -0,0 +1,181 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use anyhow::{anyhow, Result};
+use rumqttc::v5::AsyncClient;
+use tokio::{sync::Notify, task::JoinHandle, time::timeout};
+
+use crate::{client::MqttPublisher, Entity, HomeAssistantMqtt};
+
+/// Publishes the states of an entity with `expire_after` and republishes the last one when no state was published
+/// for an interval, so that slow data sources don't make the entity unavailable.
+///
+/// The republishing task is cancelled when the handle is dropped.
+///
+/// ```no_run
+/// # #[cfg(feature = "entity-sensor")]
+/// # async fn example(ha: ha_mqtt_discovery::HomeAssistantMqtt) -> anyhow::Result<()> {
+/// # use std::time::Duration;
+/// # use ha_mqtt_discovery::{mqtt::sensor::Sensor, Entity};
+/// let sensor = Entity::from(Sensor::new("garage/temperature").expire_after(Duration::from_secs(600)));
+/// let temperature = ha.keep_alive(&sensor)?;
+/// temperature.publish("21.5").await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct KeepAlive<C: MqttPublisher = AsyncClient> {
+    home_assistant: HomeAssistantMqtt<C>,
+    topic: String,
+    last_state: Arc<LastState>,
+    task: JoinHandle<()>,
+}
+
+#[derive(Default)]
+struct LastState {
+    payload: Mutex<Option<Vec<u8>>>,
+    published: Notify,
+}
+
+impl<C: MqttPublisher + 'static> HomeAssistantMqtt<C> {
+    /// Spawns a task republishing the last state of the entity every half of its `expire_after` delay, on the current
+    /// tokio runtime.
+    ///
+    /// Fails when the entity has no `expire_after` delay or no state topic.
+    pub fn keep_alive(&self, entity: &Entity) -> Result<KeepAlive<C>> {
+        let expire_after = entity
+            .availability()
+            .expire_after
+            .filter(|seconds| *seconds > 0)
+            .ok_or(anyhow!(
+                "{} entity has no expire_after delay",
+                entity.platform()
+            ))?;
+        self.keep_alive_every(entity, Duration::from_secs(expire_after) / 2)
+    }
+
+    /// Spawns a task republishing the last state of the entity when no state was published for the interval, on the
+    /// current tokio runtime.
+    ///
+    /// Fails when the entity has no state topic.
+    pub fn keep_alive_every(&self, entity: &Entity, interval: Duration) -> Result<KeepAlive<C>> {
+        let topic = entity
+            .resolved_state_topic()
+            .ok_or(anyhow!("{} entity has no state topic", entity.platform()))?
+            .to_string();
+        let last_state = Arc::new(LastState::default());
+        let task = tokio::spawn(republish_last_state(
+            self.clone(),
+            topic.clone(),
+            last_state.clone(),
+            interval,
+        ));
+        Ok(KeepAlive {
+            home_assistant: self.clone(),
+            topic,
+            last_state,
+            task,
+        })
+    }
+}
+
+impl<C: MqttPublisher> KeepAlive<C> {
+    /// Publishes a retained state and restarts the interval, returns `false` when it was skipped by the state
+    /// deduplication.
+    pub async fn publish<P: Into<Vec<u8>>>(&self, payload: P) -> Result<bool> {
+        let payload = payload.into();
+        *self.last_state.payload.lock().unwrap() = Some(payload.clone());
+        let published = self
+            .home_assistant
+            .publish_state(&self.topic, payload)
+            .await?;
+        if published {
+            self.last_state.published.notify_one();
+        }
+        Ok(published)
+    }
+
+    /// Stops republishing the last state.
+    pub fn cancel(self) {}
+}
+
+impl<C: MqttPublisher> Drop for KeepAlive<C> {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+async fn republish_last_state<C: MqttPublisher>(
+    home_assistant: HomeAssistantMqtt<C>,
+    topic: String,
+    last_state: Arc<LastState>,
+    interval: Duration,
+) {
+    loop {
+        if timeout(interval, last_state.published.notified())
+            .await
+            .is_ok()
+        {
+            continue;
+        }
+        let payload = last_state.payload.lock().unwrap().clone();
+        let Some(payload) = payload else {
+            continue;
+        };
+        // the state deduplication would skip the identical state, the failed publications are traced by the client
+        let _ = home_assistant.force_state(&topic, payload).await;
+    }
+}
+
+#[cfg(all(test, feature = "entity-sensor"))]
+mod tests {
+    use crate::{mqtt::sensor::Sensor, testing::MockClient};
+
+    use super::*;
+
+    /// Moves the paused clock forward one second at a time, letting the keep-alive task run in between.
+    async fn elapse(seconds: u64) {
+        for _ in 0..seconds {
+            tokio::time::advance(Duration::from_secs(1)).await;
+            tokio::task::yield_now().await;
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn can_keep_states_alive() {
+        let client = MockClient::default();
+        let ha = client.ha().with_state_deduplication();
+        let sensor =
+            Entity::from(Sensor::new("garage/temperature").expire_after(Duration::from_secs(60)));
+        let published = || {
+            client
+                .published()
+                .iter()
+                .filter(|message| message.topic == "garage/temperature")
+                .count()
+        };
+
+        let temperature = ha
+            .keep_alive_every(&sensor, Duration::from_secs(20))
+            .unwrap();
+        elapse(50).await;
+        assert_eq!(published(), 0);
+
+        assert!(temperature.publish("21.5").await.unwrap());
+        elapse(19).await;
+        assert_eq!(published(), 1);
+        elapse(51).await;
+        assert_eq!(published(), 4);
+        client.assert_published("garage/temperature", "21.5");
+
+        temperature.cancel();
+        elapse(60).await;
+        assert_eq!(published(), 4);
+
+        assert!(ha
+            .keep_alive(&Entity::from(Sensor::new("garage/humidity")))
+            .is_err());
+        assert!(ha.keep_alive(&sensor).is_ok());
+    }
+}
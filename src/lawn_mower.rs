@@ -0,0 +1,81 @@
+use serde_derive::{Deserialize, Serialize};
+
+use crate::{mqtt::lawn_mower::LawnMower, topic::resolve, variant::variant_name};
+
+/// The activities a lawn mower can report on its `activity_state_topic`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LawnMowerActivity {
+    Mowing,
+    Docked,
+    Paused,
+    Returning,
+    Error,
+}
+
+impl LawnMowerActivity {
+    /// The activity published on the `activity_state_topic`, eg. `mowing`.
+    pub fn as_str(&self) -> &'static str {
+        variant_name(self)
+    }
+}
+
+/// A command sent by Home Assistant to a lawn mower.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LawnMowerCommand {
+    StartMowing,
+    Pause,
+    Dock,
+}
+
+impl LawnMower {
+    /// The command sent on the given topic, `None` when it isn't one of the command topics of the lawn mower.
+    pub fn command(&self, topic: &str) -> Option<LawnMowerCommand> {
+        [
+            (
+                &self.start_mowing_command_topic,
+                LawnMowerCommand::StartMowing,
+            ),
+            (&self.pause_command_topic, LawnMowerCommand::Pause),
+            (&self.dock_command_topic, LawnMowerCommand::Dock),
+        ]
+        .into_iter()
+        .find_map(|(command_topic, command)| {
+            let command_topic = resolve(command_topic.as_ref()?, self.topic_prefix.as_deref());
+            (command_topic.as_ref() == topic).then_some(command)
+        })
+    }
+}
+
+#[cfg(all(test, feature = "client"))]
+mod tests {
+    use crate::testing::MockClient;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn can_drive_lawn_mowers() {
+        let client = MockClient::default();
+        let ha = client.ha();
+        let mower = LawnMower::default()
+            .topic_prefix("garden/mower")
+            .activity_state_topic("~/activity")
+            .start_mowing_command_topic("~/start")
+            .dock_command_topic("~/dock");
+
+        ha.publish_lawn_mower_activity(&mower, LawnMowerActivity::Mowing)
+            .await
+            .unwrap();
+
+        client.assert_published("garden/mower/activity", "mowing");
+        assert_eq!(
+            mower.command("garden/mower/start"),
+            Some(LawnMowerCommand::StartMowing)
+        );
+        assert_eq!(
+            mower.command("garden/mower/dock"),
+            Some(LawnMowerCommand::Dock)
+        );
+        assert_eq!(mower.command("garden/mower/pause"), None);
+    }
+}
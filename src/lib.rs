@@ -1,200 +1,1254 @@
 #![recursion_limit = "256"]
+// the wildcard arms of the `Entity` matches become unreachable when a single platform is enabled
+#![cfg_attr(
+    not(feature = "all-entities"),
+    allow(unreachable_patterns, irrefutable_let_patterns)
+)]
 
-use anyhow::{anyhow, Result};
-use mqtt::{
-    alarm_control_panel::AlarmControlPanel, binary_sensor::BinarySensor, button::Button,
-    camera::Camera, climate::Climate, cover::Cover, device_tracker::DeviceTracker,
-    device_trigger::DeviceTrigger, event::Event, fan::Fan, humidifier::Humidifier, image::Image,
-    lawn_mower::LawnMower, lock::Lock, number::Number, scene::Scene, select::Select,
-    sensor::Sensor, siren::Siren, switch::Switch, tag::Tag, text::Text, update::Update,
-    vacuum::Vacuum, valve::Valve, water_heater::WaterHeater,
-};
-use rumqttc::v5::{
-    mqttbytes::{v5::PublishProperties, QoS::AtLeastOnce},
-    AsyncClient,
-};
+use anyhow::Result;
+#[cfg(feature = "entity-alarm_control_panel")]
+use mqtt::alarm_control_panel::AlarmControlPanel;
+#[cfg(feature = "entity-binary_sensor")]
+use mqtt::binary_sensor::BinarySensor;
+#[cfg(feature = "entity-button")]
+use mqtt::button::Button;
+#[cfg(feature = "entity-camera")]
+use mqtt::camera::Camera;
+#[cfg(feature = "entity-climate")]
+use mqtt::climate::Climate;
+use mqtt::common::{Availability, Device, EntityCategory, Origin, Qos};
+#[cfg(feature = "entity-cover")]
+use mqtt::cover::Cover;
+#[cfg(feature = "entity-device_tracker")]
+use mqtt::device_tracker::DeviceTracker;
+#[cfg(feature = "entity-device_trigger")]
+use mqtt::device_trigger::DeviceTrigger;
+#[cfg(feature = "entity-event")]
+use mqtt::event::Event;
+#[cfg(feature = "entity-fan")]
+use mqtt::fan::Fan;
+#[cfg(feature = "entity-humidifier")]
+use mqtt::humidifier::Humidifier;
+#[cfg(feature = "entity-image")]
+use mqtt::image::Image;
+#[cfg(feature = "entity-lawn_mower")]
+use mqtt::lawn_mower::LawnMower;
+#[cfg(feature = "entity-light")]
+use mqtt::light::Light;
+#[cfg(feature = "entity-lock")]
+use mqtt::lock::Lock;
+#[cfg(feature = "entity-notify")]
+use mqtt::notify::Notify;
+#[cfg(feature = "entity-number")]
+use mqtt::number::Number;
+#[cfg(feature = "entity-scene")]
+use mqtt::scene::Scene;
+#[cfg(feature = "entity-select")]
+use mqtt::select::Select;
+#[cfg(feature = "entity-sensor")]
+use mqtt::sensor::Sensor;
+#[cfg(feature = "entity-siren")]
+use mqtt::siren::Siren;
+#[cfg(feature = "entity-switch")]
+use mqtt::switch::Switch;
+#[cfg(feature = "entity-tag")]
+use mqtt::tag::Tag;
+#[cfg(feature = "entity-text")]
+use mqtt::text::Text;
+#[cfg(feature = "entity-update")]
+use mqtt::update::Update;
+#[cfg(feature = "entity-vacuum")]
+use mqtt::vacuum::Vacuum;
+#[cfg(feature = "entity-valve")]
+use mqtt::valve::Valve;
+#[cfg(feature = "entity-water_heater")]
+use mqtt::water_heater::WaterHeater;
 use serde::Serialize;
+use topic::{validate_payload_topics, InvalidTopic, Topic};
 
+pub use device::HaDevice;
+#[cfg(feature = "derive")]
+pub use ha_mqtt_discovery_derive::HaDevice;
+#[cfg(feature = "client")]
+pub use home_assistant::HomeAssistantMqtt;
+#[cfg(feature = "client")]
 pub use rumqttc::v5;
 use serde_json::Value;
 
+#[cfg(feature = "entity-alarm_control_panel")]
+pub mod alarm_control_panel;
+#[cfg(feature = "client")]
+pub mod aligner;
+#[cfg(feature = "client")]
+pub mod availability;
+#[cfg(feature = "entity-binary_sensor")]
+pub mod binary_sensor;
+#[cfg(feature = "client")]
+pub mod blocking;
+#[cfg(feature = "client")]
+pub mod bridge;
+pub mod bus;
+#[cfg(feature = "client")]
+pub mod client;
+#[cfg(feature = "entity-climate")]
+pub mod climate;
+#[cfg(feature = "client")]
+pub mod command;
+#[cfg(feature = "client")]
+pub mod components;
+#[cfg(feature = "yaml")]
+pub mod config;
+#[cfg(feature = "client")]
+pub mod connect;
+pub mod conversion;
+#[cfg(feature = "entity-cover")]
+pub mod cover;
+pub mod decimal;
+pub mod device;
+#[cfg(feature = "client")]
+pub mod discovery;
+pub mod error;
+#[cfg(all(
+    feature = "client",
+    feature = "entity-binary_sensor",
+    feature = "entity-number",
+    feature = "entity-select",
+    feature = "entity-sensor",
+    feature = "entity-switch",
+    feature = "entity-text"
+))]
+pub mod exposes;
+#[cfg(feature = "entity-fan")]
+pub mod fan;
+#[cfg(feature = "client")]
+mod home_assistant;
+pub mod hook;
+#[cfg(feature = "entity-humidifier")]
+pub mod humidifier;
+#[cfg(any(feature = "entity-camera", feature = "entity-image"))]
+pub mod image;
+#[cfg(feature = "client")]
+pub mod keep_alive;
+#[cfg(feature = "entity-lawn_mower")]
+pub mod lawn_mower;
+pub mod locale;
+#[cfg(feature = "entity-lock")]
+pub mod lock;
+#[cfg(any(feature = "entity-number", feature = "entity-sensor"))]
+pub mod measurement;
 pub mod mqtt;
+#[cfg(all(feature = "entity-notify", feature = "client"))]
+pub mod notify;
+#[cfg(feature = "entity-number")]
+pub mod number;
+pub mod origin;
+pub mod parse;
+#[cfg(all(
+    feature = "client",
+    any(
+        all(feature = "entity-binary_sensor", feature = "entity-sensor"),
+        all(feature = "entity-sensor", feature = "entity-switch"),
+        feature = "entity-climate"
+    )
+))]
+pub mod presets;
+#[cfg(feature = "client")]
+pub mod registry;
+#[cfg(feature = "client")]
+pub mod scan;
+#[cfg(all(feature = "entity-scene", feature = "client"))]
+pub mod scene;
+#[cfg(feature = "schema")]
+mod schema;
+#[cfg(feature = "entity-select")]
+pub mod select;
+#[cfg(feature = "entity-sensor")]
+pub mod sensor;
+#[cfg(feature = "entity-siren")]
+pub mod siren;
+pub mod slug;
+#[cfg(feature = "client")]
+pub mod spec;
+#[cfg(feature = "client")]
+pub mod state;
+#[cfg(feature = "client")]
+pub mod status;
+#[cfg(feature = "entity-switch")]
+pub mod switch;
+#[cfg(any(feature = "entity-climate", feature = "entity-water_heater"))]
+pub mod temperature;
+#[cfg(any(feature = "testing", all(test, feature = "client")))]
+pub mod testing;
+#[cfg(feature = "entity-text")]
+pub mod text;
+pub mod topic;
+#[cfg(feature = "entity-device_trigger")]
+pub mod trigger;
+#[cfg(feature = "entity-update")]
+pub mod update;
+#[cfg(feature = "entity-vacuum")]
+pub mod vacuum;
+pub mod validation;
+#[cfg(feature = "entity-valve")]
+pub mod valve;
+#[cfg(any(
+    feature = "entity-alarm_control_panel",
+    feature = "entity-climate",
+    feature = "entity-fan",
+    feature = "entity-humidifier",
+    feature = "entity-lawn_mower",
+    feature = "entity-vacuum"
+))]
+mod variant;
+#[cfg(feature = "yaml")]
+mod yaml;
 
-const ONE_WEEK_SECONDS: u32 = 60 * 60 * 24 * 7;
+#[cfg(not(any(
+    feature = "entity-alarm_control_panel",
+    feature = "entity-binary_sensor",
+    feature = "entity-button",
+    feature = "entity-camera",
+    feature = "entity-climate",
+    feature = "entity-cover",
+    feature = "entity-device_tracker",
+    feature = "entity-device_trigger",
+    feature = "entity-event",
+    feature = "entity-fan",
+    feature = "entity-humidifier",
+    feature = "entity-image",
+    feature = "entity-lawn_mower",
+    feature = "entity-light",
+    feature = "entity-lock",
+    feature = "entity-notify",
+    feature = "entity-number",
+    feature = "entity-scene",
+    feature = "entity-select",
+    feature = "entity-sensor",
+    feature = "entity-siren",
+    feature = "entity-switch",
+    feature = "entity-tag",
+    feature = "entity-text",
+    feature = "entity-update",
+    feature = "entity-vacuum",
+    feature = "entity-valve",
+    feature = "entity-water_heater"
+)))]
+compile_error!("at least one `entity-<platform>` feature must be enabled");
 
-#[derive(Clone)]
-pub struct HomeAssistantMqtt {
-    client: AsyncClient,
-    discovery_prefix: String,
-}
-
-impl HomeAssistantMqtt {
-    pub fn new<S: Into<String>>(client: AsyncClient, discovery_prefix: S) -> Self {
-        Self {
-            client,
-            discovery_prefix: discovery_prefix.into(),
+/// Evaluates `$body` with `$inner` bound to the entity configuration wrapped by any `Entity` variant.
+macro_rules! with_entity {
+    ($entity:expr, $inner:ident => $body:expr) => {
+        match $entity {
+            #[cfg(feature = "entity-alarm_control_panel")]
+            Entity::AlarmControlPanel($inner) => $body,
+            #[cfg(feature = "entity-binary_sensor")]
+            Entity::BinarySensor($inner) => $body,
+            #[cfg(feature = "entity-button")]
+            Entity::Button($inner) => $body,
+            #[cfg(feature = "entity-camera")]
+            Entity::Camera($inner) => $body,
+            #[cfg(feature = "entity-climate")]
+            Entity::Climate($inner) => $body,
+            #[cfg(feature = "entity-cover")]
+            Entity::Cover($inner) => $body,
+            #[cfg(feature = "entity-device_tracker")]
+            Entity::DeviceTracker($inner) => $body,
+            #[cfg(feature = "entity-device_trigger")]
+            Entity::DeviceTrigger($inner) => $body,
+            #[cfg(feature = "entity-event")]
+            Entity::Event($inner) => $body,
+            #[cfg(feature = "entity-fan")]
+            Entity::Fan($inner) => $body,
+            #[cfg(feature = "entity-humidifier")]
+            Entity::Humidifier($inner) => $body,
+            #[cfg(feature = "entity-image")]
+            Entity::Image($inner) => $body,
+            #[cfg(feature = "entity-lawn_mower")]
+            Entity::LawnMower($inner) => $body,
+            #[cfg(feature = "entity-light")]
+            Entity::Light($inner) => $body,
+            #[cfg(feature = "entity-lock")]
+            Entity::Lock($inner) => $body,
+            #[cfg(feature = "entity-notify")]
+            Entity::Notify($inner) => $body,
+            #[cfg(feature = "entity-number")]
+            Entity::Number($inner) => $body,
+            #[cfg(feature = "entity-scene")]
+            Entity::Scene($inner) => $body,
+            #[cfg(feature = "entity-select")]
+            Entity::Select($inner) => $body,
+            #[cfg(feature = "entity-sensor")]
+            Entity::Sensor($inner) => $body,
+            #[cfg(feature = "entity-siren")]
+            Entity::Siren($inner) => $body,
+            #[cfg(feature = "entity-switch")]
+            Entity::Switch($inner) => $body,
+            #[cfg(feature = "entity-tag")]
+            Entity::Tag($inner) => $body,
+            #[cfg(feature = "entity-text")]
+            Entity::Text($inner) => $body,
+            #[cfg(feature = "entity-update")]
+            Entity::Update($inner) => $body,
+            #[cfg(feature = "entity-vacuum")]
+            Entity::Vacuum($inner) => $body,
+            #[cfg(feature = "entity-valve")]
+            Entity::Valve($inner) => $body,
+            #[cfg(feature = "entity-water_heater")]
+            Entity::WaterHeater($inner) => $body,
         }
-    }
-
-    /// The discovery topic needs to follow a specific format:
-    /// `<discovery_prefix>/<component>/[<node_id>/]<object_id>/config`
-    ///
-    /// - `<discovery_prefix>`: The Discovery Prefix defaults to homeassistant. This prefix can be changed.
-    /// - `<component>`: One of the supported MQTT integrations, eg. binary_sensor.
-    /// - `<node_id>` (Optional): ID of the node providing the topic, this is not used by Home Assistant but may be used to structure the MQTT topic. The ID of the node must only consist of characters from the character class [a-zA-Z0-9_-] (alphanumerics, underscore and hyphen).
-    /// - `<object_id>`: The ID of the device. This is only to allow for separate topics for each device and is not used for the entity_id. The ID of the device must only consist of characters from the character class [a-zA-Z0-9_-] (alphanumerics, underscore and hyphen).
-    ///
-    /// The `<node_id>` level can be used by clients to only subscribe to their own (command) topics by using one wildcard topic like <discovery_prefix>/+/<node_id>/+/set.
-    ///
-    /// Best practice for entities with a unique_id is to set `<object_id>` to unique_id and omit the `<node_id>`.
-    pub async fn publish_entity(&self, entity: Entity) -> Result<()> {
-        let component = entity.get_component_name();
-        let attributes = entity.get_attributes()?;
-        let object_id = attributes
-            .as_object()
-            .ok_or(anyhow!("entity configuration should be an object"))?
-            .get("uniq_id")
-            .ok_or(anyhow!(
-                "entity configuration should have an attribute 'uniq_id'"
-            ))?
-            .as_str()
-            .ok_or(anyhow!("'uniq_id' attribute should be a string"))?;
-        let prefix = self
-            .discovery_prefix
-            .strip_suffix("/")
-            .unwrap_or(&self.discovery_prefix);
-        let topic = format!("{prefix}/{component}/{object_id}/config");
-        let payload = serde_json::ser::to_string(&attributes).unwrap();
-        let props = PublishProperties {
-            //payload_format_indicator: Some(1),
-            message_expiry_interval: Some(ONE_WEEK_SECONDS),
-            content_type: Some("application/json".to_string()),
-            ..Default::default()
-        };
-        Ok(self
-            .client
-            .publish_with_properties(topic, AtLeastOnce, true, payload, props)
-            .await?)
-    }
-
-    pub async fn publish_data<S: Serialize>(
-        &self,
-        topic: &String,
-        payload: &S,
-        message_expiry_interval: Option<u32>,
-    ) -> Result<()> {
-        let payload = serde_json::ser::to_string(payload).unwrap();
-        let props = PublishProperties {
-            message_expiry_interval,
-            content_type: Some("application/json".to_string()),
-            ..Default::default()
-        };
-        Ok(self
-            .client
-            .publish_with_properties(topic, AtLeastOnce, true, payload, props)
-            .await?)
-    }
+    };
 }
 
-#[derive(Clone)]
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum Entity {
+    #[cfg(feature = "entity-alarm_control_panel")]
     AlarmControlPanel(AlarmControlPanel),
+    #[cfg(feature = "entity-binary_sensor")]
     BinarySensor(BinarySensor),
+    #[cfg(feature = "entity-button")]
     Button(Button),
+    #[cfg(feature = "entity-camera")]
     Camera(Camera),
+    #[cfg(feature = "entity-climate")]
     Climate(Climate),
+    #[cfg(feature = "entity-cover")]
     Cover(Cover),
+    #[cfg(feature = "entity-device_tracker")]
     DeviceTracker(DeviceTracker),
+    #[cfg(feature = "entity-device_trigger")]
     DeviceTrigger(DeviceTrigger),
+    #[cfg(feature = "entity-event")]
     Event(Event),
+    #[cfg(feature = "entity-fan")]
     Fan(Fan),
+    #[cfg(feature = "entity-humidifier")]
     Humidifier(Humidifier),
+    #[cfg(feature = "entity-image")]
     Image(Image),
+    #[cfg(feature = "entity-lawn_mower")]
     LawnMower(LawnMower),
-    //Light,
+    #[cfg(feature = "entity-light")]
+    Light(Light),
+    #[cfg(feature = "entity-lock")]
     Lock(Lock),
-    //Notify,
+    #[cfg(feature = "entity-notify")]
+    Notify(Notify),
+    #[cfg(feature = "entity-number")]
     Number(Number),
+    #[cfg(feature = "entity-scene")]
     Scene(Scene),
+    #[cfg(feature = "entity-select")]
     Select(Select),
+    #[cfg(feature = "entity-sensor")]
     Sensor(Sensor),
+    #[cfg(feature = "entity-siren")]
     Siren(Siren),
+    #[cfg(feature = "entity-switch")]
     Switch(Switch),
+    #[cfg(feature = "entity-tag")]
     Tag(Tag),
+    #[cfg(feature = "entity-text")]
     Text(Text),
+    #[cfg(feature = "entity-update")]
     Update(Update),
+    #[cfg(feature = "entity-vacuum")]
     Vacuum(Vacuum),
+    #[cfg(feature = "entity-valve")]
     Valve(Valve),
+    #[cfg(feature = "entity-water_heater")]
     WaterHeater(WaterHeater),
 }
 
 impl Entity {
-    fn get_component_name(&self) -> &str {
+    /// The MQTT integration (component) of this entity, eg. `binary_sensor`.
+    pub fn platform(&self) -> &str {
         match self {
+            #[cfg(feature = "entity-alarm_control_panel")]
             Entity::AlarmControlPanel(_) => "alarm_control_panel",
+            #[cfg(feature = "entity-binary_sensor")]
             Entity::BinarySensor(_) => "binary_sensor",
+            #[cfg(feature = "entity-button")]
             Entity::Button(_) => "button",
+            #[cfg(feature = "entity-camera")]
             Entity::Camera(_) => "camera",
+            #[cfg(feature = "entity-climate")]
             Entity::Climate(_) => "climate",
+            #[cfg(feature = "entity-cover")]
             Entity::Cover(_) => "cover",
+            #[cfg(feature = "entity-device_tracker")]
             Entity::DeviceTracker(_) => "device_tracker",
-            Entity::DeviceTrigger(_) => "device_trigger",
+            #[cfg(feature = "entity-device_trigger")]
+            Entity::DeviceTrigger(_) => "device_automation",
+            #[cfg(feature = "entity-event")]
             Entity::Event(_) => "event",
+            #[cfg(feature = "entity-fan")]
             Entity::Fan(_) => "fan",
+            #[cfg(feature = "entity-humidifier")]
             Entity::Humidifier(_) => "humidifier",
+            #[cfg(feature = "entity-image")]
             Entity::Image(_) => "image",
+            #[cfg(feature = "entity-lawn_mower")]
             Entity::LawnMower(_) => "lawn_mower",
-            //Entity::Light(_) => "light",
+            #[cfg(feature = "entity-light")]
+            Entity::Light(_) => "light",
+            #[cfg(feature = "entity-lock")]
             Entity::Lock(_) => "lock",
-            //Entity::Notify(_) => "notify",
+            #[cfg(feature = "entity-notify")]
+            Entity::Notify(_) => "notify",
+            #[cfg(feature = "entity-number")]
             Entity::Number(_) => "number",
+            #[cfg(feature = "entity-scene")]
             Entity::Scene(_) => "scene",
+            #[cfg(feature = "entity-select")]
             Entity::Select(_) => "select",
+            #[cfg(feature = "entity-sensor")]
             Entity::Sensor(_) => "sensor",
+            #[cfg(feature = "entity-siren")]
             Entity::Siren(_) => "siren",
+            #[cfg(feature = "entity-switch")]
             Entity::Switch(_) => "switch",
+            #[cfg(feature = "entity-tag")]
             Entity::Tag(_) => "tag",
+            #[cfg(feature = "entity-text")]
             Entity::Text(_) => "text",
+            #[cfg(feature = "entity-update")]
             Entity::Update(_) => "update",
+            #[cfg(feature = "entity-vacuum")]
             Entity::Vacuum(_) => "vacuum",
+            #[cfg(feature = "entity-valve")]
             Entity::Valve(_) => "valve",
+            #[cfg(feature = "entity-water_heater")]
             Entity::WaterHeater(_) => "water_heater",
         }
     }
 
-    fn get_attributes(&self) -> Result<Value> {
-        let attributes = match self {
-            Entity::AlarmControlPanel(alarm_control_panel) => {
-                serde_json::to_value(alarm_control_panel)?
+    /// An ID that uniquely identifies this entity, if any.
+    pub fn unique_id(&self) -> Option<&str> {
+        match self {
+            #[cfg(feature = "entity-device_trigger")]
+            Entity::DeviceTrigger(_) => None,
+            #[cfg(feature = "entity-tag")]
+            Entity::Tag(_) => None,
+            #[cfg(feature = "entity-alarm_control_panel")]
+            Entity::AlarmControlPanel(e) => e.unique_id.as_deref(),
+            #[cfg(feature = "entity-binary_sensor")]
+            Entity::BinarySensor(e) => e.unique_id.as_deref(),
+            #[cfg(feature = "entity-button")]
+            Entity::Button(e) => e.unique_id.as_deref(),
+            #[cfg(feature = "entity-camera")]
+            Entity::Camera(e) => e.unique_id.as_deref(),
+            #[cfg(feature = "entity-climate")]
+            Entity::Climate(e) => e.unique_id.as_deref(),
+            #[cfg(feature = "entity-cover")]
+            Entity::Cover(e) => e.unique_id.as_deref(),
+            #[cfg(feature = "entity-device_tracker")]
+            Entity::DeviceTracker(e) => e.unique_id.as_deref(),
+            #[cfg(feature = "entity-event")]
+            Entity::Event(e) => e.unique_id.as_deref(),
+            #[cfg(feature = "entity-fan")]
+            Entity::Fan(e) => e.unique_id.as_deref(),
+            #[cfg(feature = "entity-humidifier")]
+            Entity::Humidifier(e) => e.unique_id.as_deref(),
+            #[cfg(feature = "entity-image")]
+            Entity::Image(e) => e.unique_id.as_deref(),
+            #[cfg(feature = "entity-lawn_mower")]
+            Entity::LawnMower(e) => e.unique_id.as_deref(),
+            #[cfg(feature = "entity-light")]
+            Entity::Light(e) => e.unique_id.as_deref(),
+            #[cfg(feature = "entity-lock")]
+            Entity::Lock(e) => e.unique_id.as_deref(),
+            #[cfg(feature = "entity-notify")]
+            Entity::Notify(e) => e.unique_id.as_deref(),
+            #[cfg(feature = "entity-number")]
+            Entity::Number(e) => e.unique_id.as_deref(),
+            #[cfg(feature = "entity-scene")]
+            Entity::Scene(e) => e.unique_id.as_deref(),
+            #[cfg(feature = "entity-select")]
+            Entity::Select(e) => e.unique_id.as_deref(),
+            #[cfg(feature = "entity-sensor")]
+            Entity::Sensor(e) => e.unique_id.as_deref(),
+            #[cfg(feature = "entity-siren")]
+            Entity::Siren(e) => e.unique_id.as_deref(),
+            #[cfg(feature = "entity-switch")]
+            Entity::Switch(e) => e.unique_id.as_deref(),
+            #[cfg(feature = "entity-text")]
+            Entity::Text(e) => e.unique_id.as_deref(),
+            #[cfg(feature = "entity-update")]
+            Entity::Update(e) => e.unique_id.as_deref(),
+            #[cfg(feature = "entity-vacuum")]
+            Entity::Vacuum(e) => e.unique_id.as_deref(),
+            #[cfg(feature = "entity-valve")]
+            Entity::Valve(e) => e.unique_id.as_deref(),
+            #[cfg(feature = "entity-water_heater")]
+            Entity::WaterHeater(e) => e.unique_id.as_deref(),
+        }
+    }
+
+    /// The name to use when displaying this entity, if any.
+    pub fn name(&self) -> Option<&str> {
+        match self {
+            #[cfg(feature = "entity-device_trigger")]
+            Entity::DeviceTrigger(_) => None,
+            #[cfg(feature = "entity-tag")]
+            Entity::Tag(_) => None,
+            #[cfg(feature = "entity-alarm_control_panel")]
+            Entity::AlarmControlPanel(e) => e.name.as_deref(),
+            #[cfg(feature = "entity-binary_sensor")]
+            Entity::BinarySensor(e) => e.name.as_deref(),
+            #[cfg(feature = "entity-button")]
+            Entity::Button(e) => e.name.as_deref(),
+            #[cfg(feature = "entity-camera")]
+            Entity::Camera(e) => e.name.as_deref(),
+            #[cfg(feature = "entity-climate")]
+            Entity::Climate(e) => e.name.as_deref(),
+            #[cfg(feature = "entity-cover")]
+            Entity::Cover(e) => e.name.as_deref(),
+            #[cfg(feature = "entity-device_tracker")]
+            Entity::DeviceTracker(e) => e.name.as_deref(),
+            #[cfg(feature = "entity-event")]
+            Entity::Event(e) => e.name.as_deref(),
+            #[cfg(feature = "entity-fan")]
+            Entity::Fan(e) => e.name.as_deref(),
+            #[cfg(feature = "entity-humidifier")]
+            Entity::Humidifier(e) => e.name.as_deref(),
+            #[cfg(feature = "entity-image")]
+            Entity::Image(e) => e.name.as_deref(),
+            #[cfg(feature = "entity-lawn_mower")]
+            Entity::LawnMower(e) => e.name.as_deref(),
+            #[cfg(feature = "entity-light")]
+            Entity::Light(e) => e.name.as_deref(),
+            #[cfg(feature = "entity-lock")]
+            Entity::Lock(e) => e.name.as_deref(),
+            #[cfg(feature = "entity-notify")]
+            Entity::Notify(e) => e.name.as_deref(),
+            #[cfg(feature = "entity-number")]
+            Entity::Number(e) => e.name.as_deref(),
+            #[cfg(feature = "entity-scene")]
+            Entity::Scene(e) => e.name.as_deref(),
+            #[cfg(feature = "entity-select")]
+            Entity::Select(e) => e.name.as_deref(),
+            #[cfg(feature = "entity-sensor")]
+            Entity::Sensor(e) => e.name.as_deref(),
+            #[cfg(feature = "entity-siren")]
+            Entity::Siren(e) => e.name.as_deref(),
+            #[cfg(feature = "entity-switch")]
+            Entity::Switch(e) => e.name.as_deref(),
+            #[cfg(feature = "entity-text")]
+            Entity::Text(e) => e.name.as_deref(),
+            #[cfg(feature = "entity-update")]
+            Entity::Update(e) => e.name.as_deref(),
+            #[cfg(feature = "entity-vacuum")]
+            Entity::Vacuum(e) => e.name.as_deref(),
+            #[cfg(feature = "entity-valve")]
+            Entity::Valve(e) => e.name.as_deref(),
+            #[cfg(feature = "entity-water_heater")]
+            Entity::WaterHeater(e) => e.name.as_deref(),
+        }
+    }
+
+    /// The ID used instead of `name` for automatic generation of `entity_id`, if any.
+    pub fn object_id(&self) -> Option<&str> {
+        match self {
+            #[cfg(feature = "entity-device_trigger")]
+            Entity::DeviceTrigger(_) => None,
+            #[cfg(feature = "entity-tag")]
+            Entity::Tag(_) => None,
+            #[cfg(feature = "entity-alarm_control_panel")]
+            Entity::AlarmControlPanel(e) => e.object_id.as_deref(),
+            #[cfg(feature = "entity-binary_sensor")]
+            Entity::BinarySensor(e) => e.object_id.as_deref(),
+            #[cfg(feature = "entity-button")]
+            Entity::Button(e) => e.object_id.as_deref(),
+            #[cfg(feature = "entity-camera")]
+            Entity::Camera(e) => e.object_id.as_deref(),
+            #[cfg(feature = "entity-climate")]
+            Entity::Climate(e) => e.object_id.as_deref(),
+            #[cfg(feature = "entity-cover")]
+            Entity::Cover(e) => e.object_id.as_deref(),
+            #[cfg(feature = "entity-device_tracker")]
+            Entity::DeviceTracker(e) => e.object_id.as_deref(),
+            #[cfg(feature = "entity-event")]
+            Entity::Event(e) => e.object_id.as_deref(),
+            #[cfg(feature = "entity-fan")]
+            Entity::Fan(e) => e.object_id.as_deref(),
+            #[cfg(feature = "entity-humidifier")]
+            Entity::Humidifier(e) => e.object_id.as_deref(),
+            #[cfg(feature = "entity-image")]
+            Entity::Image(e) => e.object_id.as_deref(),
+            #[cfg(feature = "entity-lawn_mower")]
+            Entity::LawnMower(e) => e.object_id.as_deref(),
+            #[cfg(feature = "entity-light")]
+            Entity::Light(e) => e.object_id.as_deref(),
+            #[cfg(feature = "entity-lock")]
+            Entity::Lock(e) => e.object_id.as_deref(),
+            #[cfg(feature = "entity-notify")]
+            Entity::Notify(e) => e.object_id.as_deref(),
+            #[cfg(feature = "entity-number")]
+            Entity::Number(e) => e.object_id.as_deref(),
+            #[cfg(feature = "entity-scene")]
+            Entity::Scene(e) => e.object_id.as_deref(),
+            #[cfg(feature = "entity-select")]
+            Entity::Select(e) => e.object_id.as_deref(),
+            #[cfg(feature = "entity-sensor")]
+            Entity::Sensor(e) => e.object_id.as_deref(),
+            #[cfg(feature = "entity-siren")]
+            Entity::Siren(e) => e.object_id.as_deref(),
+            #[cfg(feature = "entity-switch")]
+            Entity::Switch(e) => e.object_id.as_deref(),
+            #[cfg(feature = "entity-text")]
+            Entity::Text(e) => e.object_id.as_deref(),
+            #[cfg(feature = "entity-update")]
+            Entity::Update(e) => e.object_id.as_deref(),
+            #[cfg(feature = "entity-vacuum")]
+            Entity::Vacuum(e) => e.object_id.as_deref(),
+            #[cfg(feature = "entity-valve")]
+            Entity::Valve(e) => e.object_id.as_deref(),
+            #[cfg(feature = "entity-water_heater")]
+            Entity::WaterHeater(e) => e.object_id.as_deref(),
+        }
+    }
+
+    /// The ID that uniquely identifies this entity, `None` for the platforms without this attribute.
+    pub fn unique_id_mut(&mut self) -> Option<&mut Option<String>> {
+        match self {
+            #[cfg(feature = "entity-device_trigger")]
+            Entity::DeviceTrigger(_) => None,
+            #[cfg(feature = "entity-tag")]
+            Entity::Tag(_) => None,
+            #[cfg(feature = "entity-alarm_control_panel")]
+            Entity::AlarmControlPanel(e) => Some(&mut e.unique_id),
+            #[cfg(feature = "entity-binary_sensor")]
+            Entity::BinarySensor(e) => Some(&mut e.unique_id),
+            #[cfg(feature = "entity-button")]
+            Entity::Button(e) => Some(&mut e.unique_id),
+            #[cfg(feature = "entity-camera")]
+            Entity::Camera(e) => Some(&mut e.unique_id),
+            #[cfg(feature = "entity-climate")]
+            Entity::Climate(e) => Some(&mut e.unique_id),
+            #[cfg(feature = "entity-cover")]
+            Entity::Cover(e) => Some(&mut e.unique_id),
+            #[cfg(feature = "entity-device_tracker")]
+            Entity::DeviceTracker(e) => Some(&mut e.unique_id),
+            #[cfg(feature = "entity-event")]
+            Entity::Event(e) => Some(&mut e.unique_id),
+            #[cfg(feature = "entity-fan")]
+            Entity::Fan(e) => Some(&mut e.unique_id),
+            #[cfg(feature = "entity-humidifier")]
+            Entity::Humidifier(e) => Some(&mut e.unique_id),
+            #[cfg(feature = "entity-image")]
+            Entity::Image(e) => Some(&mut e.unique_id),
+            #[cfg(feature = "entity-lawn_mower")]
+            Entity::LawnMower(e) => Some(&mut e.unique_id),
+            #[cfg(feature = "entity-light")]
+            Entity::Light(e) => Some(&mut e.unique_id),
+            #[cfg(feature = "entity-lock")]
+            Entity::Lock(e) => Some(&mut e.unique_id),
+            #[cfg(feature = "entity-notify")]
+            Entity::Notify(e) => Some(&mut e.unique_id),
+            #[cfg(feature = "entity-number")]
+            Entity::Number(e) => Some(&mut e.unique_id),
+            #[cfg(feature = "entity-scene")]
+            Entity::Scene(e) => Some(&mut e.unique_id),
+            #[cfg(feature = "entity-select")]
+            Entity::Select(e) => Some(&mut e.unique_id),
+            #[cfg(feature = "entity-sensor")]
+            Entity::Sensor(e) => Some(&mut e.unique_id),
+            #[cfg(feature = "entity-siren")]
+            Entity::Siren(e) => Some(&mut e.unique_id),
+            #[cfg(feature = "entity-switch")]
+            Entity::Switch(e) => Some(&mut e.unique_id),
+            #[cfg(feature = "entity-text")]
+            Entity::Text(e) => Some(&mut e.unique_id),
+            #[cfg(feature = "entity-update")]
+            Entity::Update(e) => Some(&mut e.unique_id),
+            #[cfg(feature = "entity-vacuum")]
+            Entity::Vacuum(e) => Some(&mut e.unique_id),
+            #[cfg(feature = "entity-valve")]
+            Entity::Valve(e) => Some(&mut e.unique_id),
+            #[cfg(feature = "entity-water_heater")]
+            Entity::WaterHeater(e) => Some(&mut e.unique_id),
+        }
+    }
+
+    /// The ID used instead of `name` for automatic generation of `entity_id`, `None` for the platforms without this
+    /// attribute.
+    pub fn object_id_mut(&mut self) -> Option<&mut Option<String>> {
+        match self {
+            #[cfg(feature = "entity-device_trigger")]
+            Entity::DeviceTrigger(_) => None,
+            #[cfg(feature = "entity-tag")]
+            Entity::Tag(_) => None,
+            #[cfg(feature = "entity-alarm_control_panel")]
+            Entity::AlarmControlPanel(e) => Some(&mut e.object_id),
+            #[cfg(feature = "entity-binary_sensor")]
+            Entity::BinarySensor(e) => Some(&mut e.object_id),
+            #[cfg(feature = "entity-button")]
+            Entity::Button(e) => Some(&mut e.object_id),
+            #[cfg(feature = "entity-camera")]
+            Entity::Camera(e) => Some(&mut e.object_id),
+            #[cfg(feature = "entity-climate")]
+            Entity::Climate(e) => Some(&mut e.object_id),
+            #[cfg(feature = "entity-cover")]
+            Entity::Cover(e) => Some(&mut e.object_id),
+            #[cfg(feature = "entity-device_tracker")]
+            Entity::DeviceTracker(e) => Some(&mut e.object_id),
+            #[cfg(feature = "entity-event")]
+            Entity::Event(e) => Some(&mut e.object_id),
+            #[cfg(feature = "entity-fan")]
+            Entity::Fan(e) => Some(&mut e.object_id),
+            #[cfg(feature = "entity-humidifier")]
+            Entity::Humidifier(e) => Some(&mut e.object_id),
+            #[cfg(feature = "entity-image")]
+            Entity::Image(e) => Some(&mut e.object_id),
+            #[cfg(feature = "entity-lawn_mower")]
+            Entity::LawnMower(e) => Some(&mut e.object_id),
+            #[cfg(feature = "entity-light")]
+            Entity::Light(e) => Some(&mut e.object_id),
+            #[cfg(feature = "entity-lock")]
+            Entity::Lock(e) => Some(&mut e.object_id),
+            #[cfg(feature = "entity-notify")]
+            Entity::Notify(e) => Some(&mut e.object_id),
+            #[cfg(feature = "entity-number")]
+            Entity::Number(e) => Some(&mut e.object_id),
+            #[cfg(feature = "entity-scene")]
+            Entity::Scene(e) => Some(&mut e.object_id),
+            #[cfg(feature = "entity-select")]
+            Entity::Select(e) => Some(&mut e.object_id),
+            #[cfg(feature = "entity-sensor")]
+            Entity::Sensor(e) => Some(&mut e.object_id),
+            #[cfg(feature = "entity-siren")]
+            Entity::Siren(e) => Some(&mut e.object_id),
+            #[cfg(feature = "entity-switch")]
+            Entity::Switch(e) => Some(&mut e.object_id),
+            #[cfg(feature = "entity-text")]
+            Entity::Text(e) => Some(&mut e.object_id),
+            #[cfg(feature = "entity-update")]
+            Entity::Update(e) => Some(&mut e.object_id),
+            #[cfg(feature = "entity-vacuum")]
+            Entity::Vacuum(e) => Some(&mut e.object_id),
+            #[cfg(feature = "entity-valve")]
+            Entity::Valve(e) => Some(&mut e.object_id),
+            #[cfg(feature = "entity-water_heater")]
+            Entity::WaterHeater(e) => Some(&mut e.object_id),
+        }
+    }
+
+    /// The MQTT topic Home Assistant reads the entity state from, if any.
+    ///
+    /// This is the `state_topic` attribute, or the `topic` attribute for cameras, tags and device triggers.
+    pub fn state_topic(&self) -> Option<&str> {
+        match self {
+            #[cfg(feature = "entity-alarm_control_panel")]
+            Entity::AlarmControlPanel(e) => Some(&e.state_topic),
+            #[cfg(feature = "entity-binary_sensor")]
+            Entity::BinarySensor(e) => Some(&e.state_topic),
+            #[cfg(feature = "entity-event")]
+            Entity::Event(e) => Some(&e.state_topic),
+            #[cfg(feature = "entity-sensor")]
+            Entity::Sensor(e) => Some(&e.state_topic),
+            #[cfg(feature = "entity-camera")]
+            Entity::Camera(e) => Some(&e.topic),
+            #[cfg(feature = "entity-device_trigger")]
+            Entity::DeviceTrigger(e) => Some(&e.topic),
+            #[cfg(feature = "entity-tag")]
+            Entity::Tag(e) => Some(&e.topic),
+            #[cfg(feature = "entity-cover")]
+            Entity::Cover(e) => e.state_topic.as_deref(),
+            #[cfg(feature = "entity-device_tracker")]
+            Entity::DeviceTracker(e) => e.state_topic.as_deref(),
+            #[cfg(feature = "entity-fan")]
+            Entity::Fan(e) => e.state_topic.as_deref(),
+            #[cfg(feature = "entity-humidifier")]
+            Entity::Humidifier(e) => e.state_topic.as_deref(),
+            #[cfg(feature = "entity-light")]
+            Entity::Light(e) => e.state_topic.as_deref(),
+            #[cfg(feature = "entity-lock")]
+            Entity::Lock(e) => e.state_topic.as_deref(),
+            #[cfg(feature = "entity-notify")]
+            Entity::Notify(_) => None,
+            #[cfg(feature = "entity-number")]
+            Entity::Number(e) => e.state_topic.as_deref(),
+            #[cfg(feature = "entity-select")]
+            Entity::Select(e) => e.state_topic.as_deref(),
+            #[cfg(feature = "entity-siren")]
+            Entity::Siren(e) => e.state_topic.as_deref(),
+            #[cfg(feature = "entity-switch")]
+            Entity::Switch(e) => e.state_topic.as_deref(),
+            #[cfg(feature = "entity-text")]
+            Entity::Text(e) => e.state_topic.as_deref(),
+            #[cfg(feature = "entity-update")]
+            Entity::Update(e) => e.state_topic.as_deref(),
+            #[cfg(feature = "entity-vacuum")]
+            Entity::Vacuum(e) => e.state_topic.as_deref(),
+            #[cfg(feature = "entity-valve")]
+            Entity::Valve(e) => e.state_topic.as_deref(),
+            #[cfg(feature = "entity-button")]
+            Entity::Button(_) => None,
+            #[cfg(feature = "entity-climate")]
+            Entity::Climate(_) => None,
+            #[cfg(feature = "entity-image")]
+            Entity::Image(_) => None,
+            #[cfg(feature = "entity-lawn_mower")]
+            Entity::LawnMower(_) => None,
+            #[cfg(feature = "entity-scene")]
+            Entity::Scene(_) => None,
+            #[cfg(feature = "entity-water_heater")]
+            Entity::WaterHeater(_) => None,
+        }
+    }
+
+    /// The MQTT topic Home Assistant publishes the entity commands to, if any.
+    pub fn command_topic(&self) -> Option<&str> {
+        match self {
+            #[cfg(feature = "entity-alarm_control_panel")]
+            Entity::AlarmControlPanel(e) => Some(&e.command_topic),
+            #[cfg(feature = "entity-button")]
+            Entity::Button(e) => Some(&e.command_topic),
+            #[cfg(feature = "entity-fan")]
+            Entity::Fan(e) => Some(&e.command_topic),
+            #[cfg(feature = "entity-humidifier")]
+            Entity::Humidifier(e) => Some(&e.command_topic),
+            #[cfg(feature = "entity-light")]
+            Entity::Light(e) => Some(&e.command_topic),
+            #[cfg(feature = "entity-lock")]
+            Entity::Lock(e) => Some(&e.command_topic),
+            #[cfg(feature = "entity-notify")]
+            Entity::Notify(e) => Some(&e.command_topic),
+            #[cfg(feature = "entity-number")]
+            Entity::Number(e) => Some(&e.command_topic),
+            #[cfg(feature = "entity-select")]
+            Entity::Select(e) => Some(&e.command_topic),
+            #[cfg(feature = "entity-switch")]
+            Entity::Switch(e) => Some(&e.command_topic),
+            #[cfg(feature = "entity-text")]
+            Entity::Text(e) => Some(&e.command_topic),
+            #[cfg(feature = "entity-cover")]
+            Entity::Cover(e) => e.command_topic.as_deref(),
+            #[cfg(feature = "entity-scene")]
+            Entity::Scene(e) => e.command_topic.as_deref(),
+            #[cfg(feature = "entity-siren")]
+            Entity::Siren(e) => e.command_topic.as_deref(),
+            #[cfg(feature = "entity-update")]
+            Entity::Update(e) => e.command_topic.as_deref(),
+            #[cfg(feature = "entity-vacuum")]
+            Entity::Vacuum(e) => e.command_topic.as_deref(),
+            #[cfg(feature = "entity-valve")]
+            Entity::Valve(e) => e.command_topic.as_deref(),
+            #[cfg(feature = "entity-binary_sensor")]
+            Entity::BinarySensor(_) => None,
+            #[cfg(feature = "entity-camera")]
+            Entity::Camera(_) => None,
+            #[cfg(feature = "entity-climate")]
+            Entity::Climate(_) => None,
+            #[cfg(feature = "entity-device_tracker")]
+            Entity::DeviceTracker(_) => None,
+            #[cfg(feature = "entity-device_trigger")]
+            Entity::DeviceTrigger(_) => None,
+            #[cfg(feature = "entity-event")]
+            Entity::Event(_) => None,
+            #[cfg(feature = "entity-image")]
+            Entity::Image(_) => None,
+            #[cfg(feature = "entity-lawn_mower")]
+            Entity::LawnMower(_) => None,
+            #[cfg(feature = "entity-sensor")]
+            Entity::Sensor(_) => None,
+            #[cfg(feature = "entity-tag")]
+            Entity::Tag(_) => None,
+            #[cfg(feature = "entity-water_heater")]
+            Entity::WaterHeater(_) => None,
+        }
+    }
+
+    /// The MQTT topic Home Assistant reads the entity attributes from, if any.
+    pub fn json_attributes_topic(&self) -> Option<&str> {
+        match self {
+            #[cfg(feature = "entity-alarm_control_panel")]
+            Entity::AlarmControlPanel(e) => e.json_attributes_topic.as_deref(),
+            #[cfg(feature = "entity-binary_sensor")]
+            Entity::BinarySensor(e) => e.json_attributes_topic.as_deref(),
+            #[cfg(feature = "entity-button")]
+            Entity::Button(e) => e.json_attributes_topic.as_deref(),
+            #[cfg(feature = "entity-camera")]
+            Entity::Camera(e) => e.json_attributes_topic.as_deref(),
+            #[cfg(feature = "entity-climate")]
+            Entity::Climate(e) => e.json_attributes_topic.as_deref(),
+            #[cfg(feature = "entity-cover")]
+            Entity::Cover(e) => e.json_attributes_topic.as_deref(),
+            #[cfg(feature = "entity-device_tracker")]
+            Entity::DeviceTracker(e) => e.json_attributes_topic.as_deref(),
+            #[cfg(feature = "entity-event")]
+            Entity::Event(e) => e.json_attributes_topic.as_deref(),
+            #[cfg(feature = "entity-fan")]
+            Entity::Fan(e) => e.json_attributes_topic.as_deref(),
+            #[cfg(feature = "entity-humidifier")]
+            Entity::Humidifier(e) => e.json_attributes_topic.as_deref(),
+            #[cfg(feature = "entity-image")]
+            Entity::Image(e) => e.json_attributes_topic.as_deref(),
+            #[cfg(feature = "entity-lawn_mower")]
+            Entity::LawnMower(e) => e.json_attributes_topic.as_deref(),
+            #[cfg(feature = "entity-light")]
+            Entity::Light(e) => e.json_attributes_topic.as_deref(),
+            #[cfg(feature = "entity-lock")]
+            Entity::Lock(e) => e.json_attributes_topic.as_deref(),
+            #[cfg(feature = "entity-notify")]
+            Entity::Notify(e) => e.json_attributes_topic.as_deref(),
+            #[cfg(feature = "entity-number")]
+            Entity::Number(e) => e.json_attributes_topic.as_deref(),
+            #[cfg(feature = "entity-scene")]
+            Entity::Scene(e) => e.json_attributes_topic.as_deref(),
+            #[cfg(feature = "entity-select")]
+            Entity::Select(e) => e.json_attributes_topic.as_deref(),
+            #[cfg(feature = "entity-sensor")]
+            Entity::Sensor(e) => e.json_attributes_topic.as_deref(),
+            #[cfg(feature = "entity-siren")]
+            Entity::Siren(e) => e.json_attributes_topic.as_deref(),
+            #[cfg(feature = "entity-switch")]
+            Entity::Switch(e) => e.json_attributes_topic.as_deref(),
+            #[cfg(feature = "entity-text")]
+            Entity::Text(e) => e.json_attributes_topic.as_deref(),
+            #[cfg(feature = "entity-update")]
+            Entity::Update(e) => e.json_attributes_topic.as_deref(),
+            #[cfg(feature = "entity-vacuum")]
+            Entity::Vacuum(e) => e.json_attributes_topic.as_deref(),
+            #[cfg(feature = "entity-valve")]
+            Entity::Valve(e) => e.json_attributes_topic.as_deref(),
+            #[cfg(feature = "entity-water_heater")]
+            Entity::WaterHeater(e) => e.json_attributes_topic.as_deref(),
+            #[cfg(feature = "entity-device_trigger")]
+            Entity::DeviceTrigger(_) => None,
+            #[cfg(feature = "entity-tag")]
+            Entity::Tag(_) => None,
+        }
+    }
+
+    /// The maximum QoS level to be used when receiving and publishing messages, if any.
+    pub fn qos(&self) -> Option<Qos> {
+        match self {
+            #[cfg(feature = "entity-camera")]
+            Entity::Camera(_) => None,
+            #[cfg(feature = "entity-image")]
+            Entity::Image(_) => None,
+            #[cfg(feature = "entity-tag")]
+            Entity::Tag(_) => None,
+            #[cfg(feature = "entity-alarm_control_panel")]
+            Entity::AlarmControlPanel(e) => e.qos,
+            #[cfg(feature = "entity-binary_sensor")]
+            Entity::BinarySensor(e) => e.qos,
+            #[cfg(feature = "entity-button")]
+            Entity::Button(e) => e.qos,
+            #[cfg(feature = "entity-climate")]
+            Entity::Climate(e) => e.qos,
+            #[cfg(feature = "entity-cover")]
+            Entity::Cover(e) => e.qos,
+            #[cfg(feature = "entity-device_tracker")]
+            Entity::DeviceTracker(e) => e.qos,
+            #[cfg(feature = "entity-device_trigger")]
+            Entity::DeviceTrigger(e) => e.qos,
+            #[cfg(feature = "entity-event")]
+            Entity::Event(e) => e.qos,
+            #[cfg(feature = "entity-fan")]
+            Entity::Fan(e) => e.qos,
+            #[cfg(feature = "entity-humidifier")]
+            Entity::Humidifier(e) => e.qos,
+            #[cfg(feature = "entity-lawn_mower")]
+            Entity::LawnMower(e) => e.qos,
+            #[cfg(feature = "entity-light")]
+            Entity::Light(e) => e.qos,
+            #[cfg(feature = "entity-lock")]
+            Entity::Lock(e) => e.qos,
+            #[cfg(feature = "entity-notify")]
+            Entity::Notify(e) => e.qos,
+            #[cfg(feature = "entity-number")]
+            Entity::Number(e) => e.qos,
+            #[cfg(feature = "entity-scene")]
+            Entity::Scene(e) => e.qos,
+            #[cfg(feature = "entity-select")]
+            Entity::Select(e) => e.qos,
+            #[cfg(feature = "entity-sensor")]
+            Entity::Sensor(e) => e.qos,
+            #[cfg(feature = "entity-siren")]
+            Entity::Siren(e) => e.qos,
+            #[cfg(feature = "entity-switch")]
+            Entity::Switch(e) => e.qos,
+            #[cfg(feature = "entity-text")]
+            Entity::Text(e) => e.qos,
+            #[cfg(feature = "entity-update")]
+            Entity::Update(e) => e.qos,
+            #[cfg(feature = "entity-vacuum")]
+            Entity::Vacuum(e) => e.qos,
+            #[cfg(feature = "entity-valve")]
+            Entity::Valve(e) => e.qos,
+            #[cfg(feature = "entity-water_heater")]
+            Entity::WaterHeater(e) => e.qos,
+        }
+    }
+
+    /// The flag which defines if the entity should be enabled when first added, `None` for the platforms without this attribute.
+    pub fn enabled_by_default_mut(&mut self) -> Option<&mut Option<bool>> {
+        match self {
+            #[cfg(feature = "entity-device_tracker")]
+            Entity::DeviceTracker(_) => None,
+            #[cfg(feature = "entity-device_trigger")]
+            Entity::DeviceTrigger(_) => None,
+            #[cfg(feature = "entity-tag")]
+            Entity::Tag(_) => None,
+            #[cfg(feature = "entity-vacuum")]
+            Entity::Vacuum(_) => None,
+            #[cfg(feature = "entity-alarm_control_panel")]
+            Entity::AlarmControlPanel(e) => Some(&mut e.enabled_by_default),
+            #[cfg(feature = "entity-binary_sensor")]
+            Entity::BinarySensor(e) => Some(&mut e.enabled_by_default),
+            #[cfg(feature = "entity-button")]
+            Entity::Button(e) => Some(&mut e.enabled_by_default),
+            #[cfg(feature = "entity-camera")]
+            Entity::Camera(e) => Some(&mut e.enabled_by_default),
+            #[cfg(feature = "entity-climate")]
+            Entity::Climate(e) => Some(&mut e.enabled_by_default),
+            #[cfg(feature = "entity-cover")]
+            Entity::Cover(e) => Some(&mut e.enabled_by_default),
+            #[cfg(feature = "entity-event")]
+            Entity::Event(e) => Some(&mut e.enabled_by_default),
+            #[cfg(feature = "entity-fan")]
+            Entity::Fan(e) => Some(&mut e.enabled_by_default),
+            #[cfg(feature = "entity-humidifier")]
+            Entity::Humidifier(e) => Some(&mut e.enabled_by_default),
+            #[cfg(feature = "entity-image")]
+            Entity::Image(e) => Some(&mut e.enabled_by_default),
+            #[cfg(feature = "entity-lawn_mower")]
+            Entity::LawnMower(e) => Some(&mut e.enabled_by_default),
+            #[cfg(feature = "entity-light")]
+            Entity::Light(e) => Some(&mut e.enabled_by_default),
+            #[cfg(feature = "entity-lock")]
+            Entity::Lock(e) => Some(&mut e.enabled_by_default),
+            #[cfg(feature = "entity-notify")]
+            Entity::Notify(e) => Some(&mut e.enabled_by_default),
+            #[cfg(feature = "entity-number")]
+            Entity::Number(e) => Some(&mut e.enabled_by_default),
+            #[cfg(feature = "entity-scene")]
+            Entity::Scene(e) => Some(&mut e.enabled_by_default),
+            #[cfg(feature = "entity-select")]
+            Entity::Select(e) => Some(&mut e.enabled_by_default),
+            #[cfg(feature = "entity-sensor")]
+            Entity::Sensor(e) => Some(&mut e.enabled_by_default),
+            #[cfg(feature = "entity-siren")]
+            Entity::Siren(e) => Some(&mut e.enabled_by_default),
+            #[cfg(feature = "entity-switch")]
+            Entity::Switch(e) => Some(&mut e.enabled_by_default),
+            #[cfg(feature = "entity-text")]
+            Entity::Text(e) => Some(&mut e.enabled_by_default),
+            #[cfg(feature = "entity-update")]
+            Entity::Update(e) => Some(&mut e.enabled_by_default),
+            #[cfg(feature = "entity-valve")]
+            Entity::Valve(e) => Some(&mut e.enabled_by_default),
+            #[cfg(feature = "entity-water_heater")]
+            Entity::WaterHeater(e) => Some(&mut e.enabled_by_default),
+        }
+    }
+
+    /// Replaces `~` with this value in any MQTT topic attribute.
+    pub fn topic_prefix(&self) -> Option<&str> {
+        with_entity!(self, e => e.topic_prefix.as_deref())
+    }
+
+    /// Whether the states of the entity should be retained: the events of event entities, device triggers and tag
+    /// scanners must not be replayed by the broker.
+    pub fn retains_state(&self) -> bool {
+        !matches!(self.platform(), "event" | "device_automation" | "tag")
+    }
+
+    /// Resolves the `~` placeholder at the start or the end of the given topic with the entity topic prefix.
+    pub fn resolve_topic(&self, topic: &str) -> String {
+        topic::resolve(&Topic::new(topic), self.topic_prefix()).into()
+    }
+
+    /// The state topic with the `~` placeholder resolved, this is where the entity state must be published.
+    pub fn resolved_state_topic(&self) -> Option<Topic> {
+        self.state_topic()
+            .map(|topic| Topic::new(self.resolve_topic(topic)))
+    }
+
+    /// The command topic with the `~` placeholder resolved, this is where the entity commands are received.
+    pub fn resolved_command_topic(&self) -> Option<Topic> {
+        self.command_topic()
+            .map(|topic| Topic::new(self.resolve_topic(topic)))
+    }
+
+    /// The JSON attributes topic with the `~` placeholder resolved, this is where the entity attributes must be published.
+    pub fn resolved_json_attributes_topic(&self) -> Option<Topic> {
+        self.json_attributes_topic()
+            .map(|topic| Topic::new(self.resolve_topic(topic)))
+    }
+
+    /// Checks every topic attribute of the entity can be used to publish messages.
+    pub fn validate_topics(&self) -> Result<(), InvalidTopic> {
+        let attributes = serde_json::to_value(self).expect("entity should be serializable");
+        validate_payload_topics(&attributes)
+    }
+
+    /// Information about the device this entity is a part of.
+    pub fn device(&self) -> &Device {
+        with_entity!(self, e => &e.device)
+    }
+
+    /// Information about the device this entity is a part of.
+    pub fn device_mut(&mut self) -> &mut Device {
+        with_entity!(self, e => &mut e.device)
+    }
+
+    /// Information about the origin that supplies this entity.
+    pub fn origin(&self) -> &Origin {
+        with_entity!(self, e => &e.origin)
+    }
+
+    /// Information about the origin that supplies this entity.
+    pub fn origin_mut(&mut self) -> &mut Origin {
+        with_entity!(self, e => &mut e.origin)
+    }
+
+    /// Defines how HA will check for entity availability.
+    pub fn availability(&self) -> &Availability {
+        with_entity!(self, e => &e.availability)
+    }
+
+    /// Defines how HA will check for entity availability.
+    pub fn availability_mut(&mut self) -> &mut Availability {
+        with_entity!(self, e => &mut e.availability)
+    }
+
+    /// Replaces the device this entity is a part of.
+    pub fn set_device(&mut self, device: Device) {
+        *self.device_mut() = device;
+    }
+
+    /// Replaces the origin that supplies this entity.
+    pub fn set_origin(&mut self, origin: Origin) {
+        *self.origin_mut() = origin;
+    }
+
+    /// Replaces how HA will check for entity availability.
+    pub fn set_availability(&mut self, availability: Availability) {
+        *self.availability_mut() = availability;
+    }
+
+    /// Replaces the `~` value used in any MQTT topic attribute.
+    pub fn set_topic_prefix<S: Into<String>>(&mut self, topic_prefix: S) {
+        let topic_prefix = Some(topic_prefix.into());
+        with_entity!(self, e => e.topic_prefix = topic_prefix)
+    }
+
+    /// Replaces the category of the entity.
+    pub fn set_entity_category(&mut self, entity_category: Option<EntityCategory>) {
+        with_entity!(self, e => e.entity_category = entity_category)
+    }
+
+    /// The JSON discovery configuration of the entity, without the options applied by `HomeAssistantMqtt`, to
+    /// publish it through another transport.
+    pub fn discovery_payload(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(&self.get_attributes()?)
+    }
+
+    /// The JSON Schema of the discovery payload of the entity's platform, see `Entity::json_schemas`.
+    #[cfg(feature = "schema")]
+    pub fn json_schema(&self) -> schemars::Schema {
+        with_entity!(self, e => schema::schema_of(e))
+    }
+
+    fn get_attributes(&self) -> Result<Value, serde_json::Error> {
+        #[cfg_attr(not(feature = "entity-device_trigger"), allow(unused_mut))]
+        let mut attributes = serde_json::to_value(self)?;
+        #[cfg(feature = "entity-device_trigger")]
+        if let Entity::DeviceTrigger(trigger) = self {
+            if trigger.automation_type.is_empty() {
+                attributes["atype"] = Value::from(trigger::AUTOMATION_TYPE_TRIGGER);
             }
-            Entity::BinarySensor(binary_sensor) => serde_json::to_value(binary_sensor)?,
-            Entity::Button(button) => serde_json::to_value(button)?,
-            Entity::Camera(camera) => serde_json::to_value(camera)?,
-            Entity::Climate(climate) => serde_json::to_value(climate)?,
-            Entity::Cover(cover) => serde_json::to_value(cover)?,
-            Entity::DeviceTracker(device_tracker) => serde_json::to_value(device_tracker)?,
-            Entity::DeviceTrigger(device_trigger) => serde_json::to_value(device_trigger)?,
-            Entity::Event(event) => serde_json::to_value(event)?,
-            Entity::Fan(fan) => serde_json::to_value(fan)?,
-            Entity::Humidifier(humidifier) => serde_json::to_value(humidifier)?,
-            Entity::Image(image) => serde_json::to_value(image)?,
-            Entity::LawnMower(lawn_mower) => serde_json::to_value(lawn_mower)?,
-            //Entity::Light(light) => serde_json::to_value(light)?,
-            Entity::Lock(lock) => serde_json::to_value(lock)?,
-            //Entity::Notify(notify) => serde_json::to_value(notify)?,
-            Entity::Number(number) => serde_json::to_value(number)?,
-            Entity::Scene(scene) => serde_json::to_value(scene)?,
-            Entity::Select(select) => serde_json::to_value(select)?,
-            Entity::Sensor(sensor) => serde_json::to_value(sensor)?,
-            Entity::Siren(siren) => serde_json::to_value(siren)?,
-            Entity::Switch(switch) => serde_json::to_value(switch)?,
-            Entity::Tag(tag) => serde_json::to_value(tag)?,
-            Entity::Text(text) => serde_json::to_value(text)?,
-            Entity::Update(update) => serde_json::to_value(update)?,
-            Entity::Vacuum(vacuum) => serde_json::to_value(vacuum)?,
-            Entity::Valve(valve) => serde_json::to_value(valve)?,
-            Entity::WaterHeater(water_heater) => serde_json::to_value(water_heater)?,
-        };
+        }
         Ok(attributes)
     }
 }
+
+impl Serialize for Entity {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        with_entity!(self, e => e.serialize(serializer))
+    }
+}
+
+#[cfg(all(test, feature = "entity-sensor"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_access_common_entity_attributes() {
+        let mut entity = Entity::from(
+            Sensor::new("~/state")
+                .unique_id("barometer-09AF_temperature")
+                .object_id("barometer_temperature")
+                .name("Temperature"),
+        );
+        entity.set_device(Device::default().add_identifier("barometer-09AF"));
+        entity.set_availability(Availability::single_topic("~/availability"));
+
+        assert_eq!(entity.platform(), "sensor");
+        assert_eq!(entity.unique_id(), Some("barometer-09AF_temperature"));
+        assert_eq!(entity.object_id(), Some("barometer_temperature"));
+        assert_eq!(entity.name(), Some("Temperature"));
+        assert_eq!(entity.state_topic(), Some("~/state"));
+        entity.set_topic_prefix("home/barometer");
+        assert_eq!(
+            entity.resolved_state_topic(),
+            Some(Topic::new("home/barometer/state"))
+        );
+        assert_eq!(entity.command_topic(), None);
+        assert_eq!(entity.device().identifiers, vec!["barometer-09AF"]);
+        assert_eq!(
+            entity.availability().availability[0].topic,
+            "~/availability"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "entity-button")]
+    fn can_build_entities_with_required_attributes() {
+        let button = Button::builder()
+            .command_topic("home/reboot")
+            .unique_id("reboot")
+            .build();
+
+        assert_eq!(button, Button::new("home/reboot").unique_id("reboot"));
+    }
+
+    #[test]
+    #[cfg(feature = "entity-cover")]
+    fn can_serialize_generic_device_classes() {
+        use mqtt::device_classes::CoverDeviceClass;
+
+        let cover = Cover::builder()
+            .command_topic("home/garage/set")
+            .build()
+            .device_class(CoverDeviceClass::None);
+        let attributes = serde_json::to_value(&cover).unwrap();
+        assert_eq!(attributes["dev_cla"], Value::Null);
+        assert_eq!(
+            serde_json::from_value::<Cover>(attributes)
+                .unwrap()
+                .device_class,
+            None
+        );
+        assert_eq!(
+            serde_json::from_str::<CoverDeviceClass>(r#""garage""#).unwrap(),
+            CoverDeviceClass::Garage
+        );
+        assert!(serde_json::from_str::<CoverDeviceClass>(r#""boat""#).is_err());
+    }
+
+    #[test]
+    fn can_render_discovery_payloads_without_client() {
+        let sensor = Entity::from(Sensor::new("home/temperature").unique_id("temperature"));
+        let payload: Value = serde_json::from_str(&sensor.discovery_payload().unwrap()).unwrap();
+        assert_eq!(payload["stat_t"], "home/temperature");
+        assert_eq!(payload["uniq_id"], "temperature");
+    }
+}
@@ -0,0 +1,57 @@
+use std::collections::{BTreeMap, HashMap};
+
+/// Provides the translated friendly names of the entities, looked up by a translation key.
+///
+/// Derived devices use the field name as translation key and fall back to the name declared
+/// in the `#[ha(...)]` attribute when no translation is provided:
+///
+/// ```
+/// # #[cfg(all(feature = "derive", feature = "entity-sensor"))] {
+/// # use std::collections::HashMap;
+/// # use ha_mqtt_discovery::HaDevice;
+/// # #[derive(HaDevice)]
+/// # #[ha(base_topic = "home", name = "Barometer")]
+/// # struct Barometer {
+/// #     #[ha(identifier)]
+/// #     serial_number: String,
+/// #     #[ha(sensor, name = "Temperature")]
+/// #     temperature: f32,
+/// # }
+/// # let barometer = Barometer { serial_number: "42".to_string(), temperature: 21.5 };
+/// let french = HashMap::from([("temperature".to_string(), "Température".to_string())]);
+/// let entities = barometer.localized_entities(&french);
+/// assert_eq!(entities[0].name(), Some("Température"));
+/// # }
+/// ```
+pub trait NameProvider {
+    /// The translated name for the given key, `None` to keep the default name.
+    fn name(&self, key: &str) -> Option<String>;
+}
+
+/// Keeps the default names.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DefaultNames;
+
+impl NameProvider for DefaultNames {
+    fn name(&self, _key: &str) -> Option<String> {
+        None
+    }
+}
+
+impl NameProvider for HashMap<String, String> {
+    fn name(&self, key: &str) -> Option<String> {
+        self.get(key).cloned()
+    }
+}
+
+impl NameProvider for BTreeMap<String, String> {
+    fn name(&self, key: &str) -> Option<String> {
+        self.get(key).cloned()
+    }
+}
+
+impl<F: Fn(&str) -> Option<String>> NameProvider for F {
+    fn name(&self, key: &str) -> Option<String> {
+        self(key)
+    }
+}
@@ -0,0 +1,89 @@
+use crate::mqtt::lock::Lock;
+
+/// The state of a lock, published on its `state_topic`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LockState {
+    Locked,
+    Unlocked,
+    Locking,
+    Unlocking,
+    Jammed,
+}
+
+/// A command sent by Home Assistant to the `command_topic` of a lock.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LockCommand {
+    Lock,
+    Unlock,
+    /// Only sent to the locks configuring a `payload_open`.
+    Open,
+}
+
+impl Lock {
+    /// The payload of the state, according to the `state_*` payloads of the lock.
+    pub fn state_payload(&self, state: LockState) -> &str {
+        match state {
+            LockState::Locked => self.state_locked.as_deref().unwrap_or("LOCKED"),
+            LockState::Unlocked => self.state_unlocked.as_deref().unwrap_or("UNLOCKED"),
+            LockState::Locking => self.state_locking.as_deref().unwrap_or("LOCKING"),
+            LockState::Unlocking => self.state_unlocking.as_deref().unwrap_or("UNLOCKING"),
+            LockState::Jammed => self.state_jammed.as_deref().unwrap_or("JAMMED"),
+        }
+    }
+
+    /// The command of a payload received on the `command_topic`, according to the `payload_*` payloads of the lock.
+    ///
+    /// The payloads rendered by a `command_template` aren't recognized.
+    pub fn command(&self, payload: &str) -> Option<LockCommand> {
+        if payload == self.payload_lock.as_deref().unwrap_or("LOCK") {
+            Some(LockCommand::Lock)
+        } else if payload == self.payload_unlock.as_deref().unwrap_or("UNLOCK") {
+            Some(LockCommand::Unlock)
+        } else if self.payload_open.as_deref() == Some(payload) {
+            Some(LockCommand::Open)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(all(test, feature = "client"))]
+mod tests {
+    use crate::{testing::MockClient, validation::ValidationError, Entity};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn can_drive_locks() {
+        let client = MockClient::default();
+        let ha = client.ha();
+        let lock = Lock::new("~/set")
+            .topic_prefix("home/door")
+            .state_topic("~/state")
+            .state_jammed("STUCK")
+            .payload_open("OPEN");
+
+        ha.publish_lock_state(&lock, LockState::Jammed)
+            .await
+            .unwrap();
+        client.assert_published("home/door/state", "STUCK");
+        assert_eq!(lock.state_payload(LockState::Locking), "LOCKING");
+
+        assert_eq!(lock.command("UNLOCK"), Some(LockCommand::Unlock));
+        assert_eq!(lock.command("OPEN"), Some(LockCommand::Open));
+        assert_eq!(Lock::new("home/door/set").command("OPEN"), None);
+
+        assert!(Entity::from(lock.clone().code_format("^\\d{4}$"))
+            .validate()
+            .is_ok());
+        assert!(matches!(
+            Entity::from(lock.code_format("^\\d{4"))
+                .validate()
+                .unwrap_err()[..],
+            [ValidationError::InvalidPattern {
+                attribute: "code_format",
+                ..
+            }]
+        ));
+    }
+}
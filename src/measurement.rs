@@ -0,0 +1,173 @@
+#[cfg(feature = "entity-number")]
+use crate::mqtt::number::Number;
+#[cfg(feature = "entity-sensor")]
+use crate::mqtt::sensor::Sensor;
+use crate::mqtt::{
+    device_classes::{NumberDeviceClass, SensorDeviceClass},
+    units::{
+        ConcentrationUnit, CurrencyUnit, DataRateUnit, DataUnit, ElectricalUnit, EnergyUnit,
+        FrequencyUnit, IrradiationUnit, LengthUnit, LightUnit, MassUnit, PercentageUnit, PowerUnit,
+        PrecipitationUnit, PressureUnit, SignalStrengthUnit, SpeedUnit, TempUnit, TimeUnit, Unit,
+        VoltUnit, VolumeFlowRateUnit, VolumeUnit,
+    },
+};
+
+/// A device class measured in one family of units, eg. temperatures in `TempUnit`.
+///
+/// ```
+/// # #[cfg(feature = "entity-sensor")] {
+/// # use ha_mqtt_discovery::{measurement::Temperature, mqtt::{sensor::Sensor, units::TempUnit}};
+/// let sensor = Sensor::new("home/temperature").device_class_and_unit(Temperature, TempUnit::Celsius);
+/// # }
+/// ```
+///
+/// A unit of another family doesn't compile:
+///
+/// ```compile_fail
+/// # use ha_mqtt_discovery::{measurement::Temperature, mqtt::{sensor::Sensor, units::PowerUnit}};
+/// let sensor = Sensor::new("home/temperature").device_class_and_unit(Temperature, PowerUnit::Watt);
+/// ```
+pub trait Measurement {
+    type Unit: Into<Unit>;
+}
+
+/// A measurement supported by the sensors.
+pub trait SensorMeasurement: Measurement {
+    fn sensor_device_class(&self) -> SensorDeviceClass;
+}
+
+/// A measurement supported by the numbers.
+pub trait NumberMeasurement: Measurement {
+    fn number_device_class(&self) -> NumberDeviceClass;
+}
+
+macro_rules! measurements {
+    ($($name:ident($unit:ident) $(, $number:ident)?;)*) => {
+        $(
+            #[doc = concat!("The `", stringify!($name), "` device class, measured in `", stringify!($unit), "`.")]
+            #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+            pub struct $name;
+
+            impl Measurement for $name {
+                type Unit = $unit;
+            }
+
+            impl SensorMeasurement for $name {
+                fn sensor_device_class(&self) -> SensorDeviceClass {
+                    SensorDeviceClass::$name
+                }
+            }
+
+            $(
+                impl NumberMeasurement for $name {
+                    fn number_device_class(&self) -> NumberDeviceClass {
+                        NumberDeviceClass::$number
+                    }
+                }
+            )?
+        )*
+    };
+}
+
+measurements! {
+    ApparentPower(ElectricalUnit), ApparentPower;
+    AtmosphericPressure(PressureUnit), AtmosphericPressure;
+    Battery(PercentageUnit), Battery;
+    CarbonDioxide(ConcentrationUnit), CarbonDioxide;
+    CarbonMonoxide(ConcentrationUnit), CarbonMonoxide;
+    Current(ElectricalUnit), Current;
+    DataRate(DataRateUnit), DataRate;
+    DataSize(DataUnit), DataSize;
+    Distance(LengthUnit), Distance;
+    Duration(TimeUnit);
+    Energy(EnergyUnit), Energy;
+    EnergyStorage(EnergyUnit), EnergyStorage;
+    Frequency(FrequencyUnit), Frequency;
+    Gas(VolumeUnit), Gas;
+    Humidity(PercentageUnit), Humidity;
+    Illuminance(LightUnit), Illuminance;
+    Irradiance(IrradiationUnit), Irradiance;
+    Moisture(PercentageUnit), Moisture;
+    Monetary(CurrencyUnit), Monetary;
+    NitrogenDioxide(ConcentrationUnit), NitrogenDioxide;
+    NitrogenMonoxide(ConcentrationUnit), NitrogenMonoxide;
+    NitrousOxide(ConcentrationUnit), NitrousOxide;
+    Ozone(ConcentrationUnit), Ozone;
+    Pm1(ConcentrationUnit), Pm1;
+    Pm25(ConcentrationUnit), Pm25;
+    Pm10(ConcentrationUnit), Pm10;
+    PowerFactor(PercentageUnit), PowerFactor;
+    Power(PowerUnit), Power;
+    Precipitation(LengthUnit), Precipitation;
+    PrecipitationIntensity(PrecipitationUnit), PrecipitationIntensity;
+    Pressure(PressureUnit), Pressure;
+    SignalStrength(SignalStrengthUnit), SignalStrength;
+    Speed(SpeedUnit), Speed;
+    SulphurDioxide(ConcentrationUnit), SulphurDioxide;
+    Temperature(TempUnit), Temperature;
+    VolatileOrganicCompounds(ConcentrationUnit), VolatileOrganicCompounds;
+    VolatileOrganicCompoundsParts(ConcentrationUnit);
+    Voltage(VoltUnit), Voltage;
+    Volume(VolumeUnit), Volume;
+    VolumeFlowRate(VolumeFlowRateUnit), VolumeFlowRate;
+    VolumeStorage(VolumeUnit), VolumeStorage;
+    Water(VolumeUnit), Water;
+    Weight(MassUnit), Weight;
+    WindSpeed(SpeedUnit), WindSpeed;
+}
+
+#[cfg(feature = "entity-sensor")]
+impl Sensor {
+    /// Sets the device class and a unit of measurement of the same measurement.
+    pub fn device_class_and_unit<M: SensorMeasurement>(
+        self,
+        measurement: M,
+        unit: M::Unit,
+    ) -> Self {
+        self.device_class(measurement.sensor_device_class())
+            .unit_of_measurement(unit)
+    }
+}
+
+#[cfg(feature = "entity-number")]
+impl Number {
+    /// Sets the device class and a unit of measurement of the same measurement.
+    pub fn device_class_and_unit<M: NumberMeasurement>(
+        self,
+        measurement: M,
+        unit: M::Unit,
+    ) -> Self {
+        self.device_class(measurement.number_device_class())
+            .unit_of_measurement(unit)
+    }
+}
+
+#[cfg(all(test, feature = "entity-number", feature = "entity-sensor"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_pair_device_classes_and_units() {
+        let sensor =
+            Sensor::new("home/temperature").device_class_and_unit(Temperature, TempUnit::Celsius);
+        assert_eq!(sensor.device_class, Some(SensorDeviceClass::Temperature));
+        assert_eq!(
+            sensor.unit_of_measurement,
+            Some(Unit::Temperature(TempUnit::Celsius))
+        );
+
+        let number =
+            Number::new("home/heater/power/set").device_class_and_unit(Power, PowerUnit::KiloWatt);
+        assert_eq!(number.device_class, Some(NumberDeviceClass::Power));
+        assert_eq!(
+            number.unit_of_measurement,
+            Some(Unit::Power(PowerUnit::KiloWatt))
+        );
+
+        let sensor = Sensor::new("home/voc").device_class_and_unit(
+            VolatileOrganicCompoundsParts,
+            ConcentrationUnit::PartsPerBillion,
+        );
+        assert_eq!(sensor.unit_of_measurement.unwrap().as_str(), "ppb");
+    }
+}
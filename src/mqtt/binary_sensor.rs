@@ -1,8 +1,10 @@
 use super::common::Qos;
 use super::common::{Availability, Device, EntityCategory, Origin};
 use super::device_classes::BinarySensorDeviceClass;
+use crate::topic::Topic;
 use crate::Entity;
-use serde_derive::Serialize;
+use bon::Builder;
+use serde_derive::{Deserialize, Serialize};
 
 /// ---
 /// title: "MQTT binary sensor"
@@ -290,7 +292,10 @@ use serde_derive::Serialize;
 ///       payload_off: "0"
 /// ```
 ///
-#[derive(Clone, Debug, PartialEq, Serialize, Default)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Builder)]
+#[cfg_attr(feature = "entity-default", derive(Default))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[builder(on(String, into), on(Topic, into))]
 pub struct BinarySensor {
     /// Replaces `~` with this value in any MQTT topic attribute.
     /// [See Home Assistant documentation](https://www.home-assistant.io/integrations/mqtt/#using-abbreviations-and-base-topic)
@@ -298,67 +303,114 @@ pub struct BinarySensor {
     pub topic_prefix: Option<String>,
 
     /// It is encouraged to add additional information about the origin that supplies MQTT entities via MQTT discovery by adding the origin option (can be abbreviated to o) to the discovery payload. Note that these options also support abbreviations. Information of the origin will be logged to the core event log when an item is discovered or updated.
-    #[serde(rename = "o")]
+    #[serde(rename = "o", alias = "origin", default)]
+    #[builder(default)]
     pub origin: Origin,
 
     /// Information about the device this button is a part of to tie it into the [device registry](https://developers.home-assistant.io/docs/en/device_registry_index.html). Only works when [`unique_id`](#unique_id) is set. At least one of identifiers or connections must be present to identify the device.
-    #[serde(rename = "dev")]
+    #[serde(rename = "dev", alias = "device", default)]
+    #[builder(default)]
     pub device: Device,
 
     /// A list of MQTT topics subscribed to receive availability (online/offline) updates. Must not be used together with `availability_topic`.
     #[serde(flatten)]
+    #[builder(default)]
     pub availability: Availability,
 
     /// The category of the entity. (optional, default: None)
-    #[serde(rename = "ent_cat", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "ent_cat",
+        alias = "entity_category",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub entity_category: Option<EntityCategory>,
 
     /// Sets the [class of the device](/integrations/binary_sensor/#device-class), changing the device state and icon that is displayed on the frontend. The `device_class` can be `null`.
-    #[serde(rename = "dev_cla", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "dev_cla",
+        alias = "device_class",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub device_class: Option<BinarySensorDeviceClass>,
 
     /// Flag which defines if the entity should be enabled when first added.
-    #[serde(rename = "en", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "en",
+        alias = "enabled_by_default",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub enabled_by_default: Option<bool>,
 
     /// The encoding of the payloads received. Set to `""` to disable decoding of incoming payload.
-    #[serde(rename = "e", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "e",
+        alias = "encoding",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub encoding: Option<String>,
 
     /// Sends update events (which results in update of [state object](/docs/configuration/state_object/)'s `last_changed`) even if the sensor's state hasn't changed. Useful if you want to have meaningful value graphs in history or want to create an automation that triggers on *every* incoming state message (not only when the sensor's new state is different to the current one).
-    #[serde(rename = "frc_upd", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "frc_upd",
+        alias = "force_update",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub force_update: Option<bool>,
 
     /// [Icon](/docs/configuration/customizing-devices/#icon) for the entity.
-    #[serde(rename = "ic", skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "ic", alias = "icon", skip_serializing_if = "Option::is_none")]
     pub icon: Option<String>,
 
     /// Defines a [template](/docs/configuration/templating/#using-templates-with-the-mqtt-integration) to extract the JSON dictionary from messages received on the `json_attributes_topic`. Usage example can be found in [MQTT sensor](/integrations/sensor.mqtt/#json-attributes-template-configuration) documentation.
-    #[serde(rename = "json_attr_tpl", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "json_attr_tpl",
+        alias = "json_attributes_template",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub json_attributes_template: Option<String>,
 
     /// The MQTT topic subscribed to receive a JSON dictionary payload and then set as sensor attributes. Usage example can be found in [MQTT sensor](/integrations/sensor.mqtt/#json-attributes-topic-configuration) documentation.
-    #[serde(rename = "json_attr_t", skip_serializing_if = "Option::is_none")]
-    pub json_attributes_topic: Option<String>,
+    #[serde(
+        rename = "json_attr_t",
+        alias = "json_attributes_topic",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub json_attributes_topic: Option<Topic>,
 
     /// The name of the binary sensor. Can be set to `null` if only the device name is relevant.
     #[serde(rename = "name", skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
 
     /// Used instead of `name` for automatic generation of `entity_id`
-    #[serde(rename = "obj_id", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "obj_id",
+        alias = "object_id",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub object_id: Option<String>,
 
     /// For sensors that only send `on` state updates (like PIRs), this variable sets a delay in seconds after which the sensor's state will be updated back to `off`.
-    #[serde(rename = "off_dly", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "off_dly",
+        alias = "off_delay",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub off_delay: Option<i32>,
 
     /// The string that represents the `off` state. It will be compared to the message in the `state_topic` (see `value_template` for details)
-    #[serde(rename = "pl_off", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "pl_off",
+        alias = "payload_off",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub payload_off: Option<String>,
 
     /// The string that represents the `on` state. It will be compared to the message in the `state_topic` (see `value_template` for details)
-    #[serde(rename = "pl_on", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "pl_on",
+        alias = "payload_on",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub payload_on: Option<String>,
 
     /// The maximum QoS level to be used when receiving and publishing messages.
@@ -366,19 +418,32 @@ pub struct BinarySensor {
     pub qos: Option<Qos>,
 
     /// The MQTT topic subscribed to receive sensor's state.
-    #[serde(rename = "stat_t")]
-    pub state_topic: String,
+    #[serde(rename = "stat_t", alias = "state_topic")]
+    pub state_topic: Topic,
 
     /// An ID that uniquely identifies this sensor. If two sensors have the same unique ID, Home Assistant will raise an exception.
-    #[serde(rename = "uniq_id", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "uniq_id",
+        alias = "unique_id",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub unique_id: Option<String>,
 
     /// Defines a [template](/docs/configuration/templating/#using-templates-with-the-mqtt-integration) that returns a string to be compared to `payload_on`/`payload_off` or an empty string, in which case the MQTT message will be removed. Remove this option when `payload_on` and `payload_off` are sufficient to match your payloads (i.e no preprocessing of original message is required).
-    #[serde(rename = "val_tpl", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "val_tpl",
+        alias = "value_template",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub value_template: Option<String>,
 }
 
 impl BinarySensor {
+    /// Creates the entity with its required attributes, the optional attributes are left unset.
+    pub fn new(state_topic: impl Into<Topic>) -> Self {
+        Self::builder().state_topic(state_topic).build()
+    }
+
     /// Replaces `~` with this value in any MQTT topic attribute.
     /// [See Home Assistant documentation](https://www.home-assistant.io/integrations/mqtt/#using-abbreviations-and-base-topic)
     pub fn topic_prefix<S: Into<String>>(mut self, topic_prefix: S) -> Self {
@@ -450,7 +515,7 @@ impl BinarySensor {
     }
 
     /// The MQTT topic subscribed to receive a JSON dictionary payload and then set as sensor attributes. Usage example can be found in [MQTT sensor](/integrations/sensor.mqtt/#json-attributes-topic-configuration) documentation.
-    pub fn json_attributes_topic<T: Into<String>>(mut self, json_attributes_topic: T) -> Self {
+    pub fn json_attributes_topic<T: Into<Topic>>(mut self, json_attributes_topic: T) -> Self {
         self.json_attributes_topic = Some(json_attributes_topic.into());
         self
     }
@@ -492,7 +557,7 @@ impl BinarySensor {
     }
 
     /// The MQTT topic subscribed to receive sensor's state.
-    pub fn state_topic<T: Into<String>>(mut self, state_topic: T) -> Self {
+    pub fn state_topic<T: Into<Topic>>(mut self, state_topic: T) -> Self {
         self.state_topic = state_topic.into();
         self
     }
@@ -1,8 +1,10 @@
 use super::common::Qos;
 use super::common::{Availability, Device, EntityCategory, Origin};
 use super::device_classes::ButtonDeviceClass;
+use crate::topic::Topic;
 use crate::Entity;
-use serde_derive::Serialize;
+use bon::Builder;
+use serde_derive::{Deserialize, Serialize};
 
 /// ---
 /// title: "MQTT button"
@@ -219,7 +221,10 @@ use serde_derive::Serialize;
 ///       device_class: "restart"
 /// ```
 ///
-#[derive(Clone, Debug, PartialEq, Serialize, Default)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Builder)]
+#[cfg_attr(feature = "entity-default", derive(Default))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[builder(on(String, into), on(Topic, into))]
 pub struct Button {
     /// Replaces `~` with this value in any MQTT topic attribute.
     /// [See Home Assistant documentation](https://www.home-assistant.io/integrations/mqtt/#using-abbreviations-and-base-topic)
@@ -227,63 +232,102 @@ pub struct Button {
     pub topic_prefix: Option<String>,
 
     /// It is encouraged to add additional information about the origin that supplies MQTT entities via MQTT discovery by adding the origin option (can be abbreviated to o) to the discovery payload. Note that these options also support abbreviations. Information of the origin will be logged to the core event log when an item is discovered or updated.
-    #[serde(rename = "o")]
+    #[serde(rename = "o", alias = "origin", default)]
+    #[builder(default)]
     pub origin: Origin,
 
     /// Information about the device this button is a part of to tie it into the [device registry](https://developers.home-assistant.io/docs/en/device_registry_index.html). Only works when [`unique_id`](#unique_id) is set. At least one of identifiers or connections must be present to identify the device.
-    #[serde(rename = "dev")]
+    #[serde(rename = "dev", alias = "device", default)]
+    #[builder(default)]
     pub device: Device,
 
     /// A list of MQTT topics subscribed to receive availability (online/offline) updates. Must not be used together with `availability_topic`.
     #[serde(flatten)]
+    #[builder(default)]
     pub availability: Availability,
 
     /// The category of the entity. (optional, default: None)
-    #[serde(rename = "ent_cat", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "ent_cat",
+        alias = "entity_category",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub entity_category: Option<EntityCategory>,
 
     /// Defines a [template](/docs/configuration/templating/#using-templates-with-the-mqtt-integration) to generate the payload to send to `command_topic`.
-    #[serde(rename = "cmd_tpl", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "cmd_tpl",
+        alias = "command_template",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub command_template: Option<String>,
 
     /// The MQTT topic to publish commands to trigger the button.
-    #[serde(rename = "cmd_t")]
-    pub command_topic: String,
+    #[serde(rename = "cmd_t", alias = "command_topic")]
+    pub command_topic: Topic,
 
     /// The [type/class](/integrations/button/#device-class) of the button to set the icon in the frontend. The `device_class` can be `null`.
-    #[serde(rename = "dev_cla", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "dev_cla",
+        alias = "device_class",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub device_class: Option<ButtonDeviceClass>,
 
     /// Flag which defines if the entity should be enabled when first added.
-    #[serde(rename = "en", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "en",
+        alias = "enabled_by_default",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub enabled_by_default: Option<bool>,
 
     /// The encoding of the published messages.
-    #[serde(rename = "e", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "e",
+        alias = "encoding",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub encoding: Option<String>,
 
     /// [Icon](/docs/configuration/customizing-devices/#icon) for the entity.
-    #[serde(rename = "ic", skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "ic", alias = "icon", skip_serializing_if = "Option::is_none")]
     pub icon: Option<String>,
 
     /// Defines a [template](/docs/configuration/templating/#using-templates-with-the-mqtt-integration) to extract the JSON dictionary from messages received on the `json_attributes_topic`. Usage example can be found in [MQTT sensor](/integrations/sensor.mqtt/#json-attributes-template-configuration) documentation.
-    #[serde(rename = "json_attr_tpl", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "json_attr_tpl",
+        alias = "json_attributes_template",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub json_attributes_template: Option<String>,
 
     /// The MQTT topic subscribed to receive a JSON dictionary payload and then set as sensor attributes. Usage example can be found in [MQTT sensor](/integrations/sensor.mqtt/#json-attributes-topic-configuration) documentation.
-    #[serde(rename = "json_attr_t", skip_serializing_if = "Option::is_none")]
-    pub json_attributes_topic: Option<String>,
+    #[serde(
+        rename = "json_attr_t",
+        alias = "json_attributes_topic",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub json_attributes_topic: Option<Topic>,
 
     /// The name to use when displaying this button. Can be set to `null` if only the device name is relevant.
     #[serde(rename = "name", skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
 
     /// Used instead of `name` for automatic generation of `entity_id`
-    #[serde(rename = "obj_id", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "obj_id",
+        alias = "object_id",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub object_id: Option<String>,
 
     /// The payload To send to trigger the button.
-    #[serde(rename = "pl_prs", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "pl_prs",
+        alias = "payload_press",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub payload_press: Option<String>,
 
     /// The maximum QoS level to be used when receiving and publishing messages.
@@ -291,15 +335,28 @@ pub struct Button {
     pub qos: Option<Qos>,
 
     /// If the published message should have the retain flag on or not.
-    #[serde(rename = "ret", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "ret",
+        alias = "retain",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub retain: Option<bool>,
 
     /// An ID that uniquely identifies this button entity. If two buttons have the same unique ID, Home Assistant will raise an exception.
-    #[serde(rename = "uniq_id", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "uniq_id",
+        alias = "unique_id",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub unique_id: Option<String>,
 }
 
 impl Button {
+    /// Creates the entity with its required attributes, the optional attributes are left unset.
+    pub fn new(command_topic: impl Into<Topic>) -> Self {
+        Self::builder().command_topic(command_topic).build()
+    }
+
     /// Replaces `~` with this value in any MQTT topic attribute.
     /// [See Home Assistant documentation](https://www.home-assistant.io/integrations/mqtt/#using-abbreviations-and-base-topic)
     pub fn topic_prefix<S: Into<String>>(mut self, topic_prefix: S) -> Self {
@@ -338,7 +395,7 @@ impl Button {
     }
 
     /// The MQTT topic to publish commands to trigger the button.
-    pub fn command_topic<T: Into<String>>(mut self, command_topic: T) -> Self {
+    pub fn command_topic<T: Into<Topic>>(mut self, command_topic: T) -> Self {
         self.command_topic = command_topic.into();
         self
     }
@@ -377,7 +434,7 @@ impl Button {
     }
 
     /// The MQTT topic subscribed to receive a JSON dictionary payload and then set as sensor attributes. Usage example can be found in [MQTT sensor](/integrations/sensor.mqtt/#json-attributes-topic-configuration) documentation.
-    pub fn json_attributes_topic<T: Into<String>>(mut self, json_attributes_topic: T) -> Self {
+    pub fn json_attributes_topic<T: Into<Topic>>(mut self, json_attributes_topic: T) -> Self {
         self.json_attributes_topic = Some(json_attributes_topic.into());
         self
     }
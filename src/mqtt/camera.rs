@@ -1,6 +1,8 @@
 use super::common::{Availability, Device, EntityCategory, Origin};
+use crate::topic::Topic;
 use crate::Entity;
-use serde_derive::Serialize;
+use bon::Builder;
+use serde_derive::{Deserialize, Serialize};
 
 /// ---
 /// title: "MQTT Camera"
@@ -167,7 +169,10 @@ use serde_derive::Serialize;
 ///   type: string
 /// {% endconfiguration %}
 ///
-#[derive(Clone, Debug, PartialEq, Serialize, Default)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Builder)]
+#[cfg_attr(feature = "entity-default", derive(Default))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[builder(on(String, into), on(Topic, into))]
 pub struct Camera {
     /// Replaces `~` with this value in any MQTT topic attribute.
     /// [See Home Assistant documentation](https://www.home-assistant.io/integrations/mqtt/#using-abbreviations-and-base-topic)
@@ -175,63 +180,103 @@ pub struct Camera {
     pub topic_prefix: Option<String>,
 
     /// It is encouraged to add additional information about the origin that supplies MQTT entities via MQTT discovery by adding the origin option (can be abbreviated to o) to the discovery payload. Note that these options also support abbreviations. Information of the origin will be logged to the core event log when an item is discovered or updated.
-    #[serde(rename = "o")]
+    #[serde(rename = "o", alias = "origin", default)]
+    #[builder(default)]
     pub origin: Origin,
 
     /// Information about the device this button is a part of to tie it into the [device registry](https://developers.home-assistant.io/docs/en/device_registry_index.html). Only works when [`unique_id`](#unique_id) is set. At least one of identifiers or connections must be present to identify the device.
-    #[serde(rename = "dev")]
+    #[serde(rename = "dev", alias = "device", default)]
+    #[builder(default)]
     pub device: Device,
 
     /// A list of MQTT topics subscribed to receive availability (online/offline) updates. Must not be used together with `availability_topic`.
     #[serde(flatten)]
+    #[builder(default)]
     pub availability: Availability,
 
     /// The category of the entity. (optional, default: None)
-    #[serde(rename = "ent_cat", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "ent_cat",
+        alias = "entity_category",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub entity_category: Option<EntityCategory>,
 
     /// Flag which defines if the entity should be enabled when first added.
-    #[serde(rename = "en", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "en",
+        alias = "enabled_by_default",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub enabled_by_default: Option<bool>,
 
     /// The encoding of the payloads received. Set to `""` to disable decoding of incoming payload. Use `image_encoding` to enable `Base64` decoding on `topic`.
-    #[serde(rename = "e", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "e",
+        alias = "encoding",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub encoding: Option<String>,
 
     /// [Icon](/docs/configuration/customizing-devices/#icon) for the entity.
-    #[serde(rename = "ic", skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "ic", alias = "icon", skip_serializing_if = "Option::is_none")]
     pub icon: Option<String>,
 
     /// The encoding of the image payloads received. Set to `"b64"` to enable base64 decoding of image payload. If not set, the image payload must be raw binary data.
-    #[serde(rename = "img_e", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "img_e",
+        alias = "image_encoding",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub image_encoding: Option<String>,
 
     /// Defines a [template](/docs/configuration/templating/#using-templates-with-the-mqtt-integration) to extract the JSON dictionary from messages received on the `json_attributes_topic`.
-    #[serde(rename = "json_attr_tpl", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "json_attr_tpl",
+        alias = "json_attributes_template",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub json_attributes_template: Option<String>,
 
     /// The MQTT topic subscribed to receive a JSON dictionary payload and then set as sensor attributes. Implies `force_update` of the current sensor state when a message is received on this topic.
-    #[serde(rename = "json_attr_t", skip_serializing_if = "Option::is_none")]
-    pub json_attributes_topic: Option<String>,
+    #[serde(
+        rename = "json_attr_t",
+        alias = "json_attributes_topic",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub json_attributes_topic: Option<Topic>,
 
     /// The name of the camera. Can be set to `null` if only the device name is relevant.
     #[serde(rename = "name", skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
 
     /// Used instead of `name` for automatic generation of `entity_id`
-    #[serde(rename = "obj_id", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "obj_id",
+        alias = "object_id",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub object_id: Option<String>,
 
     /// The MQTT topic to subscribe to.
-    #[serde(rename = "t")]
-    pub topic: String,
+    #[serde(rename = "t", alias = "topic")]
+    pub topic: Topic,
 
     /// An ID that uniquely identifies this camera. If two cameras have the same unique ID Home Assistant will raise an exception.
-    #[serde(rename = "uniq_id", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "uniq_id",
+        alias = "unique_id",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub unique_id: Option<String>,
 }
 
 impl Camera {
+    /// Creates the entity with its required attributes, the optional attributes are left unset.
+    pub fn new(topic: impl Into<Topic>) -> Self {
+        Self::builder().topic(topic).build()
+    }
+
     /// Replaces `~` with this value in any MQTT topic attribute.
     /// [See Home Assistant documentation](https://www.home-assistant.io/integrations/mqtt/#using-abbreviations-and-base-topic)
     pub fn topic_prefix<S: Into<String>>(mut self, topic_prefix: S) -> Self {
@@ -297,7 +342,7 @@ impl Camera {
     }
 
     /// The MQTT topic subscribed to receive a JSON dictionary payload and then set as sensor attributes. Implies `force_update` of the current sensor state when a message is received on this topic.
-    pub fn json_attributes_topic<T: Into<String>>(mut self, json_attributes_topic: T) -> Self {
+    pub fn json_attributes_topic<T: Into<Topic>>(mut self, json_attributes_topic: T) -> Self {
         self.json_attributes_topic = Some(json_attributes_topic.into());
         self
     }
@@ -315,7 +360,7 @@ impl Camera {
     }
 
     /// The MQTT topic to subscribe to.
-    pub fn topic<T: Into<String>>(mut self, topic: T) -> Self {
+    pub fn topic<T: Into<Topic>>(mut self, topic: T) -> Self {
         self.topic = topic.into();
         self
     }
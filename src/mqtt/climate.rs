@@ -1,9 +1,12 @@
 use super::common::Qos;
 use super::common::TemperatureUnit;
 use super::common::{Availability, Device, EntityCategory, Origin};
+use crate::decimal::{DecimalError, IntoDecimal, TryIntoDecimal};
+use crate::topic::Topic;
 use crate::Entity;
+use bon::Builder;
 pub use rust_decimal::Decimal;
-use serde_derive::Serialize;
+use serde_derive::{Deserialize, Serialize};
 
 /// ---
 /// title: "MQTT HVAC"
@@ -481,7 +484,9 @@ use serde_derive::Serialize;
 /// ```
 ///
 /// {% endraw %}
-#[derive(Clone, Debug, PartialEq, Serialize, Default)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default, Builder)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[builder(on(String, into), on(Topic, into))]
 pub struct Climate {
     /// Replaces `~` with this value in any MQTT topic attribute.
     /// [See Home Assistant documentation](https://www.home-assistant.io/integrations/mqtt/#using-abbreviations-and-base-topic)
@@ -489,28 +494,43 @@ pub struct Climate {
     pub topic_prefix: Option<String>,
 
     /// It is encouraged to add additional information about the origin that supplies MQTT entities via MQTT discovery by adding the origin option (can be abbreviated to o) to the discovery payload. Note that these options also support abbreviations. Information of the origin will be logged to the core event log when an item is discovered or updated.
-    #[serde(rename = "o")]
+    #[serde(rename = "o", alias = "origin", default)]
+    #[builder(default)]
     pub origin: Origin,
 
     /// Information about the device this button is a part of to tie it into the [device registry](https://developers.home-assistant.io/docs/en/device_registry_index.html). Only works when [`unique_id`](#unique_id) is set. At least one of identifiers or connections must be present to identify the device.
-    #[serde(rename = "dev")]
+    #[serde(rename = "dev", alias = "device", default)]
+    #[builder(default)]
     pub device: Device,
 
     /// A list of MQTT topics subscribed to receive availability (online/offline) updates. Must not be used together with `availability_topic`.
     #[serde(flatten)]
+    #[builder(default)]
     pub availability: Availability,
 
     /// The category of the entity. (optional, default: None)
-    #[serde(rename = "ent_cat", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "ent_cat",
+        alias = "entity_category",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub entity_category: Option<EntityCategory>,
 
     /// A template to render the value received on the `action_topic` with.
-    #[serde(rename = "act_tpl", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "act_tpl",
+        alias = "action_template",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub action_template: Option<String>,
 
     /// The MQTT topic to subscribe for changes of the current action. If this is set, the climate graph uses the value received as data source. Valid values: `off`, `heating`, `cooling`, `drying`, `idle`, `fan`.
-    #[serde(rename = "act_t", skip_serializing_if = "Option::is_none")]
-    pub action_topic: Option<String>,
+    #[serde(
+        rename = "act_t",
+        alias = "action_topic",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub action_topic: Option<Topic>,
 
     /// A template with which the value received on `current_humidity_topic` will be rendered.
     #[serde(
@@ -524,91 +544,164 @@ pub struct Climate {
         rename = "current_humidity_topic",
         skip_serializing_if = "Option::is_none"
     )]
-    pub current_humidity_topic: Option<String>,
+    pub current_humidity_topic: Option<Topic>,
 
     /// A template with which the value received on `current_temperature_topic` will be rendered.
-    #[serde(rename = "curr_temp_tpl", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "curr_temp_tpl",
+        alias = "current_temperature_template",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub current_temperature_template: Option<String>,
 
     /// The MQTT topic on which to listen for the current temperature. A `"None"` value received will reset the current temperature. Empty values (`'''`) will be ignored.
-    #[serde(rename = "curr_temp_t", skip_serializing_if = "Option::is_none")]
-    pub current_temperature_topic: Option<String>,
+    #[serde(
+        rename = "curr_temp_t",
+        alias = "current_temperature_topic",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub current_temperature_topic: Option<Topic>,
 
     /// Flag which defines if the entity should be enabled when first added.
-    #[serde(rename = "en", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "en",
+        alias = "enabled_by_default",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub enabled_by_default: Option<bool>,
 
     /// The encoding of the payloads received and published messages. Set to `""` to disable decoding of incoming payload.
-    #[serde(rename = "e", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "e",
+        alias = "encoding",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub encoding: Option<String>,
 
     /// A template to render the value sent to the `fan_mode_command_topic` with.
-    #[serde(rename = "fan_mode_cmd_tpl", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "fan_mode_cmd_tpl",
+        alias = "fan_mode_command_template",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub fan_mode_command_template: Option<String>,
 
     /// The MQTT topic to publish commands to change the fan mode.
-    #[serde(rename = "fan_mode_cmd_t", skip_serializing_if = "Option::is_none")]
-    pub fan_mode_command_topic: Option<String>,
+    #[serde(
+        rename = "fan_mode_cmd_t",
+        alias = "fan_mode_command_topic",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub fan_mode_command_topic: Option<Topic>,
 
     /// A template to render the value received on the `fan_mode_state_topic` with.
-    #[serde(rename = "fan_mode_stat_tpl", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "fan_mode_stat_tpl",
+        alias = "fan_mode_state_template",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub fan_mode_state_template: Option<String>,
 
     /// The MQTT topic to subscribe for changes of the HVAC fan mode. If this is not set, the fan mode works in optimistic mode (see below).
-    #[serde(rename = "fan_mode_stat_t", skip_serializing_if = "Option::is_none")]
-    pub fan_mode_state_topic: Option<String>,
+    #[serde(
+        rename = "fan_mode_stat_t",
+        alias = "fan_mode_state_topic",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub fan_mode_state_topic: Option<Topic>,
 
     /// A list of supported fan modes.
     #[serde(rename = "fan_modes", skip_serializing_if = "Option::is_none")]
     pub fan_modes: Option<Vec<String>>,
 
     /// Set the initial target temperature. The default value depends on the temperature unit and will be 21° or 69.8°F.
-    #[serde(rename = "init", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "init",
+        alias = "initial",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[cfg_attr(feature = "schema", schemars(with = "Option<f64>"))]
     pub initial: Option<Decimal>,
 
     /// [Icon](/docs/configuration/customizing-devices/#icon) for the entity.
-    #[serde(rename = "ic", skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "ic", alias = "icon", skip_serializing_if = "Option::is_none")]
     pub icon: Option<String>,
 
     /// Defines a [template](/docs/configuration/templating/#using-templates-with-the-mqtt-integration) to extract the JSON dictionary from messages received on the `json_attributes_topic`. Usage example can be found in [MQTT sensor](/integrations/sensor.mqtt/#json-attributes-template-configuration) documentation.
-    #[serde(rename = "json_attr_tpl", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "json_attr_tpl",
+        alias = "json_attributes_template",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub json_attributes_template: Option<String>,
 
     /// The MQTT topic subscribed to receive a JSON dictionary payload and then set as sensor attributes. Usage example can be found in [MQTT sensor](/integrations/sensor.mqtt/#json-attributes-topic-configuration) documentation.
-    #[serde(rename = "json_attr_t", skip_serializing_if = "Option::is_none")]
-    pub json_attributes_topic: Option<String>,
+    #[serde(
+        rename = "json_attr_t",
+        alias = "json_attributes_topic",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub json_attributes_topic: Option<Topic>,
 
     /// The minimum target humidity percentage that can be set.
-    #[serde(rename = "max_hum", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "max_hum",
+        alias = "max_humidity",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[cfg_attr(feature = "schema", schemars(with = "Option<f64>"))]
     pub max_humidity: Option<Decimal>,
 
     /// Maximum set point available. The default value depends on the temperature unit, and will be 35°C or 95°F.
     #[serde(rename = "max_temp", skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "schema", schemars(with = "Option<f64>"))]
     pub max_temp: Option<Decimal>,
 
     /// The maximum target humidity percentage that can be set.
-    #[serde(rename = "min_hum", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "min_hum",
+        alias = "min_humidity",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[cfg_attr(feature = "schema", schemars(with = "Option<f64>"))]
     pub min_humidity: Option<Decimal>,
 
     /// Minimum set point available. The default value depends on the temperature unit, and will be 7°C or 44.6°F.
     #[serde(rename = "min_temp", skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "schema", schemars(with = "Option<f64>"))]
     pub min_temp: Option<Decimal>,
 
     /// A template to render the value sent to the `mode_command_topic` with.
-    #[serde(rename = "mode_cmd_tpl", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "mode_cmd_tpl",
+        alias = "mode_command_template",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub mode_command_template: Option<String>,
 
     /// The MQTT topic to publish commands to change the HVAC operation mode.
-    #[serde(rename = "mode_cmd_t", skip_serializing_if = "Option::is_none")]
-    pub mode_command_topic: Option<String>,
+    #[serde(
+        rename = "mode_cmd_t",
+        alias = "mode_command_topic",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub mode_command_topic: Option<Topic>,
 
     /// A template to render the value received on the `mode_state_topic` with.
-    #[serde(rename = "mode_stat_tpl", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "mode_stat_tpl",
+        alias = "mode_state_template",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub mode_state_template: Option<String>,
 
     /// The MQTT topic to subscribe for changes of the HVAC operation mode. If this is not set, the operation mode works in optimistic mode (see below).
-    #[serde(rename = "mode_stat_t", skip_serializing_if = "Option::is_none")]
-    pub mode_state_topic: Option<String>,
+    #[serde(
+        rename = "mode_stat_t",
+        alias = "mode_state_topic",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub mode_state_topic: Option<Topic>,
 
     /// A list of supported modes. Needs to be a subset of the default values.
     #[serde(rename = "modes", skip_serializing_if = "Option::is_none")]
@@ -619,19 +712,35 @@ pub struct Climate {
     pub name: Option<String>,
 
     /// Used instead of `name` for automatic generation of `entity_id`
-    #[serde(rename = "obj_id", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "obj_id",
+        alias = "object_id",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub object_id: Option<String>,
 
     /// Flag that defines if the climate works in optimistic mode
-    #[serde(rename = "opt", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "opt",
+        alias = "optimistic",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub optimistic: Option<bool>,
 
     /// The payload sent to turn off the device.
-    #[serde(rename = "pl_off", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "pl_off",
+        alias = "payload_off",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub payload_off: Option<String>,
 
     /// The payload sent to turn the device on.
-    #[serde(rename = "pl_on", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "pl_on",
+        alias = "payload_on",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub payload_on: Option<String>,
 
     /// A template to render the value sent to the `power_command_topic` with. The `value` parameter is the payload set for `payload_on` or `payload_off`.
@@ -646,30 +755,51 @@ pub struct Climate {
         rename = "power_command_topic",
         skip_serializing_if = "Option::is_none"
     )]
-    pub power_command_topic: Option<String>,
+    pub power_command_topic: Option<Topic>,
 
     /// The desired precision for this device. Can be used to match your actual thermostat's precision. Supported values are `0.1`, `0.5` and `1.0`.
     #[serde(rename = "precision", skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "schema", schemars(with = "Option<f64>"))]
     pub precision: Option<Decimal>,
 
     /// Defines a [template](/docs/configuration/templating/#using-templates-with-the-mqtt-integration) to generate the payload to send to `preset_mode_command_topic`.
-    #[serde(rename = "pr_mode_cmd_tpl", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "pr_mode_cmd_tpl",
+        alias = "preset_mode_command_template",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub preset_mode_command_template: Option<String>,
 
     /// The MQTT topic to publish commands to change the preset mode.
-    #[serde(rename = "pr_mode_cmd_t", skip_serializing_if = "Option::is_none")]
-    pub preset_mode_command_topic: Option<String>,
+    #[serde(
+        rename = "pr_mode_cmd_t",
+        alias = "preset_mode_command_topic",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub preset_mode_command_topic: Option<Topic>,
 
     /// The MQTT topic subscribed to receive climate speed based on presets. When preset 'none' is received or `None` the `preset_mode` will be reset.
-    #[serde(rename = "pr_mode_stat_t", skip_serializing_if = "Option::is_none")]
-    pub preset_mode_state_topic: Option<String>,
+    #[serde(
+        rename = "pr_mode_stat_t",
+        alias = "preset_mode_state_topic",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub preset_mode_state_topic: Option<Topic>,
 
     /// Defines a [template](/docs/configuration/templating/#using-templates-with-the-mqtt-integration) to extract the `preset_mode` value from the payload received on `preset_mode_state_topic`.
-    #[serde(rename = "pr_mode_val_tpl", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "pr_mode_val_tpl",
+        alias = "preset_mode_value_template",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub preset_mode_value_template: Option<String>,
 
     /// List of preset modes this climate is supporting. Common examples include `eco`, `away`, `boost`, `comfort`, `home`, `sleep` and `activity`.
-    #[serde(rename = "pr_modes", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "pr_modes",
+        alias = "preset_modes",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub preset_modes: Option<Vec<String>>,
 
     /// The maximum QoS level to be used when receiving and publishing messages.
@@ -677,16 +807,28 @@ pub struct Climate {
     pub qos: Option<Qos>,
 
     /// Defines if published messages should have the retain flag set.
-    #[serde(rename = "ret", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "ret",
+        alias = "retain",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub retain: Option<bool>,
 
     /// A template to render the value sent to the `swing_mode_command_topic` with.
-    #[serde(rename = "swing_mode_cmd_tpl", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "swing_mode_cmd_tpl",
+        alias = "swing_mode_command_template",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub swing_mode_command_template: Option<String>,
 
     /// The MQTT topic to publish commands to change the swing mode.
-    #[serde(rename = "swing_mode_cmd_t", skip_serializing_if = "Option::is_none")]
-    pub swing_mode_command_topic: Option<String>,
+    #[serde(
+        rename = "swing_mode_cmd_t",
+        alias = "swing_mode_command_topic",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub swing_mode_command_topic: Option<Topic>,
 
     /// A template to render the value received on the `swing_mode_state_topic` with.
     #[serde(
@@ -696,91 +838,172 @@ pub struct Climate {
     pub swing_mode_state_template: Option<String>,
 
     /// The MQTT topic to subscribe for changes of the HVAC swing mode. If this is not set, the swing mode works in optimistic mode (see below).
-    #[serde(rename = "swing_mode_stat_t", skip_serializing_if = "Option::is_none")]
-    pub swing_mode_state_topic: Option<String>,
+    #[serde(
+        rename = "swing_mode_stat_t",
+        alias = "swing_mode_state_topic",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub swing_mode_state_topic: Option<Topic>,
 
     /// A list of supported swing modes.
     #[serde(rename = "swing_modes", skip_serializing_if = "Option::is_none")]
     pub swing_modes: Option<Vec<String>>,
 
     /// Defines a [template](/docs/configuration/templating/#using-templates-with-the-mqtt-integration) to generate the payload to send to `target_humidity_command_topic`.
-    #[serde(rename = "hum_cmd_tpl", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "hum_cmd_tpl",
+        alias = "target_humidity_command_template",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub target_humidity_command_template: Option<String>,
 
     /// The MQTT topic to publish commands to change the target humidity.
-    #[serde(rename = "hum_cmd_t", skip_serializing_if = "Option::is_none")]
-    pub target_humidity_command_topic: Option<String>,
+    #[serde(
+        rename = "hum_cmd_t",
+        alias = "target_humidity_command_topic",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub target_humidity_command_topic: Option<Topic>,
 
     /// The MQTT topic subscribed to receive the target humidity. If this is not set, the target humidity works in optimistic mode (see below). A `"None"` value received will reset the target humidity. Empty values (`'''`) will be ignored.
-    #[serde(rename = "hum_stat_t", skip_serializing_if = "Option::is_none")]
-    pub target_humidity_state_topic: Option<String>,
+    #[serde(
+        rename = "hum_stat_t",
+        alias = "target_humidity_state_topic",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub target_humidity_state_topic: Option<Topic>,
 
     /// Defines a [template](/docs/configuration/templating/#using-templates-with-the-mqtt-integration) to extract a value for the climate `target_humidity` state.
-    #[serde(rename = "hum_state_tpl", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "hum_state_tpl",
+        alias = "target_humidity_state_template",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub target_humidity_state_template: Option<String>,
 
     /// A template to render the value sent to the `temperature_command_topic` with.
-    #[serde(rename = "temp_cmd_tpl", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "temp_cmd_tpl",
+        alias = "temperature_command_template",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub temperature_command_template: Option<String>,
 
     /// The MQTT topic to publish commands to change the target temperature.
-    #[serde(rename = "temp_cmd_t", skip_serializing_if = "Option::is_none")]
-    pub temperature_command_topic: Option<String>,
+    #[serde(
+        rename = "temp_cmd_t",
+        alias = "temperature_command_topic",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub temperature_command_topic: Option<Topic>,
 
     /// A template to render the value sent to the `temperature_high_command_topic` with.
-    #[serde(rename = "temp_hi_cmd_tpl", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "temp_hi_cmd_tpl",
+        alias = "temperature_high_command_template",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub temperature_high_command_template: Option<String>,
 
     /// The MQTT topic to publish commands to change the high target temperature.
-    #[serde(rename = "temp_hi_cmd_t", skip_serializing_if = "Option::is_none")]
-    pub temperature_high_command_topic: Option<String>,
+    #[serde(
+        rename = "temp_hi_cmd_t",
+        alias = "temperature_high_command_topic",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub temperature_high_command_topic: Option<Topic>,
 
     /// A template to render the value received on the `temperature_high_state_topic` with. A `"None"` value received will reset the temperature high set point. Empty values (`'''`) will be ignored.
-    #[serde(rename = "temp_hi_stat_tpl", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "temp_hi_stat_tpl",
+        alias = "temperature_high_state_template",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub temperature_high_state_template: Option<String>,
 
     /// The MQTT topic to subscribe for changes in the target high temperature. If this is not set, the target high temperature works in optimistic mode (see below).
-    #[serde(rename = "temp_hi_stat_t", skip_serializing_if = "Option::is_none")]
-    pub temperature_high_state_topic: Option<String>,
+    #[serde(
+        rename = "temp_hi_stat_t",
+        alias = "temperature_high_state_topic",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub temperature_high_state_topic: Option<Topic>,
 
     /// A template to render the value sent to the `temperature_low_command_topic` with.
-    #[serde(rename = "temp_lo_cmd_tpl", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "temp_lo_cmd_tpl",
+        alias = "temperature_low_command_template",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub temperature_low_command_template: Option<String>,
 
     /// The MQTT topic to publish commands to change the target low temperature.
-    #[serde(rename = "temp_lo_cmd_t", skip_serializing_if = "Option::is_none")]
-    pub temperature_low_command_topic: Option<String>,
+    #[serde(
+        rename = "temp_lo_cmd_t",
+        alias = "temperature_low_command_topic",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub temperature_low_command_topic: Option<Topic>,
 
     /// A template to render the value received on the `temperature_low_state_topic` with. A `"None"` value received will reset the temperature low set point. Empty values (`'''`) will be ignored.
-    #[serde(rename = "temp_lo_stat_tpl", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "temp_lo_stat_tpl",
+        alias = "temperature_low_state_template",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub temperature_low_state_template: Option<String>,
 
     /// The MQTT topic to subscribe for changes in the target low temperature. If this is not set, the target low temperature works in optimistic mode (see below).
-    #[serde(rename = "temp_lo_stat_t", skip_serializing_if = "Option::is_none")]
-    pub temperature_low_state_topic: Option<String>,
+    #[serde(
+        rename = "temp_lo_stat_t",
+        alias = "temperature_low_state_topic",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub temperature_low_state_topic: Option<Topic>,
 
     /// A template to render the value received on the `temperature_state_topic` with.
-    #[serde(rename = "temp_stat_tpl", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "temp_stat_tpl",
+        alias = "temperature_state_template",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub temperature_state_template: Option<String>,
 
     /// The MQTT topic to subscribe for changes in the target temperature. If this is not set, the target temperature works in optimistic mode (see below). A `"None"` value received will reset the temperature set point. Empty values (`'''`) will be ignored.
-    #[serde(rename = "temp_stat_t", skip_serializing_if = "Option::is_none")]
-    pub temperature_state_topic: Option<String>,
+    #[serde(
+        rename = "temp_stat_t",
+        alias = "temperature_state_topic",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub temperature_state_topic: Option<Topic>,
 
     /// Defines the temperature unit of the device, `C` or `F`. If this is not set, the temperature unit is set to the system temperature unit.
-    #[serde(rename = "temp_unit", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "temp_unit",
+        alias = "temperature_unit",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub temperature_unit: Option<TemperatureUnit>,
 
     /// Step size for temperature set point.
     #[serde(rename = "temp_step", skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "schema", schemars(with = "Option<f64>"))]
     pub temp_step: Option<Decimal>,
 
     /// An ID that uniquely identifies this HVAC device. If two HVAC devices have the same unique ID, Home Assistant will raise an exception.
-    #[serde(rename = "uniq_id", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "uniq_id",
+        alias = "unique_id",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub unique_id: Option<String>,
 
     /// Default template to render the payloads on *all* `*_state_topic`s with.
-    #[serde(rename = "val_tpl", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "val_tpl",
+        alias = "value_template",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub value_template: Option<String>,
 }
 
@@ -823,7 +1046,7 @@ impl Climate {
     }
 
     /// The MQTT topic to subscribe for changes of the current action. If this is set, the climate graph uses the value received as data source. Valid values: `off`, `heating`, `cooling`, `drying`, `idle`, `fan`.
-    pub fn action_topic<T: Into<String>>(mut self, action_topic: T) -> Self {
+    pub fn action_topic<T: Into<Topic>>(mut self, action_topic: T) -> Self {
         self.action_topic = Some(action_topic.into());
         self
     }
@@ -838,7 +1061,7 @@ impl Climate {
     }
 
     /// The MQTT topic on which to listen for the current humidity. A `"None"` value received will reset the current humidity. Empty values (`'''`) will be ignored.
-    pub fn current_humidity_topic<T: Into<String>>(mut self, current_humidity_topic: T) -> Self {
+    pub fn current_humidity_topic<T: Into<Topic>>(mut self, current_humidity_topic: T) -> Self {
         self.current_humidity_topic = Some(current_humidity_topic.into());
         self
     }
@@ -853,7 +1076,7 @@ impl Climate {
     }
 
     /// The MQTT topic on which to listen for the current temperature. A `"None"` value received will reset the current temperature. Empty values (`'''`) will be ignored.
-    pub fn current_temperature_topic<T: Into<String>>(
+    pub fn current_temperature_topic<T: Into<Topic>>(
         mut self,
         current_temperature_topic: T,
     ) -> Self {
@@ -883,7 +1106,7 @@ impl Climate {
     }
 
     /// The MQTT topic to publish commands to change the fan mode.
-    pub fn fan_mode_command_topic<T: Into<String>>(mut self, fan_mode_command_topic: T) -> Self {
+    pub fn fan_mode_command_topic<T: Into<Topic>>(mut self, fan_mode_command_topic: T) -> Self {
         self.fan_mode_command_topic = Some(fan_mode_command_topic.into());
         self
     }
@@ -895,7 +1118,7 @@ impl Climate {
     }
 
     /// The MQTT topic to subscribe for changes of the HVAC fan mode. If this is not set, the fan mode works in optimistic mode (see below).
-    pub fn fan_mode_state_topic<T: Into<String>>(mut self, fan_mode_state_topic: T) -> Self {
+    pub fn fan_mode_state_topic<T: Into<Topic>>(mut self, fan_mode_state_topic: T) -> Self {
         self.fan_mode_state_topic = Some(fan_mode_state_topic.into());
         self
     }
@@ -907,11 +1130,16 @@ impl Climate {
     }
 
     /// Set the initial target temperature. The default value depends on the temperature unit and will be 21° or 69.8°F.
-    pub fn initial(mut self, initial: Decimal) -> Self {
-        self.initial = Some(initial);
+    pub fn initial<D: IntoDecimal>(mut self, initial: D) -> Self {
+        self.initial = Some(initial.into_decimal());
         self
     }
 
+    /// Same as [`Self::initial`] for the floats, fails when the value is NaN, infinite or out of the `Decimal` range.
+    pub fn try_initial<D: TryIntoDecimal>(self, initial: D) -> Result<Self, DecimalError> {
+        Ok(self.initial(initial.try_into_decimal()?))
+    }
+
     /// [Icon](/docs/configuration/customizing-devices/#icon) for the entity.
     pub fn icon<T: Into<String>>(mut self, icon: T) -> Self {
         self.icon = Some(icon.into());
@@ -928,35 +1156,61 @@ impl Climate {
     }
 
     /// The MQTT topic subscribed to receive a JSON dictionary payload and then set as sensor attributes. Usage example can be found in [MQTT sensor](/integrations/sensor.mqtt/#json-attributes-topic-configuration) documentation.
-    pub fn json_attributes_topic<T: Into<String>>(mut self, json_attributes_topic: T) -> Self {
+    pub fn json_attributes_topic<T: Into<Topic>>(mut self, json_attributes_topic: T) -> Self {
         self.json_attributes_topic = Some(json_attributes_topic.into());
         self
     }
 
     /// The minimum target humidity percentage that can be set.
-    pub fn max_humidity(mut self, max_humidity: Decimal) -> Self {
-        self.max_humidity = Some(max_humidity);
+    pub fn max_humidity<D: IntoDecimal>(mut self, max_humidity: D) -> Self {
+        self.max_humidity = Some(max_humidity.into_decimal());
         self
     }
 
+    /// Same as [`Self::max_humidity`] for the floats, fails when the value is NaN, infinite or out of the `Decimal` range.
+    pub fn try_max_humidity<D: TryIntoDecimal>(
+        self,
+        max_humidity: D,
+    ) -> Result<Self, DecimalError> {
+        Ok(self.max_humidity(max_humidity.try_into_decimal()?))
+    }
+
     /// Maximum set point available. The default value depends on the temperature unit, and will be 35°C or 95°F.
-    pub fn max_temp(mut self, max_temp: Decimal) -> Self {
-        self.max_temp = Some(max_temp);
+    pub fn max_temp<D: IntoDecimal>(mut self, max_temp: D) -> Self {
+        self.max_temp = Some(max_temp.into_decimal());
         self
     }
 
+    /// Same as [`Self::max_temp`] for the floats, fails when the value is NaN, infinite or out of the `Decimal` range.
+    pub fn try_max_temp<D: TryIntoDecimal>(self, max_temp: D) -> Result<Self, DecimalError> {
+        Ok(self.max_temp(max_temp.try_into_decimal()?))
+    }
+
     /// The maximum target humidity percentage that can be set.
-    pub fn min_humidity(mut self, min_humidity: Decimal) -> Self {
-        self.min_humidity = Some(min_humidity);
+    pub fn min_humidity<D: IntoDecimal>(mut self, min_humidity: D) -> Self {
+        self.min_humidity = Some(min_humidity.into_decimal());
         self
     }
 
+    /// Same as [`Self::min_humidity`] for the floats, fails when the value is NaN, infinite or out of the `Decimal` range.
+    pub fn try_min_humidity<D: TryIntoDecimal>(
+        self,
+        min_humidity: D,
+    ) -> Result<Self, DecimalError> {
+        Ok(self.min_humidity(min_humidity.try_into_decimal()?))
+    }
+
     /// Minimum set point available. The default value depends on the temperature unit, and will be 7°C or 44.6°F.
-    pub fn min_temp(mut self, min_temp: Decimal) -> Self {
-        self.min_temp = Some(min_temp);
+    pub fn min_temp<D: IntoDecimal>(mut self, min_temp: D) -> Self {
+        self.min_temp = Some(min_temp.into_decimal());
         self
     }
 
+    /// Same as [`Self::min_temp`] for the floats, fails when the value is NaN, infinite or out of the `Decimal` range.
+    pub fn try_min_temp<D: TryIntoDecimal>(self, min_temp: D) -> Result<Self, DecimalError> {
+        Ok(self.min_temp(min_temp.try_into_decimal()?))
+    }
+
     /// A template to render the value sent to the `mode_command_topic` with.
     pub fn mode_command_template<T: Into<String>>(mut self, mode_command_template: T) -> Self {
         self.mode_command_template = Some(mode_command_template.into());
@@ -964,7 +1218,7 @@ impl Climate {
     }
 
     /// The MQTT topic to publish commands to change the HVAC operation mode.
-    pub fn mode_command_topic<T: Into<String>>(mut self, mode_command_topic: T) -> Self {
+    pub fn mode_command_topic<T: Into<Topic>>(mut self, mode_command_topic: T) -> Self {
         self.mode_command_topic = Some(mode_command_topic.into());
         self
     }
@@ -976,7 +1230,7 @@ impl Climate {
     }
 
     /// The MQTT topic to subscribe for changes of the HVAC operation mode. If this is not set, the operation mode works in optimistic mode (see below).
-    pub fn mode_state_topic<T: Into<String>>(mut self, mode_state_topic: T) -> Self {
+    pub fn mode_state_topic<T: Into<Topic>>(mut self, mode_state_topic: T) -> Self {
         self.mode_state_topic = Some(mode_state_topic.into());
         self
     }
@@ -1024,17 +1278,22 @@ impl Climate {
     }
 
     /// The MQTT topic to publish commands to change the HVAC power state. Sends the payload configured with `payload_on` if the climate is turned on via the `climate.turn_on`, or the payload configured with `payload_off` if the climate is turned off via the `climate.turn_off` service. Note that `optimistic` mode is not supported through `climate.turn_on` and `climate.turn_off` services. When called, these services will send a power command to the device but will not optimistically update the state of the climate entity. The climate device should report its state back via `mode_state_topic`.
-    pub fn power_command_topic<T: Into<String>>(mut self, power_command_topic: T) -> Self {
+    pub fn power_command_topic<T: Into<Topic>>(mut self, power_command_topic: T) -> Self {
         self.power_command_topic = Some(power_command_topic.into());
         self
     }
 
     /// The desired precision for this device. Can be used to match your actual thermostat's precision. Supported values are `0.1`, `0.5` and `1.0`.
-    pub fn precision(mut self, precision: Decimal) -> Self {
-        self.precision = Some(precision);
+    pub fn precision<D: IntoDecimal>(mut self, precision: D) -> Self {
+        self.precision = Some(precision.into_decimal());
         self
     }
 
+    /// Same as [`Self::precision`] for the floats, fails when the value is NaN, infinite or out of the `Decimal` range.
+    pub fn try_precision<D: TryIntoDecimal>(self, precision: D) -> Result<Self, DecimalError> {
+        Ok(self.precision(precision.try_into_decimal()?))
+    }
+
     /// Defines a [template](/docs/configuration/templating/#using-templates-with-the-mqtt-integration) to generate the payload to send to `preset_mode_command_topic`.
     pub fn preset_mode_command_template<T: Into<String>>(
         mut self,
@@ -1045,7 +1304,7 @@ impl Climate {
     }
 
     /// The MQTT topic to publish commands to change the preset mode.
-    pub fn preset_mode_command_topic<T: Into<String>>(
+    pub fn preset_mode_command_topic<T: Into<Topic>>(
         mut self,
         preset_mode_command_topic: T,
     ) -> Self {
@@ -1054,7 +1313,7 @@ impl Climate {
     }
 
     /// The MQTT topic subscribed to receive climate speed based on presets. When preset 'none' is received or `None` the `preset_mode` will be reset.
-    pub fn preset_mode_state_topic<T: Into<String>>(mut self, preset_mode_state_topic: T) -> Self {
+    pub fn preset_mode_state_topic<T: Into<Topic>>(mut self, preset_mode_state_topic: T) -> Self {
         self.preset_mode_state_topic = Some(preset_mode_state_topic.into());
         self
     }
@@ -1096,10 +1355,7 @@ impl Climate {
     }
 
     /// The MQTT topic to publish commands to change the swing mode.
-    pub fn swing_mode_command_topic<T: Into<String>>(
-        mut self,
-        swing_mode_command_topic: T,
-    ) -> Self {
+    pub fn swing_mode_command_topic<T: Into<Topic>>(mut self, swing_mode_command_topic: T) -> Self {
         self.swing_mode_command_topic = Some(swing_mode_command_topic.into());
         self
     }
@@ -1114,7 +1370,7 @@ impl Climate {
     }
 
     /// The MQTT topic to subscribe for changes of the HVAC swing mode. If this is not set, the swing mode works in optimistic mode (see below).
-    pub fn swing_mode_state_topic<T: Into<String>>(mut self, swing_mode_state_topic: T) -> Self {
+    pub fn swing_mode_state_topic<T: Into<Topic>>(mut self, swing_mode_state_topic: T) -> Self {
         self.swing_mode_state_topic = Some(swing_mode_state_topic.into());
         self
     }
@@ -1135,7 +1391,7 @@ impl Climate {
     }
 
     /// The MQTT topic to publish commands to change the target humidity.
-    pub fn target_humidity_command_topic<T: Into<String>>(
+    pub fn target_humidity_command_topic<T: Into<Topic>>(
         mut self,
         target_humidity_command_topic: T,
     ) -> Self {
@@ -1144,7 +1400,7 @@ impl Climate {
     }
 
     /// The MQTT topic subscribed to receive the target humidity. If this is not set, the target humidity works in optimistic mode (see below). A `"None"` value received will reset the target humidity. Empty values (`'''`) will be ignored.
-    pub fn target_humidity_state_topic<T: Into<String>>(
+    pub fn target_humidity_state_topic<T: Into<Topic>>(
         mut self,
         target_humidity_state_topic: T,
     ) -> Self {
@@ -1171,7 +1427,7 @@ impl Climate {
     }
 
     /// The MQTT topic to publish commands to change the target temperature.
-    pub fn temperature_command_topic<T: Into<String>>(
+    pub fn temperature_command_topic<T: Into<Topic>>(
         mut self,
         temperature_command_topic: T,
     ) -> Self {
@@ -1189,7 +1445,7 @@ impl Climate {
     }
 
     /// The MQTT topic to publish commands to change the high target temperature.
-    pub fn temperature_high_command_topic<T: Into<String>>(
+    pub fn temperature_high_command_topic<T: Into<Topic>>(
         mut self,
         temperature_high_command_topic: T,
     ) -> Self {
@@ -1207,7 +1463,7 @@ impl Climate {
     }
 
     /// The MQTT topic to subscribe for changes in the target high temperature. If this is not set, the target high temperature works in optimistic mode (see below).
-    pub fn temperature_high_state_topic<T: Into<String>>(
+    pub fn temperature_high_state_topic<T: Into<Topic>>(
         mut self,
         temperature_high_state_topic: T,
     ) -> Self {
@@ -1225,7 +1481,7 @@ impl Climate {
     }
 
     /// The MQTT topic to publish commands to change the target low temperature.
-    pub fn temperature_low_command_topic<T: Into<String>>(
+    pub fn temperature_low_command_topic<T: Into<Topic>>(
         mut self,
         temperature_low_command_topic: T,
     ) -> Self {
@@ -1243,7 +1499,7 @@ impl Climate {
     }
 
     /// The MQTT topic to subscribe for changes in the target low temperature. If this is not set, the target low temperature works in optimistic mode (see below).
-    pub fn temperature_low_state_topic<T: Into<String>>(
+    pub fn temperature_low_state_topic<T: Into<Topic>>(
         mut self,
         temperature_low_state_topic: T,
     ) -> Self {
@@ -1261,7 +1517,7 @@ impl Climate {
     }
 
     /// The MQTT topic to subscribe for changes in the target temperature. If this is not set, the target temperature works in optimistic mode (see below). A `"None"` value received will reset the temperature set point. Empty values (`'''`) will be ignored.
-    pub fn temperature_state_topic<T: Into<String>>(mut self, temperature_state_topic: T) -> Self {
+    pub fn temperature_state_topic<T: Into<Topic>>(mut self, temperature_state_topic: T) -> Self {
         self.temperature_state_topic = Some(temperature_state_topic.into());
         self
     }
@@ -1273,11 +1529,16 @@ impl Climate {
     }
 
     /// Step size for temperature set point.
-    pub fn temp_step(mut self, temp_step: Decimal) -> Self {
-        self.temp_step = Some(temp_step);
+    pub fn temp_step<D: IntoDecimal>(mut self, temp_step: D) -> Self {
+        self.temp_step = Some(temp_step.into_decimal());
         self
     }
 
+    /// Same as [`Self::temp_step`] for the floats, fails when the value is NaN, infinite or out of the `Decimal` range.
+    pub fn try_temp_step<D: TryIntoDecimal>(self, temp_step: D) -> Result<Self, DecimalError> {
+        Ok(self.temp_step(temp_step.try_into_decimal()?))
+    }
+
     /// An ID that uniquely identifies this HVAC device. If two HVAC devices have the same unique ID, Home Assistant will raise an exception.
     pub fn unique_id<T: Into<String>>(mut self, unique_id: T) -> Self {
         self.unique_id = Some(unique_id.into());
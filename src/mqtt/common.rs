@@ -1,9 +1,14 @@
-use serde::ser::SerializeSeq;
-use serde_derive::Serialize;
+#[cfg(feature = "client")]
+use rumqttc::v5::mqttbytes::QoS;
+use serde::{ser::SerializeSeq, Deserializer};
+use serde_derive::{Deserialize, Serialize};
+
+use crate::validation::ValidationError;
 
 /// Classification of a non-primary entity.
 #[allow(dead_code)]
-#[derive(Clone, Debug, PartialEq, Serialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum EntityCategory {
     /// The entity allows changing the configuration of a device,
     /// for example a switch entity making it possible to turn the background illumination of a switch on and off.
@@ -17,13 +22,19 @@ pub enum EntityCategory {
 }
 
 /// It is encouraged to add additional information about the origin that supplies MQTT entities via MQTT discovery by adding the origin option (can be abbreviated to o) to the discovery payload. Note that these options also support abbreviations. Information of the origin will be logged to the core event log when an item is discovered or updated.
-#[derive(Clone, Debug, PartialEq, Serialize, Default)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default)]
 pub struct Origin {
     /// The name of the application that is the origin the discovered MQTT item. This option is required.
     #[serde(rename = "name")]
     pub name: String,
     /// Software version of the application that supplies the discovered MQTT item.
-    #[serde(rename = "sw", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "sw",
+        alias = "sw_version",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub sw_version: Option<String>,
     /// Support URL of the application that supplies the discovered MQTT item.
     #[serde(rename = "support_url", skip_serializing_if = "Option::is_none")]
@@ -53,34 +64,75 @@ impl Origin {
 }
 
 /// Information about the device this sensor is a part of to tie it into the [device registry](https://developers.home-assistant.io/docs/device_registry_index/). Only works when `unique_id` is set. At least one of identifiers or connections must be present to identify the device.
-#[derive(Clone, Debug, PartialEq, Serialize, Default)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default)]
 pub struct Device {
     /// The name of the device.
     #[serde(rename = "name", skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
     /// A list of IDs that uniquely identify the device. For example a serial number.
-    #[serde(rename = "ids", skip_serializing_if = "Vec::is_empty")]
+    #[serde(
+        rename = "ids",
+        alias = "identifiers",
+        skip_serializing_if = "Vec::is_empty"
+    )]
     pub identifiers: Vec<String>,
     /// A list of connections of the device to the outside world as a list of tuples `[connection_type, connection_identifier]`. For example the MAC address of a network interface: `"connections": [["mac", "02:5b:26:a8:dc:12"]]`.
-    #[serde(rename = "cns", skip_serializing_if = "Vec::is_empty")]
+    #[serde(
+        rename = "cns",
+        alias = "connections",
+        skip_serializing_if = "Vec::is_empty"
+    )]
     pub connections: Vec<DeviceConnection>,
     /// A link to the webpage that can manage the configuration of this device. Can be either an `http://`, `https://` or an internal `homeassistant://` URL.
-    #[serde(rename = "cu", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "cu",
+        alias = "configuration_url",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub configuration_url: Option<String>,
     /// The manufacturer of the device.
-    #[serde(rename = "mf", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "mf",
+        alias = "manufacturer",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub manufacturer: Option<String>,
     /// The model of the device.
-    #[serde(rename = "mdl", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "mdl",
+        alias = "model",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub model: Option<String>,
     /// Suggest an area if the device isn’t in one yet.
-    #[serde(rename = "sa", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "sa",
+        alias = "suggested_area",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub suggested_area: Option<String>,
     /// The firmware version of the device.
-    #[serde(rename = "sw", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "sw",
+        alias = "sw_version",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub sw_version: Option<String>,
+    /// The serial number of the device.
+    #[serde(
+        rename = "sn",
+        alias = "serial_number",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub serial_number: Option<String>,
     /// The hardware version of the device.
-    #[serde(rename = "hw", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "hw",
+        alias = "hw_version",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub hw_version: Option<String>,
     /// Identifier of a device that routes messages between this device and Home Assistant. Examples of such devices are hubs, or parent devices of a sub-device. This is used to show device topology in Home Assistant.
     #[serde(rename = "via_device", skip_serializing_if = "Option::is_none")]
@@ -100,7 +152,7 @@ impl Device {
         self
     }
 
-    /// Add an ID that uniquely identify the device. For example a serial number.
+    /// Add a connection of the device to the outside world, eg. `DeviceConnection::mac("02:5b:26:a8:dc:12")`.
     pub fn add_connection(mut self, connection: DeviceConnection) -> Self {
         self.connections.push(connection);
         self
@@ -136,6 +188,12 @@ impl Device {
         self
     }
 
+    /// The serial number of the device.
+    pub fn serial_number<S: Into<String>>(mut self, serial_number: S) -> Self {
+        self.serial_number = Some(serial_number.into());
+        self
+    }
+
     /// The hardware version of the device.
     pub fn hw_version<S: Into<String>>(mut self, hw_version: S) -> Self {
         self.hw_version = Some(hw_version.into());
@@ -147,6 +205,20 @@ impl Device {
         self.via_device = Some(via_device.into());
         self
     }
+
+    /// Whether the device has at least one identifier or connection, Home Assistant ignores the other devices.
+    pub fn is_identified(&self) -> bool {
+        !self.identifiers.is_empty() || !self.connections.is_empty()
+    }
+
+    /// Checks the device has at least one identifier or connection.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        if self.is_identified() {
+            Ok(())
+        } else {
+            Err(ValidationError::MissingDeviceIdentifiers)
+        }
+    }
 }
 
 /// A tuple `[connection_type, connection_identifier]`.
@@ -158,12 +230,37 @@ pub struct DeviceConnection {
 }
 
 impl DeviceConnection {
-    pub fn mac<S: Into<String>>(mac_address: S) -> Self {
+    pub fn new<T: Into<String>, S: Into<String>>(r#type: T, identifier: S) -> Self {
         DeviceConnection {
-            r#type: "mac".to_string(),
-            identifier: mac_address.into(),
+            r#type: r#type.into(),
+            identifier: identifier.into(),
         }
     }
+
+    /// The MAC address of a network interface.
+    pub fn mac<S: Into<String>>(mac_address: S) -> Self {
+        Self::new("mac", mac_address)
+    }
+
+    /// The IP address of a network interface.
+    pub fn ip<S: Into<String>>(ip_address: S) -> Self {
+        Self::new("ip", ip_address)
+    }
+
+    /// The UPnP unique device name.
+    pub fn upnp<S: Into<String>>(udn: S) -> Self {
+        Self::new("upnp", udn)
+    }
+
+    /// The IEEE address of a Zigbee device.
+    pub fn zigbee<S: Into<String>>(ieee_address: S) -> Self {
+        Self::new("zigbee", ieee_address)
+    }
+
+    /// The address of a Bluetooth device.
+    pub fn bluetooth<S: Into<String>>(address: S) -> Self {
+        Self::new("bluetooth", address)
+    }
 }
 
 impl serde::ser::Serialize for DeviceConnection {
@@ -178,8 +275,31 @@ impl serde::ser::Serialize for DeviceConnection {
     }
 }
 
+impl<'de> serde::Deserialize<'de> for DeviceConnection {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let (r#type, identifier) =
+            <(String, String) as serde::Deserialize>::deserialize(deserializer)?;
+        Ok(DeviceConnection { r#type, identifier })
+    }
+}
+
+#[cfg(feature = "schema")]
+impl schemars::JsonSchema for DeviceConnection {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "DeviceConnection".into()
+    }
+
+    fn json_schema(generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        <(String, String)>::json_schema(generator)
+    }
+}
+
 #[allow(dead_code)]
-#[derive(Clone, Debug, PartialEq, Serialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum SensorStateClass {
     /// The state represents a measurement in present time, not a historical aggregation such as statistics or a prediction of the future.
     ///
@@ -204,19 +324,121 @@ pub enum SensorStateClass {
     TotalIncreasing,
 }
 
-#[derive(Clone, Debug, PartialEq, Serialize, Default)]
+/// How a number is displayed in the UI.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum NumberMode {
+    /// A box on small screens and a slider otherwise, the default.
+    Auto,
+    Box,
+    Slider,
+}
+
+/// A feature supported by an alarm control panel, all of them are supported by default.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum AlarmFeature {
+    ArmHome,
+    ArmAway,
+    ArmNight,
+    ArmVacation,
+    ArmCustomBypass,
+    Trigger,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default)]
 pub struct Availability {
     /// Controls the conditions needed to set the entity to `available`.
-    #[serde(rename = "avty_mode")]
+    #[serde(rename = "avty_mode", alias = "availability_mode")]
     pub mode: AvailabilityMode,
     /// A list of MQTT topics subscribed to receive availability (online/offline) updates. Must not be used together with `availability_topic`.
-    #[serde(rename = "avty")]
+    #[serde(rename = "avty", alias = "availability")]
     pub availability: Vec<AvailabilityCheck>,
     /// If set, it defines the number of seconds after the sensor’s state expires, if it’s not updated.
     /// After expiry, the sensor’s state becomes unavailable. Default the sensors state never expires.
     /// (optional, default: 0)
-    #[serde(rename = "exp_aft", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "exp_aft",
+        alias = "expire_after",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub expire_after: Option<u64>,
+    /// How the availability is represented in the discovery payloads, the deserialized availability always uses the
+    /// list representation.
+    #[serde(skip)]
+    pub representation: AvailabilityRepresentation,
+}
+
+/// The representation of the availability in the discovery payloads.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum AvailabilityRepresentation {
+    /// The `availability` list of checks and the `availability_mode`.
+    #[default]
+    List,
+    /// The legacy `availability_topic`, `payload_available`, `payload_not_available` and `availability_template`
+    /// attributes, understood by the older consumers. Requires at most one check.
+    SingleTopic,
+}
+
+#[derive(Serialize)]
+struct ListAvailability<'a> {
+    #[serde(rename = "avty_mode")]
+    mode: &'a AvailabilityMode,
+    #[serde(rename = "avty")]
+    availability: &'a [AvailabilityCheck],
+    #[serde(rename = "exp_aft", skip_serializing_if = "Option::is_none")]
+    expire_after: Option<u64>,
+}
+
+#[derive(Serialize)]
+struct SingleTopicAvailability<'a> {
+    #[serde(rename = "avty_t", skip_serializing_if = "Option::is_none")]
+    topic: Option<&'a str>,
+    #[serde(rename = "pl_avail", skip_serializing_if = "Option::is_none")]
+    payload_available: Option<&'a str>,
+    #[serde(rename = "pl_not_avail", skip_serializing_if = "Option::is_none")]
+    payload_not_available: Option<&'a str>,
+    #[serde(rename = "avty_tpl", skip_serializing_if = "Option::is_none")]
+    value_template: Option<&'a str>,
+    #[serde(rename = "exp_aft", skip_serializing_if = "Option::is_none")]
+    expire_after: Option<u64>,
+}
+
+impl serde::Serialize for Availability {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.representation {
+            AvailabilityRepresentation::List => ListAvailability {
+                mode: &self.mode,
+                availability: &self.availability,
+                expire_after: self.expire_after,
+            }
+            .serialize(serializer),
+            AvailabilityRepresentation::SingleTopic => {
+                let check = match self.availability.as_slice() {
+                    [] => None,
+                    [check] => Some(check),
+                    _ => {
+                        return Err(serde::ser::Error::custom(
+                            "a single topic availability can't have several checks",
+                        ))
+                    }
+                };
+                SingleTopicAvailability {
+                    topic: check.map(|check| check.topic.as_str()),
+                    payload_available: check.and_then(|check| check.payload_available.as_deref()),
+                    payload_not_available: check
+                        .and_then(|check| check.payload_not_available.as_deref()),
+                    value_template: check.and_then(|check| check.value_template.as_deref()),
+                    expire_after: self.expire_after,
+                }
+                .serialize(serializer)
+            }
+        }
+    }
 }
 
 #[allow(dead_code)]
@@ -231,40 +453,88 @@ impl Availability {
         })
     }
 
-    /// An availability checker using a single check.
-    pub fn single(availability: AvailabilityCheck) -> Self {
+    /// An availability checker using a single check, or a single topic with the default payloads.
+    ///
+    /// ```
+    /// # use ha_mqtt_discovery::mqtt::common::Availability;
+    /// Availability::single("bridge/availability").payload_available("1").payload_not_available("0");
+    /// ```
+    pub fn single<C: Into<AvailabilityCheck>>(availability: C) -> Self {
+        Self::with_mode(AvailabilityMode::All, [availability])
+    }
+
+    /// An availability checker requiring all the given checks or topics.
+    pub fn all<I, C>(checks: I) -> Self
+    where
+        I: IntoIterator<Item = C>,
+        C: Into<AvailabilityCheck>,
+    {
+        Self::with_mode(AvailabilityMode::All, checks)
+    }
+
+    /// An availability checker requiring any the given checks or topics.
+    pub fn any<I, C>(checks: I) -> Self
+    where
+        I: IntoIterator<Item = C>,
+        C: Into<AvailabilityCheck>,
+    {
+        Self::with_mode(AvailabilityMode::Any, checks)
+    }
+
+    /// An availability checker following the last payload received on any of the given checks or topics.
+    pub fn latest<I, C>(checks: I) -> Self
+    where
+        I: IntoIterator<Item = C>,
+        C: Into<AvailabilityCheck>,
+    {
+        Self::with_mode(AvailabilityMode::Latest, checks)
+    }
+
+    fn with_mode<I, C>(mode: AvailabilityMode, checks: I) -> Self
+    where
+        I: IntoIterator<Item = C>,
+        C: Into<AvailabilityCheck>,
+    {
         Self {
-            mode: AvailabilityMode::All,
-            availability: vec![availability],
+            mode,
+            availability: checks.into_iter().map(Into::into).collect(),
             expire_after: None,
+            representation: AvailabilityRepresentation::List,
         }
     }
 
-    /// An availability checker requiring all the given checks.
-    pub fn all(checks: Vec<AvailabilityCheck>) -> Self {
-        Self {
-            mode: AvailabilityMode::All,
-            availability: checks,
-            expire_after: None,
+    /// Publishes the availability with the legacy single topic attributes, eg. `availability_topic`, instead of the
+    /// `availability` list. The discovery payload can't be serialized when there are several checks.
+    pub fn single_topic_representation(mut self) -> Self {
+        self.representation = AvailabilityRepresentation::SingleTopic;
+        self
+    }
+
+    /// Sets the payload that represents the available state on every check.
+    pub fn payload_available<S: Into<String>>(mut self, payload_available: S) -> Self {
+        let payload_available = payload_available.into();
+        for check in &mut self.availability {
+            check.payload_available = Some(payload_available.clone());
         }
+        self
     }
 
-    /// An availability checker requiring any the given checks.
-    pub fn any(checks: Vec<AvailabilityCheck>) -> Self {
-        Self {
-            mode: AvailabilityMode::Any,
-            availability: checks,
-            expire_after: None,
+    /// Sets the payload that represents the unavailable state on every check.
+    pub fn payload_not_available<S: Into<String>>(mut self, payload_not_available: S) -> Self {
+        let payload_not_available = payload_not_available.into();
+        for check in &mut self.availability {
+            check.payload_not_available = Some(payload_not_available.clone());
         }
+        self
     }
 
-    /// See `AvailabilityCheck::Latest`
-    pub fn latest(checks: Vec<AvailabilityCheck>) -> Self {
-        Self {
-            mode: AvailabilityMode::Latest,
-            availability: checks,
-            expire_after: None,
+    /// Sets the template extracting the availability from the topic on every check.
+    pub fn value_template<S: Into<String>>(mut self, value_template: S) -> Self {
+        let value_template = value_template.into();
+        for check in &mut self.availability {
+            check.value_template = Some(value_template.clone());
         }
+        self
     }
 
     /// Sets the number of seconds after the sensor’s state expires, if it’s not updated. After expiry, the sensor’s state becomes unavailable. Default the sensors state never expires.
@@ -275,7 +545,8 @@ impl Availability {
 }
 
 #[allow(dead_code)]
-#[derive(Clone, Debug, PartialEq, Serialize, Default)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum AvailabilityMode {
     /// `payload_available` must be received on all configured availability topics before the entity is marked as online.
     #[serde(rename = "all")]
@@ -289,19 +560,32 @@ pub enum AvailabilityMode {
     Latest,
 }
 
-#[derive(Clone, Debug, PartialEq, Serialize, Default)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct AvailabilityCheck {
     /// The payload that represents the available state. (optional, default: `online`)
-    #[serde(rename = "pl_avail", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "pl_avail",
+        alias = "payload_available",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub payload_available: Option<String>,
     /// The payload that represents the unavailable state. (optional, default: `offline`)
-    #[serde(rename = "pl_not_avail", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "pl_not_avail",
+        alias = "payload_not_available",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub payload_not_available: Option<String>,
     /// An MQTT topic subscribed to receive availability (online/offline) updates.
-    #[serde(rename = "t")]
+    #[serde(rename = "t", alias = "topic")]
     pub topic: String,
     /// Defines a template to extract device’s availability from the topic. To determine the devices’s availability result of this template will be compared to payload_available and payload_not_available.
-    #[serde(rename = "val_tpl", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "val_tpl",
+        alias = "value_template",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub value_template: Option<String>,
 }
 
@@ -333,9 +617,21 @@ impl AvailabilityCheck {
     }
 }
 
+impl From<&str> for AvailabilityCheck {
+    fn from(topic: &str) -> Self {
+        AvailabilityCheck::topic(topic)
+    }
+}
+
+impl From<String> for AvailabilityCheck {
+    fn from(topic: String) -> Self {
+        AvailabilityCheck::topic(topic)
+    }
+}
+
 /// The maximum QoS level to be used when receiving and publishing messages.
 #[allow(dead_code)]
-#[derive(Clone, Debug, PartialEq, Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
 pub enum Qos {
     /// At most once (QoS 0)
     #[serde(rename = "0")]
@@ -350,8 +646,55 @@ pub enum Qos {
     ExactlyOnce,
 }
 
+/// Accepts the `0`, `1` and `2` levels as numbers, like in `configuration.yaml`, or as strings.
+impl<'de> serde::Deserialize<'de> for Qos {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Level {
+            Number(u8),
+            String(String),
+        }
+        match <Level as serde::Deserialize>::deserialize(deserializer)? {
+            Level::Number(0) => Ok(Qos::AtMostOnce),
+            Level::Number(1) => Ok(Qos::AtLeastOnce),
+            Level::Number(2) => Ok(Qos::ExactlyOnce),
+            Level::String(level) if level == "0" => Ok(Qos::AtMostOnce),
+            Level::String(level) if level == "1" => Ok(Qos::AtLeastOnce),
+            Level::String(level) if level == "2" => Ok(Qos::ExactlyOnce),
+            _ => Err(serde::de::Error::custom("expected a QoS level 0, 1 or 2")),
+        }
+    }
+}
+
+#[cfg(feature = "schema")]
+impl schemars::JsonSchema for Qos {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "Qos".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({ "enum": [0, 1, 2, "0", "1", "2"] })
+    }
+}
+
+#[cfg(feature = "client")]
+impl From<Qos> for QoS {
+    fn from(value: Qos) -> Self {
+        match value {
+            Qos::AtMostOnce => QoS::AtMostOnce,
+            Qos::AtLeastOnce => QoS::AtLeastOnce,
+            Qos::ExactlyOnce => QoS::ExactlyOnce,
+        }
+    }
+}
+
 /// Defines the temperature unit of the device, `C` or `F`. If this is not set, the temperature unit is set to the system temperature unit.
-#[derive(Clone, Debug, PartialEq, Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum TemperatureUnit {
     #[serde(rename = "C")]
     Celcius,
@@ -384,6 +727,85 @@ mod tests {
         );
     }
 
+    #[test]
+    fn can_build_availabilities() {
+        assert_json_eq!(
+            json!({
+              "avty_mode": "all",
+              "avty": [
+                {"t": "bridge/availability", "pl_avail": "1", "pl_not_avail": "0", "val_tpl": "{{ value_json.state }}"}
+              ]
+            }),
+            serde_json::to_value(
+                Availability::single("bridge/availability")
+                    .payload_available("1")
+                    .payload_not_available("0")
+                    .value_template("{{ value_json.state }}")
+            )
+            .unwrap()
+        );
+        assert_json_eq!(
+            json!({
+              "avty_mode": "latest",
+              "avty": [{"t": "bridge/availability"}, {"t": "device/availability"}]
+            }),
+            serde_json::to_value(Availability::latest([
+                "bridge/availability",
+                "device/availability"
+            ]))
+            .unwrap()
+        );
+        assert_eq!(
+            Availability::any(vec![AvailabilityCheck::topic("a")]).mode,
+            AvailabilityMode::Any
+        );
+    }
+
+    #[test]
+    fn can_serialize_single_topic_availabilities() {
+        assert_json_eq!(
+            json!({
+              "avty_t": "bridge/availability",
+              "pl_avail": "1",
+              "pl_not_avail": "0",
+              "exp_aft": 60
+            }),
+            serde_json::to_value(
+                Availability::single("bridge/availability")
+                    .payload_available("1")
+                    .payload_not_available("0")
+                    .expire_after(60)
+                    .single_topic_representation()
+            )
+            .unwrap()
+        );
+        assert_json_eq!(
+            json!({}),
+            serde_json::to_value(Availability::default().single_topic_representation()).unwrap()
+        );
+        assert!(serde_json::to_value(
+            Availability::all(["bridge/availability", "device/availability"])
+                .single_topic_representation()
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn can_validate_devices() {
+        assert_eq!(
+            Device::default().name("Barometer").validate(),
+            Err(ValidationError::MissingDeviceIdentifiers)
+        );
+        let device = Device::default()
+            .add_connection(DeviceConnection::ip("192.168.1.10"))
+            .via_device("hub");
+        assert!(device.validate().is_ok());
+        assert_eq!(
+            serde_json::to_value(&device).unwrap()["cns"],
+            json!([["ip", "192.168.1.10"]])
+        );
+    }
+
     #[test]
     fn can_serialize_device() {
         let device = Device {
@@ -395,6 +817,7 @@ mod tests {
             model: Some("device model".to_string()),
             suggested_area: Some("area".to_string()),
             sw_version: Some("sw_v".to_string()),
+            serial_number: Some("sn".to_string()),
             hw_version: Some("hw_v".to_string()),
             via_device: Some("via".to_string()),
         };
@@ -416,6 +839,7 @@ mod tests {
               "mdl": "device model",
               "sa": "area",
               "sw": "sw_v",
+              "sn": "sn",
               "hw": "hw_v",
               "via_device": "via"
             }),
@@ -1,7 +1,10 @@
 use super::common::Qos;
 use super::common::{Availability, Device, EntityCategory, Origin};
+use super::device_classes::CoverDeviceClass;
+use crate::topic::Topic;
 use crate::Entity;
-use serde_derive::Serialize;
+use bon::Builder;
+use serde_derive::{Deserialize, Serialize};
 
 /// ---
 /// title: "MQTT Cover"
@@ -619,7 +622,9 @@ use serde_derive::Serialize;
 /// mosquitto_pub -h 127.0.0.1 -t home-assistant/cover/set -m "CLOSE"
 /// ```
 ///
-#[derive(Clone, Debug, PartialEq, Serialize, Default)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default, Builder)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[builder(on(String, into), on(Topic, into))]
 pub struct Cover {
     /// Replaces `~` with this value in any MQTT topic attribute.
     /// [See Home Assistant documentation](https://www.home-assistant.io/integrations/mqtt/#using-abbreviations-and-base-topic)
@@ -627,140 +632,255 @@ pub struct Cover {
     pub topic_prefix: Option<String>,
 
     /// It is encouraged to add additional information about the origin that supplies MQTT entities via MQTT discovery by adding the origin option (can be abbreviated to o) to the discovery payload. Note that these options also support abbreviations. Information of the origin will be logged to the core event log when an item is discovered or updated.
-    #[serde(rename = "o")]
+    #[serde(rename = "o", alias = "origin", default)]
+    #[builder(default)]
     pub origin: Origin,
 
     /// Information about the device this button is a part of to tie it into the [device registry](https://developers.home-assistant.io/docs/en/device_registry_index.html). Only works when [`unique_id`](#unique_id) is set. At least one of identifiers or connections must be present to identify the device.
-    #[serde(rename = "dev")]
+    #[serde(rename = "dev", alias = "device", default)]
+    #[builder(default)]
     pub device: Device,
 
     /// A list of MQTT topics subscribed to receive availability (online/offline) updates. Must not be used together with `availability_topic`.
     #[serde(flatten)]
+    #[builder(default)]
     pub availability: Availability,
 
     /// The category of the entity. (optional, default: None)
-    #[serde(rename = "ent_cat", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "ent_cat",
+        alias = "entity_category",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub entity_category: Option<EntityCategory>,
 
     /// The MQTT topic to publish commands to control the cover.
-    #[serde(rename = "cmd_t", skip_serializing_if = "Option::is_none")]
-    pub command_topic: Option<String>,
+    #[serde(
+        rename = "cmd_t",
+        alias = "command_topic",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub command_topic: Option<Topic>,
 
     /// Sets the [class of the device](/integrations/cover/), changing the device state and icon that is displayed on the frontend. The `device_class` can be `null`.
-    #[serde(rename = "dev_cla", skip_serializing_if = "Option::is_none")]
-    pub device_class: Option<String>,
+    #[serde(
+        rename = "dev_cla",
+        alias = "device_class",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub device_class: Option<CoverDeviceClass>,
 
     /// Flag which defines if the entity should be enabled when first added.
-    #[serde(rename = "en", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "en",
+        alias = "enabled_by_default",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub enabled_by_default: Option<bool>,
 
     /// The encoding of the payloads received and published messages. Set to `""` to disable decoding of incoming payload.
-    #[serde(rename = "e", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "e",
+        alias = "encoding",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub encoding: Option<String>,
 
     /// [Icon](/docs/configuration/customizing-devices/#icon) for the entity.
-    #[serde(rename = "ic", skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "ic", alias = "icon", skip_serializing_if = "Option::is_none")]
     pub icon: Option<String>,
 
     /// Defines a [template](/docs/configuration/templating/#using-templates-with-the-mqtt-integration) to extract the JSON dictionary from messages received on the `json_attributes_topic`. Usage example can be found in [MQTT sensor](/integrations/sensor.mqtt/#json-attributes-template-configuration) documentation.
-    #[serde(rename = "json_attr_tpl", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "json_attr_tpl",
+        alias = "json_attributes_template",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub json_attributes_template: Option<String>,
 
     /// The MQTT topic subscribed to receive a JSON dictionary payload and then set as sensor attributes. Usage example can be found in [MQTT sensor](/integrations/sensor.mqtt/#json-attributes-topic-configuration) documentation.
-    #[serde(rename = "json_attr_t", skip_serializing_if = "Option::is_none")]
-    pub json_attributes_topic: Option<String>,
+    #[serde(
+        rename = "json_attr_t",
+        alias = "json_attributes_topic",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub json_attributes_topic: Option<Topic>,
 
     /// The name of the cover. Can be set to `null` if only the device name is relevant.
     #[serde(rename = "name", skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
 
     /// Used instead of `name` for automatic generation of `entity_id`
-    #[serde(rename = "obj_id", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "obj_id",
+        alias = "object_id",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub object_id: Option<String>,
 
     /// Flag that defines if switch works in optimistic mode.
-    #[serde(rename = "opt", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "opt",
+        alias = "optimistic",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub optimistic: Option<bool>,
 
     /// The command payload that closes the cover.
-    #[serde(rename = "pl_cls", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "pl_cls",
+        alias = "payload_close",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub payload_close: Option<String>,
 
     /// The command payload that opens the cover.
-    #[serde(rename = "pl_open", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "pl_open",
+        alias = "payload_open",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub payload_open: Option<String>,
 
     /// The command payload that stops the cover.
-    #[serde(rename = "pl_stop", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "pl_stop",
+        alias = "payload_stop",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub payload_stop: Option<String>,
 
     /// Number which represents closed position.
-    #[serde(rename = "pos_clsd", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "pos_clsd",
+        alias = "position_closed",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub position_closed: Option<i32>,
 
     /// Number which represents open position.
-    #[serde(rename = "pos_open", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "pos_open",
+        alias = "position_open",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub position_open: Option<i32>,
 
     /// Defines a [template](/docs/configuration/templating/#using-templates-with-the-mqtt-integration) that can be used to extract the payload for the `position_topic` topic. Within the template the following variables are available: `entity_id`, `position_open`; `position_closed`; `tilt_min`; `tilt_max`. The `entity_id` can be used to reference the entity's attributes with help of the [states](/docs/configuration/templating/#states) template function;
-    #[serde(rename = "pos_tpl", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "pos_tpl",
+        alias = "position_template",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub position_template: Option<String>,
 
     /// The MQTT topic subscribed to receive cover position messages.
-    #[serde(rename = "pos_t", skip_serializing_if = "Option::is_none")]
-    pub position_topic: Option<String>,
+    #[serde(
+        rename = "pos_t",
+        alias = "position_topic",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub position_topic: Option<Topic>,
 
     /// The maximum QoS level to be used when receiving and publishing messages.
     #[serde(rename = "qos", skip_serializing_if = "Option::is_none")]
     pub qos: Option<Qos>,
 
     /// Defines if published messages should have the retain flag set.
-    #[serde(rename = "ret", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "ret",
+        alias = "retain",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub retain: Option<bool>,
 
     /// Defines a [template](/docs/configuration/templating/#using-templates-with-the-mqtt-integration) to define the position to be sent to the `set_position_topic` topic. Incoming position value is available for use in the template `{% raw %}{{ position }}{% endraw %}`. Within the template the following variables are available: `entity_id`, `position`, the target position in percent; `position_open`; `position_closed`; `tilt_min`; `tilt_max`. The `entity_id` can be used to reference the entity's attributes with help of the [states](/docs/configuration/templating/#states) template function;
-    #[serde(rename = "set_pos_tpl", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "set_pos_tpl",
+        alias = "set_position_template",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub set_position_template: Option<String>,
 
     /// The MQTT topic to publish position commands to. You need to set position_topic as well if you want to use position topic. Use template if position topic wants different values than within range `position_closed` - `position_open`. If template is not defined and `position_closed != 100` and `position_open != 0` then proper position value is calculated from percentage position.
-    #[serde(rename = "set_pos_t", skip_serializing_if = "Option::is_none")]
-    pub set_position_topic: Option<String>,
+    #[serde(
+        rename = "set_pos_t",
+        alias = "set_position_topic",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub set_position_topic: Option<Topic>,
 
     /// The payload that represents the closed state.
-    #[serde(rename = "stat_clsd", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "stat_clsd",
+        alias = "state_closed",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub state_closed: Option<String>,
 
     /// The payload that represents the closing state.
-    #[serde(rename = "stat_closing", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "stat_closing",
+        alias = "state_closing",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub state_closing: Option<String>,
 
     /// The payload that represents the open state.
-    #[serde(rename = "stat_open", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "stat_open",
+        alias = "state_open",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub state_open: Option<String>,
 
     /// The payload that represents the opening state.
-    #[serde(rename = "stat_opening", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "stat_opening",
+        alias = "state_opening",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub state_opening: Option<String>,
 
     /// The payload that represents the stopped state (for covers that do not report `open`/`closed` state).
-    #[serde(rename = "stat_stopped", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "stat_stopped",
+        alias = "state_stopped",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub state_stopped: Option<String>,
 
     /// The MQTT topic subscribed to receive cover state messages. State topic can only read (`open`, `opening`, `closed`, `closing` or `stopped`) state.
-    #[serde(rename = "stat_t", skip_serializing_if = "Option::is_none")]
-    pub state_topic: Option<String>,
+    #[serde(
+        rename = "stat_t",
+        alias = "state_topic",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub state_topic: Option<Topic>,
 
     /// The value that will be sent on a `close_cover_tilt` command.
-    #[serde(rename = "tilt_clsd_val", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "tilt_clsd_val",
+        alias = "tilt_closed_value",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub tilt_closed_value: Option<i32>,
 
     /// Defines a [template](/docs/configuration/templating/#using-templates-with-the-mqtt-integration) that can be used to extract the payload for the `tilt_command_topic` topic. Within the template the following variables are available: `entity_id`, `tilt_position`, the target tilt position in percent; `position_open`; `position_closed`; `tilt_min`; `tilt_max`. The `entity_id` can be used to reference the entity's attributes with help of the [states](/docs/configuration/templating/#states) template function;
-    #[serde(rename = "tilt_cmd_tpl", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "tilt_cmd_tpl",
+        alias = "tilt_command_template",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub tilt_command_template: Option<String>,
 
     /// The MQTT topic to publish commands to control the cover tilt.
-    #[serde(rename = "tilt_cmd_t", skip_serializing_if = "Option::is_none")]
-    pub tilt_command_topic: Option<String>,
+    #[serde(
+        rename = "tilt_cmd_t",
+        alias = "tilt_command_topic",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub tilt_command_topic: Option<Topic>,
 
     /// The maximum tilt value.
     #[serde(rename = "tilt_max", skip_serializing_if = "Option::is_none")]
@@ -771,27 +891,51 @@ pub struct Cover {
     pub tilt_min: Option<i32>,
 
     /// The value that will be sent on an `open_cover_tilt` command.
-    #[serde(rename = "tilt_opnd_val", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "tilt_opnd_val",
+        alias = "tilt_opened_value",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub tilt_opened_value: Option<i32>,
 
     /// Flag that determines if tilt works in optimistic mode.
-    #[serde(rename = "tilt_opt", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "tilt_opt",
+        alias = "tilt_optimistic",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub tilt_optimistic: Option<bool>,
 
     /// Defines a [template](/docs/configuration/templating/#using-templates-with-the-mqtt-integration) that can be used to extract the payload for the `tilt_status_topic` topic. Within the template the following variables are available: `entity_id`, `position_open`; `position_closed`; `tilt_min`; `tilt_max`. The `entity_id` can be used to reference the entity's attributes with help of the [states](/docs/configuration/templating/#states) template function;
-    #[serde(rename = "tilt_status_tpl", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "tilt_status_tpl",
+        alias = "tilt_status_template",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub tilt_status_template: Option<String>,
 
     /// The MQTT topic subscribed to receive tilt status update values.
-    #[serde(rename = "tilt_status_t", skip_serializing_if = "Option::is_none")]
-    pub tilt_status_topic: Option<String>,
+    #[serde(
+        rename = "tilt_status_t",
+        alias = "tilt_status_topic",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub tilt_status_topic: Option<Topic>,
 
     /// An ID that uniquely identifies this cover. If two covers have the same unique ID, Home Assistant will raise an exception.
-    #[serde(rename = "uniq_id", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "uniq_id",
+        alias = "unique_id",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub unique_id: Option<String>,
 
     /// Defines a [template](/docs/configuration/templating/#using-templates-with-the-mqtt-integration) that can be used to extract the payload for the `state_topic` topic.
-    #[serde(rename = "val_tpl", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "val_tpl",
+        alias = "value_template",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub value_template: Option<String>,
 }
 
@@ -828,14 +972,14 @@ impl Cover {
     }
 
     /// The MQTT topic to publish commands to control the cover.
-    pub fn command_topic<T: Into<String>>(mut self, command_topic: T) -> Self {
+    pub fn command_topic<T: Into<Topic>>(mut self, command_topic: T) -> Self {
         self.command_topic = Some(command_topic.into());
         self
     }
 
     /// Sets the [class of the device](/integrations/cover/), changing the device state and icon that is displayed on the frontend. The `device_class` can be `null`.
-    pub fn device_class<T: Into<String>>(mut self, device_class: T) -> Self {
-        self.device_class = Some(device_class.into());
+    pub fn device_class(mut self, device_class: CoverDeviceClass) -> Self {
+        self.device_class = Some(device_class);
         self
     }
 
@@ -867,7 +1011,7 @@ impl Cover {
     }
 
     /// The MQTT topic subscribed to receive a JSON dictionary payload and then set as sensor attributes. Usage example can be found in [MQTT sensor](/integrations/sensor.mqtt/#json-attributes-topic-configuration) documentation.
-    pub fn json_attributes_topic<T: Into<String>>(mut self, json_attributes_topic: T) -> Self {
+    pub fn json_attributes_topic<T: Into<Topic>>(mut self, json_attributes_topic: T) -> Self {
         self.json_attributes_topic = Some(json_attributes_topic.into());
         self
     }
@@ -927,7 +1071,7 @@ impl Cover {
     }
 
     /// The MQTT topic subscribed to receive cover position messages.
-    pub fn position_topic<T: Into<String>>(mut self, position_topic: T) -> Self {
+    pub fn position_topic<T: Into<Topic>>(mut self, position_topic: T) -> Self {
         self.position_topic = Some(position_topic.into());
         self
     }
@@ -951,7 +1095,7 @@ impl Cover {
     }
 
     /// The MQTT topic to publish position commands to. You need to set position_topic as well if you want to use position topic. Use template if position topic wants different values than within range `position_closed` - `position_open`. If template is not defined and `position_closed != 100` and `position_open != 0` then proper position value is calculated from percentage position.
-    pub fn set_position_topic<T: Into<String>>(mut self, set_position_topic: T) -> Self {
+    pub fn set_position_topic<T: Into<Topic>>(mut self, set_position_topic: T) -> Self {
         self.set_position_topic = Some(set_position_topic.into());
         self
     }
@@ -987,7 +1131,7 @@ impl Cover {
     }
 
     /// The MQTT topic subscribed to receive cover state messages. State topic can only read (`open`, `opening`, `closed`, `closing` or `stopped`) state.
-    pub fn state_topic<T: Into<String>>(mut self, state_topic: T) -> Self {
+    pub fn state_topic<T: Into<Topic>>(mut self, state_topic: T) -> Self {
         self.state_topic = Some(state_topic.into());
         self
     }
@@ -1005,7 +1149,7 @@ impl Cover {
     }
 
     /// The MQTT topic to publish commands to control the cover tilt.
-    pub fn tilt_command_topic<T: Into<String>>(mut self, tilt_command_topic: T) -> Self {
+    pub fn tilt_command_topic<T: Into<Topic>>(mut self, tilt_command_topic: T) -> Self {
         self.tilt_command_topic = Some(tilt_command_topic.into());
         self
     }
@@ -1041,7 +1185,7 @@ impl Cover {
     }
 
     /// The MQTT topic subscribed to receive tilt status update values.
-    pub fn tilt_status_topic<T: Into<String>>(mut self, tilt_status_topic: T) -> Self {
+    pub fn tilt_status_topic<T: Into<Topic>>(mut self, tilt_status_topic: T) -> Self {
         self.tilt_status_topic = Some(tilt_status_topic.into());
         self
     }
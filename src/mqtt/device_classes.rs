@@ -1,666 +1,1776 @@
-use serde_derive::Serialize;
+use std::{
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
 
-#[derive(Clone, Debug, PartialEq, Serialize)]
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+/// The device classes of the `valve` entities. The generic device class serializes as `null`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum ValveDeviceClass {
     /// Generic valve. This is the default and doesn't need to be set.
-    #[serde(rename = "None")]
     None,
 
     /// Valve that controls the flow of water through a system.
-    #[serde(rename = "water")]
     Water,
 
     /// Valve that controls the flow of gas through a system.
-    #[serde(rename = "gas")]
     Gas,
 }
 
-#[derive(Clone, Debug, PartialEq, Serialize)]
+impl ValveDeviceClass {
+    /// The device class value, `None` for the generic device class.
+    pub fn as_str(&self) -> Option<&'static str> {
+        match *self {
+            Self::None => None,
+            Self::Water => Some("water"),
+            Self::Gas => Some("gas"),
+        }
+    }
+}
+
+impl Serialize for ValveDeviceClass {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.as_str() {
+            Some(value) => serializer.serialize_str(value),
+            None => serializer.serialize_none(),
+        }
+    }
+}
+
+impl Display for ValveDeviceClass {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str().unwrap_or("None"))
+    }
+}
+
+impl FromStr for ValveDeviceClass {
+    type Err = serde::de::value::Error;
+
+    /// Parses a device class from its value, `None` for the generic device class.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "None" => Ok(Self::None),
+            "water" => Ok(Self::Water),
+            "gas" => Ok(Self::Gas),
+            _ => Err(Self::Err::unknown_variant(value, &["water", "gas"])),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ValveDeviceClass {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match Option::<String>::deserialize(deserializer)?.as_deref() {
+            Some(value) => value.parse().map_err(D::Error::custom),
+            None => Ok(Self::None),
+        }
+    }
+}
+
+#[cfg(feature = "schema")]
+impl schemars::JsonSchema for ValveDeviceClass {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "ValveDeviceClass".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "enum": [
+                null,
+                "water",
+                "gas",
+            ]
+        })
+    }
+}
+
+/// The device classes of the `cover` entities. The generic device class serializes as `null`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum CoverDeviceClass {
     /// Generic cover. This is the default and doesn't need to be set.
-    #[serde(rename = "None")]
     None,
 
     /// Control of an awning, such as an exterior retractable window, door, or patio cover.
-    #[serde(rename = "awning")]
     Awning,
 
     /// Control of blinds, which are linked slats that expand or collapse to cover an opening or may be tilted to partially covering an opening, such as window blinds.
-    #[serde(rename = "blind")]
     Blind,
 
     /// Control of curtains or drapes, which is often fabric hung above a window or door that can be drawn open.
-    #[serde(rename = "curtain")]
     Curtain,
 
     /// Control of a mechanical damper that reduces airflow, sound, or light.
-    #[serde(rename = "damper")]
     Damper,
 
     /// Control of a door or gate that provides access to an area.
-    #[serde(rename = "door")]
     Door,
 
     /// Control of a garage door that provides access to a garage.
-    #[serde(rename = "garage")]
     Garage,
 
     /// Control of a gate. Gates are found outside of a structure and are typically part of a fence.
-    #[serde(rename = "gate")]
     Gate,
 
     /// Control of shades, which are a continuous plane of material or connected cells that expanded or collapsed over an opening, such as window shades.
-    #[serde(rename = "shade")]
     Shade,
 
     /// Control of shutters, which are linked slats that swing out/in to covering an opening or may be tilted to partially cover an opening, such as indoor or exterior window shutters.
-    #[serde(rename = "shutter")]
     Shutter,
 
     /// Control of a physical window that opens and closes or may tilt.
-    #[serde(rename = "window")]
     Window,
 }
 
-#[derive(Clone, Debug, PartialEq, Serialize)]
+impl CoverDeviceClass {
+    /// The device class value, `None` for the generic device class.
+    pub fn as_str(&self) -> Option<&'static str> {
+        match *self {
+            Self::None => None,
+            Self::Awning => Some("awning"),
+            Self::Blind => Some("blind"),
+            Self::Curtain => Some("curtain"),
+            Self::Damper => Some("damper"),
+            Self::Door => Some("door"),
+            Self::Garage => Some("garage"),
+            Self::Gate => Some("gate"),
+            Self::Shade => Some("shade"),
+            Self::Shutter => Some("shutter"),
+            Self::Window => Some("window"),
+        }
+    }
+}
+
+impl Serialize for CoverDeviceClass {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.as_str() {
+            Some(value) => serializer.serialize_str(value),
+            None => serializer.serialize_none(),
+        }
+    }
+}
+
+impl Display for CoverDeviceClass {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str().unwrap_or("None"))
+    }
+}
+
+impl FromStr for CoverDeviceClass {
+    type Err = serde::de::value::Error;
+
+    /// Parses a device class from its value, `None` for the generic device class.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "None" => Ok(Self::None),
+            "awning" => Ok(Self::Awning),
+            "blind" => Ok(Self::Blind),
+            "curtain" => Ok(Self::Curtain),
+            "damper" => Ok(Self::Damper),
+            "door" => Ok(Self::Door),
+            "garage" => Ok(Self::Garage),
+            "gate" => Ok(Self::Gate),
+            "shade" => Ok(Self::Shade),
+            "shutter" => Ok(Self::Shutter),
+            "window" => Ok(Self::Window),
+            _ => Err(Self::Err::unknown_variant(
+                value,
+                &[
+                    "awning", "blind", "curtain", "damper", "door", "garage", "gate", "shade",
+                    "shutter", "window",
+                ],
+            )),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for CoverDeviceClass {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match Option::<String>::deserialize(deserializer)?.as_deref() {
+            Some(value) => value.parse().map_err(D::Error::custom),
+            None => Ok(Self::None),
+        }
+    }
+}
+
+#[cfg(feature = "schema")]
+impl schemars::JsonSchema for CoverDeviceClass {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "CoverDeviceClass".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "enum": [
+                null,
+                "awning",
+                "blind",
+                "curtain",
+                "damper",
+                "door",
+                "garage",
+                "gate",
+                "shade",
+                "shutter",
+                "window",
+            ]
+        })
+    }
+}
+
+/// The device classes of the `number` entities. The generic device class serializes as `null`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum NumberDeviceClass {
     /// Generic number. This is the default and doesn't need to be set.
-    #[serde(rename = "None")]
     None,
 
     /// Apparent power in VA.
-    #[serde(rename = "apparent_power")]
     ApparentPower,
 
     /// Air Quality Index (unitless).
-    #[serde(rename = "aqi")]
     Aqi,
 
     /// Atmospheric pressure in cbar, bar, hPa, inHg, kPa, mbar, Pa, psi
-    #[serde(rename = "atmospheric_pressure")]
     AtmosphericPressure,
 
     /// Percentage of battery that is left
-    #[serde(rename = "battery")]
     Battery,
 
     /// Carbon Dioxide in CO2 (Smoke)
-    #[serde(rename = "carbon_dioxide")]
     CarbonDioxide,
 
     /// Carbon Monoxide in CO (Gas CNG/LPG)
-    #[serde(rename = "carbon_monoxide")]
     CarbonMonoxide,
 
     /// Current in A, mA
-    #[serde(rename = "current")]
     Current,
 
     /// Data rate in bit/s, kbit/s, Mbit/s, Gbit/s, B/s, kB/s, MB/s, GB/s, KiB/s, MiB/s, or GiB/s
-    #[serde(rename = "data_rate")]
     DataRate,
 
     /// Data size in bit, kbit, Mbit, Gbit, B, kB, MB, GB, TB, PB, EB, ZB, YB, KiB, MiB, GiB, TiB, PiB, EiB, ZiB, or YiB
-    #[serde(rename = "data_size")]
     DataSize,
 
     /// Generic distance in km, m, cm, mm, mi, yd, or in
-    #[serde(rename = "distance")]
     Distance,
 
     /// Energy in Wh, kWh, MWh, MJ, or GJ
-    #[serde(rename = "energy")]
     Energy,
 
     /// Stored energy in Wh, kWh, MWh, MJ, or GJ
-    #[serde(rename = "energy_storage")]
     EnergyStorage,
 
     /// Frequency in Hz, kHz, MHz, or GHz
-    #[serde(rename = "frequency")]
     Frequency,
 
     /// Gasvolume in m³, ft³, or CCF
-    #[serde(rename = "gas")]
     Gas,
 
     /// Percentage of humidity in the air
-    #[serde(rename = "humidity")]
     Humidity,
 
     /// The current light level in lx
-    #[serde(rename = "illuminance")]
     Illuminance,
 
     /// Irradiance in W/m² or BTU/(h⋅ft²)
-    #[serde(rename = "irradiance")]
     Irradiance,
 
     /// Percentage of water in a substance
-    #[serde(rename = "moisture")]
     Moisture,
 
     /// The monetary value
-    #[serde(rename = "monetary")]
     Monetary,
 
     /// Concentration of Nitrogen Dioxide in µg/m³
-    #[serde(rename = "nitrogen_dioxide")]
     NitrogenDioxide,
 
     /// Concentration of Nitrogen Monoxide in µg/m³
-    #[serde(rename = "nitrogen_monoxide")]
     NitrogenMonoxide,
 
     /// Concentration of Nitrous Oxide in µg/m³
-    #[serde(rename = "nitrous_oxide")]
     NitrousOxide,
 
     /// Concentration of Ozone in µg/m³
-    #[serde(rename = "ozone")]
     Ozone,
 
     /// Potential hydrogen (pH) value of a water solution
-    #[serde(rename = "ph")]
     Ph,
 
     /// Concentration of particulate matter less than 1 micrometer in µg/m³
-    #[serde(rename = "pm1")]
     Pm1,
 
     /// Concentration of particulate matter less than 10 micrometers in µg/m³
-    #[serde(rename = "pm10")]
     Pm10,
 
     /// Concentration of particulate matter less than 2.5 micrometers in µg/m³
-    #[serde(rename = "pm25")]
     Pm25,
 
     /// Power factor(unitless), unit may be `None` or %
-    #[serde(rename = "power_factor")]
     PowerFactor,
 
     /// Power in W or kW
-    #[serde(rename = "power")]
     Power,
 
     /// Accumulated precipitation in cm, in or mm
-    #[serde(rename = "precipitation")]
     Precipitation,
 
     /// Precipitation intensity in in/d, in/h, mm/d, or mm/h
-    #[serde(rename = "precipitation_intensity")]
     PrecipitationIntensity,
 
     /// Pressure in Pa, kPa, hPa, bar, cbar, mbar, mmHg, inHg, or psi
-    #[serde(rename = "pressure")]
     Pressure,
 
     /// Reactive power in var
-    #[serde(rename = "reactive_power")]
     ReactivePower,
 
     /// Signal strength in dB or dBm
-    #[serde(rename = "signal_strength")]
     SignalStrength,
 
     /// Sound pressure in dB or dBA
-    #[serde(rename = "sound_pressure")]
     SoundPressure,
 
     /// Generic speed in ft/s, in/d, in/h, km/h, kn, m/s, mph, or mm/d
-    #[serde(rename = "speed")]
     Speed,
 
     /// Concentration of sulphur dioxide in µg/m³
-    #[serde(rename = "sulphur_dioxide")]
     SulphurDioxide,
 
     /// Temperature in °C, °F or K
-    #[serde(rename = "temperature")]
     Temperature,
 
     /// Concentration of volatile organic compounds in µg/m³
-    #[serde(rename = "volatile_organic_compounds")]
     VolatileOrganicCompounds,
 
     /// Voltage in V, mV
-    #[serde(rename = "voltage")]
     Voltage,
 
     /// Generic volume in L, mL, gal, fl. oz., m³, ft³, or CCF
-    #[serde(rename = "volume")]
     Volume,
 
     /// Volume flow rate in m³/h, ft³/min, L/min, gal/min
-    #[serde(rename = "volume_flow_rate")]
     VolumeFlowRate,
 
     /// Generic stored volume in L, mL, gal, fl. oz., m³, ft³, or CCF
-    #[serde(rename = "volume_storage")]
     VolumeStorage,
 
     /// Water consumption in L, gal, m³, ft³, or CCF
-    #[serde(rename = "water")]
     Water,
 
     /// Generic mass in kg, g, mg, µg, oz, lb, or st
-    #[serde(rename = "weight")]
     Weight,
 
     /// Wind speed in ft/s, km/h, kn, m/s, or mph
-    #[serde(rename = "wind_speed")]
     WindSpeed,
 }
 
-#[derive(Clone, Debug, PartialEq, Serialize)]
+impl NumberDeviceClass {
+    /// The device class value, `None` for the generic device class.
+    pub fn as_str(&self) -> Option<&'static str> {
+        match *self {
+            Self::None => None,
+            Self::ApparentPower => Some("apparent_power"),
+            Self::Aqi => Some("aqi"),
+            Self::AtmosphericPressure => Some("atmospheric_pressure"),
+            Self::Battery => Some("battery"),
+            Self::CarbonDioxide => Some("carbon_dioxide"),
+            Self::CarbonMonoxide => Some("carbon_monoxide"),
+            Self::Current => Some("current"),
+            Self::DataRate => Some("data_rate"),
+            Self::DataSize => Some("data_size"),
+            Self::Distance => Some("distance"),
+            Self::Energy => Some("energy"),
+            Self::EnergyStorage => Some("energy_storage"),
+            Self::Frequency => Some("frequency"),
+            Self::Gas => Some("gas"),
+            Self::Humidity => Some("humidity"),
+            Self::Illuminance => Some("illuminance"),
+            Self::Irradiance => Some("irradiance"),
+            Self::Moisture => Some("moisture"),
+            Self::Monetary => Some("monetary"),
+            Self::NitrogenDioxide => Some("nitrogen_dioxide"),
+            Self::NitrogenMonoxide => Some("nitrogen_monoxide"),
+            Self::NitrousOxide => Some("nitrous_oxide"),
+            Self::Ozone => Some("ozone"),
+            Self::Ph => Some("ph"),
+            Self::Pm1 => Some("pm1"),
+            Self::Pm10 => Some("pm10"),
+            Self::Pm25 => Some("pm25"),
+            Self::PowerFactor => Some("power_factor"),
+            Self::Power => Some("power"),
+            Self::Precipitation => Some("precipitation"),
+            Self::PrecipitationIntensity => Some("precipitation_intensity"),
+            Self::Pressure => Some("pressure"),
+            Self::ReactivePower => Some("reactive_power"),
+            Self::SignalStrength => Some("signal_strength"),
+            Self::SoundPressure => Some("sound_pressure"),
+            Self::Speed => Some("speed"),
+            Self::SulphurDioxide => Some("sulphur_dioxide"),
+            Self::Temperature => Some("temperature"),
+            Self::VolatileOrganicCompounds => Some("volatile_organic_compounds"),
+            Self::Voltage => Some("voltage"),
+            Self::Volume => Some("volume"),
+            Self::VolumeFlowRate => Some("volume_flow_rate"),
+            Self::VolumeStorage => Some("volume_storage"),
+            Self::Water => Some("water"),
+            Self::Weight => Some("weight"),
+            Self::WindSpeed => Some("wind_speed"),
+        }
+    }
+}
+
+impl Serialize for NumberDeviceClass {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.as_str() {
+            Some(value) => serializer.serialize_str(value),
+            None => serializer.serialize_none(),
+        }
+    }
+}
+
+impl Display for NumberDeviceClass {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str().unwrap_or("None"))
+    }
+}
+
+impl FromStr for NumberDeviceClass {
+    type Err = serde::de::value::Error;
+
+    /// Parses a device class from its value, `None` for the generic device class.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "None" => Ok(Self::None),
+            "apparent_power" => Ok(Self::ApparentPower),
+            "aqi" => Ok(Self::Aqi),
+            "atmospheric_pressure" => Ok(Self::AtmosphericPressure),
+            "battery" => Ok(Self::Battery),
+            "carbon_dioxide" => Ok(Self::CarbonDioxide),
+            "carbon_monoxide" => Ok(Self::CarbonMonoxide),
+            "current" => Ok(Self::Current),
+            "data_rate" => Ok(Self::DataRate),
+            "data_size" => Ok(Self::DataSize),
+            "distance" => Ok(Self::Distance),
+            "energy" => Ok(Self::Energy),
+            "energy_storage" => Ok(Self::EnergyStorage),
+            "frequency" => Ok(Self::Frequency),
+            "gas" => Ok(Self::Gas),
+            "humidity" => Ok(Self::Humidity),
+            "illuminance" => Ok(Self::Illuminance),
+            "irradiance" => Ok(Self::Irradiance),
+            "moisture" => Ok(Self::Moisture),
+            "monetary" => Ok(Self::Monetary),
+            "nitrogen_dioxide" => Ok(Self::NitrogenDioxide),
+            "nitrogen_monoxide" => Ok(Self::NitrogenMonoxide),
+            "nitrous_oxide" => Ok(Self::NitrousOxide),
+            "ozone" => Ok(Self::Ozone),
+            "ph" => Ok(Self::Ph),
+            "pm1" => Ok(Self::Pm1),
+            "pm10" => Ok(Self::Pm10),
+            "pm25" => Ok(Self::Pm25),
+            "power_factor" => Ok(Self::PowerFactor),
+            "power" => Ok(Self::Power),
+            "precipitation" => Ok(Self::Precipitation),
+            "precipitation_intensity" => Ok(Self::PrecipitationIntensity),
+            "pressure" => Ok(Self::Pressure),
+            "reactive_power" => Ok(Self::ReactivePower),
+            "signal_strength" => Ok(Self::SignalStrength),
+            "sound_pressure" => Ok(Self::SoundPressure),
+            "speed" => Ok(Self::Speed),
+            "sulphur_dioxide" => Ok(Self::SulphurDioxide),
+            "temperature" => Ok(Self::Temperature),
+            "volatile_organic_compounds" => Ok(Self::VolatileOrganicCompounds),
+            "voltage" => Ok(Self::Voltage),
+            "volume" => Ok(Self::Volume),
+            "volume_flow_rate" => Ok(Self::VolumeFlowRate),
+            "volume_storage" => Ok(Self::VolumeStorage),
+            "water" => Ok(Self::Water),
+            "weight" => Ok(Self::Weight),
+            "wind_speed" => Ok(Self::WindSpeed),
+            _ => Err(Self::Err::unknown_variant(
+                value,
+                &[
+                    "apparent_power",
+                    "aqi",
+                    "atmospheric_pressure",
+                    "battery",
+                    "carbon_dioxide",
+                    "carbon_monoxide",
+                    "current",
+                    "data_rate",
+                    "data_size",
+                    "distance",
+                    "energy",
+                    "energy_storage",
+                    "frequency",
+                    "gas",
+                    "humidity",
+                    "illuminance",
+                    "irradiance",
+                    "moisture",
+                    "monetary",
+                    "nitrogen_dioxide",
+                    "nitrogen_monoxide",
+                    "nitrous_oxide",
+                    "ozone",
+                    "ph",
+                    "pm1",
+                    "pm10",
+                    "pm25",
+                    "power_factor",
+                    "power",
+                    "precipitation",
+                    "precipitation_intensity",
+                    "pressure",
+                    "reactive_power",
+                    "signal_strength",
+                    "sound_pressure",
+                    "speed",
+                    "sulphur_dioxide",
+                    "temperature",
+                    "volatile_organic_compounds",
+                    "voltage",
+                    "volume",
+                    "volume_flow_rate",
+                    "volume_storage",
+                    "water",
+                    "weight",
+                    "wind_speed",
+                ],
+            )),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for NumberDeviceClass {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match Option::<String>::deserialize(deserializer)?.as_deref() {
+            Some(value) => value.parse().map_err(D::Error::custom),
+            None => Ok(Self::None),
+        }
+    }
+}
+
+#[cfg(feature = "schema")]
+impl schemars::JsonSchema for NumberDeviceClass {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "NumberDeviceClass".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "enum": [
+                null,
+                "apparent_power",
+                "aqi",
+                "atmospheric_pressure",
+                "battery",
+                "carbon_dioxide",
+                "carbon_monoxide",
+                "current",
+                "data_rate",
+                "data_size",
+                "distance",
+                "energy",
+                "energy_storage",
+                "frequency",
+                "gas",
+                "humidity",
+                "illuminance",
+                "irradiance",
+                "moisture",
+                "monetary",
+                "nitrogen_dioxide",
+                "nitrogen_monoxide",
+                "nitrous_oxide",
+                "ozone",
+                "ph",
+                "pm1",
+                "pm10",
+                "pm25",
+                "power_factor",
+                "power",
+                "precipitation",
+                "precipitation_intensity",
+                "pressure",
+                "reactive_power",
+                "signal_strength",
+                "sound_pressure",
+                "speed",
+                "sulphur_dioxide",
+                "temperature",
+                "volatile_organic_compounds",
+                "voltage",
+                "volume",
+                "volume_flow_rate",
+                "volume_storage",
+                "water",
+                "weight",
+                "wind_speed",
+            ]
+        })
+    }
+}
+
+/// The device classes of the `media_player` entities.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum MediaPlayerDeviceClass {
     /// Device is a television type device.
-    #[serde(rename = "tv")]
     Tv,
 
     /// Device is a speaker or stereo type device.
-    #[serde(rename = "speaker")]
     Speaker,
 
     /// Device is an audio/video receiver type device taking audio and outputting to speakers and video to displays.
-    #[serde(rename = "receiver")]
     Receiver,
 }
 
-#[derive(Clone, Debug, PartialEq, Serialize)]
+impl MediaPlayerDeviceClass {
+    /// The device class value, `None` for the generic device class.
+    pub fn as_str(&self) -> Option<&'static str> {
+        match *self {
+            Self::Tv => Some("tv"),
+            Self::Speaker => Some("speaker"),
+            Self::Receiver => Some("receiver"),
+        }
+    }
+}
+
+impl Serialize for MediaPlayerDeviceClass {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.as_str() {
+            Some(value) => serializer.serialize_str(value),
+            None => serializer.serialize_none(),
+        }
+    }
+}
+
+impl Display for MediaPlayerDeviceClass {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str().unwrap_or("None"))
+    }
+}
+
+impl FromStr for MediaPlayerDeviceClass {
+    type Err = serde::de::value::Error;
+
+    /// Parses a device class from its value, `None` for the generic device class.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "tv" => Ok(Self::Tv),
+            "speaker" => Ok(Self::Speaker),
+            "receiver" => Ok(Self::Receiver),
+            _ => Err(Self::Err::unknown_variant(
+                value,
+                &["tv", "speaker", "receiver"],
+            )),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for MediaPlayerDeviceClass {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match Option::<String>::deserialize(deserializer)?.as_deref() {
+            Some(value) => value.parse().map_err(D::Error::custom),
+            None => Err(D::Error::custom("device class can't be null")),
+        }
+    }
+}
+
+#[cfg(feature = "schema")]
+impl schemars::JsonSchema for MediaPlayerDeviceClass {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "MediaPlayerDeviceClass".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "enum": [
+                "tv",
+                "speaker",
+                "receiver",
+            ]
+        })
+    }
+}
+
+/// The device classes of the `binary_sensor` entities. The generic device class serializes as `null`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum BinarySensorDeviceClass {
     /// Generic on/off. This is the default and doesn't need to be set.
-    #[serde(rename = "None")]
     None,
 
     /// `on` means low, `off` means normal
-    #[serde(rename = "battery")]
     Battery,
 
     /// `on` means charging, `off` means not charging
-    #[serde(rename = "battery_charging")]
     BatteryCharging,
 
     /// `on` means carbon monoxide detected, `off` no carbon monoxide (clear)
-    #[serde(rename = "carbon_monoxide")]
     CarbonMonoxide,
 
     /// `on` means cold, `off` means normal
-    #[serde(rename = "cold")]
     Cold,
 
     /// `on` means connected, `off` means disconnected
-    #[serde(rename = "connectivity")]
     Connectivity,
 
     /// `on` means open, `off` means closed
-    #[serde(rename = "door")]
     Door,
 
     /// `on` means open, `off` means closed
-    #[serde(rename = "garage_door")]
     GarageDoor,
 
     /// `on` means gas detected, `off` means no gas (clear)
-    #[serde(rename = "gas")]
     Gas,
 
     /// `on` means hot, `off` means normal
-    #[serde(rename = "heat")]
     Heat,
 
     /// `on` means light detected, `off` means no light
-    #[serde(rename = "light")]
     Light,
 
     /// `on` means open (unlocked), `off` means closed (locked)
-    #[serde(rename = "lock")]
     Lock,
 
     /// `on` means moisture detected (wet), `off` means no moisture (dry)
-    #[serde(rename = "moisture")]
     Moisture,
 
     /// `on` means motion detected, `off` means no motion (clear)
-    #[serde(rename = "motion")]
     Motion,
 
     /// `on` means moving, `off` means not moving (stopped)
-    #[serde(rename = "moving")]
     Moving,
 
     /// `on` means occupied (detected), `off` means not occupied (clear)
-    #[serde(rename = "occupancy")]
     Occupancy,
 
     /// `on` means open, `off` means closed
-    #[serde(rename = "opening")]
     Opening,
 
     /// `on` means device is plugged in, `off` means device is unplugged
-    #[serde(rename = "plug")]
     Plug,
 
     /// `on` means power detected, `off` means no power
-    #[serde(rename = "power")]
     Power,
 
     /// `on` means home, `off` means away
-    #[serde(rename = "presence")]
     Presence,
 
     /// `on` means problem detected, `off` means no problem (OK)
-    #[serde(rename = "problem")]
     Problem,
 
     /// `on` means running, `off` means not running
-    #[serde(rename = "running")]
     Running,
 
     /// `on` means unsafe, `off` means safe
-    #[serde(rename = "safety")]
     Safety,
 
     /// `on` means smoke detected, `off` means no smoke (clear)
-    #[serde(rename = "smoke")]
     Smoke,
 
     /// `on` means sound detected, `off` means no sound (clear)
-    #[serde(rename = "sound")]
     Sound,
 
     /// `on` means tampering detected, `off` means no tampering (clear)
-    #[serde(rename = "tamper")]
     Tamper,
 
     /// `on` means update available, `off` means up-to-date
-    #[serde(rename = "update")]
     Update,
 
     /// `on` means vibration detected, `off` means no vibration (clear)
-    #[serde(rename = "vibration")]
     Vibration,
 
     /// `on` means open, `off` means closed
-    #[serde(rename = "window")]
     Window,
 }
 
-#[derive(Clone, Debug, PartialEq, Serialize)]
+impl BinarySensorDeviceClass {
+    /// The device class value, `None` for the generic device class.
+    pub fn as_str(&self) -> Option<&'static str> {
+        match *self {
+            Self::None => None,
+            Self::Battery => Some("battery"),
+            Self::BatteryCharging => Some("battery_charging"),
+            Self::CarbonMonoxide => Some("carbon_monoxide"),
+            Self::Cold => Some("cold"),
+            Self::Connectivity => Some("connectivity"),
+            Self::Door => Some("door"),
+            Self::GarageDoor => Some("garage_door"),
+            Self::Gas => Some("gas"),
+            Self::Heat => Some("heat"),
+            Self::Light => Some("light"),
+            Self::Lock => Some("lock"),
+            Self::Moisture => Some("moisture"),
+            Self::Motion => Some("motion"),
+            Self::Moving => Some("moving"),
+            Self::Occupancy => Some("occupancy"),
+            Self::Opening => Some("opening"),
+            Self::Plug => Some("plug"),
+            Self::Power => Some("power"),
+            Self::Presence => Some("presence"),
+            Self::Problem => Some("problem"),
+            Self::Running => Some("running"),
+            Self::Safety => Some("safety"),
+            Self::Smoke => Some("smoke"),
+            Self::Sound => Some("sound"),
+            Self::Tamper => Some("tamper"),
+            Self::Update => Some("update"),
+            Self::Vibration => Some("vibration"),
+            Self::Window => Some("window"),
+        }
+    }
+}
+
+impl Serialize for BinarySensorDeviceClass {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.as_str() {
+            Some(value) => serializer.serialize_str(value),
+            None => serializer.serialize_none(),
+        }
+    }
+}
+
+impl Display for BinarySensorDeviceClass {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str().unwrap_or("None"))
+    }
+}
+
+impl FromStr for BinarySensorDeviceClass {
+    type Err = serde::de::value::Error;
+
+    /// Parses a device class from its value, `None` for the generic device class.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "None" => Ok(Self::None),
+            "battery" => Ok(Self::Battery),
+            "battery_charging" => Ok(Self::BatteryCharging),
+            "carbon_monoxide" => Ok(Self::CarbonMonoxide),
+            "cold" => Ok(Self::Cold),
+            "connectivity" => Ok(Self::Connectivity),
+            "door" => Ok(Self::Door),
+            "garage_door" => Ok(Self::GarageDoor),
+            "gas" => Ok(Self::Gas),
+            "heat" => Ok(Self::Heat),
+            "light" => Ok(Self::Light),
+            "lock" => Ok(Self::Lock),
+            "moisture" => Ok(Self::Moisture),
+            "motion" => Ok(Self::Motion),
+            "moving" => Ok(Self::Moving),
+            "occupancy" => Ok(Self::Occupancy),
+            "opening" => Ok(Self::Opening),
+            "plug" => Ok(Self::Plug),
+            "power" => Ok(Self::Power),
+            "presence" => Ok(Self::Presence),
+            "problem" => Ok(Self::Problem),
+            "running" => Ok(Self::Running),
+            "safety" => Ok(Self::Safety),
+            "smoke" => Ok(Self::Smoke),
+            "sound" => Ok(Self::Sound),
+            "tamper" => Ok(Self::Tamper),
+            "update" => Ok(Self::Update),
+            "vibration" => Ok(Self::Vibration),
+            "window" => Ok(Self::Window),
+            _ => Err(Self::Err::unknown_variant(
+                value,
+                &[
+                    "battery",
+                    "battery_charging",
+                    "carbon_monoxide",
+                    "cold",
+                    "connectivity",
+                    "door",
+                    "garage_door",
+                    "gas",
+                    "heat",
+                    "light",
+                    "lock",
+                    "moisture",
+                    "motion",
+                    "moving",
+                    "occupancy",
+                    "opening",
+                    "plug",
+                    "power",
+                    "presence",
+                    "problem",
+                    "running",
+                    "safety",
+                    "smoke",
+                    "sound",
+                    "tamper",
+                    "update",
+                    "vibration",
+                    "window",
+                ],
+            )),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for BinarySensorDeviceClass {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match Option::<String>::deserialize(deserializer)?.as_deref() {
+            Some(value) => value.parse().map_err(D::Error::custom),
+            None => Ok(Self::None),
+        }
+    }
+}
+
+#[cfg(feature = "schema")]
+impl schemars::JsonSchema for BinarySensorDeviceClass {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "BinarySensorDeviceClass".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "enum": [
+                null,
+                "battery",
+                "battery_charging",
+                "carbon_monoxide",
+                "cold",
+                "connectivity",
+                "door",
+                "garage_door",
+                "gas",
+                "heat",
+                "light",
+                "lock",
+                "moisture",
+                "motion",
+                "moving",
+                "occupancy",
+                "opening",
+                "plug",
+                "power",
+                "presence",
+                "problem",
+                "running",
+                "safety",
+                "smoke",
+                "sound",
+                "tamper",
+                "update",
+                "vibration",
+                "window",
+            ]
+        })
+    }
+}
+
+/// The device classes of the `update` entities. The generic device class serializes as `null`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum UpdateDeviceClass {
     /// A generic software update. This is the default and doesn't need
-    #[serde(rename = "None")]
     None,
 
     /// This update {% term integration %} provides firmwares.
-    #[serde(rename = "firmware")]
     Firmware,
 }
 
-#[derive(Clone, Debug, PartialEq, Serialize)]
+impl UpdateDeviceClass {
+    /// The device class value, `None` for the generic device class.
+    pub fn as_str(&self) -> Option<&'static str> {
+        match *self {
+            Self::None => None,
+            Self::Firmware => Some("firmware"),
+        }
+    }
+}
+
+impl Serialize for UpdateDeviceClass {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.as_str() {
+            Some(value) => serializer.serialize_str(value),
+            None => serializer.serialize_none(),
+        }
+    }
+}
+
+impl Display for UpdateDeviceClass {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str().unwrap_or("None"))
+    }
+}
+
+impl FromStr for UpdateDeviceClass {
+    type Err = serde::de::value::Error;
+
+    /// Parses a device class from its value, `None` for the generic device class.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "None" => Ok(Self::None),
+            "firmware" => Ok(Self::Firmware),
+            _ => Err(Self::Err::unknown_variant(value, &["firmware"])),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for UpdateDeviceClass {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match Option::<String>::deserialize(deserializer)?.as_deref() {
+            Some(value) => value.parse().map_err(D::Error::custom),
+            None => Ok(Self::None),
+        }
+    }
+}
+
+#[cfg(feature = "schema")]
+impl schemars::JsonSchema for UpdateDeviceClass {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "UpdateDeviceClass".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "enum": [
+                null,
+                "firmware",
+            ]
+        })
+    }
+}
+
+/// The device classes of the `switch` entities. The generic device class serializes as `null`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum SwitchDeviceClass {
     /// Generic switch. This is the default and doesn't need to be set.
-    #[serde(rename = "None")]
     None,
 
     /// This switch, switches a power outlet.
-    #[serde(rename = "outlet")]
     Outlet,
 
     /// A generic switch.
-    #[serde(rename = "switch")]
     Switch,
 }
 
-#[derive(Clone, Debug, PartialEq, Serialize)]
+impl SwitchDeviceClass {
+    /// The device class value, `None` for the generic device class.
+    pub fn as_str(&self) -> Option<&'static str> {
+        match *self {
+            Self::None => None,
+            Self::Outlet => Some("outlet"),
+            Self::Switch => Some("switch"),
+        }
+    }
+}
+
+impl Serialize for SwitchDeviceClass {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.as_str() {
+            Some(value) => serializer.serialize_str(value),
+            None => serializer.serialize_none(),
+        }
+    }
+}
+
+impl Display for SwitchDeviceClass {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str().unwrap_or("None"))
+    }
+}
+
+impl FromStr for SwitchDeviceClass {
+    type Err = serde::de::value::Error;
+
+    /// Parses a device class from its value, `None` for the generic device class.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "None" => Ok(Self::None),
+            "outlet" => Ok(Self::Outlet),
+            "switch" => Ok(Self::Switch),
+            _ => Err(Self::Err::unknown_variant(value, &["outlet", "switch"])),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for SwitchDeviceClass {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match Option::<String>::deserialize(deserializer)?.as_deref() {
+            Some(value) => value.parse().map_err(D::Error::custom),
+            None => Ok(Self::None),
+        }
+    }
+}
+
+#[cfg(feature = "schema")]
+impl schemars::JsonSchema for SwitchDeviceClass {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "SwitchDeviceClass".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "enum": [
+                null,
+                "outlet",
+                "switch",
+            ]
+        })
+    }
+}
+
+/// The device classes of the `homeassistant` entities.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum HomeassistantDeviceClass {}
 
-#[derive(Clone, Debug, PartialEq, Serialize)]
+impl HomeassistantDeviceClass {
+    /// The device class value, `None` for the generic device class.
+    pub fn as_str(&self) -> Option<&'static str> {
+        match *self {}
+    }
+}
+
+impl Serialize for HomeassistantDeviceClass {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.as_str() {
+            Some(value) => serializer.serialize_str(value),
+            None => serializer.serialize_none(),
+        }
+    }
+}
+
+impl Display for HomeassistantDeviceClass {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str().unwrap_or("None"))
+    }
+}
+
+impl FromStr for HomeassistantDeviceClass {
+    type Err = serde::de::value::Error;
+
+    /// Parses a device class from its value, `None` for the generic device class.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            _ => Err(Self::Err::unknown_variant(value, &[])),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for HomeassistantDeviceClass {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match Option::<String>::deserialize(deserializer)?.as_deref() {
+            Some(value) => value.parse().map_err(D::Error::custom),
+            None => Err(D::Error::custom("device class can't be null")),
+        }
+    }
+}
+
+#[cfg(feature = "schema")]
+impl schemars::JsonSchema for HomeassistantDeviceClass {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "HomeassistantDeviceClass".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "enum": [
+            ]
+        })
+    }
+}
+
+/// The device classes of the `event` entities. The generic device class serializes as `null`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum EventDeviceClass {
     /// Generic event. This is the default and doesn't need to be set.
-    #[serde(rename = "None")]
     None,
 
     /// For remote control buttons.
-    #[serde(rename = "button")]
     Button,
 
     /// Specifically for buttons that are used as a doorbell.
-    #[serde(rename = "doorbell")]
     Doorbell,
 
     /// For motion events detected by a motion sensor.
-    #[serde(rename = "motion")]
     Motion,
 }
 
-#[derive(Clone, Debug, PartialEq, Serialize)]
+impl EventDeviceClass {
+    /// The device class value, `None` for the generic device class.
+    pub fn as_str(&self) -> Option<&'static str> {
+        match *self {
+            Self::None => None,
+            Self::Button => Some("button"),
+            Self::Doorbell => Some("doorbell"),
+            Self::Motion => Some("motion"),
+        }
+    }
+}
+
+impl Serialize for EventDeviceClass {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.as_str() {
+            Some(value) => serializer.serialize_str(value),
+            None => serializer.serialize_none(),
+        }
+    }
+}
+
+impl Display for EventDeviceClass {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str().unwrap_or("None"))
+    }
+}
+
+impl FromStr for EventDeviceClass {
+    type Err = serde::de::value::Error;
+
+    /// Parses a device class from its value, `None` for the generic device class.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "None" => Ok(Self::None),
+            "button" => Ok(Self::Button),
+            "doorbell" => Ok(Self::Doorbell),
+            "motion" => Ok(Self::Motion),
+            _ => Err(Self::Err::unknown_variant(
+                value,
+                &["button", "doorbell", "motion"],
+            )),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for EventDeviceClass {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match Option::<String>::deserialize(deserializer)?.as_deref() {
+            Some(value) => value.parse().map_err(D::Error::custom),
+            None => Ok(Self::None),
+        }
+    }
+}
+
+#[cfg(feature = "schema")]
+impl schemars::JsonSchema for EventDeviceClass {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "EventDeviceClass".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "enum": [
+                null,
+                "button",
+                "doorbell",
+                "motion",
+            ]
+        })
+    }
+}
+
+/// The device classes of the `sensor` entities. The generic device class serializes as `null`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum SensorDeviceClass {
     /// Generic sensor. This is the default and doesn't need to be set.
-    #[serde(rename = "None")]
     None,
 
     /// Apparent power in VA.
-    #[serde(rename = "apparent_power")]
     ApparentPower,
 
     /// Air Quality Index (unitless).
-    #[serde(rename = "aqi")]
     Aqi,
 
     /// Atmospheric pressure in cbar, bar, hPa, mmHg, inHg, kPa, mbar, Pa or psi
-    #[serde(rename = "atmospheric_pressure")]
     AtmosphericPressure,
 
     /// Percentage of battery that is left in %
-    #[serde(rename = "battery")]
     Battery,
 
     /// Carbon Dioxide in CO2 (Smoke) in ppm
-    #[serde(rename = "carbon_dioxide")]
     CarbonDioxide,
 
     /// Carbon Monoxide in CO (Gas CNG/LPG) in ppm
-    #[serde(rename = "carbon_monoxide")]
     CarbonMonoxide,
 
     /// Current in A, mA
-    #[serde(rename = "current")]
     Current,
 
     /// Data rate in bit/s, kbit/s, Mbit/s, Gbit/s, B/s, kB/s, MB/s, GB/s, KiB/s, MiB/s or GiB/s
-    #[serde(rename = "data_rate")]
     DataRate,
 
     /// Data size in bit, kbit, Mbit, Gbit, B, kB, MB, GB, TB, PB, EB, ZB, YB, KiB, MiB, GiB, TiB, PiB, EiB, ZiB or YiB
-    #[serde(rename = "data_size")]
     DataSize,
 
     /// Date string (ISO 8601)
-    #[serde(rename = "date")]
     Date,
 
     /// Generic distance in km, m, cm, mm, mi, yd, or in
-    #[serde(rename = "distance")]
     Distance,
 
     /// Duration in d, h, min, or s
-    #[serde(rename = "duration")]
     Duration,
 
     /// Energy in Wh, kWh, MWh, MJ, or GJ
-    #[serde(rename = "energy")]
     Energy,
 
     /// Stored energy in Wh, kWh, MWh, MJ, or GJ
-    #[serde(rename = "energy_storage")]
     EnergyStorage,
 
     /// Has a limited set of (non-numeric) states
-    #[serde(rename = "enum")]
     Enum,
 
     /// Frequency in Hz, kHz, MHz, or GHz
-    #[serde(rename = "frequency")]
     Frequency,
 
     /// Gasvolume in m³, ft³ or CCF
-    #[serde(rename = "gas")]
     Gas,
 
     /// Percentage of humidity in the air in %
-    #[serde(rename = "humidity")]
     Humidity,
 
     /// The current light level in lx
-    #[serde(rename = "illuminance")]
     Illuminance,
 
     /// Irradiance in W/m² or BTU/(h⋅ft²)
-    #[serde(rename = "irradiance")]
     Irradiance,
 
     /// Percentage of water in a substance in %
-    #[serde(rename = "moisture")]
     Moisture,
 
     /// The monetary value ([ISO 4217](https://en.wikipedia.org/wiki/ISO_4217#Active_codes))
-    #[serde(rename = "monetary")]
     Monetary,
 
     /// Concentration of Nitrogen Dioxide in µg/m³
-    #[serde(rename = "nitrogen_dioxide")]
     NitrogenDioxide,
 
     /// Concentration of Nitrogen Monoxide in µg/m³
-    #[serde(rename = "nitrogen_monoxide")]
     NitrogenMonoxide,
 
     /// Concentration of Nitrous Oxide in µg/m³
-    #[serde(rename = "nitrous_oxide")]
     NitrousOxide,
 
     /// Concentration of Ozone in µg/m³
-    #[serde(rename = "ozone")]
     Ozone,
 
     /// Potential hydrogen (pH) value of a water solution
-    #[serde(rename = "ph")]
     Ph,
 
     /// Concentration of particulate matter less than 1 micrometer in µg/m³
-    #[serde(rename = "pm1")]
     Pm1,
 
     /// Concentration of particulate matter less than 2.5 micrometers in µg/m³
-    #[serde(rename = "pm25")]
     Pm25,
 
     /// Concentration of particulate matter less than 10 micrometers in µg/m³
-    #[serde(rename = "pm10")]
     Pm10,
 
     /// Power factor (unitless), unit may be `None` or %
-    #[serde(rename = "power_factor")]
     PowerFactor,
 
     /// Power in W or kW
-    #[serde(rename = "power")]
     Power,
 
     /// Accumulated precipitation in cm, in or mm
-    #[serde(rename = "precipitation")]
     Precipitation,
 
     /// Precipitation intensity in in/d, in/h, mm/d or mm/h
-    #[serde(rename = "precipitation_intensity")]
     PrecipitationIntensity,
 
     /// Pressure in Pa, kPa, hPa, bar, cbar, mbar, mmHg, inHg or psi
-    #[serde(rename = "pressure")]
     Pressure,
 
     /// Reactive power in var
-    #[serde(rename = "reactive_power")]
     ReactivePower,
 
     /// Signal strength in dB or dBm
-    #[serde(rename = "signal_strength")]
     SignalStrength,
 
     /// Sound pressure in dB or dBA
-    #[serde(rename = "sound_pressure")]
     SoundPressure,
 
     /// Generic speed in ft/s, in/d, in/h, km/h, kn, m/s, mph or mm/d
-    #[serde(rename = "speed")]
     Speed,
 
     /// Concentration of sulphur dioxide in µg/m³
-    #[serde(rename = "sulphur_dioxide")]
     SulphurDioxide,
 
     /// Temperature in °C, °F or K
-    #[serde(rename = "temperature")]
     Temperature,
 
     /// Datetime object or timestamp string (ISO 8601)
-    #[serde(rename = "timestamp")]
     Timestamp,
 
     /// Concentration of volatile organic compounds in µg/m³
-    #[serde(rename = "volatile_organic_compounds")]
     VolatileOrganicCompounds,
 
     /// Ratio of volatile organic compounds in ppm or ppb
-    #[serde(rename = "volatile_organic_compounds_parts")]
     VolatileOrganicCompoundsParts,
 
     /// Voltage in V, mV
-    #[serde(rename = "voltage")]
     Voltage,
 
     /// Generic volume in L, mL, gal, fl. oz., m³, ft³, or CCF
-    #[serde(rename = "volume")]
     Volume,
 
     /// Volume flow rate in m³/h, ft³/min, L/min, gal/min
-    #[serde(rename = "volume_flow_rate")]
     VolumeFlowRate,
 
     /// Generic stored volume in L, mL, gal, fl. oz., m³, ft³, or CCF
-    #[serde(rename = "volume_storage")]
     VolumeStorage,
 
     /// Water consumption in L, gal, m³, ft³, or CCF
-    #[serde(rename = "water")]
     Water,
 
     /// Generic mass in kg, g, mg, µg, oz, lb, or st
-    #[serde(rename = "weight")]
     Weight,
 
     /// Wind speed in Beaufort, ft/s, km/h, kn, m/s, or mph
-    #[serde(rename = "wind_speed")]
     WindSpeed,
 }
 
-#[derive(Clone, Debug, PartialEq, Serialize)]
+impl SensorDeviceClass {
+    /// The device class value, `None` for the generic device class.
+    pub fn as_str(&self) -> Option<&'static str> {
+        match *self {
+            Self::None => None,
+            Self::ApparentPower => Some("apparent_power"),
+            Self::Aqi => Some("aqi"),
+            Self::AtmosphericPressure => Some("atmospheric_pressure"),
+            Self::Battery => Some("battery"),
+            Self::CarbonDioxide => Some("carbon_dioxide"),
+            Self::CarbonMonoxide => Some("carbon_monoxide"),
+            Self::Current => Some("current"),
+            Self::DataRate => Some("data_rate"),
+            Self::DataSize => Some("data_size"),
+            Self::Date => Some("date"),
+            Self::Distance => Some("distance"),
+            Self::Duration => Some("duration"),
+            Self::Energy => Some("energy"),
+            Self::EnergyStorage => Some("energy_storage"),
+            Self::Enum => Some("enum"),
+            Self::Frequency => Some("frequency"),
+            Self::Gas => Some("gas"),
+            Self::Humidity => Some("humidity"),
+            Self::Illuminance => Some("illuminance"),
+            Self::Irradiance => Some("irradiance"),
+            Self::Moisture => Some("moisture"),
+            Self::Monetary => Some("monetary"),
+            Self::NitrogenDioxide => Some("nitrogen_dioxide"),
+            Self::NitrogenMonoxide => Some("nitrogen_monoxide"),
+            Self::NitrousOxide => Some("nitrous_oxide"),
+            Self::Ozone => Some("ozone"),
+            Self::Ph => Some("ph"),
+            Self::Pm1 => Some("pm1"),
+            Self::Pm25 => Some("pm25"),
+            Self::Pm10 => Some("pm10"),
+            Self::PowerFactor => Some("power_factor"),
+            Self::Power => Some("power"),
+            Self::Precipitation => Some("precipitation"),
+            Self::PrecipitationIntensity => Some("precipitation_intensity"),
+            Self::Pressure => Some("pressure"),
+            Self::ReactivePower => Some("reactive_power"),
+            Self::SignalStrength => Some("signal_strength"),
+            Self::SoundPressure => Some("sound_pressure"),
+            Self::Speed => Some("speed"),
+            Self::SulphurDioxide => Some("sulphur_dioxide"),
+            Self::Temperature => Some("temperature"),
+            Self::Timestamp => Some("timestamp"),
+            Self::VolatileOrganicCompounds => Some("volatile_organic_compounds"),
+            Self::VolatileOrganicCompoundsParts => Some("volatile_organic_compounds_parts"),
+            Self::Voltage => Some("voltage"),
+            Self::Volume => Some("volume"),
+            Self::VolumeFlowRate => Some("volume_flow_rate"),
+            Self::VolumeStorage => Some("volume_storage"),
+            Self::Water => Some("water"),
+            Self::Weight => Some("weight"),
+            Self::WindSpeed => Some("wind_speed"),
+        }
+    }
+}
+
+impl Serialize for SensorDeviceClass {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.as_str() {
+            Some(value) => serializer.serialize_str(value),
+            None => serializer.serialize_none(),
+        }
+    }
+}
+
+impl Display for SensorDeviceClass {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str().unwrap_or("None"))
+    }
+}
+
+impl FromStr for SensorDeviceClass {
+    type Err = serde::de::value::Error;
+
+    /// Parses a device class from its value, `None` for the generic device class.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "None" => Ok(Self::None),
+            "apparent_power" => Ok(Self::ApparentPower),
+            "aqi" => Ok(Self::Aqi),
+            "atmospheric_pressure" => Ok(Self::AtmosphericPressure),
+            "battery" => Ok(Self::Battery),
+            "carbon_dioxide" => Ok(Self::CarbonDioxide),
+            "carbon_monoxide" => Ok(Self::CarbonMonoxide),
+            "current" => Ok(Self::Current),
+            "data_rate" => Ok(Self::DataRate),
+            "data_size" => Ok(Self::DataSize),
+            "date" => Ok(Self::Date),
+            "distance" => Ok(Self::Distance),
+            "duration" => Ok(Self::Duration),
+            "energy" => Ok(Self::Energy),
+            "energy_storage" => Ok(Self::EnergyStorage),
+            "enum" => Ok(Self::Enum),
+            "frequency" => Ok(Self::Frequency),
+            "gas" => Ok(Self::Gas),
+            "humidity" => Ok(Self::Humidity),
+            "illuminance" => Ok(Self::Illuminance),
+            "irradiance" => Ok(Self::Irradiance),
+            "moisture" => Ok(Self::Moisture),
+            "monetary" => Ok(Self::Monetary),
+            "nitrogen_dioxide" => Ok(Self::NitrogenDioxide),
+            "nitrogen_monoxide" => Ok(Self::NitrogenMonoxide),
+            "nitrous_oxide" => Ok(Self::NitrousOxide),
+            "ozone" => Ok(Self::Ozone),
+            "ph" => Ok(Self::Ph),
+            "pm1" => Ok(Self::Pm1),
+            "pm25" => Ok(Self::Pm25),
+            "pm10" => Ok(Self::Pm10),
+            "power_factor" => Ok(Self::PowerFactor),
+            "power" => Ok(Self::Power),
+            "precipitation" => Ok(Self::Precipitation),
+            "precipitation_intensity" => Ok(Self::PrecipitationIntensity),
+            "pressure" => Ok(Self::Pressure),
+            "reactive_power" => Ok(Self::ReactivePower),
+            "signal_strength" => Ok(Self::SignalStrength),
+            "sound_pressure" => Ok(Self::SoundPressure),
+            "speed" => Ok(Self::Speed),
+            "sulphur_dioxide" => Ok(Self::SulphurDioxide),
+            "temperature" => Ok(Self::Temperature),
+            "timestamp" => Ok(Self::Timestamp),
+            "volatile_organic_compounds" => Ok(Self::VolatileOrganicCompounds),
+            "volatile_organic_compounds_parts" => Ok(Self::VolatileOrganicCompoundsParts),
+            "voltage" => Ok(Self::Voltage),
+            "volume" => Ok(Self::Volume),
+            "volume_flow_rate" => Ok(Self::VolumeFlowRate),
+            "volume_storage" => Ok(Self::VolumeStorage),
+            "water" => Ok(Self::Water),
+            "weight" => Ok(Self::Weight),
+            "wind_speed" => Ok(Self::WindSpeed),
+            _ => Err(Self::Err::unknown_variant(
+                value,
+                &[
+                    "apparent_power",
+                    "aqi",
+                    "atmospheric_pressure",
+                    "battery",
+                    "carbon_dioxide",
+                    "carbon_monoxide",
+                    "current",
+                    "data_rate",
+                    "data_size",
+                    "date",
+                    "distance",
+                    "duration",
+                    "energy",
+                    "energy_storage",
+                    "enum",
+                    "frequency",
+                    "gas",
+                    "humidity",
+                    "illuminance",
+                    "irradiance",
+                    "moisture",
+                    "monetary",
+                    "nitrogen_dioxide",
+                    "nitrogen_monoxide",
+                    "nitrous_oxide",
+                    "ozone",
+                    "ph",
+                    "pm1",
+                    "pm25",
+                    "pm10",
+                    "power_factor",
+                    "power",
+                    "precipitation",
+                    "precipitation_intensity",
+                    "pressure",
+                    "reactive_power",
+                    "signal_strength",
+                    "sound_pressure",
+                    "speed",
+                    "sulphur_dioxide",
+                    "temperature",
+                    "timestamp",
+                    "volatile_organic_compounds",
+                    "volatile_organic_compounds_parts",
+                    "voltage",
+                    "volume",
+                    "volume_flow_rate",
+                    "volume_storage",
+                    "water",
+                    "weight",
+                    "wind_speed",
+                ],
+            )),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for SensorDeviceClass {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match Option::<String>::deserialize(deserializer)?.as_deref() {
+            Some(value) => value.parse().map_err(D::Error::custom),
+            None => Ok(Self::None),
+        }
+    }
+}
+
+#[cfg(feature = "schema")]
+impl schemars::JsonSchema for SensorDeviceClass {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "SensorDeviceClass".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "enum": [
+                null,
+                "apparent_power",
+                "aqi",
+                "atmospheric_pressure",
+                "battery",
+                "carbon_dioxide",
+                "carbon_monoxide",
+                "current",
+                "data_rate",
+                "data_size",
+                "date",
+                "distance",
+                "duration",
+                "energy",
+                "energy_storage",
+                "enum",
+                "frequency",
+                "gas",
+                "humidity",
+                "illuminance",
+                "irradiance",
+                "moisture",
+                "monetary",
+                "nitrogen_dioxide",
+                "nitrogen_monoxide",
+                "nitrous_oxide",
+                "ozone",
+                "ph",
+                "pm1",
+                "pm25",
+                "pm10",
+                "power_factor",
+                "power",
+                "precipitation",
+                "precipitation_intensity",
+                "pressure",
+                "reactive_power",
+                "signal_strength",
+                "sound_pressure",
+                "speed",
+                "sulphur_dioxide",
+                "temperature",
+                "timestamp",
+                "volatile_organic_compounds",
+                "volatile_organic_compounds_parts",
+                "voltage",
+                "volume",
+                "volume_flow_rate",
+                "volume_storage",
+                "water",
+                "weight",
+                "wind_speed",
+            ]
+        })
+    }
+}
+
+/// The device classes of the `button` entities. The generic device class serializes as `null`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum ButtonDeviceClass {
     /// Generic button. This is the default and doesn't need to be set.
-    #[serde(rename = "None")]
     None,
 
     /// The button is used to identify a device.
-    #[serde(rename = "identify")]
     Identify,
 
     /// The button restarts the device.
-    #[serde(rename = "restart")]
     Restart,
 
     /// The button updates the software of the device.
-    #[serde(rename = "update")]
     Update,
 }
+
+impl ButtonDeviceClass {
+    /// The device class value, `None` for the generic device class.
+    pub fn as_str(&self) -> Option<&'static str> {
+        match *self {
+            Self::None => None,
+            Self::Identify => Some("identify"),
+            Self::Restart => Some("restart"),
+            Self::Update => Some("update"),
+        }
+    }
+}
+
+impl Serialize for ButtonDeviceClass {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.as_str() {
+            Some(value) => serializer.serialize_str(value),
+            None => serializer.serialize_none(),
+        }
+    }
+}
+
+impl Display for ButtonDeviceClass {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str().unwrap_or("None"))
+    }
+}
+
+impl FromStr for ButtonDeviceClass {
+    type Err = serde::de::value::Error;
+
+    /// Parses a device class from its value, `None` for the generic device class.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "None" => Ok(Self::None),
+            "identify" => Ok(Self::Identify),
+            "restart" => Ok(Self::Restart),
+            "update" => Ok(Self::Update),
+            _ => Err(Self::Err::unknown_variant(
+                value,
+                &["identify", "restart", "update"],
+            )),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ButtonDeviceClass {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match Option::<String>::deserialize(deserializer)?.as_deref() {
+            Some(value) => value.parse().map_err(D::Error::custom),
+            None => Ok(Self::None),
+        }
+    }
+}
+
+#[cfg(feature = "schema")]
+impl schemars::JsonSchema for ButtonDeviceClass {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "ButtonDeviceClass".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "enum": [
+                null,
+                "identify",
+                "restart",
+                "update",
+            ]
+        })
+    }
+}
@@ -1,7 +1,9 @@
 use super::common::Qos;
 use super::common::{Availability, Device, EntityCategory, Origin};
+use crate::topic::Topic;
 use crate::Entity;
-use serde_derive::Serialize;
+use bon::Builder;
+use serde_derive::{Deserialize, Serialize};
 
 /// ---
 /// title: "MQTT device tracker"
@@ -265,7 +267,9 @@ use serde_derive::Serialize;
 ///
 /// {% endraw %}
 ///
-#[derive(Clone, Debug, PartialEq, Serialize, Default)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default, Builder)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[builder(on(String, into), on(Topic, into))]
 pub struct DeviceTracker {
     /// Replaces `~` with this value in any MQTT topic attribute.
     /// [See Home Assistant documentation](https://www.home-assistant.io/integrations/mqtt/#using-abbreviations-and-base-topic)
@@ -273,27 +277,38 @@ pub struct DeviceTracker {
     pub topic_prefix: Option<String>,
 
     /// It is encouraged to add additional information about the origin that supplies MQTT entities via MQTT discovery by adding the origin option (can be abbreviated to o) to the discovery payload. Note that these options also support abbreviations. Information of the origin will be logged to the core event log when an item is discovered or updated.
-    #[serde(rename = "o")]
+    #[serde(rename = "o", alias = "origin", default)]
+    #[builder(default)]
     pub origin: Origin,
 
     /// Information about the device this button is a part of to tie it into the [device registry](https://developers.home-assistant.io/docs/en/device_registry_index.html). Only works when [`unique_id`](#unique_id) is set. At least one of identifiers or connections must be present to identify the device.
-    #[serde(rename = "dev")]
+    #[serde(rename = "dev", alias = "device", default)]
+    #[builder(default)]
     pub device: Device,
 
     /// A list of MQTT topics subscribed to receive availability (online/offline) updates. Must not be used together with `availability_topic`.
     #[serde(flatten)]
+    #[builder(default)]
     pub availability: Availability,
 
     /// The category of the entity. (optional, default: None)
-    #[serde(rename = "ent_cat", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "ent_cat",
+        alias = "entity_category",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub entity_category: Option<EntityCategory>,
 
     /// [Icon](/docs/configuration/customizing-devices/#icon) for the entity.
-    #[serde(rename = "ic", skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "ic", alias = "icon", skip_serializing_if = "Option::is_none")]
     pub icon: Option<String>,
 
     /// Defines a [template](/docs/configuration/templating/#using-templates-with-the-mqtt-integration) to extract the JSON dictionary from messages received on the `json_attributes_topic`. Usage example can be found in [MQTT sensor](/integrations/sensor.mqtt/#json-attributes-template-configuration) documentation.
-    #[serde(rename = "json_attr_tpl", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "json_attr_tpl",
+        alias = "json_attributes_template",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub json_attributes_template: Option<String>,
 
     /// The MQTT topic subscribed to receive a JSON dictionary message containing device tracker attributes.
@@ -303,27 +318,47 @@ pub struct DeviceTracker {
     /// If these conditions are met, it is not required to configure `state_topic`.\n\n
     /// Be aware that any location message received at `state_topic`  overrides the location received via `json_attributes_topic` until a message configured with `payload_reset` is received at `state_topic`. For a more generic usage example of the `json_attributes_topic`, refer to the [MQTT sensor](/integrations/sensor.mqtt/#json-attributes-topic-configuration) documentation.
     ///
-    #[serde(rename = "json_attr_t", skip_serializing_if = "Option::is_none")]
-    pub json_attributes_topic: Option<String>,
+    #[serde(
+        rename = "json_attr_t",
+        alias = "json_attributes_topic",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub json_attributes_topic: Option<Topic>,
 
     /// The name of the MQTT device_tracker.
     #[serde(rename = "name", skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
 
     /// Used instead of `name` for automatic generation of `entity_id`
-    #[serde(rename = "obj_id", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "obj_id",
+        alias = "object_id",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub object_id: Option<String>,
 
     /// The payload value that represents the 'home' state for the device.
-    #[serde(rename = "pl_home", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "pl_home",
+        alias = "payload_home",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub payload_home: Option<String>,
 
     /// The payload value that represents the 'not_home' state for the device.
-    #[serde(rename = "pl_not_home", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "pl_not_home",
+        alias = "payload_not_home",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub payload_not_home: Option<String>,
 
     /// The payload value that will have the device's location automatically derived from Home Assistant's zones.
-    #[serde(rename = "pl_rst", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "pl_rst",
+        alias = "payload_reset",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub payload_reset: Option<String>,
 
     /// The maximum QoS level to be used when receiving and publishing messages.
@@ -331,19 +366,35 @@ pub struct DeviceTracker {
     pub qos: Option<Qos>,
 
     /// Attribute of a device tracker that affects state when being used to track a [person](/integrations/person/). Valid options are `gps`, `router`, `bluetooth`, or `bluetooth_le`.
-    #[serde(rename = "src_type", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "src_type",
+        alias = "source_type",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub source_type: Option<String>,
 
     /// The MQTT topic subscribed to receive device tracker state changes. The states defined in `state_topic` override the location states defined by the `json_attributes_topic`. This state override is turned inactive if the `state_topic` receives a message containing `payload_reset`. The `state_topic` can only be omitted if `json_attributes_topic` is used.
-    #[serde(rename = "stat_t", skip_serializing_if = "Option::is_none")]
-    pub state_topic: Option<String>,
+    #[serde(
+        rename = "stat_t",
+        alias = "state_topic",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub state_topic: Option<Topic>,
 
     /// An ID that uniquely identifies this device_tracker. If two device_trackers have the same unique ID, Home Assistant will raise an exception.
-    #[serde(rename = "uniq_id", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "uniq_id",
+        alias = "unique_id",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub unique_id: Option<String>,
 
     /// Defines a [template](/docs/configuration/templating/#using-templates-with-the-mqtt-integration) that returns a device tracker state.
-    #[serde(rename = "val_tpl", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "val_tpl",
+        alias = "value_template",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub value_template: Option<String>,
 }
 
@@ -401,7 +452,7 @@ impl DeviceTracker {
     /// If these conditions are met, it is not required to configure `state_topic`.\n\n
     /// Be aware that any location message received at `state_topic`  overrides the location received via `json_attributes_topic` until a message configured with `payload_reset` is received at `state_topic`. For a more generic usage example of the `json_attributes_topic`, refer to the [MQTT sensor](/integrations/sensor.mqtt/#json-attributes-topic-configuration) documentation.
     ///
-    pub fn json_attributes_topic<T: Into<String>>(mut self, json_attributes_topic: T) -> Self {
+    pub fn json_attributes_topic<T: Into<Topic>>(mut self, json_attributes_topic: T) -> Self {
         self.json_attributes_topic = Some(json_attributes_topic.into());
         self
     }
@@ -449,7 +500,7 @@ impl DeviceTracker {
     }
 
     /// The MQTT topic subscribed to receive device tracker state changes. The states defined in `state_topic` override the location states defined by the `json_attributes_topic`. This state override is turned inactive if the `state_topic` receives a message containing `payload_reset`. The `state_topic` can only be omitted if `json_attributes_topic` is used.
-    pub fn state_topic<T: Into<String>>(mut self, state_topic: T) -> Self {
+    pub fn state_topic<T: Into<Topic>>(mut self, state_topic: T) -> Self {
         self.state_topic = Some(state_topic.into());
         self
     }
@@ -1,7 +1,9 @@
 use super::common::Qos;
 use super::common::{Availability, Device, EntityCategory, Origin};
+use crate::topic::Topic;
 use crate::Entity;
-use serde_derive::Serialize;
+use bon::Builder;
+use serde_derive::{Deserialize, Serialize};
 
 /// ---
 /// title: "MQTT Device trigger"
@@ -155,7 +157,10 @@ use serde_derive::Serialize;
 /// - Trigger topic: `zigbee2mqtt/0x90fd9ffffedf1266/action`
 /// - Trigger payload: `arrow_right_click`
 ///
-#[derive(Clone, Debug, PartialEq, Serialize, Default)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Builder)]
+#[cfg_attr(feature = "entity-default", derive(Default))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[builder(on(String, into), on(Topic, into))]
 pub struct DeviceTrigger {
     /// Replaces `~` with this value in any MQTT topic attribute.
     /// [See Home Assistant documentation](https://www.home-assistant.io/integrations/mqtt/#using-abbreviations-and-base-topic)
@@ -163,27 +168,38 @@ pub struct DeviceTrigger {
     pub topic_prefix: Option<String>,
 
     /// It is encouraged to add additional information about the origin that supplies MQTT entities via MQTT discovery by adding the origin option (can be abbreviated to o) to the discovery payload. Note that these options also support abbreviations. Information of the origin will be logged to the core event log when an item is discovered or updated.
-    #[serde(rename = "o")]
+    #[serde(rename = "o", alias = "origin", default)]
+    #[builder(default)]
     pub origin: Origin,
 
     /// Information about the device this button is a part of to tie it into the [device registry](https://developers.home-assistant.io/docs/en/device_registry_index.html). Only works when [`unique_id`](#unique_id) is set. At least one of identifiers or connections must be present to identify the device.
-    #[serde(rename = "dev")]
+    #[serde(rename = "dev", alias = "device", default)]
+    #[builder(default)]
     pub device: Device,
 
     /// A list of MQTT topics subscribed to receive availability (online/offline) updates. Must not be used together with `availability_topic`.
     #[serde(flatten)]
+    #[builder(default)]
     pub availability: Availability,
 
     /// The category of the entity. (optional, default: None)
-    #[serde(rename = "ent_cat", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "ent_cat",
+        alias = "entity_category",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub entity_category: Option<EntityCategory>,
 
     /// The type of automation, must be 'trigger'.
-    #[serde(rename = "atype")]
+    #[serde(rename = "atype", alias = "automation_type")]
     pub automation_type: String,
 
     /// Optional payload to match the payload being sent over the topic.
-    #[serde(rename = "pl", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "pl",
+        alias = "payload",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub payload: Option<String>,
 
     /// The maximum QoS level to be used when receiving and publishing messages.
@@ -191,23 +207,42 @@ pub struct DeviceTrigger {
     pub qos: Option<Qos>,
 
     /// The MQTT topic subscribed to receive trigger events.
-    #[serde(rename = "t")]
-    pub topic: String,
+    #[serde(rename = "t", alias = "topic")]
+    pub topic: Topic,
 
     /// The type of the trigger, e.g. `button_short_press`. Entries supported by the frontend: `button_short_press`, `button_short_release`, `button_long_press`, `button_long_release`, `button_double_press`, `button_triple_press`, `button_quadruple_press`, `button_quintuple_press`. If set to an unsupported value, will render as `subtype type`, e.g. `button_1 spammed` with `type` set to `spammed` and `subtype` set to `button_1`
     #[serde(rename = "type")]
     pub r#type: String,
 
     /// The subtype of the trigger, e.g. `button_1`. Entries supported by the frontend: `turn_on`, `turn_off`, `button_1`, `button_2`, `button_3`, `button_4`, `button_5`, `button_6`. If set to an unsupported value, will render as `subtype type`, e.g. `left_button pressed` with `type` set to `button_short_press` and `subtype` set to `left_button`
-    #[serde(rename = "stype")]
+    #[serde(rename = "stype", alias = "subtype")]
     pub subtype: String,
 
     /// Defines a [template](/docs/configuration/templating/#using-templates-with-the-mqtt-integration) to extract the value.
-    #[serde(rename = "val_tpl", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "val_tpl",
+        alias = "value_template",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub value_template: Option<String>,
 }
 
 impl DeviceTrigger {
+    /// Creates the entity with its required attributes, the optional attributes are left unset.
+    pub fn new(
+        automation_type: impl Into<String>,
+        topic: impl Into<Topic>,
+        r#type: impl Into<String>,
+        subtype: impl Into<String>,
+    ) -> Self {
+        Self::builder()
+            .automation_type(automation_type)
+            .topic(topic)
+            .r#type(r#type)
+            .subtype(subtype)
+            .build()
+    }
+
     /// Replaces `~` with this value in any MQTT topic attribute.
     /// [See Home Assistant documentation](https://www.home-assistant.io/integrations/mqtt/#using-abbreviations-and-base-topic)
     pub fn topic_prefix<S: Into<String>>(mut self, topic_prefix: S) -> Self {
@@ -258,7 +293,7 @@ impl DeviceTrigger {
     }
 
     /// The MQTT topic subscribed to receive trigger events.
-    pub fn topic<T: Into<String>>(mut self, topic: T) -> Self {
+    pub fn topic<T: Into<Topic>>(mut self, topic: T) -> Self {
         self.topic = topic.into();
         self
     }
@@ -0,0 +1,635 @@
+use super::common::Qos;
+use super::common::{Availability, Device, EntityCategory, Origin};
+use crate::decimal::{DecimalError, IntoDecimal, TryIntoDecimal};
+use crate::topic::Topic;
+use crate::Entity;
+use bon::Builder;
+use rust_decimal::Decimal;
+use serde_derive::{Deserialize, Serialize};
+
+/// The light MQTT documentation mixes the `default`, `json` and `template` schemas so this entity isn't generated,
+/// only the `json` schema is supported.
+///
+/// The `mqtt` light platform with JSON schema lets you control a MQTT-enabled light that can receive JSON messages.
+///
+/// The light receives its commands as a `LightJsonState` JSON payload on the `command_topic` and reports its state
+/// with the same payload on the `state_topic`.
+///
+/// ```yaml
+/// # Example configuration.yaml entry
+/// mqtt:
+///   - light:
+///       schema: json
+///       name: mqtt_json_light_1
+///       state_topic: "home/rgb1"
+///       command_topic: "home/rgb1/set"
+///       brightness: true
+///       supported_color_modes: ["rgb"]
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Builder)]
+#[cfg_attr(feature = "entity-default", derive(Default))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[builder(on(String, into), on(Topic, into))]
+pub struct Light {
+    /// Replaces `~` with this value in any MQTT topic attribute.
+    /// [See Home Assistant documentation](https://www.home-assistant.io/integrations/mqtt/#using-abbreviations-and-base-topic)
+    #[serde(rename = "~", skip_serializing_if = "Option::is_none")]
+    pub topic_prefix: Option<String>,
+
+    /// It is encouraged to add additional information about the origin that supplies MQTT entities via MQTT discovery by adding the origin option (can be abbreviated to o) to the discovery payload. Note that these options also support abbreviations. Information of the origin will be logged to the core event log when an item is discovered or updated.
+    #[serde(rename = "o", alias = "origin", default)]
+    #[builder(default)]
+    pub origin: Origin,
+
+    /// Information about the device this light is a part of to tie it into the [device registry](https://developers.home-assistant.io/docs/en/device_registry_index.html). Only works when [`unique_id`](#unique_id) is set. At least one of identifiers or connections must be present to identify the device.
+    #[serde(rename = "dev", alias = "device", default)]
+    #[builder(default)]
+    pub device: Device,
+
+    /// A list of MQTT topics subscribed to receive availability (online/offline) updates. Must not be used together with `availability_topic`.
+    #[serde(flatten)]
+    #[builder(default)]
+    pub availability: Availability,
+
+    /// The category of the entity. (optional, default: None)
+    #[serde(
+        rename = "ent_cat",
+        alias = "entity_category",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub entity_category: Option<EntityCategory>,
+
+    /// The schema to use, always `json`.
+    #[serde(rename = "schema")]
+    #[builder(default)]
+    pub schema: LightSchema,
+
+    /// Flag that defines if light supports brightness.
+    #[serde(rename = "brightness", skip_serializing_if = "Option::is_none")]
+    pub brightness: Option<bool>,
+
+    /// Defines the maximum brightness value (i.e., 100%) of the MQTT device.
+    #[serde(
+        rename = "bri_scl",
+        alias = "brightness_scale",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub brightness_scale: Option<i32>,
+
+    /// The MQTT topic to publish commands to change the light’s state.
+    #[serde(rename = "cmd_t", alias = "command_topic")]
+    pub command_topic: Topic,
+
+    /// Flag that defines if the light supports effects.
+    #[serde(rename = "effect", skip_serializing_if = "Option::is_none")]
+    pub effect: Option<bool>,
+
+    /// The list of effects the light supports.
+    #[serde(
+        rename = "fx_list",
+        alias = "effect_list",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub effect_list: Option<Vec<String>>,
+
+    /// Flag which defines if the entity should be enabled when first added.
+    #[serde(
+        rename = "en",
+        alias = "enabled_by_default",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub enabled_by_default: Option<bool>,
+
+    /// The encoding of the payloads received and published messages. Set to `""` to disable decoding of incoming payload.
+    #[serde(
+        rename = "e",
+        alias = "encoding",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub encoding: Option<String>,
+
+    /// The duration, in seconds, of a “long” flash.
+    #[serde(
+        rename = "flsh_tlng",
+        alias = "flash_time_long",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub flash_time_long: Option<i32>,
+
+    /// The duration, in seconds, of a “short” flash.
+    #[serde(
+        rename = "flsh_tsht",
+        alias = "flash_time_short",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub flash_time_short: Option<i32>,
+
+    /// [Icon](/docs/configuration/customizing-devices/#icon) for the entity.
+    #[serde(rename = "ic", alias = "icon", skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+
+    /// Defines a [template](/docs/configuration/templating/#using-templates-with-the-mqtt-integration) to extract the JSON dictionary from messages received on the `json_attributes_topic`.
+    #[serde(
+        rename = "json_attr_tpl",
+        alias = "json_attributes_template",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub json_attributes_template: Option<String>,
+
+    /// The MQTT topic subscribed to receive a JSON dictionary payload and then set as sensor attributes.
+    #[serde(
+        rename = "json_attr_t",
+        alias = "json_attributes_topic",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub json_attributes_topic: Option<Topic>,
+
+    /// The maximum color temperature in mireds.
+    #[serde(
+        rename = "max_mirs",
+        alias = "max_mireds",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub max_mireds: Option<i32>,
+
+    /// The minimum color temperature in mireds.
+    #[serde(
+        rename = "min_mirs",
+        alias = "min_mireds",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub min_mireds: Option<i32>,
+
+    /// The name of the light. Can be set to `null` if only the device name is relevant.
+    #[serde(rename = "name", skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    /// Used instead of `name` for automatic generation of `entity_id`
+    #[serde(
+        rename = "obj_id",
+        alias = "object_id",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub object_id: Option<String>,
+
+    /// Flag that defines if the light works in optimistic mode.
+    #[serde(
+        rename = "opt",
+        alias = "optimistic",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub optimistic: Option<bool>,
+
+    /// The maximum QoS level to be used when receiving and publishing messages.
+    #[serde(rename = "qos", skip_serializing_if = "Option::is_none")]
+    pub qos: Option<Qos>,
+
+    /// If the published message should have the retain flag on or not.
+    #[serde(
+        rename = "ret",
+        alias = "retain",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub retain: Option<bool>,
+
+    /// The MQTT topic subscribed to receive state updates.
+    #[serde(
+        rename = "stat_t",
+        alias = "state_topic",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub state_topic: Option<Topic>,
+
+    /// A list of color modes supported by the light.
+    #[serde(
+        rename = "sup_clrm",
+        alias = "supported_color_modes",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub supported_color_modes: Option<Vec<ColorMode>>,
+
+    /// An ID that uniquely identifies this light. If two lights have the same unique ID, Home Assistant will raise an exception.
+    #[serde(
+        rename = "uniq_id",
+        alias = "unique_id",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub unique_id: Option<String>,
+
+    /// Defines the maximum white level (i.e., 100%) of the MQTT device.
+    #[serde(
+        rename = "whit_scl",
+        alias = "white_scale",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub white_scale: Option<i32>,
+}
+
+/// The light schema, only `json` is supported.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum LightSchema {
+    #[serde(rename = "json")]
+    #[default]
+    Json,
+}
+
+/// A color mode of a light.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum ColorMode {
+    Onoff,
+    Brightness,
+    ColorTemp,
+    Hs,
+    Xy,
+    Rgb,
+    Rgbw,
+    Rgbww,
+    White,
+}
+
+impl Light {
+    /// Creates the entity with its required attributes, the optional attributes are left unset.
+    pub fn new(command_topic: impl Into<Topic>) -> Self {
+        Self::builder().command_topic(command_topic).build()
+    }
+
+    /// Replaces `~` with this value in any MQTT topic attribute.
+    /// [See Home Assistant documentation](https://www.home-assistant.io/integrations/mqtt/#using-abbreviations-and-base-topic)
+    pub fn topic_prefix<S: Into<String>>(mut self, topic_prefix: S) -> Self {
+        self.topic_prefix = Some(topic_prefix.into());
+        self
+    }
+
+    /// It is encouraged to add additional information about the origin that supplies MQTT entities via MQTT discovery by adding the origin option (can be abbreviated to o) to the discovery payload. Note that these options also support abbreviations. Information of the origin will be logged to the core event log when an item is discovered or updated.
+    pub fn origin(mut self, origin: Origin) -> Self {
+        self.origin = origin;
+        self
+    }
+
+    /// Information about the device this light is a part of to tie it into the [device registry](https://developers.home-assistant.io/docs/device_registry_index/). Only works when `unique_id` is set. At least one of identifiers or connections must be present to identify the device.
+    pub fn device(mut self, device: Device) -> Self {
+        self.device = device;
+        self
+    }
+
+    /// The category of the entity. (optional, default: None)
+    pub fn entity_category(mut self, entity_category: EntityCategory) -> Self {
+        self.entity_category = Some(entity_category);
+        self
+    }
+
+    /// Defines how HA will check for entity availability.
+    pub fn availability(mut self, availability: Availability) -> Self {
+        self.availability = availability;
+        self
+    }
+
+    /// Defines the maximum brightness value (i.e., 100%) of the MQTT device.
+    pub fn brightness_scale(mut self, brightness_scale: i32) -> Self {
+        self.brightness_scale = Some(brightness_scale);
+        self
+    }
+
+    /// The MQTT topic to publish commands to change the light’s state.
+    pub fn command_topic<T: Into<Topic>>(mut self, command_topic: T) -> Self {
+        self.command_topic = command_topic.into();
+        self
+    }
+
+    /// The list of effects the light supports, also enables the effects.
+    pub fn effect_list<T: Into<String>>(mut self, effect_list: Vec<T>) -> Self {
+        self.effect = Some(true);
+        self.effect_list = Some(effect_list.into_iter().map(|v| v.into()).collect());
+        self
+    }
+
+    /// Flag which defines if the entity should be enabled when first added.
+    pub fn enabled_by_default(mut self, enabled_by_default: bool) -> Self {
+        self.enabled_by_default = Some(enabled_by_default);
+        self
+    }
+
+    /// The encoding of the payloads received and published messages. Set to `""` to disable decoding of incoming payload.
+    pub fn encoding<T: Into<String>>(mut self, encoding: T) -> Self {
+        self.encoding = Some(encoding.into());
+        self
+    }
+
+    /// The duration, in seconds, of a “long” flash.
+    pub fn flash_time_long(mut self, flash_time_long: i32) -> Self {
+        self.flash_time_long = Some(flash_time_long);
+        self
+    }
+
+    /// The duration, in seconds, of a “short” flash.
+    pub fn flash_time_short(mut self, flash_time_short: i32) -> Self {
+        self.flash_time_short = Some(flash_time_short);
+        self
+    }
+
+    /// [Icon](/docs/configuration/customizing-devices/#icon) for the entity.
+    pub fn icon<T: Into<String>>(mut self, icon: T) -> Self {
+        self.icon = Some(icon.into());
+        self
+    }
+
+    /// Defines a [template](/docs/configuration/templating/#using-templates-with-the-mqtt-integration) to extract the JSON dictionary from messages received on the `json_attributes_topic`.
+    pub fn json_attributes_template<T: Into<String>>(
+        mut self,
+        json_attributes_template: T,
+    ) -> Self {
+        self.json_attributes_template = Some(json_attributes_template.into());
+        self
+    }
+
+    /// The MQTT topic subscribed to receive a JSON dictionary payload and then set as sensor attributes.
+    pub fn json_attributes_topic<T: Into<Topic>>(mut self, json_attributes_topic: T) -> Self {
+        self.json_attributes_topic = Some(json_attributes_topic.into());
+        self
+    }
+
+    /// The color temperature range in mireds.
+    pub fn mireds(mut self, min_mireds: i32, max_mireds: i32) -> Self {
+        self.min_mireds = Some(min_mireds);
+        self.max_mireds = Some(max_mireds);
+        self
+    }
+
+    /// The name of the light. Can be set to `null` if only the device name is relevant.
+    pub fn name<T: Into<String>>(mut self, name: T) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Used instead of `name` for automatic generation of `entity_id`
+    pub fn object_id<T: Into<String>>(mut self, object_id: T) -> Self {
+        self.object_id = Some(object_id.into());
+        self
+    }
+
+    /// Flag that defines if the light works in optimistic mode.
+    pub fn optimistic(mut self, optimistic: bool) -> Self {
+        self.optimistic = Some(optimistic);
+        self
+    }
+
+    /// The maximum QoS level to be used when receiving and publishing messages.
+    pub fn qos(mut self, qos: Qos) -> Self {
+        self.qos = Some(qos);
+        self
+    }
+
+    /// If the published message should have the retain flag on or not.
+    pub fn retain(mut self, retain: bool) -> Self {
+        self.retain = Some(retain);
+        self
+    }
+
+    /// The MQTT topic subscribed to receive state updates.
+    pub fn state_topic<T: Into<Topic>>(mut self, state_topic: T) -> Self {
+        self.state_topic = Some(state_topic.into());
+        self
+    }
+
+    /// The color modes supported by the light, `brightness` is set accordingly.
+    ///
+    /// Home Assistant requires `onoff` and `brightness` to be used alone: `onoff` is dropped when the light
+    /// supports another mode and `brightness` is dropped when the light supports colors or color temperature,
+    /// which imply a brightness control.
+    pub fn supported_color_modes(mut self, supported_color_modes: Vec<ColorMode>) -> Self {
+        let mut modes = supported_color_modes;
+        modes.sort();
+        modes.dedup();
+        if modes.len() > 1 {
+            modes.retain(|mode| *mode != ColorMode::Onoff);
+        }
+        if modes.len() > 1 {
+            modes.retain(|mode| *mode != ColorMode::Brightness);
+        }
+        self.brightness = Some(!modes.is_empty() && modes != [ColorMode::Onoff]);
+        self.supported_color_modes = Some(modes);
+        self
+    }
+
+    /// An ID that uniquely identifies this light. If two lights have the same unique ID, Home Assistant will raise an exception.
+    pub fn unique_id<T: Into<String>>(mut self, unique_id: T) -> Self {
+        self.unique_id = Some(unique_id.into());
+        self
+    }
+
+    /// Defines the maximum white level (i.e., 100%) of the MQTT device.
+    pub fn white_scale(mut self, white_scale: i32) -> Self {
+        self.white_scale = Some(white_scale);
+        self
+    }
+}
+
+impl From<Light> for Entity {
+    fn from(value: Light) -> Self {
+        Entity::Light(value)
+    }
+}
+
+/// The `ON` or `OFF` state of a light.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum LightPower {
+    #[serde(rename = "ON")]
+    On,
+    #[serde(rename = "OFF")]
+    Off,
+}
+
+/// The color of a light, its variant matches a color mode.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(untagged)]
+pub enum LightColor {
+    Rgbww {
+        r: u8,
+        g: u8,
+        b: u8,
+        c: u8,
+        w: u8,
+    },
+    Rgbw {
+        r: u8,
+        g: u8,
+        b: u8,
+        w: u8,
+    },
+    Rgb {
+        r: u8,
+        g: u8,
+        b: u8,
+    },
+    Xy {
+        #[cfg_attr(feature = "schema", schemars(with = "f64"))]
+        x: Decimal,
+        #[cfg_attr(feature = "schema", schemars(with = "f64"))]
+        y: Decimal,
+    },
+    Hs {
+        #[cfg_attr(feature = "schema", schemars(with = "f64"))]
+        h: Decimal,
+        #[cfg_attr(feature = "schema", schemars(with = "f64"))]
+        s: Decimal,
+    },
+}
+
+impl LightColor {
+    /// The color mode matching the color.
+    pub fn color_mode(&self) -> ColorMode {
+        match self {
+            LightColor::Rgbww { .. } => ColorMode::Rgbww,
+            LightColor::Rgbw { .. } => ColorMode::Rgbw,
+            LightColor::Rgb { .. } => ColorMode::Rgb,
+            LightColor::Xy { .. } => ColorMode::Xy,
+            LightColor::Hs { .. } => ColorMode::Hs,
+        }
+    }
+}
+
+/// The JSON payload of a light with the `json` schema: the state published on `state_topic` and the commands
+/// received on `command_topic`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct LightJsonState {
+    pub state: LightPower,
+    /// The brightness, from 0 to `brightness_scale`.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub brightness: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub color_mode: Option<ColorMode>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub color: Option<LightColor>,
+    /// The color temperature in mireds.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub color_temp: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub effect: Option<String>,
+    /// The transition duration in seconds.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    #[cfg_attr(feature = "schema", schemars(with = "Option<f64>"))]
+    pub transition: Option<Decimal>,
+    /// The white level, from 0 to `white_scale`.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub white: Option<u32>,
+}
+
+impl LightJsonState {
+    pub fn new(state: LightPower) -> Self {
+        Self {
+            state,
+            brightness: None,
+            color_mode: None,
+            color: None,
+            color_temp: None,
+            effect: None,
+            transition: None,
+            white: None,
+        }
+    }
+
+    pub fn on() -> Self {
+        Self::new(LightPower::On)
+    }
+
+    pub fn off() -> Self {
+        Self::new(LightPower::Off)
+    }
+
+    pub fn brightness(mut self, brightness: u32) -> Self {
+        self.brightness = Some(brightness);
+        self
+    }
+
+    /// The color and its matching color mode.
+    pub fn color(mut self, color: LightColor) -> Self {
+        self.color_mode = Some(color.color_mode());
+        self.color = Some(color);
+        self
+    }
+
+    /// The color temperature in mireds, with the `color_temp` color mode.
+    pub fn color_temp(mut self, color_temp: u32) -> Self {
+        self.color_mode = Some(ColorMode::ColorTemp);
+        self.color_temp = Some(color_temp);
+        self
+    }
+
+    pub fn effect<S: Into<String>>(mut self, effect: S) -> Self {
+        self.effect = Some(effect.into());
+        self
+    }
+
+    pub fn transition<D: IntoDecimal>(mut self, transition: D) -> Self {
+        self.transition = Some(transition.into_decimal());
+        self
+    }
+
+    /// Same as [`Self::transition`] for the floats, fails when the value is NaN, infinite or out of the `Decimal` range.
+    pub fn try_transition<D: TryIntoDecimal>(self, transition: D) -> Result<Self, DecimalError> {
+        Ok(self.transition(transition.try_into_decimal()?))
+    }
+
+    /// The white level, with the `white` color mode.
+    pub fn white(mut self, white: u32) -> Self {
+        self.color_mode = Some(ColorMode::White);
+        self.white = Some(white);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn can_serialize_json_lights() {
+        let light = Light::new("home/rgb1/set")
+            .state_topic("home/rgb1")
+            .supported_color_modes(vec![
+                ColorMode::Rgb,
+                ColorMode::Onoff,
+                ColorMode::Brightness,
+                ColorMode::ColorTemp,
+                ColorMode::Rgb,
+            ]);
+        assert_eq!(
+            serde_json::to_value(&light).unwrap(),
+            json!({
+                "o": {"name": ""},
+                "dev": {},
+                "avty_mode": "all",
+                "avty": [],
+                "schema": "json",
+                "brightness": true,
+                "cmd_t": "home/rgb1/set",
+                "stat_t": "home/rgb1",
+                "sup_clrm": ["color_temp", "rgb"]
+            })
+        );
+
+        let state =
+            LightJsonState::on()
+                .brightness(128)
+                .color(LightColor::Rgb { r: 255, g: 0, b: 0 });
+        let payload = json!({
+            "state": "ON",
+            "brightness": 128,
+            "color_mode": "rgb",
+            "color": {"r": 255, "g": 0, "b": 0}
+        });
+        assert_eq!(serde_json::to_value(&state).unwrap(), payload);
+        assert_eq!(
+            serde_json::from_value::<LightJsonState>(payload).unwrap(),
+            state
+        );
+    }
+}
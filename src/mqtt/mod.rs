@@ -1,30 +1,69 @@
+// entity documentations are copied as is from the Home Assistant markdown documentation
+#![allow(clippy::doc_lazy_continuation)]
+// clippy mistakes the `into` of distinct builder `on(...)` clauses for duplicated attributes
+#![allow(clippy::duplicated_attributes)]
+// the device classes parsing is a single wildcard for the entities without device classes
+#![allow(clippy::match_single_binding)]
+
+pub mod abbreviations;
 pub mod common;
 pub mod device_classes;
+// hand-written, the light documentation mixes three schemas
+#[cfg(feature = "entity-light")]
+pub mod light;
 pub mod units;
 
+#[cfg(feature = "entity-alarm_control_panel")]
 pub mod alarm_control_panel;
+#[cfg(feature = "entity-binary_sensor")]
 pub mod binary_sensor;
+#[cfg(feature = "entity-button")]
 pub mod button;
+#[cfg(feature = "entity-camera")]
 pub mod camera;
+#[cfg(feature = "entity-climate")]
 pub mod climate;
+#[cfg(feature = "entity-cover")]
 pub mod cover;
+#[cfg(feature = "entity-device_tracker")]
 pub mod device_tracker;
+#[cfg(feature = "entity-device_trigger")]
 pub mod device_trigger;
+#[cfg(feature = "entity-event")]
 pub mod event;
+#[cfg(feature = "entity-fan")]
 pub mod fan;
+#[cfg(feature = "entity-humidifier")]
 pub mod humidifier;
+#[cfg(feature = "entity-image")]
 pub mod image;
+#[cfg(feature = "entity-lawn_mower")]
 pub mod lawn_mower;
+#[cfg(feature = "entity-lock")]
 pub mod lock;
+#[cfg(feature = "entity-notify")]
+pub mod notify;
+#[cfg(feature = "entity-number")]
 pub mod number;
+#[cfg(feature = "entity-scene")]
 pub mod scene;
+#[cfg(feature = "entity-select")]
 pub mod select;
+#[cfg(feature = "entity-sensor")]
 pub mod sensor;
+#[cfg(feature = "entity-siren")]
 pub mod siren;
+#[cfg(feature = "entity-switch")]
 pub mod switch;
+#[cfg(feature = "entity-tag")]
 pub mod tag;
+#[cfg(feature = "entity-text")]
 pub mod text;
+#[cfg(feature = "entity-update")]
 pub mod update;
+#[cfg(feature = "entity-vacuum")]
 pub mod vacuum;
+#[cfg(feature = "entity-valve")]
 pub mod valve;
+#[cfg(feature = "entity-water_heater")]
 pub mod water_heater;
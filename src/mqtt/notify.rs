@@ -0,0 +1,442 @@
+use super::common::Qos;
+use super::common::{Availability, Device, EntityCategory, Origin};
+use crate::topic::Topic;
+use crate::Entity;
+use bon::Builder;
+use serde_derive::{Deserialize, Serialize};
+
+/// ---
+/// title: "MQTT notify"
+/// description: "Instructions on how to integrate MQTT notify entities into Home Assistant."
+/// ha_category:
+///   - Notifications
+/// ha_release: 2024.5
+/// ha_iot_class: Configurable
+/// ha_domain: mqtt
+/// ---
+///
+/// The **MQTT notify** platform lets you send an MQTT message when the `send_message` service is called. This can be used to expose a service of a remote device that allows processing a message, such as showing it on a screen.
+///
+/// ## Configuration
+///
+/// ```yaml
+/// # Example configuration.yaml entry
+/// mqtt:
+///   - notify:
+///       command_topic: "home/living_room/status_screen/notifications"
+/// ```
+///
+/// {% configuration %}
+/// availability:
+///   description: A list of MQTT topics subscribed to receive availability (online/offline) updates. Must not be used together with `availability_topic`.
+///   required: false
+///   type: list
+///   keys:
+///     payload_available:
+///       description: The payload that represents the available state.
+///       required: false
+///       type: string
+///       default: online
+///     payload_not_available:
+///       description: The payload that represents the unavailable state.
+///       required: false
+///       type: string
+///       default: offline
+///     topic:
+///       description: An MQTT topic subscribed to receive availability (online/offline) updates.
+///       required: true
+///       type: string
+///     value_template:
+///       description: "Defines a [template](/docs/configuration/templating/#using-templates-with-the-mqtt-integration) to extract the device's availability from the `topic`. To determine the device's availability, the result of this template will be compared to `payload_available` and `payload_not_available`."
+///       required: false
+///       type: template
+/// availability_mode:
+///   description: When `availability` is configured, this controls the conditions needed to set the entity to `available`. Valid entries are `all`, `any`, and `latest`. If set to `all`, `payload_available` must be received on all configured availability topics before the entity is marked as online. If set to `any`, `payload_available` must be received on at least one configured availability topic before the entity is marked as online. If set to `latest`, the last `payload_available` or `payload_not_available` received on any configured availability topic controls the availability.
+///   required: false
+///   type: string
+///   default: latest
+/// availability_template:
+///   description: "Defines a [template](/docs/configuration/templating/#using-templates-with-the-mqtt-integration) to extract the device's availability from the `availability_topic`. To determine the device's availability result, the template will be compared to `payload_available` and `payload_not_available`."
+///   required: false
+///   type: template
+/// availability_topic:
+///   description: The MQTT topic subscribed to receive availability (online/offline) updates. Must not be used together with `availability`.
+///   required: false
+///   type: string
+/// command_template:
+///   description: Defines a [template](/docs/configuration/templating/#using-templates-with-the-mqtt-integration) to generate the payload to send to `command_topic`.
+///   required: false
+///   type: template
+/// command_topic:
+///   description: The MQTT topic to publish send message commands at.
+///   required: true
+///   type: string
+/// device:
+///   description: "Information about the device this notify entity is a part of to tie it into the [device registry](https://developers.home-assistant.io/docs/en/device_registry_index.html). Only works when [`unique_id`](#unique_id) is set. At least one of the identifiers or connections must be present to identify the device."
+///   required: false
+///   type: map
+///   keys:
+///     configuration_url:
+///       description: 'A link to the webpage that can manage the configuration of this device. Can be either an `http://`, `https://` or an internal `homeassistant://` URL.'
+///       required: false
+///       type: string
+///     connections:
+///       description: 'A list of connections of the device to the outside world as a list of tuples `[connection_type, connection_identifier]`. For example, the MAC address of a network interface: `"connections": [["mac", "02:5b:26:a8:dc:12"]]`.'
+///       required: false
+///       type: list
+///     hw_version:
+///       description: The hardware version of the device.
+///       required: false
+///       type: string
+///     identifiers:
+///       description: A list of IDs that uniquely identify the device. For example, a serial number.
+///       required: false
+///       type: [string, list]
+///     manufacturer:
+///       description: The manufacturer of the device.
+///       required: false
+///       type: string
+///     model:
+///       description: The model of the device.
+///       required: false
+///       type: string
+///     name:
+///       description: The name of the device.
+///       required: false
+///       type: string
+///     serial_number:
+///       description: "The serial number of the device."
+///       required: false
+///       type: string
+///     suggested_area:
+///       description: 'Suggest an area if the device isn’t in one yet.'
+///       required: false
+///       type: string
+///     sw_version:
+///       description: The firmware version of the device.
+///       required: false
+///       type: string
+///     via_device:
+///       description: 'Identifier of a device that routes messages between this device and Home Assistant. Examples of such devices are hubs, or parent devices of a sub-device. This is used to show device topology in Home Assistant.'
+///       required: false
+///       type: string
+/// enabled_by_default:
+///   description: Flag which defines if the entity should be enabled when first added.
+///   required: false
+///   type: boolean
+///   default: true
+/// encoding:
+///   description: The encoding of the published messages.
+///   required: false
+///   type: string
+///   default: "utf-8"
+/// entity_category:
+///   description: The [category](https://developers.home-assistant.io/docs/core/entity#generic-properties) of the entity.
+///   required: false
+///   type: string
+/// icon:
+///   description: "[Icon](/docs/configuration/customizing-devices/#icon) for the entity."
+///   required: false
+///   type: icon
+/// json_attributes_template:
+///   description: "Defines a [template](/docs/configuration/templating/#using-templates-with-the-mqtt-integration) to extract the JSON dictionary from messages received on the `json_attributes_topic`. Usage example can be found in [MQTT sensor](/integrations/sensor.mqtt/#json-attributes-template-configuration) documentation."
+///   required: false
+///   type: template
+/// json_attributes_topic:
+///   description: The MQTT topic subscribed to receive a JSON dictionary payload and then set as sensor attributes. Usage example can be found in [MQTT sensor](/integrations/sensor.mqtt/#json-attributes-topic-configuration) documentation.
+///   required: false
+///   type: string
+/// name:
+///   description: The name to use when displaying this notify entity. Can be set to `null` if only the device name is relevant.
+///   required: false
+///   type: string
+///   default: MQTT notify
+/// object_id:
+///   description: Used instead of `name` for automatic generation of `entity_id`
+///   required: false
+///   type: string
+/// payload_available:
+///   description: The payload that represents the available state.
+///   required: false
+///   type: string
+///   default: online
+/// payload_not_available:
+///   description: The payload that represents the unavailable state.
+///   required: false
+///   type: string
+///   default: offline
+/// qos:
+///   description: The maximum QoS level to be used when receiving and publishing messages.
+///   required: false
+///   type: integer
+///   default: 0
+/// retain:
+///   description: If the published message should have the retain flag on or not.
+///   required: false
+///   type: boolean
+///   default: false
+/// unique_id:
+///   description: An ID that uniquely identifies this notify entity. If two notify entities have the same unique ID, Home Assistant will raise an exception.
+///   required: false
+///   type: string
+/// {% endconfiguration %}
+///
+/// <div class='note warning'>
+///
+/// Make sure that your topic matches exactly. `some-topic/` and `some-topic` are different topics.
+///
+/// </div>
+///
+/// ## Examples
+///
+/// In this section, you will find some real-life examples of how to use this feature.
+///
+/// ### Full configuration
+///
+/// The example below shows a full configuration for a notify entity.
+///
+/// ```yaml
+/// # Example configuration.yaml entry
+/// mqtt:
+///   - notify:
+///       unique_id: living_room_stat_scr01
+///       name: "Living room status screen"
+///       command_topic: "home/living_room/status_screen/notifications"
+///       availability:
+///         - topic: "home/living_room/status_screen/available"
+///       qos: 0
+///       retain: false
+/// ```
+///
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Builder)]
+#[cfg_attr(feature = "entity-default", derive(Default))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[builder(on(String, into), on(Topic, into))]
+pub struct Notify {
+    /// Replaces `~` with this value in any MQTT topic attribute.
+    /// [See Home Assistant documentation](https://www.home-assistant.io/integrations/mqtt/#using-abbreviations-and-base-topic)
+    #[serde(rename = "~", skip_serializing_if = "Option::is_none")]
+    pub topic_prefix: Option<String>,
+
+    /// It is encouraged to add additional information about the origin that supplies MQTT entities via MQTT discovery by adding the origin option (can be abbreviated to o) to the discovery payload. Note that these options also support abbreviations. Information of the origin will be logged to the core event log when an item is discovered or updated.
+    #[serde(rename = "o", alias = "origin", default)]
+    #[builder(default)]
+    pub origin: Origin,
+
+    /// Information about the device this button is a part of to tie it into the [device registry](https://developers.home-assistant.io/docs/en/device_registry_index.html). Only works when [`unique_id`](#unique_id) is set. At least one of identifiers or connections must be present to identify the device.
+    #[serde(rename = "dev", alias = "device", default)]
+    #[builder(default)]
+    pub device: Device,
+
+    /// A list of MQTT topics subscribed to receive availability (online/offline) updates. Must not be used together with `availability_topic`.
+    #[serde(flatten)]
+    #[builder(default)]
+    pub availability: Availability,
+
+    /// The category of the entity. (optional, default: None)
+    #[serde(
+        rename = "ent_cat",
+        alias = "entity_category",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub entity_category: Option<EntityCategory>,
+
+    /// Defines a [template](/docs/configuration/templating/#using-templates-with-the-mqtt-integration) to generate the payload to send to `command_topic`.
+    #[serde(
+        rename = "cmd_tpl",
+        alias = "command_template",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub command_template: Option<String>,
+
+    /// The MQTT topic to publish send message commands at.
+    #[serde(rename = "cmd_t", alias = "command_topic")]
+    pub command_topic: Topic,
+
+    /// Flag which defines if the entity should be enabled when first added.
+    #[serde(
+        rename = "en",
+        alias = "enabled_by_default",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub enabled_by_default: Option<bool>,
+
+    /// The encoding of the published messages.
+    #[serde(
+        rename = "e",
+        alias = "encoding",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub encoding: Option<String>,
+
+    /// [Icon](/docs/configuration/customizing-devices/#icon) for the entity.
+    #[serde(rename = "ic", alias = "icon", skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+
+    /// Defines a [template](/docs/configuration/templating/#using-templates-with-the-mqtt-integration) to extract the JSON dictionary from messages received on the `json_attributes_topic`. Usage example can be found in [MQTT sensor](/integrations/sensor.mqtt/#json-attributes-template-configuration) documentation.
+    #[serde(
+        rename = "json_attr_tpl",
+        alias = "json_attributes_template",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub json_attributes_template: Option<String>,
+
+    /// The MQTT topic subscribed to receive a JSON dictionary payload and then set as sensor attributes. Usage example can be found in [MQTT sensor](/integrations/sensor.mqtt/#json-attributes-topic-configuration) documentation.
+    #[serde(
+        rename = "json_attr_t",
+        alias = "json_attributes_topic",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub json_attributes_topic: Option<Topic>,
+
+    /// The name to use when displaying this notify entity. Can be set to `null` if only the device name is relevant.
+    #[serde(rename = "name", skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    /// Used instead of `name` for automatic generation of `entity_id`
+    #[serde(
+        rename = "obj_id",
+        alias = "object_id",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub object_id: Option<String>,
+
+    /// The maximum QoS level to be used when receiving and publishing messages.
+    #[serde(rename = "qos", skip_serializing_if = "Option::is_none")]
+    pub qos: Option<Qos>,
+
+    /// If the published message should have the retain flag on or not.
+    #[serde(
+        rename = "ret",
+        alias = "retain",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub retain: Option<bool>,
+
+    /// An ID that uniquely identifies this notify entity. If two notify entities have the same unique ID, Home Assistant will raise an exception.
+    #[serde(
+        rename = "uniq_id",
+        alias = "unique_id",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub unique_id: Option<String>,
+}
+
+impl Notify {
+    /// Creates the entity with its required attributes, the optional attributes are left unset.
+    pub fn new(command_topic: impl Into<Topic>) -> Self {
+        Self::builder().command_topic(command_topic).build()
+    }
+
+    /// Replaces `~` with this value in any MQTT topic attribute.
+    /// [See Home Assistant documentation](https://www.home-assistant.io/integrations/mqtt/#using-abbreviations-and-base-topic)
+    pub fn topic_prefix<S: Into<String>>(mut self, topic_prefix: S) -> Self {
+        self.topic_prefix = Some(topic_prefix.into());
+        self
+    }
+
+    /// It is encouraged to add additional information about the origin that supplies MQTT entities via MQTT discovery by adding the origin option (can be abbreviated to o) to the discovery payload. Note that these options also support abbreviations. Information of the origin will be logged to the core event log when an item is discovered or updated.
+    pub fn origin(mut self, origin: Origin) -> Self {
+        self.origin = origin;
+        self
+    }
+
+    /// Information about the device this sensor is a part of to tie it into the [device registry](https://developers.home-assistant.io/docs/device_registry_index/). Only works when `unique_id` is set. At least one of identifiers or connections must be present to identify the device.
+    pub fn device(mut self, device: Device) -> Self {
+        self.device = device;
+        self
+    }
+
+    /// The category of the entity. (optional, default: None)
+    pub fn entity_category(mut self, entity_category: EntityCategory) -> Self {
+        self.entity_category = Some(entity_category);
+        self
+    }
+
+    /// Defines how HA will check for entity availability.
+    pub fn availability(mut self, availability: Availability) -> Self {
+        self.availability = availability;
+        self
+    }
+
+    /// Defines a [template](/docs/configuration/templating/#using-templates-with-the-mqtt-integration) to generate the payload to send to `command_topic`.
+    pub fn command_template<T: Into<String>>(mut self, command_template: T) -> Self {
+        self.command_template = Some(command_template.into());
+        self
+    }
+
+    /// The MQTT topic to publish send message commands at.
+    pub fn command_topic<T: Into<Topic>>(mut self, command_topic: T) -> Self {
+        self.command_topic = command_topic.into();
+        self
+    }
+
+    /// Flag which defines if the entity should be enabled when first added.
+    pub fn enabled_by_default(mut self, enabled_by_default: bool) -> Self {
+        self.enabled_by_default = Some(enabled_by_default);
+        self
+    }
+
+    /// The encoding of the published messages.
+    pub fn encoding<T: Into<String>>(mut self, encoding: T) -> Self {
+        self.encoding = Some(encoding.into());
+        self
+    }
+
+    /// [Icon](/docs/configuration/customizing-devices/#icon) for the entity.
+    pub fn icon<T: Into<String>>(mut self, icon: T) -> Self {
+        self.icon = Some(icon.into());
+        self
+    }
+
+    /// Defines a [template](/docs/configuration/templating/#using-templates-with-the-mqtt-integration) to extract the JSON dictionary from messages received on the `json_attributes_topic`. Usage example can be found in [MQTT sensor](/integrations/sensor.mqtt/#json-attributes-template-configuration) documentation.
+    pub fn json_attributes_template<T: Into<String>>(
+        mut self,
+        json_attributes_template: T,
+    ) -> Self {
+        self.json_attributes_template = Some(json_attributes_template.into());
+        self
+    }
+
+    /// The MQTT topic subscribed to receive a JSON dictionary payload and then set as sensor attributes. Usage example can be found in [MQTT sensor](/integrations/sensor.mqtt/#json-attributes-topic-configuration) documentation.
+    pub fn json_attributes_topic<T: Into<Topic>>(mut self, json_attributes_topic: T) -> Self {
+        self.json_attributes_topic = Some(json_attributes_topic.into());
+        self
+    }
+
+    /// The name to use when displaying this notify entity. Can be set to `null` if only the device name is relevant.
+    pub fn name<T: Into<String>>(mut self, name: T) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Used instead of `name` for automatic generation of `entity_id`
+    pub fn object_id<T: Into<String>>(mut self, object_id: T) -> Self {
+        self.object_id = Some(object_id.into());
+        self
+    }
+
+    /// The maximum QoS level to be used when receiving and publishing messages.
+    pub fn qos(mut self, qos: Qos) -> Self {
+        self.qos = Some(qos);
+        self
+    }
+
+    /// If the published message should have the retain flag on or not.
+    pub fn retain(mut self, retain: bool) -> Self {
+        self.retain = Some(retain);
+        self
+    }
+
+    /// An ID that uniquely identifies this notify entity. If two notify entities have the same unique ID, Home Assistant will raise an exception.
+    pub fn unique_id<T: Into<String>>(mut self, unique_id: T) -> Self {
+        self.unique_id = Some(unique_id.into());
+        self
+    }
+}
+
+impl From<Notify> for Entity {
+    fn from(value: Notify) -> Self {
+        Entity::Notify(value)
+    }
+}
@@ -1,10 +1,14 @@
+use super::common::NumberMode;
 use super::common::Qos;
 use super::common::{Availability, Device, EntityCategory, Origin};
 use super::device_classes::NumberDeviceClass;
 use super::units::Unit;
+use crate::decimal::{DecimalError, IntoDecimal, TryIntoDecimal};
+use crate::topic::Topic;
 use crate::Entity;
+use bon::Builder;
 pub use rust_decimal::Decimal;
-use serde_derive::Serialize;
+use serde_derive::{Deserialize, Serialize};
 
 /// ---
 /// title: "MQTT Number"
@@ -217,7 +221,10 @@ use serde_derive::Serialize;
 ///
 /// </div>
 ///
-#[derive(Clone, Debug, PartialEq, Serialize, Default)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Builder)]
+#[cfg_attr(feature = "entity-default", derive(Default))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[builder(on(String, into), on(Topic, into))]
 pub struct Number {
     /// Replaces `~` with this value in any MQTT topic attribute.
     /// [See Home Assistant documentation](https://www.home-assistant.io/integrations/mqtt/#using-abbreviations-and-base-topic)
@@ -225,79 +232,124 @@ pub struct Number {
     pub topic_prefix: Option<String>,
 
     /// It is encouraged to add additional information about the origin that supplies MQTT entities via MQTT discovery by adding the origin option (can be abbreviated to o) to the discovery payload. Note that these options also support abbreviations. Information of the origin will be logged to the core event log when an item is discovered or updated.
-    #[serde(rename = "o")]
+    #[serde(rename = "o", alias = "origin", default)]
+    #[builder(default)]
     pub origin: Origin,
 
     /// Information about the device this button is a part of to tie it into the [device registry](https://developers.home-assistant.io/docs/en/device_registry_index.html). Only works when [`unique_id`](#unique_id) is set. At least one of identifiers or connections must be present to identify the device.
-    #[serde(rename = "dev")]
+    #[serde(rename = "dev", alias = "device", default)]
+    #[builder(default)]
     pub device: Device,
 
     /// A list of MQTT topics subscribed to receive availability (online/offline) updates. Must not be used together with `availability_topic`.
     #[serde(flatten)]
+    #[builder(default)]
     pub availability: Availability,
 
     /// The category of the entity. (optional, default: None)
-    #[serde(rename = "ent_cat", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "ent_cat",
+        alias = "entity_category",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub entity_category: Option<EntityCategory>,
 
     /// Defines a [template](/docs/configuration/templating/#using-templates-with-the-mqtt-integration) to generate the payload to send to `command_topic`.
-    #[serde(rename = "cmd_tpl", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "cmd_tpl",
+        alias = "command_template",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub command_template: Option<String>,
 
     /// The MQTT topic to publish commands to change the number.
-    #[serde(rename = "cmd_t")]
-    pub command_topic: String,
+    #[serde(rename = "cmd_t", alias = "command_topic")]
+    pub command_topic: Topic,
 
     /// The [type/class](/integrations/number/#device-class) of the number. The `device_class` can be `null`.
-    #[serde(rename = "dev_cla", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "dev_cla",
+        alias = "device_class",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub device_class: Option<NumberDeviceClass>,
 
     /// Flag which defines if the entity should be enabled when first added.
-    #[serde(rename = "en", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "en",
+        alias = "enabled_by_default",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub enabled_by_default: Option<bool>,
 
     /// The encoding of the payloads received and published messages. Set to `""` to disable decoding of incoming payload.
-    #[serde(rename = "e", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "e",
+        alias = "encoding",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub encoding: Option<String>,
 
     /// [Icon](/docs/configuration/customizing-devices/#icon) for the entity.
-    #[serde(rename = "ic", skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "ic", alias = "icon", skip_serializing_if = "Option::is_none")]
     pub icon: Option<String>,
 
     /// Defines a [template](/docs/configuration/templating/#using-templates-with-the-mqtt-integration) to extract the JSON dictionary from messages received on the `json_attributes_topic`.
-    #[serde(rename = "json_attr_tpl", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "json_attr_tpl",
+        alias = "json_attributes_template",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub json_attributes_template: Option<String>,
 
     /// The MQTT topic subscribed to receive a JSON dictionary payload and then set as number attributes. Implies `force_update` of the current number state when a message is received on this topic.
-    #[serde(rename = "json_attr_t", skip_serializing_if = "Option::is_none")]
-    pub json_attributes_topic: Option<String>,
+    #[serde(
+        rename = "json_attr_t",
+        alias = "json_attributes_topic",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub json_attributes_topic: Option<Topic>,
 
     /// Minimum value.
     #[serde(rename = "min", skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "schema", schemars(with = "Option<f64>"))]
     pub min: Option<Decimal>,
 
     /// Maximum value.
     #[serde(rename = "max", skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "schema", schemars(with = "Option<f64>"))]
     pub max: Option<Decimal>,
 
     /// Control how the number should be displayed in the UI. Can be set to `box` or `slider` to force a display mode.
     #[serde(rename = "mode", skip_serializing_if = "Option::is_none")]
-    pub mode: Option<String>,
+    pub mode: Option<NumberMode>,
 
     /// The name of the Number. Can be set to `null` if only the device name is relevant.
     #[serde(rename = "name", skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
 
     /// Used instead of `name` for automatic generation of `entity_id`
-    #[serde(rename = "obj_id", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "obj_id",
+        alias = "object_id",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub object_id: Option<String>,
 
     /// Flag that defines if number works in optimistic mode.
-    #[serde(rename = "opt", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "opt",
+        alias = "optimistic",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub optimistic: Option<bool>,
 
     /// A special payload that resets the state to `unknown` when received on the `state_topic`.
-    #[serde(rename = "pl_rst", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "pl_rst",
+        alias = "payload_reset",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub payload_reset: Option<String>,
 
     /// The maximum QoS level to be used when receiving and publishing messages.
@@ -305,31 +357,57 @@ pub struct Number {
     pub qos: Option<Qos>,
 
     /// If the published message should have the retain flag on or not.
-    #[serde(rename = "ret", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "ret",
+        alias = "retain",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub retain: Option<bool>,
 
     /// The MQTT topic subscribed to receive number values.
-    #[serde(rename = "stat_t", skip_serializing_if = "Option::is_none")]
-    pub state_topic: Option<String>,
+    #[serde(
+        rename = "stat_t",
+        alias = "state_topic",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub state_topic: Option<Topic>,
 
     /// Step value. Smallest value `0.001`.
     #[serde(rename = "step", skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "schema", schemars(with = "Option<f64>"))]
     pub step: Option<Decimal>,
 
     /// An ID that uniquely identifies this Number. If two Numbers have the same unique ID Home Assistant will raise an exception.
-    #[serde(rename = "uniq_id", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "uniq_id",
+        alias = "unique_id",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub unique_id: Option<String>,
 
     /// Defines the unit of measurement of the sensor, if any. The `unit_of_measurement` can be `null`.
-    #[serde(rename = "unit_of_meas", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "unit_of_meas",
+        alias = "unit_of_measurement",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub unit_of_measurement: Option<Unit>,
 
     /// Defines a [template](/docs/configuration/templating/#using-templates-with-the-mqtt-integration) to extract the value.
-    #[serde(rename = "val_tpl", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "val_tpl",
+        alias = "value_template",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub value_template: Option<String>,
 }
 
 impl Number {
+    /// Creates the entity with its required attributes, the optional attributes are left unset.
+    pub fn new(command_topic: impl Into<Topic>) -> Self {
+        Self::builder().command_topic(command_topic).build()
+    }
+
     /// Replaces `~` with this value in any MQTT topic attribute.
     /// [See Home Assistant documentation](https://www.home-assistant.io/integrations/mqtt/#using-abbreviations-and-base-topic)
     pub fn topic_prefix<S: Into<String>>(mut self, topic_prefix: S) -> Self {
@@ -368,7 +446,7 @@ impl Number {
     }
 
     /// The MQTT topic to publish commands to change the number.
-    pub fn command_topic<T: Into<String>>(mut self, command_topic: T) -> Self {
+    pub fn command_topic<T: Into<Topic>>(mut self, command_topic: T) -> Self {
         self.command_topic = command_topic.into();
         self
     }
@@ -407,26 +485,36 @@ impl Number {
     }
 
     /// The MQTT topic subscribed to receive a JSON dictionary payload and then set as number attributes. Implies `force_update` of the current number state when a message is received on this topic.
-    pub fn json_attributes_topic<T: Into<String>>(mut self, json_attributes_topic: T) -> Self {
+    pub fn json_attributes_topic<T: Into<Topic>>(mut self, json_attributes_topic: T) -> Self {
         self.json_attributes_topic = Some(json_attributes_topic.into());
         self
     }
 
     /// Minimum value.
-    pub fn min(mut self, min: Decimal) -> Self {
-        self.min = Some(min);
+    pub fn min<D: IntoDecimal>(mut self, min: D) -> Self {
+        self.min = Some(min.into_decimal());
         self
     }
 
+    /// Same as [`Self::min`] for the floats, fails when the value is NaN, infinite or out of the `Decimal` range.
+    pub fn try_min<D: TryIntoDecimal>(self, min: D) -> Result<Self, DecimalError> {
+        Ok(self.min(min.try_into_decimal()?))
+    }
+
     /// Maximum value.
-    pub fn max(mut self, max: Decimal) -> Self {
-        self.max = Some(max);
+    pub fn max<D: IntoDecimal>(mut self, max: D) -> Self {
+        self.max = Some(max.into_decimal());
         self
     }
 
+    /// Same as [`Self::max`] for the floats, fails when the value is NaN, infinite or out of the `Decimal` range.
+    pub fn try_max<D: TryIntoDecimal>(self, max: D) -> Result<Self, DecimalError> {
+        Ok(self.max(max.try_into_decimal()?))
+    }
+
     /// Control how the number should be displayed in the UI. Can be set to `box` or `slider` to force a display mode.
-    pub fn mode<T: Into<String>>(mut self, mode: T) -> Self {
-        self.mode = Some(mode.into());
+    pub fn mode(mut self, mode: NumberMode) -> Self {
+        self.mode = Some(mode);
         self
     }
 
@@ -467,17 +555,22 @@ impl Number {
     }
 
     /// The MQTT topic subscribed to receive number values.
-    pub fn state_topic<T: Into<String>>(mut self, state_topic: T) -> Self {
+    pub fn state_topic<T: Into<Topic>>(mut self, state_topic: T) -> Self {
         self.state_topic = Some(state_topic.into());
         self
     }
 
     /// Step value. Smallest value `0.001`.
-    pub fn step(mut self, step: Decimal) -> Self {
-        self.step = Some(step);
+    pub fn step<D: IntoDecimal>(mut self, step: D) -> Self {
+        self.step = Some(step.into_decimal());
         self
     }
 
+    /// Same as [`Self::step`] for the floats, fails when the value is NaN, infinite or out of the `Decimal` range.
+    pub fn try_step<D: TryIntoDecimal>(self, step: D) -> Result<Self, DecimalError> {
+        Ok(self.step(step.try_into_decimal()?))
+    }
+
     /// An ID that uniquely identifies this Number. If two Numbers have the same unique ID Home Assistant will raise an exception.
     pub fn unique_id<T: Into<String>>(mut self, unique_id: T) -> Self {
         self.unique_id = Some(unique_id.into());
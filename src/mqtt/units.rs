@@ -1,8 +1,20 @@
-use serde_derive::Serialize;
+use std::{
+    collections::BTreeSet,
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+    sync::{Mutex, PoisonError},
+};
+
+use serde::{
+    de::{value::StrDeserializer, Error},
+    Deserializer,
+};
+use serde_derive::{Deserialize, Serialize};
 
 /// Units of measurement
 #[allow(dead_code)]
-#[derive(Clone, Debug, PartialEq, Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum Unit {
     Power(PowerUnit),
@@ -31,372 +43,490 @@ pub enum Unit {
     SignalStrength(SignalStrengthUnit),
     Data(DataUnit),
     DataRateUnit(DataRateUnit),
+    /// A unit unknown to this crate, published as is, eg. `CFU/m³`. The symbols are interned so that `Unit` stays
+    /// `Copy`, build it with `Unit::custom`.
+    #[serde(deserialize_with = "deserialize_custom")]
+    Custom(Symbol),
 }
 
-/// Power units
-#[allow(dead_code)]
-#[derive(Clone, Debug, PartialEq, Serialize)]
-pub enum PowerUnit {
-    #[serde(rename = "W")]
-    Watt,
-    #[serde(rename = "kW")]
-    KiloWatt,
-}
+impl FromStr for Unit {
+    type Err = serde::de::value::Error;
 
-/// Volt unit
-#[allow(dead_code)]
-#[derive(Clone, Debug, PartialEq, Serialize)]
-pub enum VoltUnit {
-    #[serde(rename = "V")]
-    Volt,
+    /// Parses a unit of measurement from its symbol, eg. `°C`. The unknown symbols are rejected, see `Unit::custom`.
+    fn from_str(symbol: &str) -> Result<Self, Self::Err> {
+        match serde::Deserialize::deserialize(StrDeserializer::new(symbol))? {
+            Unit::Custom(_) => Err(Self::Err::custom(format!(
+                "unknown unit of measurement '{symbol}'"
+            ))),
+            unit => Ok(unit),
+        }
+    }
 }
 
-/// Energy units
-#[allow(dead_code)]
-#[derive(Clone, Debug, PartialEq, Serialize)]
-pub enum EnergyUnit {
-    #[serde(rename = "Wh")]
-    WattHour,
-    #[serde(rename = "kWh")]
-    KiloWattHour,
-}
+impl Unit {
+    /// A unit unknown to this crate. The known symbols are parsed into their typed unit, eg. `%` gives
+    /// `Unit::Percentage`, so that the units compare equal whichever way they were built.
+    pub fn custom<S: Into<String>>(symbol: S) -> Self {
+        let symbol = symbol.into();
+        symbol
+            .parse()
+            .unwrap_or_else(|_| Unit::Custom(intern(symbol)))
+    }
 
-/// Electrical units
-#[allow(dead_code)]
-#[derive(Clone, Debug, PartialEq, Serialize)]
-pub enum ElectricalUnit {
-    #[serde(rename = "A")]
-    CurrentAmpere,
-    #[serde(rename = "VA")]
-    VoltAmpere,
+    /// The symbol of the unit, eg. `°C`.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Unit::Power(unit) => unit.as_str(),
+            Unit::Volt(unit) => unit.as_str(),
+            Unit::Energy(unit) => unit.as_str(),
+            Unit::Electrical(unit) => unit.as_str(),
+            Unit::Angle(unit) => unit.as_str(),
+            Unit::Currency(unit) => unit.as_str(),
+            Unit::Temperature(unit) => unit.as_str(),
+            Unit::Time(unit) => unit.as_str(),
+            Unit::Length(unit) => unit.as_str(),
+            Unit::Frequency(unit) => unit.as_str(),
+            Unit::Pressure(unit) => unit.as_str(),
+            Unit::Volume(unit) => unit.as_str(),
+            Unit::VolumeFlowRate(unit) => unit.as_str(),
+            Unit::Area(unit) => unit.as_str(),
+            Unit::Mass(unit) => unit.as_str(),
+            Unit::Conductivity(unit) => unit.as_str(),
+            Unit::Light(unit) => unit.as_str(),
+            Unit::Uv(unit) => unit.as_str(),
+            Unit::Percentage(unit) => unit.as_str(),
+            Unit::Irradiation(unit) => unit.as_str(),
+            Unit::Precipitation(unit) => unit.as_str(),
+            Unit::Concentration(unit) => unit.as_str(),
+            Unit::Speed(unit) => unit.as_str(),
+            Unit::SignalStrength(unit) => unit.as_str(),
+            Unit::Data(unit) => unit.as_str(),
+            Unit::DataRateUnit(unit) => unit.as_str(),
+            Unit::Custom(symbol) => symbol,
+        }
+    }
 }
 
-/// Angle units
-#[allow(dead_code)]
-#[derive(Clone, Debug, PartialEq, Serialize)]
-pub enum AngleUnit {
-    #[serde(rename = "°")]
-    Degree,
-}
+/// A `&'static str` which serde doesn't try to borrow from the deserialized input, the custom symbols are interned.
+type Symbol = &'static str;
 
-/// Currency units
-#[allow(dead_code)]
-#[derive(Clone, Debug, PartialEq, Serialize)]
-pub enum CurrencyUnit {
-    #[serde(rename = "€")]
-    Euro,
-    #[serde(rename = "$")]
-    Dollar,
-    #[serde(rename = "¢")]
-    Cent,
+/// Leaks every distinct custom symbol once, the units of a program are a handful of symbols.
+fn intern(symbol: String) -> &'static str {
+    static SYMBOLS: Mutex<BTreeSet<&'static str>> = Mutex::new(BTreeSet::new());
+    let mut symbols = SYMBOLS.lock().unwrap_or_else(PoisonError::into_inner);
+    match symbols.get(symbol.as_str()) {
+        Some(interned) => interned,
+        None => {
+            let interned = Box::leak(symbol.into_boxed_str());
+            symbols.insert(interned);
+            interned
+        }
+    }
 }
 
-/// Temperature units
-#[allow(dead_code)]
-#[derive(Clone, Debug, PartialEq, Serialize)]
-pub enum TempUnit {
-    #[serde(rename = "°C")]
-    Celsius,
-    #[serde(rename = "°F")]
-    TempFahrenheit,
-    #[serde(rename = "K")]
-    TempKelvin,
+fn deserialize_custom<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<&'static str, D::Error> {
+    let symbol: String = serde::Deserialize::deserialize(deserializer)?;
+    Ok(intern(symbol))
 }
 
-/// Time units
-#[allow(dead_code)]
-#[derive(Clone, Debug, PartialEq, Serialize)]
-pub enum TimeUnit {
-    #[serde(rename = "μs")]
-    Microseconds,
-    #[serde(rename = "ms")]
-    Milliseconds,
-    #[serde(rename = "s")]
-    Seconds,
-    #[serde(rename = "min")]
-    Minutes,
-    #[serde(rename = "h")]
-    Hours,
-    #[serde(rename = "d")]
-    Days,
-    #[serde(rename = "w")]
-    Weeks,
-    #[serde(rename = "m")]
-    Months,
-    #[serde(rename = "y")]
-    Years,
+impl Display for Unit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
 }
 
-/// Length units
-#[allow(dead_code)]
-#[derive(Clone, Debug, PartialEq, Serialize)]
-pub enum LengthUnit {
-    #[serde(rename = "mm")]
-    Millimeters,
-    #[serde(rename = "cm")]
-    Centimeters,
-    #[serde(rename = "m")]
-    Meters,
-    #[serde(rename = "km")]
-    Kilometers,
-
-    #[serde(rename = "in")]
-    Inches,
-    #[serde(rename = "ft")]
-    Feet,
-    #[serde(rename = "yd")]
-    Yard,
-    #[serde(rename = "mi")]
-    Miles,
-}
+/// Declares the units of a measurement, serialized as their symbol.
+macro_rules! units {
+    ($($(#[$meta:meta])* $name:ident { $($variant:ident => $symbol:literal,)* })*) => {
+        $(
+            $(#[$meta])*
+            #[allow(dead_code)]
+            #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+            #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+            pub enum $name {
+                $(
+                    #[serde(rename = $symbol)]
+                    $variant,
+                )*
+            }
 
-/// Frequency units
-#[allow(dead_code)]
-#[derive(Clone, Debug, PartialEq, Serialize)]
-pub enum FrequencyUnit {
-    #[serde(rename = "Hz")]
-    Hertz,
-    #[serde(rename = "GHz")]
-    GigaHertz,
-}
+            impl $name {
+                /// The symbol of the unit.
+                pub fn as_str(&self) -> &'static str {
+                    match self {
+                        $(Self::$variant => $symbol,)*
+                    }
+                }
 
-/// Pressure units
-#[allow(dead_code)]
-#[derive(Clone, Debug, PartialEq, Serialize)]
-pub enum PressureUnit {
-    #[serde(rename = "Pa")]
-    Pa,
-    #[serde(rename = "hPa")]
-    HPa,
-    #[serde(rename = "bar")]
-    Bar,
-    #[serde(rename = "mbar")]
-    MBar,
-    #[serde(rename = "inHg")]
-    InHg,
-    #[serde(rename = "psi")]
-    Psi,
-}
+                /// The unit of the symbol, usable in constants.
+                pub const fn from_symbol(symbol: &str) -> Option<Self> {
+                    $(
+                        if str_eq(symbol, $symbol) {
+                            return Some(Self::$variant);
+                        }
+                    )*
+                    None
+                }
+            }
 
-/// Volume units
-#[allow(dead_code)]
-#[derive(Clone, Debug, PartialEq, Serialize)]
-pub enum VolumeUnit {
-    #[serde(rename = "L")]
-    Liters,
-    #[serde(rename = "mL")]
-    Milliliters,
-    #[serde(rename = "m³")]
-    CubicMeters,
-    #[serde(rename = "ft³")]
-    CubicFeet,
-
-    #[serde(rename = "gal")]
-    Gallons,
-    #[serde(rename = "fl. oz.")]
-    FluidOunce,
-}
+            impl Display for $name {
+                fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                    f.write_str(self.as_str())
+                }
+            }
 
-/// Volume Flow Rate units
-#[allow(dead_code)]
-#[derive(Clone, Debug, PartialEq, Serialize)]
-pub enum VolumeFlowRateUnit {
-    #[serde(rename = "m³/h")]
-    CubicMetersPerHour,
-    #[serde(rename = "ft³/m")]
-    CubicFeetPerMinute,
-}
-/// Area units
-#[allow(dead_code)]
-#[derive(Clone, Debug, PartialEq, Serialize)]
-pub enum AreaUnit {
-    #[serde(rename = "m²")]
-    SquareMeters,
-}
+            impl FromStr for $name {
+                type Err = serde::de::value::Error;
 
-/// Mass units
-#[allow(dead_code)]
-#[derive(Clone, Debug, PartialEq, Serialize)]
-pub enum MassUnit {
-    #[serde(rename = "g")]
-    Grams,
-    #[serde(rename = "kg")]
-    Kilograms,
-    #[serde(rename = "mg")]
-    Milligrams,
-    #[serde(rename = "µg")]
-    Micrograms,
-
-    #[serde(rename = "oz")]
-    Ounces,
-    #[serde(rename = "lb")]
-    Pounds,
+                fn from_str(symbol: &str) -> Result<Self, Self::Err> {
+                    match symbol {
+                        $($symbol => Ok(Self::$variant),)*
+                        _ => Err(Self::Err::unknown_variant(symbol, &[$($symbol),*])),
+                    }
+                }
+            }
+        )*
+    };
 }
 
-/// Conductivity units
-#[allow(dead_code)]
-#[derive(Clone, Debug, PartialEq, Serialize)]
-pub enum ConductivityUnit {
-    #[serde(rename = "µS/cm")]
-    Conductivity,
-}
+macro_rules! unit_from {
+    ($($variant:ident($unit:ident)),* $(,)?) => {
+        $(
+            impl From<$unit> for Unit {
+                fn from(unit: $unit) -> Self {
+                    Unit::$variant(unit)
+                }
+            }
+        )*
 
-/// Light units
-#[allow(dead_code)]
-#[derive(Clone, Debug, PartialEq, Serialize)]
-pub enum LightUnit {
-    #[serde(rename = "lx")]
-    Lux,
+        impl Unit {
+            /// Whether the symbol is a known unit of measurement, usable in constants to check a unit at compile time.
+            pub const fn is_known_symbol(symbol: &str) -> bool {
+                $(
+                    if $unit::from_symbol(symbol).is_some() {
+                        return true;
+                    }
+                )*
+                false
+            }
+        }
+    };
 }
 
-/// UV Index units
-#[allow(dead_code)]
-#[derive(Clone, Debug, PartialEq, Serialize)]
-pub enum UvUnit {
-    #[serde(rename = "UV index")]
-    UvIndex,
+/// String equality usable in constants.
+const fn str_eq(left: &str, right: &str) -> bool {
+    let (left, right) = (left.as_bytes(), right.as_bytes());
+    if left.len() != right.len() {
+        return false;
+    }
+    let mut index = 0;
+    while index < left.len() {
+        if left[index] != right[index] {
+            return false;
+        }
+        index += 1;
+    }
+    true
 }
 
-/// Percentage units
-#[allow(dead_code)]
-#[derive(Clone, Debug, PartialEq, Serialize)]
-pub enum PercentageUnit {
-    #[serde(rename = "%")]
-    Percentage,
+unit_from! {
+    Power(PowerUnit),
+    Volt(VoltUnit),
+    Energy(EnergyUnit),
+    Electrical(ElectricalUnit),
+    Angle(AngleUnit),
+    Currency(CurrencyUnit),
+    Temperature(TempUnit),
+    Time(TimeUnit),
+    Length(LengthUnit),
+    Frequency(FrequencyUnit),
+    Pressure(PressureUnit),
+    Volume(VolumeUnit),
+    VolumeFlowRate(VolumeFlowRateUnit),
+    Area(AreaUnit),
+    Mass(MassUnit),
+    Conductivity(ConductivityUnit),
+    Light(LightUnit),
+    Uv(UvUnit),
+    Percentage(PercentageUnit),
+    Irradiation(IrradiationUnit),
+    Precipitation(PrecipitationUnit),
+    Concentration(ConcentrationUnit),
+    Speed(SpeedUnit),
+    SignalStrength(SignalStrengthUnit),
+    Data(DataUnit),
+    DataRateUnit(DataRateUnit),
 }
 
-/// Irradiation units
-#[allow(dead_code)]
-#[derive(Clone, Debug, PartialEq, Serialize)]
-pub enum IrradiationUnit {
-    #[serde(rename = "W/m²")]
-    WattsPerSquareMeter,
-}
+units! {
+    /// Power units
+    PowerUnit {
+        Watt => "W",
+        KiloWatt => "kW",
+    }
 
-/// Precipitation units
-#[allow(dead_code)]
-#[derive(Clone, Debug, PartialEq, Serialize)]
-pub enum PrecipitationUnit {
-    #[serde(rename = "mm/h")]
-    MillimetersPerHour,
-}
+    /// Volt unit
+    VoltUnit {
+        Volt => "V",
+    }
 
-/// Concentration units
-#[allow(dead_code)]
-#[derive(Clone, Debug, PartialEq, Serialize)]
-pub enum ConcentrationUnit {
-    #[serde(rename = "µg/m³")]
-    MicrogramsPerCubicMeter,
-    #[serde(rename = "mg/m³")]
-    MilligramsPerCubicMeter,
-    #[serde(rename = "p/m³")]
-    PartsPerCubicMeter,
-    #[serde(rename = "ppm")]
-    PartsPerMillion,
-    #[serde(rename = "ppb")]
-    PartsPerBillion,
-}
+    /// Energy units
+    EnergyUnit {
+        WattHour => "Wh",
+        KiloWattHour => "kWh",
+    }
 
-/// Speed units
-#[allow(dead_code)]
-#[derive(Clone, Debug, PartialEq, Serialize)]
-pub enum SpeedUnit {
-    #[serde(rename = "mm/d")]
-    MillimetersPerDay,
-    #[serde(rename = "in/d")]
-    InchesPerDay,
-    #[serde(rename = "m/s")]
-    MetersPerSecond,
-    #[serde(rename = "in/h")]
-    InchesPerHour,
-    #[serde(rename = "km/h")]
-    KilometersPerHour,
-    #[serde(rename = "mph")]
-    MilesPerHour,
-}
+    /// Electrical units
+    ElectricalUnit {
+        CurrentAmpere => "A",
+        VoltAmpere => "VA",
+    }
 
-/// Signal_strength units
-#[allow(dead_code)]
-#[derive(Clone, Debug, PartialEq, Serialize)]
-pub enum SignalStrengthUnit {
-    #[serde(rename = "dB")]
-    Decibels,
-    #[serde(rename = "dBm")]
-    DecibelsMilliwatt,
-}
+    /// Angle units
+    AngleUnit {
+        Degree => "°",
+    }
 
-/// Data units
-#[allow(dead_code)]
-#[derive(Clone, Debug, PartialEq, Serialize)]
-pub enum DataUnit {
-    #[serde(rename = "bit")]
-    Bits,
-    #[serde(rename = "kbit")]
-    Kilobits,
-    #[serde(rename = "Mbit")]
-    Megabits,
-    #[serde(rename = "Gbit")]
-    Gigabits,
-    #[serde(rename = "B")]
-    Bytes,
-    #[serde(rename = "kB")]
-    Kilobytes,
-    #[serde(rename = "MB")]
-    Megabytes,
-    #[serde(rename = "GB")]
-    Gigabytes,
-    #[serde(rename = "TB")]
-    Terabytes,
-    #[serde(rename = "PB")]
-    Petabytes,
-    #[serde(rename = "EB")]
-    Exabytes,
-    #[serde(rename = "ZB")]
-    Zettabytes,
-    #[serde(rename = "YB")]
-    Yottabytes,
-    #[serde(rename = "KiB")]
-    Kibibytes,
-    #[serde(rename = "MiB")]
-    Mebibytes,
-    #[serde(rename = "GiB")]
-    Gibibytes,
-    #[serde(rename = "TiB")]
-    Tebibytes,
-    #[serde(rename = "PiB")]
-    Pebibytes,
-    #[serde(rename = "EiB")]
-    Exbibytes,
-    #[serde(rename = "ZiB")]
-    Zebibytes,
-    #[serde(rename = "YiB")]
-    Yobibytes,
+    /// Currency units
+    CurrencyUnit {
+        Euro => "€",
+        Dollar => "$",
+        Cent => "¢",
+    }
+
+    /// Temperature units
+    TempUnit {
+        Celsius => "°C",
+        TempFahrenheit => "°F",
+        TempKelvin => "K",
+    }
+
+    /// Time units
+    TimeUnit {
+        Microseconds => "μs",
+        Milliseconds => "ms",
+        Seconds => "s",
+        Minutes => "min",
+        Hours => "h",
+        Days => "d",
+        Weeks => "w",
+        Months => "m",
+        Years => "y",
+    }
+
+    /// Length units
+    LengthUnit {
+        Millimeters => "mm",
+        Centimeters => "cm",
+        Meters => "m",
+        Kilometers => "km",
+        Inches => "in",
+        Feet => "ft",
+        Yard => "yd",
+        Miles => "mi",
+    }
+
+    /// Frequency units
+    FrequencyUnit {
+        Hertz => "Hz",
+        GigaHertz => "GHz",
+    }
+
+    /// Pressure units
+    PressureUnit {
+        Pa => "Pa",
+        HPa => "hPa",
+        Bar => "bar",
+        MBar => "mbar",
+        InHg => "inHg",
+        Psi => "psi",
+    }
+
+    /// Volume units
+    VolumeUnit {
+        Liters => "L",
+        Milliliters => "mL",
+        CubicMeters => "m³",
+        CubicFeet => "ft³",
+        Gallons => "gal",
+        FluidOunce => "fl. oz.",
+    }
+
+    /// Volume Flow Rate units
+    VolumeFlowRateUnit {
+        CubicMetersPerHour => "m³/h",
+        CubicFeetPerMinute => "ft³/m",
+    }
+
+    /// Area units
+    AreaUnit {
+        SquareMeters => "m²",
+    }
+
+    /// Mass units
+    MassUnit {
+        Grams => "g",
+        Kilograms => "kg",
+        Milligrams => "mg",
+        Micrograms => "µg",
+        Ounces => "oz",
+        Pounds => "lb",
+    }
+
+    /// Conductivity units
+    ConductivityUnit {
+        Conductivity => "µS/cm",
+    }
+
+    /// Light units
+    LightUnit {
+        Lux => "lx",
+    }
+
+    /// UV Index units
+    UvUnit {
+        UvIndex => "UV index",
+    }
+
+    /// Percentage units
+    PercentageUnit {
+        Percentage => "%",
+    }
+
+    /// Irradiation units
+    IrradiationUnit {
+        WattsPerSquareMeter => "W/m²",
+    }
+
+    /// Precipitation units
+    PrecipitationUnit {
+        MillimetersPerHour => "mm/h",
+    }
+
+    /// Concentration units
+    ConcentrationUnit {
+        MicrogramsPerCubicMeter => "µg/m³",
+        MilligramsPerCubicMeter => "mg/m³",
+        PartsPerCubicMeter => "p/m³",
+        PartsPerMillion => "ppm",
+        PartsPerBillion => "ppb",
+    }
+
+    /// Speed units
+    SpeedUnit {
+        MillimetersPerDay => "mm/d",
+        InchesPerDay => "in/d",
+        MetersPerSecond => "m/s",
+        InchesPerHour => "in/h",
+        KilometersPerHour => "km/h",
+        MilesPerHour => "mph",
+    }
+
+    /// Signal_strength units
+    SignalStrengthUnit {
+        Decibels => "dB",
+        DecibelsMilliwatt => "dBm",
+    }
+
+    /// Data units
+    DataUnit {
+        Bits => "bit",
+        Kilobits => "kbit",
+        Megabits => "Mbit",
+        Gigabits => "Gbit",
+        Bytes => "B",
+        Kilobytes => "kB",
+        Megabytes => "MB",
+        Gigabytes => "GB",
+        Terabytes => "TB",
+        Petabytes => "PB",
+        Exabytes => "EB",
+        Zettabytes => "ZB",
+        Yottabytes => "YB",
+        Kibibytes => "KiB",
+        Mebibytes => "MiB",
+        Gibibytes => "GiB",
+        Tebibytes => "TiB",
+        Pebibytes => "PiB",
+        Exbibytes => "EiB",
+        Zebibytes => "ZiB",
+        Yobibytes => "YiB",
+    }
+
+    /// Data rate units
+    DataRateUnit {
+        BitsPerSecond => "bit/s",
+        KilobitsPerSecond => "kbit/s",
+        MegabitsPerSecond => "Mbit/s",
+        GigabitsPerSecond => "Gbit/s",
+        BytesPerSecond => "B/s",
+        KilobytesPerSecond => "kB/s",
+        MegabytesPerSecond => "MB/s",
+        GigabytesPerSecond => "GB/s",
+        KibibytesPerSecond => "KiB/s",
+        MebibytesPerSecond => "MiB/s",
+        GibibytesPerSecond => "GiB/s",
+    }
 }
 
-/// Data rate units
-#[allow(dead_code)]
-#[derive(Clone, Debug, PartialEq, Serialize)]
-pub enum DataRateUnit {
-    #[serde(rename = "bit/s")]
-    BitsPerSecond,
-    #[serde(rename = "kbit/s")]
-    KilobitsPerSecond,
-    #[serde(rename = "Mbit/s")]
-    MegabitsPerSecond,
-    #[serde(rename = "Gbit/s")]
-    GigabitsPerSecond,
-    #[serde(rename = "B/s")]
-    BytesPerSecond,
-    #[serde(rename = "kB/s")]
-    KilobytesPerSecond,
-    #[serde(rename = "MB/s")]
-    MegabytesPerSecond,
-    #[serde(rename = "GB/s")]
-    GigabytesPerSecond,
-    #[serde(rename = "KiB/s")]
-    KibibytesPerSecond,
-    #[serde(rename = "MiB/s")]
-    MebibytesPerSecond,
-    #[serde(rename = "GiB/s")]
-    GibibytesPerSecond,
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::mqtt::device_classes::{CoverDeviceClass, SensorDeviceClass};
+
+    use super::*;
+
+    #[test]
+    fn can_parse_and_display_units_and_device_classes() {
+        assert_eq!("°C".parse::<TempUnit>().unwrap(), TempUnit::Celsius);
+        assert_eq!(TempUnit::Celsius.to_string(), "°C");
+        assert_eq!(Unit::from(PowerUnit::KiloWatt).to_string(), "kW");
+        assert!("°X".parse::<TempUnit>().is_err());
+        assert_eq!(TempUnit::from_symbol("°C"), Some(TempUnit::Celsius));
+        assert!(Unit::is_known_symbol("kWh"));
+        assert!(!Unit::is_known_symbol("kwh"));
+
+        assert_eq!(
+            "temperature".parse::<SensorDeviceClass>().unwrap(),
+            SensorDeviceClass::Temperature
+        );
+        assert_eq!(SensorDeviceClass::Temperature.to_string(), "temperature");
+        assert_eq!(
+            "None".parse::<CoverDeviceClass>().unwrap(),
+            CoverDeviceClass::None
+        );
+        assert!("oven".parse::<CoverDeviceClass>().is_err());
+
+        assert_eq!(
+            "%".parse::<Unit>().unwrap(),
+            PercentageUnit::Percentage.into()
+        );
+        assert!("CFU/m³".parse::<Unit>().is_err());
+        assert_eq!(
+            Unit::custom("ppm"),
+            ConcentrationUnit::PartsPerMillion.into()
+        );
+        assert_eq!(Unit::custom("CFU/m³").to_string(), "CFU/m³");
+        assert_eq!(
+            serde_json::from_str::<Unit>(&String::from(r#""CFU/m³""#)).unwrap(),
+            Unit::Custom("CFU/m³")
+        );
+        let unit = Unit::custom(String::from("CFU/m³"));
+        let copy = unit;
+        assert_eq!(unit, copy);
+        assert!(std::ptr::eq(unit.as_str(), Unit::custom("CFU/m³").as_str()));
+        assert_eq!(
+            serde_json::to_string(&Unit::custom("CFU/m³")).unwrap(),
+            r#""CFU/m³""#
+        );
+
+        let units = HashMap::from([(SensorDeviceClass::Power, Unit::from(PowerUnit::Watt))]);
+        assert_eq!(
+            units[&SensorDeviceClass::Power],
+            Unit::Power(PowerUnit::Watt)
+        );
+    }
 }
@@ -1,8 +1,10 @@
 use super::common::Qos;
 use super::common::{Availability, Device, EntityCategory, Origin};
 use super::device_classes::UpdateDeviceClass;
+use crate::topic::Topic;
 use crate::Entity;
-use serde_derive::Serialize;
+use bon::Builder;
+use serde_derive::{Deserialize, Serialize};
 
 /// ---
 /// title: "MQTT Update"
@@ -306,7 +308,9 @@ use serde_derive::Serialize;
 ///
 /// {% endraw %}
 ///
-#[derive(Clone, Debug, PartialEq, Serialize, Default)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default, Builder)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[builder(on(String, into), on(Topic, into))]
 pub struct Update {
     /// Replaces `~` with this value in any MQTT topic attribute.
     /// [See Home Assistant documentation](https://www.home-assistant.io/integrations/mqtt/#using-abbreviations-and-base-topic)
@@ -314,71 +318,122 @@ pub struct Update {
     pub topic_prefix: Option<String>,
 
     /// It is encouraged to add additional information about the origin that supplies MQTT entities via MQTT discovery by adding the origin option (can be abbreviated to o) to the discovery payload. Note that these options also support abbreviations. Information of the origin will be logged to the core event log when an item is discovered or updated.
-    #[serde(rename = "o")]
+    #[serde(rename = "o", alias = "origin", default)]
+    #[builder(default)]
     pub origin: Origin,
 
     /// Information about the device this button is a part of to tie it into the [device registry](https://developers.home-assistant.io/docs/en/device_registry_index.html). Only works when [`unique_id`](#unique_id) is set. At least one of identifiers or connections must be present to identify the device.
-    #[serde(rename = "dev")]
+    #[serde(rename = "dev", alias = "device", default)]
+    #[builder(default)]
     pub device: Device,
 
     /// A list of MQTT topics subscribed to receive availability (online/offline) updates. Must not be used together with `availability_topic`.
     #[serde(flatten)]
+    #[builder(default)]
     pub availability: Availability,
 
     /// The category of the entity. (optional, default: None)
-    #[serde(rename = "ent_cat", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "ent_cat",
+        alias = "entity_category",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub entity_category: Option<EntityCategory>,
 
     /// The MQTT topic to publish `payload_install` to start installing process.
-    #[serde(rename = "cmd_t", skip_serializing_if = "Option::is_none")]
-    pub command_topic: Option<String>,
+    #[serde(
+        rename = "cmd_t",
+        alias = "command_topic",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub command_topic: Option<Topic>,
 
     /// The [type/class](/integrations/update/#device-classes) of the update to set the icon in the frontend. The `device_class` can be `null`.
-    #[serde(rename = "dev_cla", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "dev_cla",
+        alias = "device_class",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub device_class: Option<UpdateDeviceClass>,
 
     /// Flag which defines if the entity should be enabled when first added.
-    #[serde(rename = "en", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "en",
+        alias = "enabled_by_default",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub enabled_by_default: Option<bool>,
 
     /// The encoding of the payloads received and published messages. Set to `""` to disable decoding of incoming payload.
-    #[serde(rename = "e", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "e",
+        alias = "encoding",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub encoding: Option<String>,
 
     /// Picture URL for the entity.
-    #[serde(rename = "ent_pic", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "ent_pic",
+        alias = "entity_picture",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub entity_picture: Option<String>,
 
     /// [Icon](/docs/configuration/customizing-devices/#icon) for the entity.
-    #[serde(rename = "ic", skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "ic", alias = "icon", skip_serializing_if = "Option::is_none")]
     pub icon: Option<String>,
 
     /// Defines a [template](/docs/configuration/templating/#using-templates-with-the-mqtt-integration) to extract the JSON dictionary from messages received on the `json_attributes_topic`.
-    #[serde(rename = "json_attr_tpl", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "json_attr_tpl",
+        alias = "json_attributes_template",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub json_attributes_template: Option<String>,
 
     /// The MQTT topic subscribed to receive a JSON dictionary payload and then set as entity attributes. Implies `force_update` of the current select state when a message is received on this topic.
-    #[serde(rename = "json_attr_t", skip_serializing_if = "Option::is_none")]
-    pub json_attributes_topic: Option<String>,
+    #[serde(
+        rename = "json_attr_t",
+        alias = "json_attributes_topic",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub json_attributes_topic: Option<Topic>,
 
     /// Defines a [template](/docs/configuration/templating/#using-templates-with-the-mqtt-integration) to extract the latest version value.
-    #[serde(rename = "l_ver_tpl", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "l_ver_tpl",
+        alias = "latest_version_template",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub latest_version_template: Option<String>,
 
     /// The MQTT topic subscribed to receive an update of the latest version.
-    #[serde(rename = "l_ver_t", skip_serializing_if = "Option::is_none")]
-    pub latest_version_topic: Option<String>,
+    #[serde(
+        rename = "l_ver_t",
+        alias = "latest_version_topic",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub latest_version_topic: Option<Topic>,
 
     /// The name of the Update. Can be set to `null` if only the device name is relevant.
     #[serde(rename = "name", skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
 
     /// Used instead of `name` for automatic generation of `entity_id`
-    #[serde(rename = "obj_id", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "obj_id",
+        alias = "object_id",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub object_id: Option<String>,
 
     /// The MQTT payload to start installing process.
-    #[serde(rename = "pl_inst", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "pl_inst",
+        alias = "payload_install",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub payload_install: Option<String>,
 
     /// The maximum QoS level to be used when receiving and publishing messages.
@@ -386,31 +441,59 @@ pub struct Update {
     pub qos: Option<Qos>,
 
     /// Summary of the release notes or changelog. This is suitable a brief update description of max 255 characters.
-    #[serde(rename = "rel_s", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "rel_s",
+        alias = "release_summary",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub release_summary: Option<String>,
 
     /// URL to the full release notes of the latest version available.
-    #[serde(rename = "rel_u", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "rel_u",
+        alias = "release_url",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub release_url: Option<String>,
 
     /// If the published message should have the retain flag on or not.
-    #[serde(rename = "ret", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "ret",
+        alias = "retain",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub retain: Option<bool>,
 
     /// The MQTT topic subscribed to receive state updates. The state update may be either JSON or a simple string with `installed_version` value. When a JSON payload is detected, the state value of the JSON payload should supply the `installed_version` and can optional supply: `latest_version`, `title`, `release_summary`, `release_url` or an `entity_picture` URL.
-    #[serde(rename = "stat_t", skip_serializing_if = "Option::is_none")]
-    pub state_topic: Option<String>,
+    #[serde(
+        rename = "stat_t",
+        alias = "state_topic",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub state_topic: Option<Topic>,
 
     /// Title of the software, or firmware update. This helps to differentiate between the device or entity name versus the title of the software installed.
-    #[serde(rename = "tit", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "tit",
+        alias = "title",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub title: Option<String>,
 
     /// An ID that uniquely identifies this Update. If two Updates have the same unique ID Home Assistant will raise an exception.
-    #[serde(rename = "uniq_id", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "uniq_id",
+        alias = "unique_id",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub unique_id: Option<String>,
 
     /// Defines a [template](/docs/configuration/templating/#using-templates-with-the-mqtt-integration) to extract the `installed_version` state value or to render to a valid JSON payload on from the payload received on `state_topic`.
-    #[serde(rename = "val_tpl", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "val_tpl",
+        alias = "value_template",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub value_template: Option<String>,
 }
 
@@ -447,7 +530,7 @@ impl Update {
     }
 
     /// The MQTT topic to publish `payload_install` to start installing process.
-    pub fn command_topic<T: Into<String>>(mut self, command_topic: T) -> Self {
+    pub fn command_topic<T: Into<Topic>>(mut self, command_topic: T) -> Self {
         self.command_topic = Some(command_topic.into());
         self
     }
@@ -492,7 +575,7 @@ impl Update {
     }
 
     /// The MQTT topic subscribed to receive a JSON dictionary payload and then set as entity attributes. Implies `force_update` of the current select state when a message is received on this topic.
-    pub fn json_attributes_topic<T: Into<String>>(mut self, json_attributes_topic: T) -> Self {
+    pub fn json_attributes_topic<T: Into<Topic>>(mut self, json_attributes_topic: T) -> Self {
         self.json_attributes_topic = Some(json_attributes_topic.into());
         self
     }
@@ -504,7 +587,7 @@ impl Update {
     }
 
     /// The MQTT topic subscribed to receive an update of the latest version.
-    pub fn latest_version_topic<T: Into<String>>(mut self, latest_version_topic: T) -> Self {
+    pub fn latest_version_topic<T: Into<Topic>>(mut self, latest_version_topic: T) -> Self {
         self.latest_version_topic = Some(latest_version_topic.into());
         self
     }
@@ -552,7 +635,7 @@ impl Update {
     }
 
     /// The MQTT topic subscribed to receive state updates. The state update may be either JSON or a simple string with `installed_version` value. When a JSON payload is detected, the state value of the JSON payload should supply the `installed_version` and can optional supply: `latest_version`, `title`, `release_summary`, `release_url` or an `entity_picture` URL.
-    pub fn state_topic<T: Into<String>>(mut self, state_topic: T) -> Self {
+    pub fn state_topic<T: Into<Topic>>(mut self, state_topic: T) -> Self {
         self.state_topic = Some(state_topic.into());
         self
     }
@@ -1,7 +1,9 @@
 use super::common::Qos;
 use super::common::{Availability, Device, EntityCategory, Origin};
+use crate::topic::Topic;
 use crate::Entity;
-use serde_derive::Serialize;
+use bon::Builder;
+use serde_derive::{Deserialize, Serialize};
 
 /// ---
 /// title: "MQTT Vacuum"
@@ -337,7 +339,9 @@ use serde_derive::Serialize;
 /// - Retrofitting your old Roomba with an ESP8266. [This repository](https://github.com/johnboiles/esp-roomba-mqtt) provides MQTT client firmware.
 /// - If you own a non-wifi Neato, you can refer to [this repository](https://github.com/jeroenterheerdt/neato-serial) that uses a Raspberry Pi to retrofit an old Neato.
 ///
-#[derive(Clone, Debug, PartialEq, Serialize, Default)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default, Builder)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[builder(on(String, into), on(Topic, into))]
 pub struct Vacuum {
     /// Replaces `~` with this value in any MQTT topic attribute.
     /// [See Home Assistant documentation](https://www.home-assistant.io/integrations/mqtt/#using-abbreviations-and-base-topic)
@@ -345,71 +349,126 @@ pub struct Vacuum {
     pub topic_prefix: Option<String>,
 
     /// It is encouraged to add additional information about the origin that supplies MQTT entities via MQTT discovery by adding the origin option (can be abbreviated to o) to the discovery payload. Note that these options also support abbreviations. Information of the origin will be logged to the core event log when an item is discovered or updated.
-    #[serde(rename = "o")]
+    #[serde(rename = "o", alias = "origin", default)]
+    #[builder(default)]
     pub origin: Origin,
 
     /// Information about the device this button is a part of to tie it into the [device registry](https://developers.home-assistant.io/docs/en/device_registry_index.html). Only works when [`unique_id`](#unique_id) is set. At least one of identifiers or connections must be present to identify the device.
-    #[serde(rename = "dev")]
+    #[serde(rename = "dev", alias = "device", default)]
+    #[builder(default)]
     pub device: Device,
 
     /// A list of MQTT topics subscribed to receive availability (online/offline) updates. Must not be used together with `availability_topic`.
     #[serde(flatten)]
+    #[builder(default)]
     pub availability: Availability,
 
     /// The category of the entity. (optional, default: None)
-    #[serde(rename = "ent_cat", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "ent_cat",
+        alias = "entity_category",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub entity_category: Option<EntityCategory>,
 
     /// The MQTT topic to publish commands to control the vacuum.
-    #[serde(rename = "cmd_t", skip_serializing_if = "Option::is_none")]
-    pub command_topic: Option<String>,
+    #[serde(
+        rename = "cmd_t",
+        alias = "command_topic",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub command_topic: Option<Topic>,
 
     /// The encoding of the payloads received and published messages. Set to `""` to disable decoding of incoming payload.
-    #[serde(rename = "e", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "e",
+        alias = "encoding",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub encoding: Option<String>,
 
     /// List of possible fan speeds for the vacuum.
-    #[serde(rename = "fanspd_lst", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "fanspd_lst",
+        alias = "fan_speed_list",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub fan_speed_list: Option<Vec<String>>,
 
     /// Defines a [template](/docs/configuration/templating/#using-templates-with-the-mqtt-integration) to extract the JSON dictionary from messages received on the `json_attributes_topic`. Usage example can be found in [MQTT sensor](/integrations/sensor.mqtt/#json-attributes-template-configuration) documentation.
-    #[serde(rename = "json_attr_tpl", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "json_attr_tpl",
+        alias = "json_attributes_template",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub json_attributes_template: Option<String>,
 
     /// The MQTT topic subscribed to receive a JSON dictionary payload and then set as sensor attributes. Usage example can be found in [MQTT sensor](/integrations/sensor.mqtt/#json-attributes-topic-configuration) documentation.
-    #[serde(rename = "json_attr_t", skip_serializing_if = "Option::is_none")]
-    pub json_attributes_topic: Option<String>,
+    #[serde(
+        rename = "json_attr_t",
+        alias = "json_attributes_topic",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub json_attributes_topic: Option<Topic>,
 
     /// The name of the vacuum. Can be set to `null` if only the device name is relevant.
     #[serde(rename = "name", skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
 
     /// Used instead of `name` for automatic generation of `entity_id`
-    #[serde(rename = "obj_id", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "obj_id",
+        alias = "object_id",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub object_id: Option<String>,
 
     /// The payload to send to the `command_topic` to begin a spot cleaning cycle.
-    #[serde(rename = "pl_cln_sp", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "pl_cln_sp",
+        alias = "payload_clean_spot",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub payload_clean_spot: Option<String>,
 
     /// The payload to send to the `command_topic` to locate the vacuum (typically plays a song).
-    #[serde(rename = "pl_loc", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "pl_loc",
+        alias = "payload_locate",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub payload_locate: Option<String>,
 
     /// The payload to send to the `command_topic` to pause the vacuum.
-    #[serde(rename = "pl_paus", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "pl_paus",
+        alias = "payload_pause",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub payload_pause: Option<String>,
 
     /// The payload to send to the `command_topic` to tell the vacuum to return to base.
-    #[serde(rename = "pl_ret", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "pl_ret",
+        alias = "payload_return_to_base",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub payload_return_to_base: Option<String>,
 
     /// The payload to send to the `command_topic` to begin the cleaning cycle.
-    #[serde(rename = "pl_strt", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "pl_strt",
+        alias = "payload_start",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub payload_start: Option<String>,
 
     /// The payload to send to the `command_topic` to stop cleaning.
-    #[serde(rename = "pl_stop", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "pl_stop",
+        alias = "payload_stop",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub payload_stop: Option<String>,
 
     /// The maximum QoS level to be used when receiving and publishing messages.
@@ -417,27 +476,51 @@ pub struct Vacuum {
     pub qos: Option<Qos>,
 
     /// If the published message should have the retain flag on or not.
-    #[serde(rename = "ret", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "ret",
+        alias = "retain",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub retain: Option<bool>,
 
     /// The MQTT topic to publish custom commands to the vacuum.
-    #[serde(rename = "send_cmd_t", skip_serializing_if = "Option::is_none")]
-    pub send_command_topic: Option<String>,
+    #[serde(
+        rename = "send_cmd_t",
+        alias = "send_command_topic",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub send_command_topic: Option<Topic>,
 
     /// The MQTT topic to publish commands to control the vacuum's fan speed.
-    #[serde(rename = "set_fan_spd_t", skip_serializing_if = "Option::is_none")]
-    pub set_fan_speed_topic: Option<String>,
+    #[serde(
+        rename = "set_fan_spd_t",
+        alias = "set_fan_speed_topic",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub set_fan_speed_topic: Option<Topic>,
 
     /// The MQTT topic subscribed to receive state messages from the vacuum. Messages received on the `state_topic` must be a valid JSON dictionary, with a mandatory `state` key and optionally `battery_level` and `fan_speed` keys as shown in the [example](#configuration-example).
-    #[serde(rename = "stat_t", skip_serializing_if = "Option::is_none")]
-    pub state_topic: Option<String>,
+    #[serde(
+        rename = "stat_t",
+        alias = "state_topic",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub state_topic: Option<Topic>,
 
     /// List of features that the vacuum supports (possible values are `start`, `stop`, `pause`, `return_home`, `battery`, `status`, `locate`, `clean_spot`, `fan_speed`, `send_command`).
-    #[serde(rename = "sup_feat", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "sup_feat",
+        alias = "supported_features",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub supported_features: Option<Vec<String>>,
 
     /// An ID that uniquely identifies this vacuum. If two vacuums have the same unique ID, Home Assistant will raise an exception.
-    #[serde(rename = "uniq_id", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "uniq_id",
+        alias = "unique_id",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub unique_id: Option<String>,
 }
 
@@ -474,7 +557,7 @@ impl Vacuum {
     }
 
     /// The MQTT topic to publish commands to control the vacuum.
-    pub fn command_topic<T: Into<String>>(mut self, command_topic: T) -> Self {
+    pub fn command_topic<T: Into<Topic>>(mut self, command_topic: T) -> Self {
         self.command_topic = Some(command_topic.into());
         self
     }
@@ -501,7 +584,7 @@ impl Vacuum {
     }
 
     /// The MQTT topic subscribed to receive a JSON dictionary payload and then set as sensor attributes. Usage example can be found in [MQTT sensor](/integrations/sensor.mqtt/#json-attributes-topic-configuration) documentation.
-    pub fn json_attributes_topic<T: Into<String>>(mut self, json_attributes_topic: T) -> Self {
+    pub fn json_attributes_topic<T: Into<Topic>>(mut self, json_attributes_topic: T) -> Self {
         self.json_attributes_topic = Some(json_attributes_topic.into());
         self
     }
@@ -567,19 +650,19 @@ impl Vacuum {
     }
 
     /// The MQTT topic to publish custom commands to the vacuum.
-    pub fn send_command_topic<T: Into<String>>(mut self, send_command_topic: T) -> Self {
+    pub fn send_command_topic<T: Into<Topic>>(mut self, send_command_topic: T) -> Self {
         self.send_command_topic = Some(send_command_topic.into());
         self
     }
 
     /// The MQTT topic to publish commands to control the vacuum's fan speed.
-    pub fn set_fan_speed_topic<T: Into<String>>(mut self, set_fan_speed_topic: T) -> Self {
+    pub fn set_fan_speed_topic<T: Into<Topic>>(mut self, set_fan_speed_topic: T) -> Self {
         self.set_fan_speed_topic = Some(set_fan_speed_topic.into());
         self
     }
 
     /// The MQTT topic subscribed to receive state messages from the vacuum. Messages received on the `state_topic` must be a valid JSON dictionary, with a mandatory `state` key and optionally `battery_level` and `fan_speed` keys as shown in the [example](#configuration-example).
-    pub fn state_topic<T: Into<String>>(mut self, state_topic: T) -> Self {
+    pub fn state_topic<T: Into<Topic>>(mut self, state_topic: T) -> Self {
         self.state_topic = Some(state_topic.into());
         self
     }
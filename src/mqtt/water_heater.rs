@@ -1,9 +1,12 @@
 use super::common::Qos;
 use super::common::TemperatureUnit;
 use super::common::{Availability, Device, EntityCategory, Origin};
+use crate::decimal::{DecimalError, IntoDecimal, TryIntoDecimal};
+use crate::topic::Topic;
 use crate::Entity;
+use bon::Builder;
 pub use rust_decimal::Decimal;
-use serde_derive::Serialize;
+use serde_derive::{Deserialize, Serialize};
 
 /// ---
 /// title: "MQTT water heater"
@@ -326,7 +329,9 @@ use serde_derive::Serialize;
 ///
 /// {% endraw %}
 ///
-#[derive(Clone, Debug, PartialEq, Serialize, Default)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default, Builder)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[builder(on(String, into), on(Topic, into))]
 pub struct WaterHeater {
     /// Replaces `~` with this value in any MQTT topic attribute.
     /// [See Home Assistant documentation](https://www.home-assistant.io/integrations/mqtt/#using-abbreviations-and-base-topic)
@@ -334,76 +339,129 @@ pub struct WaterHeater {
     pub topic_prefix: Option<String>,
 
     /// It is encouraged to add additional information about the origin that supplies MQTT entities via MQTT discovery by adding the origin option (can be abbreviated to o) to the discovery payload. Note that these options also support abbreviations. Information of the origin will be logged to the core event log when an item is discovered or updated.
-    #[serde(rename = "o")]
+    #[serde(rename = "o", alias = "origin", default)]
+    #[builder(default)]
     pub origin: Origin,
 
     /// Information about the device this button is a part of to tie it into the [device registry](https://developers.home-assistant.io/docs/en/device_registry_index.html). Only works when [`unique_id`](#unique_id) is set. At least one of identifiers or connections must be present to identify the device.
-    #[serde(rename = "dev")]
+    #[serde(rename = "dev", alias = "device", default)]
+    #[builder(default)]
     pub device: Device,
 
     /// A list of MQTT topics subscribed to receive availability (online/offline) updates. Must not be used together with `availability_topic`.
     #[serde(flatten)]
+    #[builder(default)]
     pub availability: Availability,
 
     /// The category of the entity. (optional, default: None)
-    #[serde(rename = "ent_cat", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "ent_cat",
+        alias = "entity_category",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub entity_category: Option<EntityCategory>,
 
     /// A template with which the value received on `current_temperature_topic` will be rendered.
-    #[serde(rename = "curr_temp_tpl", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "curr_temp_tpl",
+        alias = "current_temperature_template",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub current_temperature_template: Option<String>,
 
     /// The MQTT topic on which to listen for the current temperature. A `"None"` value received will reset the current temperature. Empty values (`'''`) will be ignored.
-    #[serde(rename = "curr_temp_t", skip_serializing_if = "Option::is_none")]
-    pub current_temperature_topic: Option<String>,
+    #[serde(
+        rename = "curr_temp_t",
+        alias = "current_temperature_topic",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub current_temperature_topic: Option<Topic>,
 
     /// Flag which defines if the entity should be enabled when first added.
-    #[serde(rename = "en", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "en",
+        alias = "enabled_by_default",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub enabled_by_default: Option<bool>,
 
     /// The encoding of the payloads received and published messages. Set to `""` to disable decoding of incoming payload.
-    #[serde(rename = "e", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "e",
+        alias = "encoding",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub encoding: Option<String>,
 
     /// Set the initial target temperature. The default value depends on the temperature unit, and will be 43.3°C or 110°F.
-    #[serde(rename = "init", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "init",
+        alias = "initial",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub initial: Option<i32>,
 
     /// [Icon](/docs/configuration/customizing-devices/#icon) for the entity.
-    #[serde(rename = "ic", skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "ic", alias = "icon", skip_serializing_if = "Option::is_none")]
     pub icon: Option<String>,
 
     /// Defines a [template](/docs/configuration/templating/#using-templates-with-the-mqtt-integration) to extract the JSON dictionary from messages received on the `json_attributes_topic`. Usage example can be found in [MQTT sensor](/integrations/sensor.mqtt/#json-attributes-template-configuration) documentation.
-    #[serde(rename = "json_attr_tpl", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "json_attr_tpl",
+        alias = "json_attributes_template",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub json_attributes_template: Option<String>,
 
     /// The MQTT topic subscribed to receive a JSON dictionary payload and then set as sensor attributes. Usage example can be found in [MQTT sensor](/integrations/sensor.mqtt/#json-attributes-topic-configuration) documentation.
-    #[serde(rename = "json_attr_t", skip_serializing_if = "Option::is_none")]
-    pub json_attributes_topic: Option<String>,
+    #[serde(
+        rename = "json_attr_t",
+        alias = "json_attributes_topic",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub json_attributes_topic: Option<Topic>,
 
     /// Maximum set point available. The default value depends on the temperature unit, and will be 60°C or 140°F.
     #[serde(rename = "max_temp", skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "schema", schemars(with = "Option<f64>"))]
     pub max_temp: Option<Decimal>,
 
     /// Minimum set point available. The default value depends on the temperature unit, and will be 43.3°C or 110°F.
     #[serde(rename = "min_temp", skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "schema", schemars(with = "Option<f64>"))]
     pub min_temp: Option<Decimal>,
 
     /// A template to render the value sent to the `mode_command_topic` with.
-    #[serde(rename = "mode_cmd_tpl", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "mode_cmd_tpl",
+        alias = "mode_command_template",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub mode_command_template: Option<String>,
 
     /// The MQTT topic to publish commands to change the water heater operation mode.
-    #[serde(rename = "mode_cmd_t", skip_serializing_if = "Option::is_none")]
-    pub mode_command_topic: Option<String>,
+    #[serde(
+        rename = "mode_cmd_t",
+        alias = "mode_command_topic",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub mode_command_topic: Option<Topic>,
 
     /// A template to render the value received on the `mode_state_topic` with.
-    #[serde(rename = "mode_stat_tpl", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "mode_stat_tpl",
+        alias = "mode_state_template",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub mode_state_template: Option<String>,
 
     /// The MQTT topic to subscribe for changes of the water heater operation mode. If this is not set, the operation mode works in optimistic mode (see below).
-    #[serde(rename = "mode_stat_t", skip_serializing_if = "Option::is_none")]
-    pub mode_state_topic: Option<String>,
+    #[serde(
+        rename = "mode_stat_t",
+        alias = "mode_state_topic",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub mode_state_topic: Option<Topic>,
 
     /// A list of supported modes. Needs to be a subset of the default values.
     #[serde(rename = "modes", skip_serializing_if = "Option::is_none")]
@@ -414,19 +472,35 @@ pub struct WaterHeater {
     pub name: Option<String>,
 
     /// Used instead of `name` for automatic generation of `entity_id`
-    #[serde(rename = "obj_id", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "obj_id",
+        alias = "object_id",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub object_id: Option<String>,
 
     /// Flag that defines if the water heater works in optimistic mode
-    #[serde(rename = "opt", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "opt",
+        alias = "optimistic",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub optimistic: Option<bool>,
 
     /// The payload that represents disabled state.
-    #[serde(rename = "pl_off", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "pl_off",
+        alias = "payload_off",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub payload_off: Option<String>,
 
     /// The payload that represents enabled state.
-    #[serde(rename = "pl_on", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "pl_on",
+        alias = "payload_on",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub payload_on: Option<String>,
 
     /// A template to render the value sent to the `power_command_topic` with. The `value` parameter is the payload set for `payload_on` or `payload_off`.
@@ -441,10 +515,11 @@ pub struct WaterHeater {
         rename = "power_command_topic",
         skip_serializing_if = "Option::is_none"
     )]
-    pub power_command_topic: Option<String>,
+    pub power_command_topic: Option<Topic>,
 
     /// The desired precision for this device. Can be used to match your actual water heater's precision. Supported values are `0.1`, `0.5` and `1.0`.
     #[serde(rename = "precision", skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "schema", schemars(with = "Option<f64>"))]
     pub precision: Option<Decimal>,
 
     /// The maximum QoS level to be used when receiving and publishing messages.
@@ -452,35 +527,67 @@ pub struct WaterHeater {
     pub qos: Option<Qos>,
 
     /// Defines if published messages should have the retain flag set.
-    #[serde(rename = "ret", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "ret",
+        alias = "retain",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub retain: Option<bool>,
 
     /// A template to render the value sent to the `temperature_command_topic` with.
-    #[serde(rename = "temp_cmd_tpl", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "temp_cmd_tpl",
+        alias = "temperature_command_template",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub temperature_command_template: Option<String>,
 
     /// The MQTT topic to publish commands to change the target temperature.
-    #[serde(rename = "temp_cmd_t", skip_serializing_if = "Option::is_none")]
-    pub temperature_command_topic: Option<String>,
+    #[serde(
+        rename = "temp_cmd_t",
+        alias = "temperature_command_topic",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub temperature_command_topic: Option<Topic>,
 
     /// A template to render the value received on the `temperature_state_topic` with.
-    #[serde(rename = "temp_stat_tpl", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "temp_stat_tpl",
+        alias = "temperature_state_template",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub temperature_state_template: Option<String>,
 
     /// The MQTT topic to subscribe for changes in the target temperature. If this is not set, the target temperature works in optimistic mode (see below). A `"None"` value received will reset the temperature set point. Empty values (`'''`) will be ignored.
-    #[serde(rename = "temp_stat_t", skip_serializing_if = "Option::is_none")]
-    pub temperature_state_topic: Option<String>,
+    #[serde(
+        rename = "temp_stat_t",
+        alias = "temperature_state_topic",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub temperature_state_topic: Option<Topic>,
 
     /// Defines the temperature unit of the device, `C` or `F`. If this is not set, the temperature unit is set to the system temperature unit.
-    #[serde(rename = "temp_unit", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "temp_unit",
+        alias = "temperature_unit",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub temperature_unit: Option<TemperatureUnit>,
 
     /// An ID that uniquely identifies this water heater device. If two water heater devices have the same unique ID, Home Assistant will raise an exception.
-    #[serde(rename = "uniq_id", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "uniq_id",
+        alias = "unique_id",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub unique_id: Option<String>,
 
     /// Default template to render the payloads on *all* `*_state_topic`s with.
-    #[serde(rename = "val_tpl", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "val_tpl",
+        alias = "value_template",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub value_template: Option<String>,
 }
 
@@ -526,7 +633,7 @@ impl WaterHeater {
     }
 
     /// The MQTT topic on which to listen for the current temperature. A `"None"` value received will reset the current temperature. Empty values (`'''`) will be ignored.
-    pub fn current_temperature_topic<T: Into<String>>(
+    pub fn current_temperature_topic<T: Into<Topic>>(
         mut self,
         current_temperature_topic: T,
     ) -> Self {
@@ -568,23 +675,33 @@ impl WaterHeater {
     }
 
     /// The MQTT topic subscribed to receive a JSON dictionary payload and then set as sensor attributes. Usage example can be found in [MQTT sensor](/integrations/sensor.mqtt/#json-attributes-topic-configuration) documentation.
-    pub fn json_attributes_topic<T: Into<String>>(mut self, json_attributes_topic: T) -> Self {
+    pub fn json_attributes_topic<T: Into<Topic>>(mut self, json_attributes_topic: T) -> Self {
         self.json_attributes_topic = Some(json_attributes_topic.into());
         self
     }
 
     /// Maximum set point available. The default value depends on the temperature unit, and will be 60°C or 140°F.
-    pub fn max_temp(mut self, max_temp: Decimal) -> Self {
-        self.max_temp = Some(max_temp);
+    pub fn max_temp<D: IntoDecimal>(mut self, max_temp: D) -> Self {
+        self.max_temp = Some(max_temp.into_decimal());
         self
     }
 
+    /// Same as [`Self::max_temp`] for the floats, fails when the value is NaN, infinite or out of the `Decimal` range.
+    pub fn try_max_temp<D: TryIntoDecimal>(self, max_temp: D) -> Result<Self, DecimalError> {
+        Ok(self.max_temp(max_temp.try_into_decimal()?))
+    }
+
     /// Minimum set point available. The default value depends on the temperature unit, and will be 43.3°C or 110°F.
-    pub fn min_temp(mut self, min_temp: Decimal) -> Self {
-        self.min_temp = Some(min_temp);
+    pub fn min_temp<D: IntoDecimal>(mut self, min_temp: D) -> Self {
+        self.min_temp = Some(min_temp.into_decimal());
         self
     }
 
+    /// Same as [`Self::min_temp`] for the floats, fails when the value is NaN, infinite or out of the `Decimal` range.
+    pub fn try_min_temp<D: TryIntoDecimal>(self, min_temp: D) -> Result<Self, DecimalError> {
+        Ok(self.min_temp(min_temp.try_into_decimal()?))
+    }
+
     /// A template to render the value sent to the `mode_command_topic` with.
     pub fn mode_command_template<T: Into<String>>(mut self, mode_command_template: T) -> Self {
         self.mode_command_template = Some(mode_command_template.into());
@@ -592,7 +709,7 @@ impl WaterHeater {
     }
 
     /// The MQTT topic to publish commands to change the water heater operation mode.
-    pub fn mode_command_topic<T: Into<String>>(mut self, mode_command_topic: T) -> Self {
+    pub fn mode_command_topic<T: Into<Topic>>(mut self, mode_command_topic: T) -> Self {
         self.mode_command_topic = Some(mode_command_topic.into());
         self
     }
@@ -604,7 +721,7 @@ impl WaterHeater {
     }
 
     /// The MQTT topic to subscribe for changes of the water heater operation mode. If this is not set, the operation mode works in optimistic mode (see below).
-    pub fn mode_state_topic<T: Into<String>>(mut self, mode_state_topic: T) -> Self {
+    pub fn mode_state_topic<T: Into<Topic>>(mut self, mode_state_topic: T) -> Self {
         self.mode_state_topic = Some(mode_state_topic.into());
         self
     }
@@ -652,17 +769,22 @@ impl WaterHeater {
     }
 
     /// The MQTT topic to publish commands to change the water heater power state. Sends the payload configured with `payload_on` if the water heater is turned on via the `water_heater.turn_on`, or the payload configured with `payload_off` if the water heater is turned off via the `water_heater.turn_off` service. Note that `optimistic` mode is not supported through `water_heater.turn_on` and `water_heater.turn_off` services. When called, these services will send a power command to the device but will not optimistically update the state of the water heater. The water heater device should report its state back via `mode_state_topic`.
-    pub fn power_command_topic<T: Into<String>>(mut self, power_command_topic: T) -> Self {
+    pub fn power_command_topic<T: Into<Topic>>(mut self, power_command_topic: T) -> Self {
         self.power_command_topic = Some(power_command_topic.into());
         self
     }
 
     /// The desired precision for this device. Can be used to match your actual water heater's precision. Supported values are `0.1`, `0.5` and `1.0`.
-    pub fn precision(mut self, precision: Decimal) -> Self {
-        self.precision = Some(precision);
+    pub fn precision<D: IntoDecimal>(mut self, precision: D) -> Self {
+        self.precision = Some(precision.into_decimal());
         self
     }
 
+    /// Same as [`Self::precision`] for the floats, fails when the value is NaN, infinite or out of the `Decimal` range.
+    pub fn try_precision<D: TryIntoDecimal>(self, precision: D) -> Result<Self, DecimalError> {
+        Ok(self.precision(precision.try_into_decimal()?))
+    }
+
     /// The maximum QoS level to be used when receiving and publishing messages.
     pub fn qos(mut self, qos: Qos) -> Self {
         self.qos = Some(qos);
@@ -685,7 +807,7 @@ impl WaterHeater {
     }
 
     /// The MQTT topic to publish commands to change the target temperature.
-    pub fn temperature_command_topic<T: Into<String>>(
+    pub fn temperature_command_topic<T: Into<Topic>>(
         mut self,
         temperature_command_topic: T,
     ) -> Self {
@@ -703,7 +825,7 @@ impl WaterHeater {
     }
 
     /// The MQTT topic to subscribe for changes in the target temperature. If this is not set, the target temperature works in optimistic mode (see below). A `"None"` value received will reset the temperature set point. Empty values (`'''`) will be ignored.
-    pub fn temperature_state_topic<T: Into<String>>(mut self, temperature_state_topic: T) -> Self {
+    pub fn temperature_state_topic<T: Into<Topic>>(mut self, temperature_state_topic: T) -> Self {
         self.temperature_state_topic = Some(temperature_state_topic.into());
         self
     }
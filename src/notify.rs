@@ -0,0 +1,115 @@
+use anyhow::{bail, Result};
+use rumqttc::v5::mqttbytes::QoS;
+
+use crate::{
+    client::{publish_traced, MqttPublisher, PublishOptions},
+    mqtt::notify::Notify,
+    topic::resolve,
+    HomeAssistantMqtt,
+};
+
+impl Notify {
+    /// Sends a notification like the `notify.send_message` action does: publishes the message on the command topic,
+    /// with the QoS and the retain flag of the entity.
+    ///
+    /// The `command_template` is rendered with the `value` (or `message`) and `title` variables, only the plain
+    /// `{{ name }}` expressions are supported. Without a command template the message is published as is and the title
+    /// is dropped, as Home Assistant does.
+    pub async fn publish_notification<C: MqttPublisher>(
+        &self,
+        ha: &HomeAssistantMqtt<C>,
+        title: Option<&str>,
+        message: &str,
+    ) -> Result<()> {
+        let topic = resolve(&self.command_topic, self.topic_prefix.as_deref());
+        let payload = match &self.command_template {
+            Some(template) => render(
+                template,
+                &[
+                    ("value", message),
+                    ("message", message),
+                    ("title", title.unwrap_or_default()),
+                ],
+            )?,
+            None => message.to_string(),
+        };
+        publish_traced(
+            &ha.client,
+            topic.to_string(),
+            self.qos.map(QoS::from).unwrap_or(QoS::AtMostOnce),
+            self.retain.unwrap_or(false),
+            payload.into_bytes(),
+            PublishOptions::default(),
+        )
+        .await?;
+        Ok(())
+    }
+}
+
+/// Replaces the `{{ name }}` expressions of the template with the values of the variables.
+fn render(template: &str, variables: &[(&str, &str)]) -> Result<String> {
+    if template.contains("{%") {
+        bail!("unsupported template statement in '{template}'");
+    }
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        rendered.push_str(&rest[..start]);
+        let Some(end) = rest[start..].find("}}") else {
+            bail!("unterminated template expression in '{template}'");
+        };
+        let name = rest[start + 2..start + end].trim();
+        match variables.iter().find(|(variable, _)| *variable == name) {
+            Some((_, value)) => rendered.push_str(value),
+            None => bail!("unsupported template expression '{name}' in '{template}'"),
+        }
+        rest = &rest[start + end + 2..];
+    }
+    rendered.push_str(rest);
+    Ok(rendered)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::MockClient;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn can_publish_notifications() {
+        let client = MockClient::default();
+        let ha = HomeAssistantMqtt::new(client.clone(), "homeassistant");
+        let screen = Notify::new("~/notifications").topic_prefix("home/screen");
+
+        screen
+            .publish_notification(&ha, Some("Door"), "The door is open")
+            .await
+            .unwrap();
+        assert_eq!(
+            client
+                .last_published("home/screen/notifications")
+                .unwrap()
+                .payload_str(),
+            "The door is open"
+        );
+
+        let screen = screen.command_template(r#"{"title": "{{ title }}", "text": "{{value}}"}"#);
+        screen
+            .publish_notification(&ha, Some("Door"), "The door is open")
+            .await
+            .unwrap();
+        assert_eq!(
+            client
+                .last_published("home/screen/notifications")
+                .unwrap()
+                .payload_str(),
+            r#"{"title": "Door", "text": "The door is open"}"#
+        );
+
+        let screen = screen.command_template("{{ value | upper }}");
+        assert!(screen
+            .publish_notification(&ha, None, "The door is open")
+            .await
+            .is_err());
+    }
+}
@@ -0,0 +1,60 @@
+use rust_decimal::Decimal;
+
+use crate::{
+    mqtt::{
+        common::NumberMode,
+        device_classes::NumberDeviceClass,
+        number::Number,
+        units::{PercentageUnit, TempUnit, Unit},
+    },
+    topic::Topic,
+};
+
+/// Numbers with a coherent range, step, display mode and unit of measurement.
+impl Number {
+    /// A percentage from 0 to 100 % set with a slider.
+    pub fn percentage(unique_id: impl Into<String>, command_topic: impl Into<Topic>) -> Self {
+        Number::new(command_topic)
+            .unique_id(unique_id)
+            .min(Decimal::ZERO)
+            .max(Decimal::ONE_HUNDRED)
+            .step(Decimal::ONE)
+            .mode(NumberMode::Slider)
+            .unit_of_measurement(Unit::Percentage(PercentageUnit::Percentage))
+    }
+
+    /// A temperature setpoint from 5 to 35 °C, set in a box by steps of 0.5 °C.
+    pub fn temperature(unique_id: impl Into<String>, command_topic: impl Into<Topic>) -> Self {
+        Number::new(command_topic)
+            .unique_id(unique_id)
+            .device_class(NumberDeviceClass::Temperature)
+            .min(Decimal::from(5))
+            .max(Decimal::from(35))
+            .step(Decimal::new(5, 1))
+            .mode(NumberMode::Box)
+            .unit_of_measurement(Unit::Temperature(TempUnit::Celsius))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Entity;
+
+    use super::*;
+
+    #[test]
+    fn can_build_number_presets() {
+        let number = Number::temperature("heater_setpoint", "heater/setpoint/set");
+
+        let attributes = serde_json::to_value(&number).unwrap();
+        assert_eq!(attributes["mode"], "box");
+        assert_eq!(attributes["step"], 0.5);
+        assert_eq!(attributes["unit_of_meas"], "°C");
+        assert!(Entity::from(number).validate().is_ok());
+        assert!(
+            Entity::from(Number::percentage("fan_speed", "fan/speed/set"))
+                .validate()
+                .is_ok()
+        );
+    }
+}
@@ -0,0 +1,80 @@
+use crate::{error::PublishError, mqtt::common::Origin};
+
+/// How the publisher handles the discovery configurations without origin information.
+///
+/// Home Assistant logs a warning for the discovered items without an origin, a policy enforces it for all the
+/// configurations published by a `HomeAssistantMqtt`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum OriginPolicy {
+    /// Publishes the configurations as they are.
+    #[default]
+    Ignore,
+    /// Refuses to publish the configurations without an origin name.
+    Strict,
+    /// Sets the origin of the configurations without an origin name.
+    Inject(Origin),
+}
+
+impl OriginPolicy {
+    /// Sets this crate as the origin of the configurations without an origin name.
+    pub fn permissive() -> Self {
+        OriginPolicy::Inject(default_origin())
+    }
+
+    /// Applies the policy to the origin of a configuration.
+    pub fn apply(&self, origin: &mut Origin) -> Result<(), PublishError> {
+        if !origin.name.is_empty() {
+            return Ok(());
+        }
+        match self {
+            OriginPolicy::Ignore => Ok(()),
+            OriginPolicy::Strict => Err(PublishError::MissingOrigin),
+            OriginPolicy::Inject(default_origin) => {
+                *origin = default_origin.clone();
+                Ok(())
+            }
+        }
+    }
+}
+
+/// This crate as the origin of the discovered items.
+pub fn default_origin() -> Origin {
+    Origin::new(env!("CARGO_PKG_NAME")).with_sw_version(env!("CARGO_PKG_VERSION"))
+}
+
+#[cfg(all(test, feature = "client", feature = "entity-sensor"))]
+mod tests {
+    use crate::{mqtt::sensor::Sensor, testing::MockClient, Entity, HomeAssistantMqtt};
+
+    use super::*;
+
+    #[test]
+    fn can_enforce_origins() {
+        let sensor = Entity::from(Sensor::new("home/temperature").unique_id("temperature"));
+        let ha = HomeAssistantMqtt::new(MockClient::default(), "homeassistant");
+
+        assert!(ha.discovery_message(&sensor).is_ok());
+        assert!(matches!(
+            ha.clone()
+                .with_origin_policy(OriginPolicy::Strict)
+                .discovery_message(&sensor),
+            Err(PublishError::MissingOrigin)
+        ));
+        let mut named_sensor = sensor.clone();
+        named_sensor.set_origin(Origin::new("bridge"));
+        assert!(ha
+            .clone()
+            .with_origin_policy(OriginPolicy::Strict)
+            .discovery_message(&named_sensor)
+            .is_ok());
+
+        let (_, payload) = ha
+            .with_origin_policy(OriginPolicy::permissive())
+            .discovery_message(&sensor)
+            .unwrap();
+        assert!(payload.contains(&format!(
+            r#""o":{{"name":"ha-mqtt-discovery","sw":"{}"}}"#,
+            env!("CARGO_PKG_VERSION")
+        )));
+    }
+}
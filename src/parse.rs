@@ -0,0 +1,340 @@
+use anyhow::{anyhow, bail, Result};
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+
+#[cfg(feature = "entity-alarm_control_panel")]
+use crate::mqtt::alarm_control_panel::AlarmControlPanel;
+#[cfg(feature = "entity-binary_sensor")]
+use crate::mqtt::binary_sensor::BinarySensor;
+#[cfg(feature = "entity-button")]
+use crate::mqtt::button::Button;
+#[cfg(feature = "entity-camera")]
+use crate::mqtt::camera::Camera;
+#[cfg(feature = "entity-climate")]
+use crate::mqtt::climate::Climate;
+#[cfg(feature = "entity-cover")]
+use crate::mqtt::cover::Cover;
+#[cfg(feature = "entity-device_tracker")]
+use crate::mqtt::device_tracker::DeviceTracker;
+#[cfg(feature = "entity-device_trigger")]
+use crate::mqtt::device_trigger::DeviceTrigger;
+#[cfg(feature = "entity-event")]
+use crate::mqtt::event::Event;
+#[cfg(feature = "entity-fan")]
+use crate::mqtt::fan::Fan;
+#[cfg(feature = "entity-humidifier")]
+use crate::mqtt::humidifier::Humidifier;
+#[cfg(feature = "entity-image")]
+use crate::mqtt::image::Image;
+#[cfg(feature = "entity-lawn_mower")]
+use crate::mqtt::lawn_mower::LawnMower;
+#[cfg(feature = "entity-light")]
+use crate::mqtt::light::Light;
+#[cfg(feature = "entity-lock")]
+use crate::mqtt::lock::Lock;
+#[cfg(feature = "entity-notify")]
+use crate::mqtt::notify::Notify;
+#[cfg(feature = "entity-number")]
+use crate::mqtt::number::Number;
+#[cfg(feature = "entity-scene")]
+use crate::mqtt::scene::Scene;
+#[cfg(feature = "entity-select")]
+use crate::mqtt::select::Select;
+#[cfg(feature = "entity-sensor")]
+use crate::mqtt::sensor::Sensor;
+#[cfg(feature = "entity-siren")]
+use crate::mqtt::siren::Siren;
+#[cfg(feature = "entity-switch")]
+use crate::mqtt::switch::Switch;
+#[cfg(feature = "entity-tag")]
+use crate::mqtt::tag::Tag;
+#[cfg(feature = "entity-text")]
+use crate::mqtt::text::Text;
+#[cfg(feature = "entity-update")]
+use crate::mqtt::update::Update;
+#[cfg(feature = "entity-vacuum")]
+use crate::mqtt::vacuum::Vacuum;
+#[cfg(feature = "entity-valve")]
+use crate::mqtt::valve::Valve;
+#[cfg(feature = "entity-water_heater")]
+use crate::mqtt::water_heater::WaterHeater;
+use crate::Entity;
+
+/// The components of the discovery topics, whether or not their entity feature is enabled.
+const PLATFORMS: [&str; 28] = [
+    "alarm_control_panel",
+    "binary_sensor",
+    "button",
+    "camera",
+    "climate",
+    "cover",
+    "device_automation",
+    "device_tracker",
+    "event",
+    "fan",
+    "humidifier",
+    "image",
+    "lawn_mower",
+    "light",
+    "lock",
+    "notify",
+    "number",
+    "scene",
+    "select",
+    "sensor",
+    "siren",
+    "switch",
+    "tag",
+    "text",
+    "update",
+    "vacuum",
+    "valve",
+    "water_heater",
+];
+
+impl Entity {
+    /// Deserializes the attributes of an entity of the platform, with their abbreviated or full names.
+    ///
+    /// The legacy `availability_topic`, `payload_available`, `payload_not_available` and `availability_template`
+    /// attributes are converted to an availability check.
+    pub fn from_platform(platform: &str, mut attributes: Value) -> Result<Entity> {
+        if let Value::Object(attributes) = &mut attributes {
+            convert_availability_topic(attributes);
+        }
+        let entity = match platform {
+            #[cfg(feature = "entity-alarm_control_panel")]
+            "alarm_control_panel" => parse::<AlarmControlPanel>(attributes)?,
+            #[cfg(feature = "entity-binary_sensor")]
+            "binary_sensor" => parse::<BinarySensor>(attributes)?,
+            #[cfg(feature = "entity-button")]
+            "button" => parse::<Button>(attributes)?,
+            #[cfg(feature = "entity-camera")]
+            "camera" => parse::<Camera>(attributes)?,
+            #[cfg(feature = "entity-climate")]
+            "climate" => parse::<Climate>(attributes)?,
+            #[cfg(feature = "entity-cover")]
+            "cover" => parse::<Cover>(attributes)?,
+            #[cfg(feature = "entity-device_tracker")]
+            "device_tracker" => parse::<DeviceTracker>(attributes)?,
+            #[cfg(feature = "entity-device_trigger")]
+            "device_automation" | "device_trigger" => parse::<DeviceTrigger>(attributes)?,
+            #[cfg(feature = "entity-event")]
+            "event" => parse::<Event>(attributes)?,
+            #[cfg(feature = "entity-fan")]
+            "fan" => parse::<Fan>(attributes)?,
+            #[cfg(feature = "entity-humidifier")]
+            "humidifier" => parse::<Humidifier>(attributes)?,
+            #[cfg(feature = "entity-image")]
+            "image" => parse::<Image>(attributes)?,
+            #[cfg(feature = "entity-lawn_mower")]
+            "lawn_mower" => parse::<LawnMower>(attributes)?,
+            #[cfg(feature = "entity-light")]
+            "light" => parse::<Light>(attributes)?,
+            #[cfg(feature = "entity-lock")]
+            "lock" => parse::<Lock>(attributes)?,
+            #[cfg(feature = "entity-notify")]
+            "notify" => parse::<Notify>(attributes)?,
+            #[cfg(feature = "entity-number")]
+            "number" => parse::<Number>(attributes)?,
+            #[cfg(feature = "entity-scene")]
+            "scene" => parse::<Scene>(attributes)?,
+            #[cfg(feature = "entity-select")]
+            "select" => parse::<Select>(attributes)?,
+            #[cfg(feature = "entity-sensor")]
+            "sensor" => parse::<Sensor>(attributes)?,
+            #[cfg(feature = "entity-siren")]
+            "siren" => parse::<Siren>(attributes)?,
+            #[cfg(feature = "entity-switch")]
+            "switch" => parse::<Switch>(attributes)?,
+            #[cfg(feature = "entity-tag")]
+            "tag" => parse::<Tag>(attributes)?,
+            #[cfg(feature = "entity-text")]
+            "text" => parse::<Text>(attributes)?,
+            #[cfg(feature = "entity-update")]
+            "update" => parse::<Update>(attributes)?,
+            #[cfg(feature = "entity-vacuum")]
+            "vacuum" => parse::<Vacuum>(attributes)?,
+            #[cfg(feature = "entity-valve")]
+            "valve" => parse::<Valve>(attributes)?,
+            #[cfg(feature = "entity-water_heater")]
+            "water_heater" => parse::<WaterHeater>(attributes)?,
+            _ => bail!("unsupported component '{platform}'"),
+        };
+        Ok(entity)
+    }
+
+    /// Deserializes a discovery configuration received on `<discovery_prefix>/<component>/[<node_id>/]<object_id>/config`.
+    ///
+    /// The platform is the `p` (`platform`) attribute of the payload when present, otherwise the component level
+    /// of the topic. See `Entity::from_device_discovery` for the device discovery configurations.
+    pub fn from_discovery(topic: &str, payload: &[u8]) -> Result<Entity> {
+        let mut attributes: Value = serde_json::from_slice(payload)?;
+        let attributes_map = attributes
+            .as_object_mut()
+            .ok_or(anyhow!("the discovery payload is not a JSON object"))?;
+        let platform = match attributes_map
+            .remove("p")
+            .or_else(|| attributes_map.remove("platform"))
+        {
+            Some(Value::String(platform)) => platform,
+            Some(platform) => bail!("invalid platform {platform}"),
+            None => topic_component(topic)
+                .ok_or(anyhow!("no component in the discovery topic '{topic}'"))?
+                .to_string(),
+        };
+        if platform == "device" {
+            bail!("'{topic}' is a device discovery topic");
+        }
+        Entity::from_platform(&platform, attributes)
+    }
+
+    /// Deserializes the components of a device discovery configuration, by key.
+    ///
+    /// Every component gets the device, the origin and the attributes shared at the device level, the components
+    /// only holding their platform, which Home Assistant removes, are skipped.
+    pub fn from_device_discovery(payload: &[u8]) -> Result<Vec<(String, Entity)>> {
+        let Value::Object(mut shared) = serde_json::from_slice(payload)? else {
+            bail!("the discovery payload is not a JSON object");
+        };
+        let components = match shared
+            .remove("cmps")
+            .or_else(|| shared.remove("components"))
+        {
+            Some(Value::Object(components)) => components,
+            _ => bail!("the device discovery payload has no components"),
+        };
+        let mut entities = vec![];
+        for (key, component) in components {
+            let Value::Object(component) = component else {
+                bail!("the component '{key}' is not a JSON object");
+            };
+            if component
+                .keys()
+                .all(|name| name == "p" || name == "platform")
+            {
+                continue;
+            }
+            let mut attributes = shared.clone();
+            attributes.extend(component);
+            let platform = match attributes
+                .remove("p")
+                .or_else(|| attributes.remove("platform"))
+            {
+                Some(Value::String(platform)) => platform,
+                _ => bail!("the component '{key}' has no platform"),
+            };
+            let entity = Entity::from_platform(&platform, Value::Object(attributes))?;
+            entities.push((key, entity));
+        }
+        Ok(entities)
+    }
+}
+
+/// The component level of a discovery topic, the one before `[<node_id>/]<object_id>/config`.
+fn topic_component(topic: &str) -> Option<&str> {
+    let levels: Vec<&str> = topic.strip_suffix("/config")?.split('/').collect();
+    [2, 3]
+        .into_iter()
+        .filter_map(|depth| levels.len().checked_sub(depth).map(|index| levels[index]))
+        .find(|level| PLATFORMS.contains(level) || *level == "device")
+}
+
+fn parse<T: DeserializeOwned + Into<Entity>>(attributes: Value) -> Result<Entity> {
+    Ok(serde_json::from_value::<T>(attributes)?.into())
+}
+
+fn convert_availability_topic(attributes: &mut Map<String, Value>) {
+    let Some(topic) = attributes
+        .remove("availability_topic")
+        .or_else(|| attributes.remove("avty_t"))
+    else {
+        return;
+    };
+    let mut check = Map::new();
+    check.insert("topic".to_string(), topic);
+    for (attributes_names, check_attribute) in [
+        (["payload_available", "pl_avail"], "payload_available"),
+        (
+            ["payload_not_available", "pl_not_avail"],
+            "payload_not_available",
+        ),
+        (["availability_template", "avty_tpl"], "value_template"),
+    ] {
+        for attribute in attributes_names {
+            if let Some(value) = attributes.remove(attribute) {
+                check.insert(check_attribute.to_string(), value);
+            }
+        }
+    }
+    attributes.insert(
+        "availability".to_string(),
+        Value::Array(vec![Value::Object(check)]),
+    );
+}
+
+#[cfg(all(test, feature = "entity-sensor", feature = "entity-switch"))]
+mod tests {
+    use crate::mqtt::{
+        common::{Availability, AvailabilityCheck, Device},
+        sensor::Sensor,
+        switch::Switch,
+    };
+
+    use super::*;
+
+    #[test]
+    fn can_parse_discovery_payloads() {
+        let Entity::Sensor(sensor) = Entity::from_discovery(
+            "homeassistant/sensor/garage/temperature/config",
+            br#"{"~": "garage", "stat_t": "~/temperature", "uniq_id": "temperature",
+                 "unit_of_measurement": "W", "avty_t": "garage/status", "pl_avail": "up"}"#,
+        )
+        .unwrap() else {
+            panic!("expected a sensor");
+        };
+        assert_eq!(
+            sensor,
+            Sensor::new("~/temperature")
+                .topic_prefix("garage")
+                .unique_id("temperature")
+                .unit_of_measurement(crate::mqtt::units::PowerUnit::Watt)
+                .availability(Availability {
+                    availability: vec![
+                        AvailabilityCheck::topic("garage/status").payload_available("up")
+                    ],
+                    ..Default::default()
+                })
+        );
+        assert!(matches!(
+            Entity::from_discovery(
+                "homeassistant/sensor/garage/door/config",
+                br#"{"p": "switch", "cmd_t": "garage/door/set"}"#
+            ),
+            Ok(Entity::Switch(_))
+        ));
+        assert!(Entity::from_discovery("homeassistant/thing/door/config", b"{}").is_err());
+        assert!(Entity::from_discovery("homeassistant/sensor/door/config", b"[]").is_err());
+
+        let entities = Entity::from_device_discovery(
+            br#"{"dev": {"ids": ["garage"]}, "o": {"name": "bridge"}, "stat_t": "garage/state",
+                 "cmps": {
+                   "door": {"p": "switch", "cmd_t": "garage/door/set", "uniq_id": "door"},
+                   "light": {"p": "light"}
+                 }}"#,
+        )
+        .unwrap();
+        assert_eq!(entities.len(), 1);
+        let (key, Entity::Switch(switch)) = &entities[0] else {
+            panic!("expected a switch");
+        };
+        assert_eq!(key, "door");
+        assert_eq!(
+            *switch,
+            Switch::new("garage/door/set")
+                .unique_id("door")
+                .state_topic("garage/state")
+                .device(Device::default().add_identifier("garage"))
+                .origin(crate::mqtt::common::Origin::new("bridge"))
+        );
+    }
+}
@@ -0,0 +1,229 @@
+//! Ready-made components of common devices, wired with consistent topics under a base topic.
+//!
+//! The object ID and the `unique_id` of the components are derived from the first identifier or connection of the
+//! device, see `Entity::derive_unique_id`. The `localized_*` variants look up the names of the components by key in a
+//! `NameProvider`, the main component of the device is unnamed by default so that it takes the name of the device.
+//!
+//! ```no_run
+//! # #[cfg(all(feature = "entity-sensor", feature = "entity-switch"))]
+//! # async fn example(ha: ha_mqtt_discovery::HomeAssistantMqtt) -> anyhow::Result<()> {
+//! # use ha_mqtt_discovery::{mqtt::common::Device, presets};
+//! let plug = presets::smart_plug(Device::default().name("Plug").add_identifier("plug-42"), "home/plug");
+//! ha.publish_device_components(&plug).await?;
+//! ha.publish_state("home/plug/power", "12.5").await?;
+//! # Ok(())
+//! # }
+//! ```
+
+#[cfg(feature = "entity-sensor")]
+use crate::mqtt::sensor::Sensor;
+#[cfg(feature = "entity-switch")]
+use crate::mqtt::switch::Switch;
+#[cfg(feature = "entity-binary_sensor")]
+use crate::mqtt::{binary_sensor::BinarySensor, device_classes::BinarySensorDeviceClass};
+#[cfg(feature = "entity-climate")]
+use crate::{
+    climate::HvacMode,
+    mqtt::{climate::Climate, common::TemperatureUnit},
+};
+use crate::{
+    components::DeviceComponents,
+    locale::{DefaultNames, NameProvider},
+    mqtt::common::Device,
+    Entity,
+};
+
+/// The object ID of the device components.
+fn object_id(device: &Device) -> String {
+    Entity::derive_unique_id(device, "")
+}
+
+/// A switchable plug metering its consumption.
+///
+/// | key       | topics                                   |
+/// |-----------|------------------------------------------|
+/// | `switch`  | `<base>/state`, `<base>/set` (`ON`/`OFF`) |
+/// | `power`   | `<base>/power` in W                      |
+/// | `energy`  | `<base>/energy` in kWh                   |
+/// | `voltage` | `<base>/voltage` in V                    |
+/// | `current` | `<base>/current` in A                    |
+#[cfg(all(feature = "entity-sensor", feature = "entity-switch"))]
+pub fn smart_plug(device: Device, base_topic: &str) -> DeviceComponents {
+    localized_smart_plug(device, base_topic, &DefaultNames)
+}
+
+/// A `smart_plug` with the names of its components translated by the given provider.
+#[cfg(all(feature = "entity-sensor", feature = "entity-switch"))]
+pub fn localized_smart_plug(
+    device: Device,
+    base_topic: &str,
+    names: &dyn NameProvider,
+) -> DeviceComponents {
+    let id = |name: &str| Entity::derive_unique_id(&device, name);
+    let topic = |name: &str| format!("{base_topic}/{name}");
+    let name = |key: &str, default: &str| names.name(key).unwrap_or_else(|| default.to_string());
+    let mut switch = Switch::new(topic("set"))
+        .unique_id(id("switch"))
+        .state_topic(topic("state"));
+    switch.name = names.name("switch");
+    DeviceComponents::new(object_id(&device), device.clone())
+        .component("switch", switch)
+        .component(
+            "power",
+            Sensor::power_w(id("power"), topic("power")).name(name("power", "Power")),
+        )
+        .component(
+            "energy",
+            Sensor::energy_kwh(id("energy"), topic("energy")).name(name("energy", "Energy")),
+        )
+        .component(
+            "voltage",
+            Sensor::voltage_v(id("voltage"), topic("voltage")).name(name("voltage", "Voltage")),
+        )
+        .component(
+            "current",
+            Sensor::current_a(id("current"), topic("current")).name(name("current", "Current")),
+        )
+}
+
+/// A battery-powered door or window contact.
+///
+/// | key       | topics                                     |
+/// |-----------|--------------------------------------------|
+/// | `contact` | `<base>/contact`, `ON` when open, `OFF` when closed |
+/// | `battery` | `<base>/battery` in %                      |
+#[cfg(all(feature = "entity-binary_sensor", feature = "entity-sensor"))]
+pub fn contact_sensor(device: Device, base_topic: &str) -> DeviceComponents {
+    localized_contact_sensor(device, base_topic, &DefaultNames)
+}
+
+/// A `contact_sensor` with the names of its components translated by the given provider.
+#[cfg(all(feature = "entity-binary_sensor", feature = "entity-sensor"))]
+pub fn localized_contact_sensor(
+    device: Device,
+    base_topic: &str,
+    names: &dyn NameProvider,
+) -> DeviceComponents {
+    let id = |name: &str| Entity::derive_unique_id(&device, name);
+    let topic = |name: &str| format!("{base_topic}/{name}");
+    let mut contact = BinarySensor::new(topic("contact"))
+        .unique_id(id("contact"))
+        .device_class(BinarySensorDeviceClass::Opening);
+    contact.name = names.name("contact");
+    let battery = names
+        .name("battery")
+        .unwrap_or_else(|| "Battery".to_string());
+    DeviceComponents::new(object_id(&device), device.clone())
+        .component("contact", contact)
+        .component(
+            "battery",
+            Sensor::battery_percent(id("battery"), topic("battery")).name(battery),
+        )
+}
+
+/// A heating thermostat in °C, switching between the `off` and `heat` modes.
+///
+/// | key          | topics                                                                 |
+/// |--------------|------------------------------------------------------------------------|
+/// | `thermostat` | `<base>/state` (a `ClimateState` document), `<base>/temperature/set`, `<base>/mode/set` |
+#[cfg(feature = "entity-climate")]
+pub fn thermostat(device: Device, base_topic: &str) -> DeviceComponents {
+    localized_thermostat(device, base_topic, &DefaultNames)
+}
+
+/// A `thermostat` with the name of its component translated by the given provider.
+#[cfg(feature = "entity-climate")]
+pub fn localized_thermostat(
+    device: Device,
+    base_topic: &str,
+    names: &dyn NameProvider,
+) -> DeviceComponents {
+    let topic = |name: &str| format!("{base_topic}/{name}");
+    let mut thermostat = Climate::default()
+        .unique_id(Entity::derive_unique_id(&device, "thermostat"))
+        .json_state_topic(topic("state"))
+        .temperature_command_topic(topic("temperature/set"))
+        .mode_command_topic(topic("mode/set"))
+        .modes(vec![HvacMode::Off, HvacMode::Heat])
+        .temperature_unit(TemperatureUnit::Celcius)
+        .temperature_defaults();
+    thermostat.name = names.name("thermostat");
+    DeviceComponents::new(object_id(&device), device).component("thermostat", thermostat)
+}
+
+#[cfg(all(
+    test,
+    feature = "client",
+    feature = "entity-binary_sensor",
+    feature = "entity-climate",
+    feature = "entity-sensor",
+    feature = "entity-switch"
+))]
+mod tests {
+    use std::collections::HashMap;
+
+    use serde_json::Value;
+
+    use crate::{testing::MockClient, HomeAssistantMqtt};
+
+    use super::*;
+
+    #[test]
+    fn can_build_presets() {
+        let ha = HomeAssistantMqtt::new(MockClient::default(), "homeassistant");
+        let device = Device::default().name("Plug").add_identifier("plug-42");
+
+        let plug = smart_plug(device.clone(), "home/plug");
+        assert_eq!(plug.object_id(), "plug_42");
+        assert_eq!(
+            plug.components().keys().collect::<Vec<_>>(),
+            ["current", "energy", "power", "switch", "voltage"]
+        );
+        let (topic, payload) = ha.device_discovery_message(&plug).unwrap();
+        assert_eq!(topic, "homeassistant/device/plug_42/config");
+        let payload: Value = serde_json::from_str(&payload).unwrap();
+        assert_eq!(payload["cmps"]["switch"]["uniq_id"], "plug_42_switch");
+        assert_eq!(payload["cmps"]["switch"]["cmd_t"], "home/plug/set");
+        assert_eq!(payload["cmps"]["power"]["stat_t"], "home/plug/power");
+
+        for components in [
+            contact_sensor(device.clone(), "home/door"),
+            thermostat(device, "home/heater"),
+        ] {
+            assert!(ha.device_discovery_message(&components).is_ok());
+            for entity in components.components().values() {
+                assert_eq!(entity.validate(), Ok(()));
+            }
+        }
+    }
+
+    #[test]
+    fn can_localize_presets() {
+        let device = Device::default().name("Prise").add_identifier("plug-42");
+        let french = HashMap::from([
+            ("power".to_string(), "Puissance".to_string()),
+            ("switch".to_string(), "Interrupteur".to_string()),
+        ]);
+
+        let plug = localized_smart_plug(device.clone(), "home/plug", &french);
+        assert_eq!(plug.components()["power"].name(), Some("Puissance"));
+        assert_eq!(plug.components()["switch"].name(), Some("Interrupteur"));
+        assert_eq!(plug.components()["energy"].name(), Some("Energy"));
+        assert_eq!(
+            smart_plug(device.clone(), "home/plug").components()["switch"].name(),
+            None
+        );
+
+        let french = HashMap::from([("battery".to_string(), "Batterie".to_string())]);
+        let contact = localized_contact_sensor(device.clone(), "home/door", &french);
+        assert_eq!(contact.components()["battery"].name(), Some("Batterie"));
+        assert_eq!(contact.components()["contact"].name(), None);
+
+        let french = HashMap::from([("thermostat".to_string(), "Chauffage".to_string())]);
+        let thermostat = localized_thermostat(device, "home/heater", &french);
+        assert_eq!(
+            thermostat.components()["thermostat"].name(),
+            Some("Chauffage")
+        );
+    }
+}
@@ -0,0 +1,393 @@
+use std::{
+    collections::BTreeMap,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+
+use anyhow::{bail, Result};
+use serde_derive::{Deserialize, Serialize};
+
+use crate::{client::MqttPublisher, Entity, HomeAssistantMqtt};
+
+/// A discovery configuration published by the bridge.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegistryEntry {
+    /// The discovery topic.
+    pub topic: String,
+    /// The `unique_id` of the entity, if any.
+    pub unique_id: Option<String>,
+    /// FNV-1a hash of the discovery payload.
+    pub payload_hash: u64,
+}
+
+/// Persists the discovery configurations published by the bridge between two runs.
+pub trait RegistryStore {
+    /// The entries saved by the previous run, empty on the first run.
+    fn load(&self) -> Result<Vec<RegistryEntry>>;
+
+    /// Saves the entries of the current run.
+    fn save(&self, entries: &[RegistryEntry]) -> Result<()>;
+}
+
+/// Stores the registry entries in a JSON file.
+#[derive(Clone, Debug)]
+pub struct JsonFileStore {
+    path: PathBuf,
+}
+
+impl JsonFileStore {
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl RegistryStore for JsonFileStore {
+    fn load(&self) -> Result<Vec<RegistryEntry>> {
+        if !self.path.exists() {
+            return Ok(vec![]);
+        }
+        Ok(serde_json::from_slice(&std::fs::read(&self.path)?)?)
+    }
+
+    fn save(&self, entries: &[RegistryEntry]) -> Result<()> {
+        Ok(std::fs::write(
+            &self.path,
+            serde_json::to_vec_pretty(entries)?,
+        )?)
+    }
+}
+
+/// Keeps the registry entries in memory, clones share the same entries.
+#[derive(Clone, Debug, Default)]
+pub struct MemoryStore {
+    entries: Arc<Mutex<Vec<RegistryEntry>>>,
+}
+
+impl RegistryStore for MemoryStore {
+    fn load(&self) -> Result<Vec<RegistryEntry>> {
+        Ok(self.entries.lock().unwrap().clone())
+    }
+
+    fn save(&self, entries: &[RegistryEntry]) -> Result<()> {
+        *self.entries.lock().unwrap() = entries.to_vec();
+        Ok(())
+    }
+}
+
+/// Records the entities published during a session to remove the ones which are no longer declared.
+///
+/// ```no_run
+/// # use ha_mqtt_discovery::{registry::{DiscoveryRegistry, JsonFileStore}, Entity, HomeAssistantMqtt};
+/// # async fn example(home_assistant: HomeAssistantMqtt, entities: Vec<Entity>) -> anyhow::Result<()> {
+/// let mut registry = DiscoveryRegistry::new(JsonFileStore::new("discovery.json"))?;
+/// for entity in entities {
+///     registry.publish(&home_assistant, entity).await?;
+/// }
+/// // removes the entities published by the previous run but not by this one
+/// registry.sync(&home_assistant).await?;
+/// // entities can then be enabled or disabled at runtime
+/// registry.disable_platform(&home_assistant, "sensor").await?;
+/// if let Some(mut entity) = registry.entity("heater_power") {
+///     entity.set_enabled(&home_assistant, true).await?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct DiscoveryRegistry<S: RegistryStore> {
+    store: S,
+    previous: BTreeMap<String, RegistryEntry>,
+    current: BTreeMap<String, RegistryEntry>,
+    entities: BTreeMap<String, Entity>,
+}
+
+impl<S: RegistryStore> DiscoveryRegistry<S> {
+    /// A registry loading the entries of the previous run from the store.
+    pub fn new(store: S) -> Result<Self> {
+        let previous = store
+            .load()?
+            .into_iter()
+            .map(|entry| (entry.topic.clone(), entry))
+            .collect();
+        Ok(Self {
+            store,
+            previous,
+            current: BTreeMap::new(),
+            entities: BTreeMap::new(),
+        })
+    }
+
+    /// The entries recorded during this session.
+    pub fn entries(&self) -> impl Iterator<Item = &RegistryEntry> {
+        self.current.values()
+    }
+
+    /// Publishes the discovery configuration of the entity and records it.
+    pub async fn publish<C: MqttPublisher>(
+        &mut self,
+        home_assistant: &HomeAssistantMqtt<C>,
+        entity: Entity,
+    ) -> Result<()> {
+        let (topic, payload) = home_assistant.discovery_message(&entity)?;
+        let entry = RegistryEntry {
+            topic: topic.clone(),
+            unique_id: entity.unique_id().map(str::to_string),
+            payload_hash: fnv1a(payload.as_bytes()),
+        };
+        home_assistant.publish_entity(entity.clone()).await?;
+        self.current.insert(topic.clone(), entry);
+        self.entities.insert(topic, entity);
+        Ok(())
+    }
+
+    /// The entity published during this session with the given `unique_id`.
+    pub fn entity(&mut self, unique_id: &str) -> Option<EntityRef<'_, S>> {
+        let topic = self
+            .entities
+            .iter()
+            .find(|(_, entity)| entity.unique_id() == Some(unique_id))
+            .map(|(topic, _)| topic.clone())?;
+        Some(EntityRef {
+            registry: self,
+            topic,
+        })
+    }
+
+    /// Disables every entity of the platform, eg. `sensor`, published during this session.
+    ///
+    /// Returns the number of entities whose configuration changed.
+    pub async fn disable_platform<C: MqttPublisher>(
+        &mut self,
+        home_assistant: &HomeAssistantMqtt<C>,
+        platform: &str,
+    ) -> Result<usize> {
+        let topics: Vec<String> = self
+            .entities
+            .iter()
+            .filter(|(_, entity)| entity.platform() == platform)
+            .map(|(topic, _)| topic.clone())
+            .collect();
+        let mut changed = 0;
+        for topic in topics {
+            if self.set_enabled(home_assistant, &topic, false).await? {
+                changed += 1;
+            }
+        }
+        Ok(changed)
+    }
+
+    async fn set_enabled<C: MqttPublisher>(
+        &mut self,
+        home_assistant: &HomeAssistantMqtt<C>,
+        topic: &str,
+        enabled: bool,
+    ) -> Result<bool> {
+        let Some(mut entity) = self.entities.get(topic).cloned() else {
+            return Ok(false);
+        };
+        let platform = entity.platform().to_string();
+        let Some(enabled_by_default) = entity.enabled_by_default_mut() else {
+            bail!("{platform} entities can't be disabled");
+        };
+        if enabled_by_default.unwrap_or(true) == enabled {
+            return Ok(false);
+        }
+        *enabled_by_default = Some(enabled);
+        self.publish(home_assistant, entity).await?;
+        Ok(true)
+    }
+
+    /// Removes the discovery configurations recorded by the previous run which weren't published during this session,
+    /// then saves the entries of this session.
+    ///
+    /// The store is left untouched when the same configurations as the previous run were published, as told by the
+    /// payload hashes of the entries.
+    ///
+    /// Returns the removed discovery topics.
+    pub async fn sync<C: MqttPublisher>(
+        &mut self,
+        home_assistant: &HomeAssistantMqtt<C>,
+    ) -> Result<Vec<String>> {
+        let mut removed = vec![];
+        for topic in self.previous.keys() {
+            if !self.current.contains_key(topic) {
+                home_assistant.remove_discovery(topic).await?;
+                removed.push(topic.clone());
+            }
+        }
+        let changed = self.current.len() != self.previous.len()
+            || self.current.values().any(|entry| {
+                let previous = self.previous.get(&entry.topic);
+                previous.map(|previous| previous.payload_hash) != Some(entry.payload_hash)
+            });
+        if changed {
+            let entries: Vec<RegistryEntry> = self.current.values().cloned().collect();
+            self.store.save(&entries)?;
+            self.previous = self.current.clone();
+        }
+        Ok(removed)
+    }
+}
+
+/// An entity published through the registry.
+pub struct EntityRef<'a, S: RegistryStore> {
+    registry: &'a mut DiscoveryRegistry<S>,
+    topic: String,
+}
+
+impl<S: RegistryStore> EntityRef<'_, S> {
+    /// The discovery topic of the entity.
+    pub fn topic(&self) -> &str {
+        &self.topic
+    }
+
+    /// The discovery configuration of the entity.
+    pub fn entity(&self) -> &Entity {
+        &self.registry.entities[&self.topic]
+    }
+
+    /// Sets `enabled_by_default` and publishes the configuration again, in place.
+    ///
+    /// Home Assistant only reads this flag when the entity is registered: an entity already registered keeps the
+    /// state chosen by the user. Nothing is published when the flag already has the requested value.
+    ///
+    /// Returns whether the configuration changed.
+    pub async fn set_enabled<C: MqttPublisher>(
+        &mut self,
+        home_assistant: &HomeAssistantMqtt<C>,
+        enabled: bool,
+    ) -> Result<bool> {
+        self.registry
+            .set_enabled(home_assistant, &self.topic, enabled)
+            .await
+    }
+}
+
+/// 64-bit FNV-1a, stable across Rust releases unlike the standard library hasher.
+pub(crate) fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x100000001b3)
+    })
+}
+
+#[cfg(all(test, feature = "entity-sensor"))]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use crate::{mqtt::sensor::Sensor, testing::MockClient};
+
+    use super::*;
+
+    #[derive(Clone, Default)]
+    struct CountingStore {
+        entries: MemoryStore,
+        saves: Arc<AtomicUsize>,
+    }
+
+    impl RegistryStore for CountingStore {
+        fn load(&self) -> Result<Vec<RegistryEntry>> {
+            self.entries.load()
+        }
+
+        fn save(&self, entries: &[RegistryEntry]) -> Result<()> {
+            self.saves.fetch_add(1, Ordering::Relaxed);
+            self.entries.save(entries)
+        }
+    }
+
+    #[tokio::test]
+    async fn can_remove_entities_no_longer_declared() {
+        let client = MockClient::default();
+        let home_assistant = client.ha();
+        let store = MemoryStore::default();
+        let sensor = |unique_id: &str| Entity::from(Sensor::new("home/state").unique_id(unique_id));
+
+        let mut registry = DiscoveryRegistry::new(store.clone()).unwrap();
+        registry
+            .publish(&home_assistant, sensor("temperature"))
+            .await
+            .unwrap();
+        registry
+            .publish(&home_assistant, sensor("humidity"))
+            .await
+            .unwrap();
+        assert!(registry.sync(&home_assistant).await.unwrap().is_empty());
+
+        let mut registry = DiscoveryRegistry::new(store).unwrap();
+        registry
+            .publish(&home_assistant, sensor("temperature"))
+            .await
+            .unwrap();
+        assert_eq!(
+            registry.sync(&home_assistant).await.unwrap(),
+            vec!["homeassistant/sensor/humidity/config"]
+        );
+        let removal = client
+            .last_published("homeassistant/sensor/humidity/config")
+            .unwrap();
+        assert!(removal.payload.is_empty());
+        assert!(removal.retain);
+    }
+
+    #[tokio::test]
+    async fn can_toggle_entities() {
+        let client = MockClient::default();
+        let home_assistant = client.ha();
+        let mut registry = DiscoveryRegistry::new(MemoryStore::default()).unwrap();
+        for unique_id in ["temperature", "humidity"] {
+            registry
+                .publish(
+                    &home_assistant,
+                    Sensor::new("home/state").unique_id(unique_id).into(),
+                )
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(
+            registry
+                .disable_platform(&home_assistant, "sensor")
+                .await
+                .unwrap(),
+            2
+        );
+        let mut humidity = registry.entity("humidity").unwrap();
+        assert!(humidity.set_enabled(&home_assistant, true).await.unwrap());
+        assert!(!humidity.set_enabled(&home_assistant, true).await.unwrap());
+
+        let configuration = client
+            .last_published("homeassistant/sensor/humidity/config")
+            .unwrap();
+        assert!(configuration.payload_str().contains("\"en\":true"));
+        let configuration = client
+            .last_published("homeassistant/sensor/temperature/config")
+            .unwrap();
+        assert!(configuration.payload_str().contains("\"en\":false"));
+        assert!(client
+            .published()
+            .iter()
+            .all(|publish| !publish.payload.is_empty()));
+    }
+
+    #[tokio::test]
+    async fn can_skip_saving_unchanged_entries() {
+        let home_assistant = MockClient::default().ha();
+        let store = CountingStore::default();
+        let sensor = |name: &str| {
+            Entity::from(
+                Sensor::new("home/temperature")
+                    .unique_id("temperature")
+                    .name(name),
+            )
+        };
+
+        for (name, saves) in [("Temperature", 1), ("Temperature", 1), ("Outdoor", 2)] {
+            let mut registry = DiscoveryRegistry::new(store.clone()).unwrap();
+            registry
+                .publish(&home_assistant, sensor(name))
+                .await
+                .unwrap();
+            registry.sync(&home_assistant).await.unwrap();
+            assert_eq!(store.saves.load(Ordering::Relaxed), saves, "{name}");
+        }
+    }
+}
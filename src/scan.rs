@@ -0,0 +1,520 @@
+use std::{collections::BTreeMap, time::Duration};
+
+use anyhow::Result;
+use rumqttc::v5::{
+    mqttbytes::{
+        v5::{Filter, Packet, Publish},
+        QoS,
+    },
+    AsyncClient, Event, EventLoop,
+};
+use serde_json::Value;
+use tokio::time::{timeout_at, Instant};
+
+use crate::{
+    client::MqttPublisher, discovery::DiscoveryTopic, error::PublishError, mqtt::common::Device,
+    Entity, HomeAssistantMqtt,
+};
+
+/// A retained discovery configuration found on the broker.
+#[derive(Clone)]
+pub struct DiscoveredConfiguration {
+    pub topic: DiscoveryTopic,
+    /// The `unique_id` of the entity, `None` for the device discovery configurations.
+    pub unique_id: Option<String>,
+    /// The configuration as published.
+    pub configuration: Value,
+    /// The entity, `None` when the configuration can't be parsed or is a device discovery configuration.
+    pub entity: Option<Entity>,
+    /// The components of a device discovery configuration by key.
+    pub components: Vec<(String, Entity)>,
+}
+
+impl DiscoveredConfiguration {
+    /// The name of the origin which published the configuration, read from its `o` attribute.
+    pub fn origin_name(&self) -> Option<&str> {
+        self.configuration
+            .get("o")
+            .or(self.configuration.get("origin"))
+            .and_then(|origin| origin.get("name"))
+            .and_then(Value::as_str)
+    }
+
+    /// The `unique_id` of the entity, or of the components of a device discovery configuration.
+    pub fn unique_ids(&self) -> impl Iterator<Item = &str> {
+        self.unique_id.as_deref().into_iter().chain(
+            self.components
+                .iter()
+                .filter_map(|(_, entity)| entity.unique_id()),
+        )
+    }
+}
+
+/// The retained discovery configurations published under a discovery prefix, by discovery topic.
+#[derive(Clone, Default)]
+pub struct DiscoveryInventory {
+    configurations: BTreeMap<String, DiscoveredConfiguration>,
+}
+
+impl DiscoveryInventory {
+    /// The configurations by discovery topic.
+    pub fn configurations(&self) -> &BTreeMap<String, DiscoveredConfiguration> {
+        &self.configurations
+    }
+
+    /// The `unique_id` published on more than one discovery topic, including the components of the devices, with
+    /// their topics.
+    pub fn duplicate_unique_ids(&self) -> BTreeMap<&str, Vec<&str>> {
+        let mut topics_by_id: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+        for (topic, configuration) in &self.configurations {
+            for unique_id in configuration.unique_ids() {
+                topics_by_id.entry(unique_id).or_default().push(topic);
+            }
+        }
+        topics_by_id.retain(|_, topics| topics.len() > 1);
+        topics_by_id
+    }
+
+    /// The discovery topics of the configurations sharing an identifier or a connection with the device, including
+    /// the device discovery configurations.
+    pub fn device_topics(&self, device: &Device) -> Vec<&str> {
+        self.configurations
+            .iter()
+            .filter(|(_, configuration)| {
+                let Some(other) = configuration
+                    .configuration
+                    .get("dev")
+                    .or(configuration.configuration.get("device"))
+                    .and_then(|other| serde_json::from_value::<Device>(other.clone()).ok())
+                else {
+                    return false;
+                };
+                device
+                    .identifiers
+                    .iter()
+                    .any(|id| other.identifiers.contains(id))
+                    || device
+                        .connections
+                        .iter()
+                        .any(|c| other.connections.contains(c))
+            })
+            .map(|(topic, _)| topic.as_str())
+            .collect()
+    }
+
+    /// The discovery topics of the configurations published by the origin whose `unique_id` isn't kept, the device
+    /// discovery configurations are kept when one of their components is.
+    pub fn orphan_topics(&self, origin_name: &str, keep: &[&str]) -> Vec<&str> {
+        self.configurations
+            .iter()
+            .filter(|(_, configuration)| {
+                configuration.origin_name() == Some(origin_name)
+                    && !configuration
+                        .unique_ids()
+                        .any(|unique_id| keep.contains(&unique_id))
+            })
+            .map(|(topic, _)| topic.as_str())
+            .collect()
+    }
+
+    /// Records a retained discovery configuration, the other messages are ignored.
+    pub fn handle_publish(&mut self, discovery_prefix: &str, publish: &Publish) {
+        if !publish.retain || publish.payload.is_empty() {
+            return;
+        }
+        let Ok(topic_name) = std::str::from_utf8(&publish.topic) else {
+            return;
+        };
+        let Some(topic) = DiscoveryTopic::parse(discovery_prefix, topic_name) else {
+            return;
+        };
+        let Ok(configuration) = serde_json::from_slice::<Value>(&publish.payload) else {
+            return;
+        };
+        let (entity, components) = if topic.component == "device" {
+            let components = Entity::from_device_discovery(&publish.payload).unwrap_or_default();
+            (None, components)
+        } else {
+            (
+                Entity::from_discovery(topic_name, &publish.payload).ok(),
+                vec![],
+            )
+        };
+        let unique_id = configuration
+            .get("uniq_id")
+            .or(configuration.get("unique_id"))
+            .and_then(Value::as_str)
+            .map(str::to_string);
+        self.configurations.insert(
+            topic_name.to_string(),
+            DiscoveredConfiguration {
+                topic,
+                unique_id,
+                configuration,
+                entity,
+                components,
+            },
+        );
+    }
+}
+
+impl<C: MqttPublisher> HomeAssistantMqtt<C> {
+    /// Removes every discovery configuration of the inventory attached to the device, see
+    /// `DiscoveryInventory::device_topics`, and returns their topics.
+    ///
+    /// The removed configurations are dropped from the inventory. Only the configurations retained by the broker when
+    /// the inventory was scanned are found.
+    ///
+    /// ```no_run
+    /// # use std::time::Duration;
+    /// # use ha_mqtt_discovery::{
+    /// #     mqtt::common::Device,
+    /// #     scan::scan_discovery,
+    /// #     v5::{AsyncClient, EventLoop},
+    /// #     HomeAssistantMqtt,
+    /// # };
+    /// # async fn example(
+    /// #     client: AsyncClient,
+    /// #     mut eventloop: EventLoop,
+    /// #     ha: HomeAssistantMqtt,
+    /// #     device: Device,
+    /// # ) -> anyhow::Result<()> {
+    /// let mut inventory = scan_discovery(&client, &mut eventloop, "homeassistant", Duration::from_secs(2)).await?;
+    /// ha.purge_device(&device, &mut inventory).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn purge_device(
+        &self,
+        device: &Device,
+        inventory: &mut DiscoveryInventory,
+    ) -> Result<Vec<String>, PublishError> {
+        let topics = inventory
+            .device_topics(device)
+            .into_iter()
+            .map(str::to_string)
+            .collect();
+        self.remove_inventoried(topics, inventory).await
+    }
+
+    /// Removes the configurations of the inventory published by the origin and no longer declared, see
+    /// `DiscoveryInventory::orphan_topics`, and returns their topics.
+    ///
+    /// The origin name is the ownership marker of the configurations: publish them with an origin, see
+    /// `HomeAssistantMqtt::with_default_origin`, to clean them up once their entities are gone. The configurations
+    /// without a `unique_id` are never kept.
+    ///
+    /// ```no_run
+    /// # use std::time::Duration;
+    /// # use ha_mqtt_discovery::{
+    /// #     mqtt::common::Device,
+    /// #     scan::scan_discovery,
+    /// #     v5::{AsyncClient, EventLoop},
+    /// #     HomeAssistantMqtt,
+    /// # };
+    /// # async fn example(
+    /// #     client: AsyncClient,
+    /// #     mut eventloop: EventLoop,
+    /// #     ha: HomeAssistantMqtt,
+    /// #     device: Device,
+    /// # ) -> anyhow::Result<()> {
+    /// let mut inventory = scan_discovery(&client, &mut eventloop, "homeassistant", Duration::from_secs(2)).await?;
+    /// ha.cleanup_orphans("my-bridge", &["garage_temperature", "garage_door"], &mut inventory).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn cleanup_orphans(
+        &self,
+        origin_name: &str,
+        keep: &[&str],
+        inventory: &mut DiscoveryInventory,
+    ) -> Result<Vec<String>, PublishError> {
+        let topics = inventory
+            .orphan_topics(origin_name, keep)
+            .into_iter()
+            .map(str::to_string)
+            .collect();
+        self.remove_inventoried(topics, inventory).await
+    }
+
+    async fn remove_inventoried(
+        &self,
+        topics: Vec<String>,
+        inventory: &mut DiscoveryInventory,
+    ) -> Result<Vec<String>, PublishError> {
+        for topic in &topics {
+            self.remove_discovery(topic).await?;
+            inventory.configurations.remove(topic);
+        }
+        Ok(topics)
+    }
+}
+
+/// Collects the retained discovery configurations published under the discovery prefix during the window.
+///
+/// The event loop must not be polled elsewhere in the meantime. The topics are unsubscribed at the end of the window,
+/// and the event loop is polled for at most another window until the broker acknowledges it. On a connection error the
+/// unsubscriptions are only queued, the caller must keep polling the event loop to send them.
+///
+/// ```no_run
+/// # use std::time::Duration;
+/// # use ha_mqtt_discovery::{
+/// #     scan::scan_discovery,
+/// #     v5::{AsyncClient, MqttOptions},
+/// # };
+/// # async fn example() -> anyhow::Result<()> {
+/// let (client, mut eventloop) = AsyncClient::new(MqttOptions::new("audit", "localhost", 1883), 10);
+/// let inventory = scan_discovery(&client, &mut eventloop, "homeassistant", Duration::from_secs(2)).await?;
+/// for (unique_id, topics) in inventory.duplicate_unique_ids() {
+///     println!("{unique_id} is published on {topics:?}");
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub async fn scan_discovery(
+    client: &AsyncClient,
+    eventloop: &mut EventLoop,
+    discovery_prefix: &str,
+    window: Duration,
+) -> Result<DiscoveryInventory> {
+    let discovery_prefix = discovery_prefix.trim_end_matches('/');
+    let filters = [
+        format!("{discovery_prefix}/+/+/config"),
+        format!("{discovery_prefix}/+/+/+/config"),
+    ];
+    client
+        .subscribe_many(
+            filters
+                .iter()
+                .map(|filter| Filter::new(filter.clone(), QoS::AtLeastOnce)),
+        )
+        .await?;
+    let mut inventory = DiscoveryInventory::default();
+    let scanned = poll_until(eventloop, Instant::now() + window, |event| {
+        if let Event::Incoming(Packet::Publish(publish)) = event {
+            inventory.handle_publish(discovery_prefix, &publish);
+        }
+        false
+    })
+    .await;
+    let mut unsubscribed = Ok(());
+    for filter in &filters {
+        unsubscribed = unsubscribed.and(client.unsubscribe(filter.clone()).await);
+    }
+    scanned?;
+    unsubscribed?;
+    let mut acknowledged = 0;
+    poll_until(eventloop, Instant::now() + window, |event| {
+        if let Event::Incoming(Packet::UnsubAck(_)) = event {
+            acknowledged += 1;
+        }
+        acknowledged == filters.len()
+    })
+    .await?;
+    Ok(inventory)
+}
+
+/// Polls the event loop until `handle` returns `true` or the deadline, fails on the first connection error.
+async fn poll_until<F: FnMut(Event) -> bool>(
+    eventloop: &mut EventLoop,
+    deadline: Instant,
+    mut handle: F,
+) -> Result<()> {
+    while let Ok(event) = timeout_at(deadline, eventloop.poll()).await {
+        if handle(event?) {
+            break;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(all(test, feature = "entity-sensor"))]
+mod tests {
+    use rumqttc::v5::{MqttOptions, Request};
+
+    use crate::{
+        mqtt::{common::Origin, sensor::Sensor},
+        testing::MockClient,
+    };
+
+    use super::*;
+
+    fn retained(topic: &str, payload: &str) -> Publish {
+        let mut publish = Publish::new(topic, QoS::AtLeastOnce, payload.to_string(), None);
+        publish.retain = true;
+        publish
+    }
+
+    #[test]
+    fn can_inventory_discovery_configurations() {
+        let mut inventory = DiscoveryInventory::default();
+        for publish in [
+            retained(
+                "homeassistant/sensor/garage/temperature/config",
+                r#"{"stat_t": "garage/temperature", "uniq_id": "temperature"}"#,
+            ),
+            retained(
+                "homeassistant/device/garage/config",
+                r#"{"dev": {"ids": ["garage"]}, "cmps": {
+                    "temperature": {"p": "sensor", "stat_t": "garage/t", "uniq_id": "temperature"}
+                }}"#,
+            ),
+            retained("homeassistant/sensor/legacy/config", r#"{"uniq_id": 42}"#),
+            retained("homeassistant/sensor/garage/state", "21.5"),
+            Publish::new(
+                "homeassistant/sensor/live/config",
+                QoS::AtLeastOnce,
+                "{}",
+                None,
+            ),
+        ] {
+            inventory.handle_publish("homeassistant", &publish);
+        }
+
+        let configurations = inventory.configurations();
+        assert_eq!(configurations.len(), 3);
+        let temperature = &configurations["homeassistant/sensor/garage/temperature/config"];
+        assert_eq!(temperature.topic.node_id.as_deref(), Some("garage"));
+        assert_eq!(temperature.unique_id.as_deref(), Some("temperature"));
+        assert!(matches!(temperature.entity, Some(Entity::Sensor(_))));
+        let legacy = &configurations["homeassistant/sensor/legacy/config"];
+        assert!(legacy.entity.is_none());
+        assert_eq!(legacy.configuration["uniq_id"], 42);
+        assert_eq!(
+            configurations["homeassistant/device/garage/config"]
+                .components
+                .len(),
+            1
+        );
+        assert_eq!(
+            inventory.duplicate_unique_ids(),
+            BTreeMap::from([(
+                "temperature",
+                vec![
+                    "homeassistant/device/garage/config",
+                    "homeassistant/sensor/garage/temperature/config"
+                ]
+            )])
+        );
+    }
+
+    #[tokio::test]
+    async fn can_purge_devices() {
+        let mut inventory = DiscoveryInventory::default();
+        for (topic, payload) in [
+            (
+                "homeassistant/device/garage/config",
+                r#"{"dev": {"ids": ["garage"]}, "cmps": {"door": {"p": "switch", "cmd_t": "garage/set"}}}"#,
+            ),
+            (
+                "homeassistant/sensor/garage_temperature/config",
+                r#"{"stat_t": "garage/temperature", "device": {"identifiers": ["other", "garage"]}}"#,
+            ),
+            (
+                "homeassistant/sensor/kitchen_temperature/config",
+                r#"{"stat_t": "kitchen/temperature", "dev": {"ids": ["kitchen"]}}"#,
+            ),
+        ] {
+            inventory.handle_publish("homeassistant", &retained(topic, payload));
+        }
+        let client = MockClient::default();
+        let ha = HomeAssistantMqtt::new(client.clone(), "homeassistant");
+
+        let removed = ha
+            .purge_device(&Device::default().add_identifier("garage"), &mut inventory)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            removed,
+            [
+                "homeassistant/device/garage/config",
+                "homeassistant/sensor/garage_temperature/config"
+            ]
+        );
+        assert!(client
+            .published()
+            .iter()
+            .all(|message| message.payload.is_empty() && message.retain));
+        assert_eq!(client.published().len(), 2);
+        assert_eq!(inventory.configurations().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn can_cleanup_orphans() {
+        let client = MockClient::default();
+        let ha = HomeAssistantMqtt::new(client.clone(), "homeassistant")
+            .with_default_origin(Origin::new("my-bridge"));
+        for entity in [
+            Sensor::new("garage/temperature").unique_id("garage_temperature"),
+            Sensor::new("garage/humidity").unique_id("garage_humidity"),
+        ] {
+            ha.publish_entity(entity.into()).await.unwrap();
+        }
+        let mut inventory = DiscoveryInventory::default();
+        for message in client.published() {
+            inventory.handle_publish(
+                "homeassistant",
+                &retained(&message.topic, message.payload_str()),
+            );
+        }
+        inventory.handle_publish(
+            "homeassistant",
+            &retained(
+                "homeassistant/sensor/kitchen_temperature/config",
+                r#"{"stat_t": "kitchen/temperature", "uniq_id": "kitchen_temperature", "o": {"name": "other"}}"#,
+            ),
+        );
+
+        let removed = ha
+            .cleanup_orphans("my-bridge", &["garage_temperature"], &mut inventory)
+            .await
+            .unwrap();
+
+        assert_eq!(removed, ["homeassistant/sensor/garage_humidity/config"]);
+        assert!(client
+            .last_published("homeassistant/sensor/garage_humidity/config")
+            .unwrap()
+            .payload
+            .is_empty());
+        assert_eq!(
+            inventory.configurations().keys().collect::<Vec<_>>(),
+            [
+                "homeassistant/sensor/garage_temperature/config",
+                "homeassistant/sensor/kitchen_temperature/config"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn can_unsubscribe_after_connection_errors() {
+        let (client, mut eventloop) =
+            AsyncClient::new(MqttOptions::new("audit", "127.0.0.1", 1), 10);
+
+        assert!(scan_discovery(
+            &client,
+            &mut eventloop,
+            "homeassistant/",
+            Duration::from_secs(5)
+        )
+        .await
+        .is_err());
+
+        eventloop.clean();
+        let unsubscribed: Vec<_> = eventloop
+            .pending
+            .iter()
+            .filter_map(|request| match request {
+                Request::Unsubscribe(unsubscribe) => Some(unsubscribe.filters.clone()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            unsubscribed,
+            vec![
+                vec!["homeassistant/+/+/config"],
+                vec!["homeassistant/+/+/+/config"]
+            ]
+        );
+    }
+}
@@ -0,0 +1,55 @@
+use anyhow::{anyhow, Result};
+use rumqttc::v5::mqttbytes::QoS;
+
+use crate::{
+    client::{publish_traced, MqttPublisher, PublishOptions},
+    mqtt::scene::Scene,
+    topic::resolve,
+    HomeAssistantMqtt,
+};
+
+impl Scene {
+    /// Activates the scene like Home Assistant does: publishes `payload_on` on the command topic, with the QoS and
+    /// the retain flag of the scene.
+    pub async fn trigger<C: MqttPublisher>(&self, ha: &HomeAssistantMqtt<C>) -> Result<()> {
+        let topic = self
+            .command_topic
+            .as_ref()
+            .ok_or(anyhow!("scene entity has no command topic"))?;
+        let topic = resolve(topic, self.topic_prefix.as_deref());
+        let payload = self.payload_on.as_deref().unwrap_or("ON");
+        publish_traced(
+            &ha.client,
+            topic.to_string(),
+            self.qos.map(QoS::from).unwrap_or(QoS::AtMostOnce),
+            self.retain.unwrap_or(false),
+            payload.as_bytes().to_vec(),
+            PublishOptions::default(),
+        )
+        .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::MockClient;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn can_trigger_scenes() {
+        let client = MockClient::default();
+        let ha = HomeAssistantMqtt::new(client.clone(), "homeassistant");
+        let scene = Scene::default()
+            .command_topic("home/scene/movie/set")
+            .payload_on("activate");
+
+        scene.trigger(&ha).await.unwrap();
+
+        let published = client.last_published("home/scene/movie/set").unwrap();
+        assert_eq!(published.payload_str(), "activate");
+        assert!(!published.retain);
+        assert!(Scene::default().trigger(&ha).await.is_err());
+    }
+}
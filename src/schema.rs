@@ -0,0 +1,111 @@
+use std::collections::BTreeMap;
+
+use schemars::{JsonSchema, Schema, SchemaGenerator};
+
+use crate::Entity;
+
+/// Inserts the schema of each entity configuration whose platform is enabled.
+macro_rules! insert_schemas {
+    ($schemas:ident, $($feature:literal => $platform:literal: $entity:ty,)*) => {
+        $(
+            #[cfg(feature = $feature)]
+            $schemas.insert($platform, root_schema::<$entity>());
+        )*
+    };
+}
+
+impl Entity {
+    /// The JSON Schema of the discovery payload of every enabled platform, keyed by platform, eg. `sensor`.
+    ///
+    /// The schemas describe the payloads this crate publishes and parses: the attributes are listed under their
+    /// abbreviation, and the other properties, like the full attribute names, are allowed without being validated.
+    pub fn json_schemas() -> BTreeMap<&'static str, Schema> {
+        let mut schemas = BTreeMap::new();
+        insert_schemas!(
+            schemas,
+            "entity-alarm_control_panel" => "alarm_control_panel": crate::mqtt::alarm_control_panel::AlarmControlPanel,
+            "entity-binary_sensor" => "binary_sensor": crate::mqtt::binary_sensor::BinarySensor,
+            "entity-button" => "button": crate::mqtt::button::Button,
+            "entity-camera" => "camera": crate::mqtt::camera::Camera,
+            "entity-climate" => "climate": crate::mqtt::climate::Climate,
+            "entity-cover" => "cover": crate::mqtt::cover::Cover,
+            "entity-device_tracker" => "device_tracker": crate::mqtt::device_tracker::DeviceTracker,
+            "entity-device_trigger" => "device_automation": crate::mqtt::device_trigger::DeviceTrigger,
+            "entity-event" => "event": crate::mqtt::event::Event,
+            "entity-fan" => "fan": crate::mqtt::fan::Fan,
+            "entity-humidifier" => "humidifier": crate::mqtt::humidifier::Humidifier,
+            "entity-image" => "image": crate::mqtt::image::Image,
+            "entity-lawn_mower" => "lawn_mower": crate::mqtt::lawn_mower::LawnMower,
+            "entity-light" => "light": crate::mqtt::light::Light,
+            "entity-lock" => "lock": crate::mqtt::lock::Lock,
+            "entity-notify" => "notify": crate::mqtt::notify::Notify,
+            "entity-number" => "number": crate::mqtt::number::Number,
+            "entity-scene" => "scene": crate::mqtt::scene::Scene,
+            "entity-select" => "select": crate::mqtt::select::Select,
+            "entity-sensor" => "sensor": crate::mqtt::sensor::Sensor,
+            "entity-siren" => "siren": crate::mqtt::siren::Siren,
+            "entity-switch" => "switch": crate::mqtt::switch::Switch,
+            "entity-tag" => "tag": crate::mqtt::tag::Tag,
+            "entity-text" => "text": crate::mqtt::text::Text,
+            "entity-update" => "update": crate::mqtt::update::Update,
+            "entity-vacuum" => "vacuum": crate::mqtt::vacuum::Vacuum,
+            "entity-valve" => "valve": crate::mqtt::valve::Valve,
+            "entity-water_heater" => "water_heater": crate::mqtt::water_heater::WaterHeater,
+        );
+        schemas
+    }
+}
+
+/// The schema of an entity configuration, see `Entity::json_schema`.
+pub(crate) fn schema_of<T: JsonSchema>(_: &T) -> Schema {
+    root_schema::<T>()
+}
+
+fn root_schema<T: JsonSchema>() -> Schema {
+    SchemaGenerator::default().into_root_schema_for::<T>()
+}
+
+#[cfg(all(test, feature = "entity-sensor"))]
+mod tests {
+    use serde_json::{json, Value};
+
+    use crate::mqtt::{
+        device_classes::SensorDeviceClass,
+        sensor::Sensor,
+        units::{TempUnit, Unit},
+    };
+
+    use super::*;
+
+    #[test]
+    fn can_export_entity_json_schemas() {
+        let schemas = Entity::json_schemas();
+        #[cfg(feature = "all-entities")]
+        assert_eq!(schemas.len(), 28);
+
+        let sensor = Entity::from(
+            Sensor::new("~/state")
+                .device_class(SensorDeviceClass::Temperature)
+                .unit_of_measurement(Unit::Temperature(TempUnit::Celsius))
+                .suggested_display_precision(1),
+        );
+        let schema = sensor.json_schema();
+        assert_eq!(Some(&schema), schemas.get("sensor"));
+
+        let schema = schema.as_value();
+        assert_eq!(schema["required"], json!(["stat_t"]));
+        let properties = schema["properties"].as_object().unwrap();
+        for attribute in sensor.get_attributes().unwrap().as_object().unwrap().keys() {
+            assert!(properties.contains_key(attribute), "{attribute}");
+        }
+        let device_classes = schema["$defs"]["SensorDeviceClass"]["enum"]
+            .as_array()
+            .unwrap();
+        assert!(device_classes.contains(&Value::from("temperature")));
+        assert!(device_classes.contains(&Value::Null));
+        assert_eq!(
+            schema["$defs"]["Qos"]["enum"],
+            json!([0, 1, 2, "0", "1", "2"])
+        );
+    }
+}
@@ -0,0 +1,180 @@
+use std::str::FromStr;
+
+#[cfg(feature = "client")]
+use anyhow::{anyhow, Result};
+
+#[cfg(feature = "client")]
+use crate::{client::MqttPublisher, HomeAssistantMqtt};
+use crate::{
+    mqtt::select::Select,
+    topic::{resolve, Topic},
+    Entity,
+};
+
+/// A select whose options are the values of a Rust type, usually an enum with `Display` and `FromStr`
+/// implementations.
+///
+/// ```no_run
+/// # use std::{fmt, str::FromStr};
+/// # use ha_mqtt_discovery::{mqtt::select::Select, select::TypedSelect};
+/// # enum Mode {
+/// #     Eco,
+/// #     Comfort,
+/// # }
+/// # impl fmt::Display for Mode {
+/// #     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+/// #         f.write_str(match self {
+/// #             Mode::Eco => "eco",
+/// #             Mode::Comfort => "comfort",
+/// #         })
+/// #     }
+/// # }
+/// # impl FromStr for Mode {
+/// #     type Err = ();
+/// #     fn from_str(mode: &str) -> Result<Self, ()> {
+/// #         match mode {
+/// #             "eco" => Ok(Mode::Eco),
+/// #             "comfort" => Ok(Mode::Comfort),
+/// #             _ => Err(()),
+/// #         }
+/// #     }
+/// # }
+/// # #[cfg(feature = "client")]
+/// # async fn example(ha: ha_mqtt_discovery::HomeAssistantMqtt, payload: &str) -> anyhow::Result<()> {
+/// let select = TypedSelect::new(Select::new("heater/mode/set", Vec::<String>::new()), [Mode::Eco, Mode::Comfort])
+///     .state_topic("heater/mode");
+/// ha.publish_entity(select.entity()).await?;
+/// if let Some(mode) = select.command(payload) {
+///     select.publish_state(&ha, &mode).await?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct TypedSelect<T> {
+    select: Select,
+    options: Vec<T>,
+}
+
+impl<T: ToString + FromStr> TypedSelect<T> {
+    /// Replaces the options of the select with the given values.
+    pub fn new<I: IntoIterator<Item = T>>(select: Select, options: I) -> Self {
+        let options: Vec<T> = options.into_iter().collect();
+        Self {
+            select: select.options(options.iter().map(ToString::to_string).collect()),
+            options,
+        }
+    }
+
+    /// The MQTT topic subscribed to receive update of the selected option.
+    pub fn state_topic<S: Into<Topic>>(mut self, state_topic: S) -> Self {
+        self.select = self.select.state_topic(state_topic);
+        self
+    }
+
+    /// The select configuration.
+    pub fn select(&self) -> &Select {
+        &self.select
+    }
+
+    /// The select configuration, to publish its discovery configuration.
+    pub fn entity(&self) -> Entity {
+        self.select.clone().into()
+    }
+
+    /// The values of the options.
+    pub fn options(&self) -> &[T] {
+        &self.options
+    }
+
+    /// The value of a command payload, `None` when the payload isn't one of the options.
+    pub fn command(&self, payload: &str) -> Option<T> {
+        if !self.select.options.iter().any(|option| option == payload) {
+            return None;
+        }
+        T::from_str(payload).ok()
+    }
+
+    /// Publishes the selected value on the state topic of the select.
+    #[cfg(feature = "client")]
+    pub async fn publish_state<C: MqttPublisher>(
+        &self,
+        ha: &HomeAssistantMqtt<C>,
+        value: &T,
+    ) -> Result<()> {
+        let state = value.to_string();
+        if !self.select.options.contains(&state) {
+            return Err(anyhow!("'{state}' is not one of the select options"));
+        }
+        let topic = self
+            .select
+            .state_topic
+            .as_ref()
+            .ok_or(anyhow!("select entity has no state topic"))?;
+        let topic = resolve(topic, self.select.topic_prefix.as_deref());
+        ha.publish_state(&topic, state).await?;
+        Ok(())
+    }
+}
+
+impl<T> From<TypedSelect<T>> for Entity {
+    fn from(select: TypedSelect<T>) -> Self {
+        select.select.into()
+    }
+}
+
+#[cfg(all(test, feature = "client"))]
+mod tests {
+    use std::fmt::Display;
+
+    use crate::testing::MockClient;
+
+    use super::*;
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    enum Mode {
+        Eco,
+        Comfort,
+    }
+
+    impl Display for Mode {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Mode::Eco => write!(f, "eco"),
+                Mode::Comfort => write!(f, "comfort"),
+            }
+        }
+    }
+
+    impl FromStr for Mode {
+        type Err = ();
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            match s {
+                "eco" => Ok(Mode::Eco),
+                "comfort" => Ok(Mode::Comfort),
+                _ => Err(()),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn can_round_trip_typed_options() {
+        let client = MockClient::default();
+        let ha = HomeAssistantMqtt::new(client.clone(), "homeassistant");
+        let select = TypedSelect::new(
+            Select::new("heater/mode/set", Vec::<String>::new()),
+            [Mode::Eco, Mode::Comfort],
+        )
+        .state_topic("heater/mode");
+
+        assert_eq!(select.select().options, vec!["eco", "comfort"]);
+        assert_eq!(select.command("comfort"), Some(Mode::Comfort));
+        assert_eq!(select.command("boost"), None);
+        select.publish_state(&ha, &Mode::Eco).await.unwrap();
+        assert_eq!(
+            client.last_published("heater/mode").unwrap().payload_str(),
+            "eco"
+        );
+    }
+}
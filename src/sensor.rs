@@ -0,0 +1,180 @@
+use std::time::Duration;
+
+use crate::{
+    mqtt::{
+        common::SensorStateClass,
+        device_classes::SensorDeviceClass,
+        sensor::Sensor,
+        units::{
+            ElectricalUnit, EnergyUnit, PercentageUnit, PowerUnit, PressureUnit, TempUnit, Unit,
+            VoltUnit,
+        },
+    },
+    topic::Topic,
+};
+
+impl Sensor {
+    /// The state becomes unavailable when it isn't updated for this duration, rounded up to the second.
+    ///
+    /// Battery-powered sensors reporting at a fixed interval should expire after a few missed reports.
+    pub fn expire_after(mut self, expire_after: Duration) -> Self {
+        let seconds = expire_after.as_secs() + u64::from(expire_after.subsec_nanos() > 0);
+        self.availability = self.availability.expire_after(seconds);
+        self
+    }
+}
+
+/// Sensors with a consistent device class, state class, unit of measurement and display precision, as expected by
+/// the Home Assistant long-term statistics.
+impl Sensor {
+    fn preset(
+        unique_id: impl Into<String>,
+        state_topic: impl Into<Topic>,
+        device_class: SensorDeviceClass,
+        state_class: SensorStateClass,
+        unit: Unit,
+        precision: i32,
+    ) -> Self {
+        Sensor::new(state_topic)
+            .unique_id(unique_id)
+            .device_class(device_class)
+            .state_class(state_class)
+            .unit_of_measurement(unit)
+            .suggested_display_precision(precision)
+    }
+
+    /// An energy meter in kWh, whose total only increases.
+    pub fn energy_kwh(unique_id: impl Into<String>, state_topic: impl Into<Topic>) -> Self {
+        Self::preset(
+            unique_id,
+            state_topic,
+            SensorDeviceClass::Energy,
+            SensorStateClass::TotalIncreasing,
+            Unit::Energy(EnergyUnit::KiloWattHour),
+            2,
+        )
+    }
+
+    /// A power measurement in W.
+    pub fn power_w(unique_id: impl Into<String>, state_topic: impl Into<Topic>) -> Self {
+        Self::preset(
+            unique_id,
+            state_topic,
+            SensorDeviceClass::Power,
+            SensorStateClass::Measurement,
+            Unit::Power(PowerUnit::Watt),
+            0,
+        )
+    }
+
+    /// A voltage measurement in V.
+    pub fn voltage_v(unique_id: impl Into<String>, state_topic: impl Into<Topic>) -> Self {
+        Self::preset(
+            unique_id,
+            state_topic,
+            SensorDeviceClass::Voltage,
+            SensorStateClass::Measurement,
+            Unit::Volt(VoltUnit::Volt),
+            1,
+        )
+    }
+
+    /// A current measurement in A.
+    pub fn current_a(unique_id: impl Into<String>, state_topic: impl Into<Topic>) -> Self {
+        Self::preset(
+            unique_id,
+            state_topic,
+            SensorDeviceClass::Current,
+            SensorStateClass::Measurement,
+            Unit::Electrical(ElectricalUnit::CurrentAmpere),
+            2,
+        )
+    }
+
+    /// A temperature measurement in °C.
+    pub fn temperature_celsius(
+        unique_id: impl Into<String>,
+        state_topic: impl Into<Topic>,
+    ) -> Self {
+        Self::preset(
+            unique_id,
+            state_topic,
+            SensorDeviceClass::Temperature,
+            SensorStateClass::Measurement,
+            Unit::Temperature(TempUnit::Celsius),
+            1,
+        )
+    }
+
+    /// A relative humidity measurement in %.
+    pub fn humidity_percent(unique_id: impl Into<String>, state_topic: impl Into<Topic>) -> Self {
+        Self::preset(
+            unique_id,
+            state_topic,
+            SensorDeviceClass::Humidity,
+            SensorStateClass::Measurement,
+            Unit::Percentage(PercentageUnit::Percentage),
+            0,
+        )
+    }
+
+    /// An atmospheric pressure measurement in hPa.
+    pub fn pressure_hpa(unique_id: impl Into<String>, state_topic: impl Into<Topic>) -> Self {
+        Self::preset(
+            unique_id,
+            state_topic,
+            SensorDeviceClass::AtmosphericPressure,
+            SensorStateClass::Measurement,
+            Unit::Pressure(PressureUnit::HPa),
+            1,
+        )
+    }
+
+    /// A battery level in %.
+    pub fn battery_percent(unique_id: impl Into<String>, state_topic: impl Into<Topic>) -> Self {
+        Self::preset(
+            unique_id,
+            state_topic,
+            SensorDeviceClass::Battery,
+            SensorStateClass::Measurement,
+            Unit::Percentage(PercentageUnit::Percentage),
+            0,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Entity;
+
+    use super::*;
+
+    #[test]
+    fn can_build_sensor_presets() {
+        let sensor = Sensor::energy_kwh("meter_energy", "home/meter/energy");
+
+        assert_eq!(sensor.unique_id.as_deref(), Some("meter_energy"));
+        let attributes = serde_json::to_value(&sensor).unwrap();
+        assert_eq!(attributes["dev_cla"], "energy");
+        assert_eq!(attributes["stat_cla"], "total_increasing");
+        assert_eq!(attributes["unit_of_meas"], "kWh");
+        assert_eq!(attributes["sug_dsp_prc"], 2);
+        assert!(
+            Entity::from(Sensor::temperature_celsius("temp", "home/temp"))
+                .validate()
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn can_expire_sensor_states() {
+        let sensor = Sensor::battery_percent("battery", "home/battery")
+            .expire_after(Duration::from_millis(3_600_500));
+
+        assert_eq!(sensor.availability.expire_after, Some(3601));
+        assert_eq!(serde_json::to_value(&sensor).unwrap()["exp_aft"], 3601);
+        assert!(Entity::from(sensor.expire_after(Duration::ZERO))
+            .validate()
+            .is_err());
+    }
+}
@@ -0,0 +1,148 @@
+use anyhow::{anyhow, Result};
+use rust_decimal::Decimal;
+use serde_json::{json, Map, Value};
+
+use crate::{
+    decimal::{DecimalError, IntoDecimal, TryIntoDecimal},
+    mqtt::siren::Siren,
+};
+
+/// The turn on/off parameters of a siren, sent as the JSON command payload or reported as the JSON state.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SirenCommand {
+    pub on: bool,
+    /// One of the `available_tones`.
+    pub tone: Option<String>,
+    /// The volume, from 0 to 1, requires `support_volume_set`.
+    pub volume_level: Option<Decimal>,
+    /// The duration in seconds, requires `support_duration`.
+    pub duration: Option<u32>,
+}
+
+impl SirenCommand {
+    pub fn on() -> Self {
+        Self {
+            on: true,
+            tone: None,
+            volume_level: None,
+            duration: None,
+        }
+    }
+
+    pub fn off() -> Self {
+        Self {
+            on: false,
+            ..Self::on()
+        }
+    }
+
+    /// One of the `available_tones`.
+    pub fn tone<S: Into<String>>(mut self, tone: S) -> Self {
+        self.tone = Some(tone.into());
+        self
+    }
+
+    /// The volume, from 0 to 1, requires `support_volume_set`.
+    pub fn volume_level<D: IntoDecimal>(mut self, volume_level: D) -> Self {
+        self.volume_level = Some(volume_level.into_decimal());
+        self
+    }
+
+    /// Same as [`Self::volume_level`] for the floats, fails when the value is NaN, infinite or out of the `Decimal` range.
+    pub fn try_volume_level<D: TryIntoDecimal>(
+        self,
+        volume_level: D,
+    ) -> Result<Self, DecimalError> {
+        Ok(self.volume_level(volume_level.try_into_decimal()?))
+    }
+
+    /// The duration in seconds, requires `support_duration`.
+    pub fn duration(mut self, duration: u32) -> Self {
+        self.duration = Some(duration);
+        self
+    }
+}
+
+impl Siren {
+    /// The JSON payload of the parameters, `{"state": <payload_on|payload_off>, "tone": ..., "volume_level": ...,
+    /// "duration": ...}`, as sent by Home Assistant when no `command_template` is configured.
+    ///
+    /// Fails when a parameter isn't supported by the siren: a tone outside of the `available_tones`, a volume when
+    /// `support_volume_set` is `false` or a duration when `support_duration` is `false`.
+    pub fn command_payload(&self, command: &SirenCommand) -> Result<String> {
+        let state = match command.on {
+            true => self.payload_on.as_deref().unwrap_or("ON"),
+            false => self.payload_off.as_deref().unwrap_or("OFF"),
+        };
+        let mut payload = Map::new();
+        payload.insert("state".to_string(), json!(state));
+        if let Some(tone) = &command.tone {
+            let available_tones = self.available_tones.as_deref().unwrap_or_default();
+            if !available_tones.contains(tone) {
+                return Err(anyhow!(
+                    "tone '{tone}' is not one of the siren available tones {available_tones:?}"
+                ));
+            }
+            payload.insert("tone".to_string(), json!(tone));
+        }
+        if let Some(volume_level) = command.volume_level {
+            if self.support_volume_set == Some(false) {
+                return Err(anyhow!("siren doesn't support setting the volume"));
+            }
+            if !(Decimal::ZERO..=Decimal::ONE).contains(&volume_level) {
+                return Err(anyhow!("volume level {volume_level} is outside of 0..=1"));
+            }
+            payload.insert("volume_level".to_string(), json!(volume_level));
+        }
+        if let Some(duration) = command.duration {
+            if self.support_duration == Some(false) {
+                return Err(anyhow!("siren doesn't support durations"));
+            }
+            payload.insert("duration".to_string(), json!(duration));
+        }
+        Ok(Value::Object(payload).to_string())
+    }
+}
+
+#[cfg(all(test, feature = "client"))]
+mod tests {
+    use crate::testing::MockClient;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn can_publish_siren_commands() {
+        let client = MockClient::default();
+        let ha = client.ha();
+        let siren = Siren::default()
+            .command_topic("home/siren/set")
+            .state_topic("home/siren")
+            .available_tones(vec!["ping", "siren"])
+            .support_duration(false);
+
+        ha.publish_siren_command(
+            &siren,
+            &SirenCommand::on()
+                .tone("ping")
+                .volume_level(Decimal::new(5, 1)),
+        )
+        .await
+        .unwrap();
+        ha.publish_siren_state(&siren, &SirenCommand::off())
+            .await
+            .unwrap();
+
+        assert!(!client.last_published("home/siren/set").unwrap().retain);
+        client.assert_published_json(
+            "home/siren/set",
+            json!({"state": "ON", "tone": "ping", "volume_level": 0.5}),
+        );
+        client.assert_published("home/siren", r#"{"state":"OFF"}"#);
+        assert!(siren
+            .command_payload(&SirenCommand::on().tone("beep"))
+            .is_err());
+        assert!(siren
+            .command_payload(&SirenCommand::on().duration(10))
+            .is_err());
+    }
+}
@@ -0,0 +1,209 @@
+use std::collections::HashSet;
+
+use crate::{mqtt::common::Device, Entity};
+
+/// The slug of the texts without any supported character.
+const UNKNOWN: &str = "unknown";
+
+/// Converts names into identifiers made of `[a-z0-9_]`, like Home Assistant does to build the entity IDs.
+///
+/// Accented latin letters are transliterated, the other characters are replaced by a single separator and an empty
+/// slug becomes `unknown`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SlugOptions {
+    lowercase: bool,
+    max_length: Option<usize>,
+    separator: char,
+}
+
+impl Default for SlugOptions {
+    fn default() -> Self {
+        Self {
+            lowercase: true,
+            max_length: None,
+            separator: '_',
+        }
+    }
+}
+
+impl SlugOptions {
+    /// Whether the letters are lowercased, `true` by default.
+    pub fn lowercase(mut self, lowercase: bool) -> Self {
+        self.lowercase = lowercase;
+        self
+    }
+
+    /// The maximum number of characters of the slugs, including the collision suffix. It's at least 7, the length of
+    /// the `unknown` slug.
+    pub fn max_length(mut self, max_length: usize) -> Self {
+        self.max_length = Some(max_length.max(UNKNOWN.len()));
+        self
+    }
+
+    /// The ASCII character replacing the unsupported characters, `_` by default.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the separator isn't an ASCII character, the slugs are truncated by bytes.
+    pub fn separator(mut self, separator: char) -> Self {
+        assert!(
+            separator.is_ascii(),
+            "the slug separator '{separator}' isn't an ASCII character"
+        );
+        self.separator = separator;
+        self
+    }
+
+    /// The slug of the text.
+    pub fn slugify(&self, text: &str) -> String {
+        let mut slug = String::with_capacity(text.len());
+        let mut pending_separator = false;
+        for c in text.chars() {
+            let mut buffer = [0; 4];
+            let letters = if c.is_ascii_alphanumeric() {
+                c.encode_utf8(&mut buffer)
+            } else {
+                transliterate(c)
+            };
+            if letters.is_empty() {
+                pending_separator = !slug.is_empty();
+                continue;
+            }
+            if pending_separator {
+                slug.push(self.separator);
+                pending_separator = false;
+            }
+            if self.lowercase {
+                slug.push_str(&letters.to_ascii_lowercase());
+            } else {
+                slug.push_str(letters);
+            }
+        }
+        if slug.is_empty() {
+            slug.push_str(UNKNOWN);
+        }
+        self.truncate(slug, 0)
+    }
+
+    /// The slug of the text, suffixed with `_2`, `_3`... when it is already taken, the returned slug is then taken.
+    pub fn slugify_unique(&self, text: &str, taken: &mut HashSet<String>) -> String {
+        let slug = self.slugify(text);
+        let mut candidate = slug.clone();
+        let mut index = 1;
+        while taken.contains(&candidate) {
+            index += 1;
+            let suffix = format!("{}{index}", self.separator);
+            candidate = self.truncate(slug.clone(), suffix.len()) + &suffix;
+        }
+        taken.insert(candidate.clone());
+        candidate
+    }
+
+    fn truncate(&self, mut slug: String, reserved: usize) -> String {
+        if let Some(max_length) = self.max_length {
+            // slugs only contain ASCII characters
+            slug.truncate(max_length.saturating_sub(reserved));
+            while slug.ends_with(self.separator) {
+                slug.pop();
+            }
+        }
+        slug
+    }
+}
+
+/// The slug of the text with the default options.
+pub fn slugify(text: &str) -> String {
+    SlugOptions::default().slugify(text)
+}
+
+/// The ASCII letters of an accented latin letter, empty for the characters to replace with a separator.
+fn transliterate(c: char) -> &'static str {
+    match c {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' => "a",
+        'À' | 'Á' | 'Â' | 'Ã' | 'Ä' | 'Å' => "A",
+        'æ' => "ae",
+        'Æ' => "AE",
+        'ç' => "c",
+        'Ç' => "C",
+        'è' | 'é' | 'ê' | 'ë' => "e",
+        'È' | 'É' | 'Ê' | 'Ë' => "E",
+        'ì' | 'í' | 'î' | 'ï' => "i",
+        'Ì' | 'Í' | 'Î' | 'Ï' => "I",
+        'ñ' => "n",
+        'Ñ' => "N",
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' => "o",
+        'Ò' | 'Ó' | 'Ô' | 'Õ' | 'Ö' | 'Ø' => "O",
+        'œ' => "oe",
+        'Œ' => "OE",
+        'ù' | 'ú' | 'û' | 'ü' => "u",
+        'Ù' | 'Ú' | 'Û' | 'Ü' => "U",
+        'ý' | 'ÿ' => "y",
+        'Ý' => "Y",
+        'ß' => "ss",
+        _ => "",
+    }
+}
+
+impl Entity {
+    /// A `unique_id` built from the first identifier or connection of the device, or its name, and the entity name.
+    pub fn derive_unique_id(device: &Device, name: &str) -> String {
+        let device_id = device
+            .identifiers
+            .first()
+            .or(device.connections.first().map(|c| &c.identifier))
+            .or(device.name.as_ref());
+        match device_id {
+            Some(device_id) => slugify(&format!("{device_id} {name}")),
+            None => slugify(name),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_slugify_names() {
+        assert_eq!(slugify("Température du Salon"), "temperature_du_salon");
+        assert_eq!(slugify("  CPU -- load (%) "), "cpu_load");
+        assert_eq!(slugify("!!!"), "unknown");
+        assert_eq!(
+            SlugOptions::default()
+                .lowercase(false)
+                .separator('-')
+                .slugify("Living Room"),
+            "Living-Room"
+        );
+
+        let options = SlugOptions::default().max_length(8);
+        let mut taken = HashSet::new();
+        assert_eq!(
+            options.slugify_unique("Temperature", &mut taken),
+            "temperat"
+        );
+        assert_eq!(
+            options.slugify_unique("Temperature", &mut taken),
+            "temper_2"
+        );
+        assert_eq!(
+            SlugOptions::default().max_length(0).slugify("!!!"),
+            "unknown"
+        );
+        assert_eq!(
+            SlugOptions::default().max_length(0).slugify("Temperature"),
+            "tempera"
+        );
+
+        assert_eq!(
+            Entity::derive_unique_id(&Device::default().add_identifier("0xABCD"), "Battery"),
+            "0xabcd_battery"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "isn't an ASCII character")]
+    fn can_reject_non_ascii_separators() {
+        SlugOptions::default().separator('·');
+    }
+}
@@ -0,0 +1,254 @@
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+use rumqttc::v5::MqttOptions;
+
+#[cfg(feature = "entity-binary_sensor")]
+use crate::mqtt::binary_sensor::BinarySensor;
+#[cfg(feature = "entity-button")]
+use crate::mqtt::button::Button;
+#[cfg(feature = "entity-sensor")]
+use crate::mqtt::sensor::Sensor;
+#[cfg(feature = "entity-switch")]
+use crate::mqtt::switch::Switch;
+use crate::{
+    bridge::{Bridge, BridgeBuilder},
+    command::Command,
+    mqtt::common::Device,
+    Entity, HomeAssistantMqtt,
+};
+
+type CommandHandler = Box<dyn Fn(Command<'_>) + Send + Sync>;
+
+/// A declarative description of a device and its entities, from which a `Bridge` is built.
+///
+/// Every entity is declared under a suffix: its topics are relative to `<base_topic>/<suffix>` through the `~`
+/// prefix, its `unique_id` defaults to the slug of the device identifier and the suffix, and it belongs to the
+/// device of the spec.
+///
+/// ```no_run
+/// # #[cfg(all(feature = "entity-sensor", feature = "entity-switch"))]
+/// # async fn example() -> anyhow::Result<()> {
+/// # use ha_mqtt_discovery::{mqtt::common::Device, spec::BridgeSpec, v5::MqttOptions};
+/// let (mut bridge, states) = BridgeSpec::new("garage", Device::default().name("Garage").add_identifier("garage"))
+///     .sensor("temperature", "Temperature")
+///     .switch("door", "Door", |command| println!("{:?}", command.payload_str()))
+///     .build(MqttOptions::new("garage", "localhost", 1883))?;
+/// let temperature = states.get("temperature").unwrap();
+/// tokio::spawn(async move { temperature.publish("21.5").await });
+/// bridge.run().await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct BridgeSpec {
+    base_topic: String,
+    device: Device,
+    entities: Vec<(String, Entity, Option<CommandHandler>)>,
+}
+
+impl BridgeSpec {
+    pub fn new<S: Into<String>>(base_topic: S, device: Device) -> Self {
+        Self {
+            base_topic: base_topic.into(),
+            device,
+            entities: vec![],
+        }
+    }
+
+    /// Declares an entity, its topics should start with `~/`.
+    pub fn entity<S: Into<String>, E: Into<Entity>>(mut self, suffix: S, entity: E) -> Self {
+        let (suffix, entity) = self.attach(suffix.into(), entity.into());
+        self.entities.push((suffix, entity, None));
+        self
+    }
+
+    /// Declares an entity and handles the commands sent to its command topic.
+    pub fn on_command<S, E, F>(mut self, suffix: S, entity: E, handler: F) -> Self
+    where
+        S: Into<String>,
+        E: Into<Entity>,
+        F: Fn(Command<'_>) + Send + Sync + 'static,
+    {
+        let (suffix, entity) = self.attach(suffix.into(), entity.into());
+        self.entities
+            .push((suffix, entity, Some(Box::new(handler))));
+        self
+    }
+
+    /// Declares a sensor publishing its state on `~/state`.
+    #[cfg(feature = "entity-sensor")]
+    pub fn sensor<S: Into<String>, N: Into<String>>(self, suffix: S, name: N) -> Self {
+        self.entity(suffix, Sensor::new("~/state").name(name))
+    }
+
+    /// Declares a binary sensor publishing `ON` or `OFF` on `~/state`.
+    #[cfg(feature = "entity-binary_sensor")]
+    pub fn binary_sensor<S: Into<String>, N: Into<String>>(self, suffix: S, name: N) -> Self {
+        self.entity(suffix, BinarySensor::new("~/state").name(name))
+    }
+
+    /// Declares a switch receiving `ON` or `OFF` on `~/set` and publishing its state on `~/state`.
+    #[cfg(feature = "entity-switch")]
+    pub fn switch<S, N, F>(self, suffix: S, name: N, handler: F) -> Self
+    where
+        S: Into<String>,
+        N: Into<String>,
+        F: Fn(Command<'_>) + Send + Sync + 'static,
+    {
+        let switch = Switch::new("~/set").state_topic("~/state").name(name);
+        self.on_command(suffix, switch, handler)
+    }
+
+    /// Declares a button receiving `PRESS` on `~/press`.
+    #[cfg(feature = "entity-button")]
+    pub fn button<S, N, F>(self, suffix: S, name: N, handler: F) -> Self
+    where
+        S: Into<String>,
+        N: Into<String>,
+        F: Fn(Command<'_>) + Send + Sync + 'static,
+    {
+        self.on_command(suffix, Button::new("~/press").name(name), handler)
+    }
+
+    /// The declared entities by suffix.
+    pub fn entities(&self) -> impl Iterator<Item = (&str, &Entity)> {
+        self.entities
+            .iter()
+            .map(|(suffix, entity, _)| (suffix.as_str(), entity))
+    }
+
+    /// The configuration of a bridge announcing the entities and routing their commands, to customize before
+    /// building it.
+    pub fn builder(self, mqtt_options: MqttOptions) -> BridgeBuilder {
+        let mut builder = Bridge::builder(mqtt_options);
+        for (_, entity, handler) in self.entities {
+            builder = match handler {
+                Some(handler) => builder.on_command(entity, handler),
+                None => builder.entity(entity),
+            };
+        }
+        builder
+    }
+
+    /// Builds the bridge and the handles publishing the states of the entities.
+    pub fn build(self, mqtt_options: MqttOptions) -> Result<(Bridge, StateHandles)> {
+        let entities: Vec<_> = self
+            .entities()
+            .map(|(suffix, entity)| (suffix.to_string(), entity.clone()))
+            .collect();
+        let bridge = self.builder(mqtt_options).build()?;
+        let publisher = bridge.publisher();
+        let handles = entities
+            .into_iter()
+            .map(|(suffix, entity)| {
+                let handle = StateHandle {
+                    publisher: publisher.clone(),
+                    entity,
+                };
+                (suffix, handle)
+            })
+            .collect();
+        Ok((bridge, StateHandles { handles }))
+    }
+
+    fn attach(&self, suffix: String, mut entity: Entity) -> (String, Entity) {
+        entity.set_topic_prefix(format!("{}/{suffix}", self.base_topic));
+        if let Some(unique_id) = entity.unique_id_mut().filter(|id| id.is_none()) {
+            *unique_id = Some(Entity::derive_unique_id(&self.device, &suffix));
+        }
+        entity.set_device(self.device.clone());
+        (suffix, entity)
+    }
+}
+
+/// The state handles of the entities declared by a `BridgeSpec`, by suffix.
+pub struct StateHandles {
+    handles: BTreeMap<String, StateHandle>,
+}
+
+impl StateHandles {
+    /// The handle of the entity declared under the suffix.
+    pub fn get(&self, suffix: &str) -> Option<StateHandle> {
+        self.handles.get(suffix).cloned()
+    }
+}
+
+/// Publishes the states of an entity, it can be moved to another task.
+#[derive(Clone)]
+pub struct StateHandle {
+    publisher: HomeAssistantMqtt,
+    entity: Entity,
+}
+
+impl StateHandle {
+    /// The entity as announced.
+    pub fn entity(&self) -> &Entity {
+        &self.entity
+    }
+
+    /// Publishes a state on the state topic of the entity, see `HomeAssistantMqtt::publish_entity_state`.
+    pub async fn publish<P: Into<Vec<u8>>>(&self, payload: P) -> Result<bool> {
+        self.publisher
+            .publish_entity_state(&self.entity, payload)
+            .await
+    }
+}
+
+#[cfg(all(
+    test,
+    feature = "entity-binary_sensor",
+    feature = "entity-button",
+    feature = "entity-sensor",
+    feature = "entity-switch"
+))]
+mod tests {
+    use crate::mqtt::common::AvailabilityCheck;
+
+    use super::*;
+
+    #[test]
+    fn can_build_bridges_from_specs() {
+        let spec = BridgeSpec::new(
+            "garage",
+            Device::default().name("Garage").add_identifier("garage"),
+        )
+        .sensor("temperature", "Temperature")
+        .binary_sensor("motion", "Motion")
+        .switch("door", "Door", |_| {})
+        .button("restart", "Restart", |_| {});
+
+        let (_, door) = spec
+            .entities()
+            .find(|(suffix, _)| *suffix == "door")
+            .unwrap();
+        assert_eq!(door.unique_id(), Some("garage_door"));
+        assert_eq!(
+            door.resolved_command_topic().unwrap().as_str(),
+            "garage/door/set"
+        );
+        assert_eq!(
+            door.resolved_state_topic().unwrap().as_str(),
+            "garage/door/state"
+        );
+        assert_eq!(door.device().identifiers, vec!["garage"]);
+
+        let (bridge, states) = spec
+            .build(MqttOptions::new("garage", "localhost", 1883))
+            .unwrap();
+        assert_eq!(bridge.entities().len(), 4);
+        assert_eq!(
+            bridge.entities()[0].availability().availability,
+            vec![AvailabilityCheck::topic("garage/availability")]
+        );
+        let temperature = states.get("temperature").unwrap();
+        assert_eq!(
+            temperature
+                .entity()
+                .resolved_state_topic()
+                .unwrap()
+                .as_str(),
+            "garage/temperature/state"
+        );
+        assert!(states.get("humidity").is_none());
+    }
+}
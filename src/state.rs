@@ -0,0 +1,63 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use crate::registry::fnv1a;
+
+/// Counters of the retained state publications.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StateMetrics {
+    /// States sent to the broker.
+    pub published: u64,
+    /// States skipped because they were identical to the last state retained on the same topic.
+    pub skipped: u64,
+}
+
+/// Remembers the last state published on each topic, clones share the same cache.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct StateCache {
+    last_payloads: Arc<Mutex<HashMap<String, u64>>>,
+    published: Arc<AtomicU64>,
+    skipped: Arc<AtomicU64>,
+}
+
+impl StateCache {
+    /// Whether the payload differs from the last one published on the topic.
+    pub(crate) fn has_changed(&self, topic: &str, payload: &[u8]) -> bool {
+        let last_payloads = self.last_payloads.lock().unwrap();
+        last_payloads.get(topic) != Some(&fnv1a(payload))
+    }
+
+    pub(crate) fn record_published(&self, topic: &str, payload: &[u8]) {
+        self.last_payloads
+            .lock()
+            .unwrap()
+            .insert(topic.to_string(), fnv1a(payload));
+        self.published.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_skipped(&self) {
+        self.skipped.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Forgets the last payload of the topic, the next payload is published whatever it is.
+    pub(crate) fn forget(&self, topic: &str) {
+        self.last_payloads.lock().unwrap().remove(topic);
+    }
+
+    /// Forgets the last payloads, the next payloads are published whatever they are.
+    pub(crate) fn clear(&self) {
+        self.last_payloads.lock().unwrap().clear();
+    }
+
+    pub(crate) fn metrics(&self) -> StateMetrics {
+        StateMetrics {
+            published: self.published.load(Ordering::Relaxed),
+            skipped: self.skipped.load(Ordering::Relaxed),
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use anyhow::Result;
+use rumqttc::v5::{
+    mqttbytes::{
+        v5::{Filter, Packet, Publish},
+        QoS,
+    },
+    AsyncClient, Event,
+};
+
+/// The status of Home Assistant, published on `<discovery_prefix>/status` when it starts and stops.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HomeAssistantStatus {
+    /// Home Assistant (re)started, the discovery configurations should be published again.
+    Online,
+    Offline,
+}
+
+/// Tracks the birth and last will messages of Home Assistant.
+#[derive(Clone, Debug)]
+pub struct StatusWatcher {
+    topic: String,
+}
+
+impl StatusWatcher {
+    pub fn new<S: Into<String>>(discovery_prefix: S) -> Self {
+        let discovery_prefix: String = discovery_prefix.into();
+        Self {
+            topic: format!("{}/status", discovery_prefix.trim_end_matches('/')),
+        }
+    }
+
+    /// The status topic, `<discovery_prefix>/status`.
+    pub fn topic(&self) -> &str {
+        &self.topic
+    }
+
+    /// The topic filter of the status topic.
+    pub fn filter(&self) -> Filter {
+        Filter::new(self.topic.clone(), QoS::AtLeastOnce)
+    }
+
+    /// Converts a received message into a status.
+    ///
+    /// Returns `None` for the messages which aren't published on the status topic or carry an unknown payload.
+    pub fn handle_publish(&self, publish: &Publish) -> Option<HomeAssistantStatus> {
+        if publish.topic.as_ref() != self.topic.as_bytes() {
+            return None;
+        }
+        match publish.payload.as_ref() {
+            b"online" => Some(HomeAssistantStatus::Online),
+            b"offline" => Some(HomeAssistantStatus::Offline),
+            _ => None,
+        }
+    }
+
+    /// Handles an event from the MQTT event loop: the status topic is subscribed on (re)connection.
+    pub fn handle_event(
+        &self,
+        client: &AsyncClient,
+        event: &Event,
+    ) -> Result<Option<HomeAssistantStatus>> {
+        match event {
+            Event::Incoming(Packet::Publish(publish)) => Ok(self.handle_publish(publish)),
+            Event::Incoming(Packet::ConnAck(_)) => {
+                client.try_subscribe_many([self.filter()])?;
+                Ok(None)
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn publish(topic: &str, payload: &str) -> Publish {
+        Publish::new(topic, QoS::AtLeastOnce, payload.to_string(), None)
+    }
+
+    #[test]
+    fn can_parse_home_assistant_status() {
+        let watcher = StatusWatcher::new("homeassistant/");
+
+        assert_eq!(watcher.topic(), "homeassistant/status");
+        assert_eq!(
+            watcher.handle_publish(&publish("homeassistant/status", "online")),
+            Some(HomeAssistantStatus::Online)
+        );
+        assert_eq!(
+            watcher.handle_publish(&publish("homeassistant/status", "offline")),
+            Some(HomeAssistantStatus::Offline)
+        );
+        assert_eq!(
+            watcher.handle_publish(&publish("homeassistant/status", "starting")),
+            None
+        );
+        assert_eq!(
+            watcher.handle_publish(&publish("homeassistant/sensor/status", "online")),
+            None
+        );
+    }
+}
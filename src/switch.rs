@@ -0,0 +1,75 @@
+use crate::mqtt::switch::Switch;
+
+impl Switch {
+    /// The payload of the state, `state_on` or `state_off` which default to the `payload_*` payloads of the switch.
+    pub fn state_payload(&self, on: bool) -> &str {
+        let (state, payload, default) = if on {
+            (&self.state_on, &self.payload_on, "ON")
+        } else {
+            (&self.state_off, &self.payload_off, "OFF")
+        };
+        state.as_deref().or(payload.as_deref()).unwrap_or(default)
+    }
+
+    /// Whether a payload received on the `command_topic` turns the switch on, according to its `payload_on` and
+    /// `payload_off` payloads. `None` for the other payloads.
+    pub fn command(&self, payload: &str) -> Option<bool> {
+        if payload == self.payload_on.as_deref().unwrap_or("ON") {
+            Some(true)
+        } else if payload == self.payload_off.as_deref().unwrap_or("OFF") {
+            Some(false)
+        } else {
+            None
+        }
+    }
+}
+
+/// Whether a command payload turns the switch on, see `Switch::command`.
+pub fn parse_switch_command(switch: &Switch, payload: &str) -> Option<bool> {
+    switch.command(payload)
+}
+
+#[cfg(all(test, feature = "client"))]
+mod tests {
+    use crate::{testing::MockClient, HomeAssistantMqtt};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn can_drive_switches_with_booleans() {
+        let client = MockClient::default();
+        let ha = HomeAssistantMqtt::new(client.clone(), "homeassistant");
+        let switch = Switch::new("~/set")
+            .topic_prefix("home/plug")
+            .state_topic("~/state")
+            .payload_on("1")
+            .payload_off("0")
+            .state_off("off");
+
+        ha.publish_switch_state(&switch, true).await.unwrap();
+        assert_eq!(
+            client
+                .last_published("home/plug/state")
+                .unwrap()
+                .payload_str(),
+            "1"
+        );
+        ha.publish_switch_state(&switch, false).await.unwrap();
+        assert_eq!(
+            client
+                .last_published("home/plug/state")
+                .unwrap()
+                .payload_str(),
+            "off"
+        );
+        assert!(ha
+            .publish_switch_state(&Switch::new("home/plug/set"), true)
+            .await
+            .is_err());
+
+        assert_eq!(parse_switch_command(&switch, "1"), Some(true));
+        assert_eq!(parse_switch_command(&switch, "0"), Some(false));
+        assert_eq!(parse_switch_command(&switch, "ON"), None);
+        assert_eq!(Switch::new("home/plug/set").command("OFF"), Some(false));
+    }
+}
@@ -0,0 +1,224 @@
+#[cfg(feature = "entity-water_heater")]
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+
+#[cfg(feature = "entity-climate")]
+use crate::mqtt::climate::Climate;
+#[cfg(feature = "entity-water_heater")]
+use crate::mqtt::water_heater::WaterHeater;
+use crate::{mqtt::common::TemperatureUnit, validation::ValidationError};
+
+/// The temperatures Home Assistant assumes for a platform in one unit, and the plausible temperatures of the platform.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TemperatureRange {
+    /// The default `min_temp`.
+    pub min_temp: Decimal,
+    /// The default `max_temp`.
+    pub max_temp: Decimal,
+    /// The default `initial` target temperature.
+    pub initial: Decimal,
+    /// The lowest plausible temperature.
+    pub lowest: Decimal,
+    /// The highest plausible temperature.
+    pub highest: Decimal,
+}
+
+impl TemperatureRange {
+    /// The climate temperatures: 7°C to 35°C starting at 21°C, or 44.6°F to 95°F starting at 69.8°F.
+    pub fn climate(unit: TemperatureUnit) -> Self {
+        match unit {
+            TemperatureUnit::Celcius => TemperatureRange {
+                min_temp: Decimal::from(7),
+                max_temp: Decimal::from(35),
+                initial: Decimal::from(21),
+                lowest: Decimal::from(-50),
+                highest: Decimal::from(60),
+            },
+            TemperatureUnit::Fahrenheit => TemperatureRange {
+                min_temp: Decimal::new(446, 1),
+                max_temp: Decimal::from(95),
+                initial: Decimal::new(698, 1),
+                lowest: Decimal::from(-58),
+                highest: Decimal::from(140),
+            },
+        }
+    }
+
+    /// The water heater temperatures: 43.3°C to 60°C starting at 43.3°C, or 110°F to 140°F starting at 110°F.
+    pub fn water_heater(unit: TemperatureUnit) -> Self {
+        match unit {
+            TemperatureUnit::Celcius => TemperatureRange {
+                min_temp: Decimal::new(433, 1),
+                max_temp: Decimal::from(60),
+                initial: Decimal::new(433, 1),
+                lowest: Decimal::ZERO,
+                highest: Decimal::from(100),
+            },
+            TemperatureUnit::Fahrenheit => TemperatureRange {
+                min_temp: Decimal::from(110),
+                max_temp: Decimal::from(140),
+                initial: Decimal::from(110),
+                lowest: Decimal::from(32),
+                highest: Decimal::from(212),
+            },
+        }
+    }
+
+    /// Checks the configured temperatures are plausible, ordered, and that the initial temperature is between the
+    /// minimum and maximum ones.
+    ///
+    /// Without a unit, the temperatures are only compared with each other since the system unit isn't known.
+    pub(crate) fn validate(
+        range: fn(TemperatureUnit) -> Self,
+        unit: Option<TemperatureUnit>,
+        min_temp: Option<Decimal>,
+        max_temp: Option<Decimal>,
+        initial: Option<Decimal>,
+        errors: &mut Vec<ValidationError>,
+    ) {
+        let (min, max) = match unit {
+            Some(unit) => {
+                let range = range(unit);
+                for (attribute, value) in [
+                    ("min_temp", min_temp),
+                    ("max_temp", max_temp),
+                    ("initial", initial),
+                ] {
+                    if let Some(value) =
+                        value.filter(|v| !(range.lowest..=range.highest).contains(v))
+                    {
+                        errors.push(ValidationError::TemperatureOutOfRange {
+                            attribute,
+                            value,
+                            lowest: range.lowest,
+                            highest: range.highest,
+                        });
+                    }
+                }
+                (
+                    min_temp.unwrap_or(range.min_temp),
+                    max_temp.unwrap_or(range.max_temp),
+                )
+            }
+            None => match (min_temp, max_temp) {
+                (Some(min), Some(max)) => (min, max),
+                _ => return,
+            },
+        };
+        if min >= max {
+            errors.push(ValidationError::InvalidRange { min, max });
+        } else if let Some(initial) = initial.filter(|i| !(min..=max).contains(i)) {
+            errors.push(ValidationError::TemperatureOutOfRange {
+                attribute: "initial",
+                value: initial,
+                lowest: min,
+                highest: max,
+            });
+        }
+    }
+}
+
+#[cfg(feature = "entity-climate")]
+impl Climate {
+    /// Sets the `min_temp`, `max_temp` and `initial` temperatures left unset to the Home Assistant defaults in the
+    /// `temperature_unit`, see `TemperatureRange::climate`.
+    ///
+    /// Nothing is set when the unit isn't, Home Assistant then uses the system unit.
+    pub fn temperature_defaults(mut self) -> Self {
+        if let Some(unit) = self.temperature_unit {
+            let defaults = TemperatureRange::climate(unit);
+            self.min_temp.get_or_insert(defaults.min_temp);
+            self.max_temp.get_or_insert(defaults.max_temp);
+            self.initial.get_or_insert(defaults.initial);
+        }
+        self
+    }
+}
+
+#[cfg(feature = "entity-water_heater")]
+impl WaterHeater {
+    /// Sets the `min_temp`, `max_temp` and `initial` temperatures left unset to the Home Assistant defaults in the
+    /// `temperature_unit`, see `TemperatureRange::water_heater`.
+    ///
+    /// Nothing is set when the unit isn't, Home Assistant then uses the system unit. The initial temperature is
+    /// rounded up to stay above the minimum one.
+    pub fn temperature_defaults(mut self) -> Self {
+        if let Some(unit) = self.temperature_unit {
+            let defaults = TemperatureRange::water_heater(unit);
+            self.min_temp.get_or_insert(defaults.min_temp);
+            self.max_temp.get_or_insert(defaults.max_temp);
+            self.initial
+                .get_or_insert_with(|| defaults.initial.ceil().to_i32().unwrap_or_default());
+        }
+        self
+    }
+}
+
+#[cfg(all(test, feature = "entity-climate", feature = "entity-water_heater"))]
+mod tests {
+    use crate::Entity;
+
+    use super::*;
+
+    #[test]
+    fn can_apply_temperature_unit_defaults() {
+        let climate = Climate::default()
+            .temperature_unit(TemperatureUnit::Fahrenheit)
+            .max_temp(Decimal::from(90))
+            .temperature_defaults();
+        assert_eq!(climate.min_temp, Some(Decimal::new(446, 1)));
+        assert_eq!(climate.max_temp, Some(Decimal::from(90)));
+        assert_eq!(climate.initial, Some(Decimal::new(698, 1)));
+        assert_eq!(Entity::from(climate).validate(), Ok(()));
+        assert_eq!(Climate::default().temperature_defaults().min_temp, None);
+
+        let water_heater = WaterHeater::default()
+            .temperature_unit(TemperatureUnit::Celcius)
+            .temperature_defaults();
+        assert_eq!(water_heater.initial, Some(44));
+        assert_eq!(Entity::from(water_heater).validate(), Ok(()));
+    }
+
+    #[test]
+    fn can_validate_temperature_ranges() {
+        // Fahrenheit limits with a Celsius unit
+        let climate = Climate::default()
+            .temperature_unit(TemperatureUnit::Celcius)
+            .min_temp(Decimal::new(446, 1))
+            .max_temp(Decimal::from(95))
+            .initial(Decimal::from(21));
+        assert_eq!(
+            Entity::from(climate).validate(),
+            Err(vec![
+                ValidationError::TemperatureOutOfRange {
+                    attribute: "max_temp",
+                    value: Decimal::from(95),
+                    lowest: Decimal::from(-50),
+                    highest: Decimal::from(60),
+                },
+                ValidationError::TemperatureOutOfRange {
+                    attribute: "initial",
+                    value: Decimal::from(21),
+                    lowest: Decimal::new(446, 1),
+                    highest: Decimal::from(95),
+                }
+            ])
+        );
+        assert_eq!(
+            Entity::from(
+                WaterHeater::default()
+                    .temperature_unit(TemperatureUnit::Fahrenheit)
+                    .min_temp(Decimal::from(150))
+            )
+            .validate(),
+            Err(vec![ValidationError::InvalidRange {
+                min: Decimal::from(150),
+                max: Decimal::from(140)
+            }])
+        );
+        assert_eq!(
+            Entity::from(WaterHeater::default().min_temp(Decimal::from(150))).validate(),
+            Ok(())
+        );
+    }
+}
@@ -0,0 +1,518 @@
+use std::{
+    collections::HashMap,
+    fmt::{self, Debug, Display, Formatter},
+    path::Path,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+use anyhow::{anyhow, Result};
+use rumqttc::v5::{
+    mqttbytes::{v5::Packet, QoS},
+    AsyncClient, Event, EventLoop, MqttOptions,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+
+use crate::{
+    client::{MqttClient, MqttPublisher, PublishOptions},
+    components::DeviceComponents,
+    discovery::{DiscoveryEvent, DiscoveryTopic, DiscoveryWatcher},
+    mqtt::abbreviations::{ABBREVIATIONS, DEVICE_ABBREVIATIONS},
+    topic::{resolve, Topic},
+    Entity, HomeAssistantMqtt,
+};
+
+/// A message published through a `MockClient`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PublishedMessage {
+    pub topic: String,
+    pub qos: QoS,
+    pub retain: bool,
+    pub payload: Vec<u8>,
+    pub options: PublishOptions,
+}
+
+impl PublishedMessage {
+    /// The payload as an UTF-8 string.
+    pub fn payload_str(&self) -> &str {
+        std::str::from_utf8(&self.payload).expect("payload should be an UTF-8 string")
+    }
+}
+
+/// An in-memory MQTT client recording the published messages and the subscriptions, to unit test an integration without a broker.
+///
+/// Clones share the same records.
+#[derive(Clone, Debug, Default)]
+pub struct MockClient {
+    published: Arc<Mutex<Vec<PublishedMessage>>>,
+    subscriptions: Arc<Mutex<Vec<(String, QoS)>>>,
+    failing: Arc<AtomicBool>,
+}
+
+/// The error of a `MockClient` set as failing.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MockClientError;
+
+impl Display for MockClientError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("the mock client is failing")
+    }
+}
+
+impl std::error::Error for MockClientError {}
+
+impl MockClient {
+    /// Every message published so far.
+    pub fn published(&self) -> Vec<PublishedMessage> {
+        self.published.lock().unwrap().clone()
+    }
+
+    /// The last message published on the topic.
+    pub fn last_published(&self, topic: &str) -> Option<PublishedMessage> {
+        self.published
+            .lock()
+            .unwrap()
+            .iter()
+            .rev()
+            .find(|message| message.topic == topic)
+            .cloned()
+    }
+
+    /// Every topic filter subscribed so far, with the requested QoS.
+    pub fn subscriptions(&self) -> Vec<(String, QoS)> {
+        self.subscriptions.lock().unwrap().clone()
+    }
+
+    /// Whether the publications and subscriptions fail, without being recorded, `false` by default.
+    pub fn set_failing(&self, failing: bool) {
+        self.failing.store(failing, Ordering::Relaxed);
+    }
+
+    /// Forgets the published messages and the subscriptions.
+    pub fn clear(&self) {
+        self.published.lock().unwrap().clear();
+        self.subscriptions.lock().unwrap().clear();
+    }
+
+    /// A `HomeAssistantMqtt` publishing through this client, with the `homeassistant` discovery prefix.
+    pub fn ha(&self) -> HomeAssistantMqtt<MockClient> {
+        HomeAssistantMqtt::new(self.clone(), "homeassistant")
+    }
+
+    /// Asserts the last message published on the topic has the payload.
+    #[track_caller]
+    pub fn assert_published(&self, topic: &str, payload: &str) {
+        let published = self
+            .last_published(topic)
+            .unwrap_or_else(|| panic!("nothing published on '{topic}'"));
+        assert_eq!(
+            published.payload_str(),
+            payload,
+            "payload published on '{topic}'"
+        );
+    }
+
+    /// Asserts the last message published on the topic is the JSON value, regardless of the formatting.
+    #[track_caller]
+    pub fn assert_published_json(&self, topic: &str, payload: Value) {
+        let published = self
+            .last_published(topic)
+            .unwrap_or_else(|| panic!("nothing published on '{topic}'"));
+        let published: Value = serde_json::from_slice(&published.payload)
+            .unwrap_or_else(|e| panic!("payload published on '{topic}' should be JSON: {e}"));
+        assert_eq!(published, payload, "payload published on '{topic}'");
+    }
+}
+
+impl MqttPublisher for MockClient {
+    type Error = MockClientError;
+
+    async fn publish(
+        &self,
+        topic: String,
+        qos: QoS,
+        retain: bool,
+        payload: Vec<u8>,
+        options: PublishOptions,
+    ) -> Result<(), Self::Error> {
+        if self.failing.load(Ordering::Relaxed) {
+            return Err(MockClientError);
+        }
+        self.published.lock().unwrap().push(PublishedMessage {
+            topic,
+            qos,
+            retain,
+            payload,
+            options,
+        });
+        Ok(())
+    }
+}
+
+impl MqttClient for MockClient {
+    async fn subscribe(&self, filter: String, qos: QoS) -> Result<(), Self::Error> {
+        if self.failing.load(Ordering::Relaxed) {
+            return Err(MockClientError);
+        }
+        self.subscriptions.lock().unwrap().push((filter, qos));
+        Ok(())
+    }
+}
+
+/// A Home Assistant test double connected to a (mock) broker, for closed-loop tests of bridges.
+///
+/// It records the discovered entities and the last message received on every other topic, and sends commands
+/// to the discovered entities:
+///
+/// ```no_run
+/// # use std::time::Duration;
+/// # use ha_mqtt_discovery::{testing::FakeHomeAssistant, v5::MqttOptions};
+/// # async fn example(options: MqttOptions) -> anyhow::Result<()> {
+/// let mut home_assistant = FakeHomeAssistant::new(options, "homeassistant");
+/// home_assistant.wait_for_entity("heater", Duration::from_secs(5)).await?;
+/// home_assistant.send_command("heater", "ON").await?;
+/// home_assistant.wait_for_state("home/heater/state", "ON", Duration::from_secs(5)).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct FakeHomeAssistant {
+    client: AsyncClient,
+    eventloop: EventLoop,
+    watcher: DiscoveryWatcher,
+    entities: HashMap<DiscoveryTopic, Value>,
+    states: HashMap<String, String>,
+}
+
+impl FakeHomeAssistant {
+    pub fn new<S: Into<String>>(options: MqttOptions, discovery_prefix: S) -> Self {
+        let (client, eventloop) = AsyncClient::new(options, 100);
+        Self {
+            client,
+            eventloop,
+            watcher: DiscoveryWatcher::new(discovery_prefix),
+            entities: HashMap::new(),
+            states: HashMap::new(),
+        }
+    }
+
+    /// The discovery configuration of every discovered entity.
+    pub fn entities(&self) -> &HashMap<DiscoveryTopic, Value> {
+        &self.entities
+    }
+
+    /// The discovery configuration of the entity with the given `unique_id`.
+    pub fn entity(&self, unique_id: &str) -> Option<&Value> {
+        self.entities
+            .values()
+            .find(|configuration| configuration["uniq_id"] == unique_id)
+    }
+
+    /// The last message received on a topic which isn't a discovery topic.
+    pub fn state(&self, topic: &str) -> Option<&str> {
+        self.states.get(topic).map(String::as_str)
+    }
+
+    /// Polls the next event of the MQTT connection and records the received messages.
+    pub async fn poll(&mut self) -> Result<()> {
+        let event = self.eventloop.poll().await?;
+        self.handle_event(&event)
+    }
+
+    /// Polls the MQTT connection until the entity with the given `unique_id` is discovered.
+    pub async fn wait_for_entity(&mut self, unique_id: &str, timeout: Duration) -> Result<Value> {
+        tokio::time::timeout(timeout, async {
+            loop {
+                if let Some(configuration) = self.entity(unique_id) {
+                    return Ok(configuration.clone());
+                }
+                self.poll().await?;
+            }
+        })
+        .await
+        .map_err(|_| anyhow!("entity '{unique_id}' wasn't discovered within {timeout:?}"))?
+    }
+
+    /// Polls the MQTT connection until the expected payload is received on the topic.
+    pub async fn wait_for_state(
+        &mut self,
+        topic: &str,
+        expected: &str,
+        timeout: Duration,
+    ) -> Result<()> {
+        tokio::time::timeout(timeout, async {
+            loop {
+                if self.state(topic) == Some(expected) {
+                    return Ok(());
+                }
+                self.poll().await?;
+            }
+        })
+        .await
+        .map_err(|_| {
+            anyhow!(
+                "expected '{expected}' on '{topic}' within {timeout:?}, last state is {:?}",
+                self.state(topic)
+            )
+        })?
+    }
+
+    /// Sends a command payload to the command topic of the entity with the given `unique_id`.
+    pub async fn send_command(&mut self, unique_id: &str, payload: &str) -> Result<()> {
+        let configuration = self
+            .entity(unique_id)
+            .ok_or(anyhow!("entity '{unique_id}' isn't discovered"))?;
+        let command_topic = configuration["cmd_t"]
+            .as_str()
+            .ok_or(anyhow!("entity '{unique_id}' has no command topic"))?;
+        let command_topic = resolve(&Topic::new(command_topic), configuration["~"].as_str());
+        self.client
+            .publish(
+                command_topic.as_str(),
+                QoS::AtLeastOnce,
+                false,
+                payload.to_string(),
+            )
+            .await?;
+        Ok(())
+    }
+
+    fn handle_event(&mut self, event: &Event) -> Result<()> {
+        match event {
+            Event::Incoming(Packet::ConnAck(_)) => {
+                self.client.try_subscribe("#", QoS::AtLeastOnce)?;
+            }
+            Event::Incoming(Packet::Publish(publish)) => match self.watcher.handle_publish(publish)
+            {
+                Some(DiscoveryEvent::Created {
+                    topic,
+                    configuration,
+                    ..
+                })
+                | Some(DiscoveryEvent::Updated {
+                    topic,
+                    configuration,
+                    ..
+                }) => {
+                    self.entities.insert(topic, configuration);
+                }
+                Some(DiscoveryEvent::Removed { topic }) => {
+                    self.entities.remove(&topic);
+                }
+                None => {
+                    let topic = String::from_utf8_lossy(&publish.topic).to_string();
+                    let payload = String::from_utf8_lossy(&publish.payload).to_string();
+                    self.states.insert(topic, payload);
+                }
+            },
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+/// A discovery configuration which can be locked in a snapshot.
+pub trait DiscoveryPayload {
+    /// The JSON payload, as published without the availability payloads and hooks of a `HomeAssistantMqtt`.
+    fn discovery_payload(&self) -> Result<Value>;
+}
+
+impl DiscoveryPayload for Entity {
+    fn discovery_payload(&self) -> Result<Value> {
+        Ok(self.get_attributes()?)
+    }
+}
+
+impl DiscoveryPayload for DeviceComponents {
+    fn discovery_payload(&self) -> Result<Value> {
+        Ok(self.payload(Entity::get_attributes)?)
+    }
+}
+
+/// The payload as pretty JSON with sorted keys, ending with a new line.
+pub fn canonical_json<P: DiscoveryPayload + ?Sized>(payload: &P) -> Result<String> {
+    Ok(serde_json::to_string_pretty(&payload.discovery_payload()?)? + "\n")
+}
+
+/// Compares the canonical JSON of the payload with a golden file.
+///
+/// The golden file is only written when the `UPDATE_SNAPSHOTS` environment variable is set, review and commit it to
+/// lock the payload.
+///
+/// # Panics
+///
+/// Panics with the canonical JSON when the golden file is missing or doesn't match.
+pub fn assert_snapshot<P: DiscoveryPayload + ?Sized, F: AsRef<Path>>(payload: &P, golden_file: F) {
+    let actual = canonical_json(payload).expect("payload should serialize");
+    check_snapshot(
+        &actual,
+        golden_file.as_ref(),
+        std::env::var_os("UPDATE_SNAPSHOTS").is_some(),
+    );
+}
+
+fn check_snapshot(actual: &str, golden_file: &Path, update: bool) {
+    if update {
+        if let Some(parent) = golden_file.parent() {
+            std::fs::create_dir_all(parent).expect("snapshot directory should be writable");
+        }
+        std::fs::write(golden_file, actual).expect("snapshot should be writable");
+        return;
+    }
+    let Ok(expected) = std::fs::read_to_string(golden_file) else {
+        panic!(
+            "missing snapshot {}, set UPDATE_SNAPSHOTS=1 to write it\n+++ actual\n{actual}",
+            golden_file.display()
+        );
+    };
+    assert!(
+        expected == actual,
+        "payload doesn't match the snapshot {}, set UPDATE_SNAPSHOTS=1 to update it\n--- expected\n{expected}\n+++ actual\n{actual}",
+        golden_file.display()
+    );
+}
+
+/// Lists the attributes of the payload, of its device and of its availability checks using their long name
+/// instead of their abbreviation.
+pub fn unabbreviated_attributes(payload: &Value) -> Vec<String> {
+    let mut attributes = vec![];
+    collect_unabbreviated(payload, ABBREVIATIONS, "", &mut attributes);
+    if let Some(device) = payload.get("dev") {
+        collect_unabbreviated(device, DEVICE_ABBREVIATIONS, "dev.", &mut attributes);
+    }
+    for check in payload
+        .get("avty")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        collect_unabbreviated(check, ABBREVIATIONS, "avty.", &mut attributes);
+    }
+    for (key, component) in payload
+        .get("cmps")
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten()
+    {
+        let prefix = format!("cmps.{key}.");
+        for attribute in unabbreviated_attributes(component) {
+            attributes.push(format!("{prefix}{attribute}"));
+        }
+    }
+    attributes
+}
+
+fn collect_unabbreviated(
+    payload: &Value,
+    abbreviations: &[(&str, &str)],
+    prefix: &str,
+    attributes: &mut Vec<String>,
+) {
+    let Some(payload) = payload.as_object() else {
+        return;
+    };
+    for key in payload.keys() {
+        if abbreviations
+            .iter()
+            .any(|(abbreviation, name)| name == key && abbreviation != key)
+        {
+            attributes.push(format!("{prefix}{key}"));
+        }
+    }
+}
+
+/// Asserts every attribute of the payload having an abbreviation uses it.
+pub fn assert_abbreviated<P: DiscoveryPayload + ?Sized>(payload: &P) {
+    let payload = payload
+        .discovery_payload()
+        .expect("payload should serialize");
+    let attributes = unabbreviated_attributes(&payload);
+    assert!(
+        attributes.is_empty(),
+        "attributes should be abbreviated: {attributes:?}"
+    );
+}
+
+/// Asserts the configuration is unchanged once serialized and deserialized.
+pub fn assert_round_trip<T: Serialize + DeserializeOwned + PartialEq + Debug>(configuration: &T) {
+    let json = serde_json::to_value(configuration).expect("configuration should serialize");
+    let parsed: T = serde_json::from_value(json).expect("configuration should deserialize");
+    assert_eq!(&parsed, configuration);
+}
+
+#[cfg(all(test, feature = "entity-event", feature = "entity-sensor"))]
+mod tests {
+    use rumqttc::v5::mqttbytes::v5::Publish;
+    use serde_json::json;
+
+    use super::*;
+
+    fn publish(topic: &str, payload: &str) -> Event {
+        Event::Incoming(Packet::Publish(Publish::new(
+            topic,
+            QoS::AtLeastOnce,
+            payload.to_string(),
+            None,
+        )))
+    }
+
+    #[test]
+    fn can_check_payload_snapshots() {
+        use crate::mqtt::{common::Device, sensor::Sensor};
+
+        let sensor = Sensor::new("home/temperature")
+            .unique_id("temperature")
+            .device(Device::default().add_identifier("barometer"));
+        let golden_file = std::env::temp_dir()
+            .join(format!("ha-mqtt-discovery-{}", std::process::id()))
+            .join("temperature.json");
+
+        assert_round_trip(&sensor);
+        assert_abbreviated(&Entity::from(sensor.clone()));
+        let actual = canonical_json(&Entity::from(sensor.clone())).unwrap();
+        assert!(std::panic::catch_unwind(|| check_snapshot(&actual, &golden_file, false)).is_err());
+        assert!(!golden_file.exists());
+        check_snapshot(&actual, &golden_file, true);
+        check_snapshot(&actual, &golden_file, false);
+        let renamed = canonical_json(&Entity::from(sensor.name("Temperature"))).unwrap();
+        assert!(
+            std::panic::catch_unwind(|| check_snapshot(&renamed, &golden_file, false)).is_err()
+        );
+        assert_eq!(
+            unabbreviated_attributes(
+                &json!({"uniq_id": "a", "state_topic": "b", "dev": {"identifiers": ["c"]}})
+            ),
+            vec!["state_topic", "dev.identifiers"]
+        );
+        std::fs::remove_dir_all(golden_file.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn can_record_entities_and_states() {
+        let mut home_assistant =
+            FakeHomeAssistant::new(MqttOptions::new("test", "localhost", 1883), "homeassistant");
+
+        home_assistant
+            .handle_event(&publish(
+                "homeassistant/switch/heater/config",
+                r#"{"uniq_id": "heater", "~": "home/heater", "cmd_t": "~/set"}"#,
+            ))
+            .unwrap();
+        home_assistant
+            .handle_event(&publish("home/heater/state", "ON"))
+            .unwrap();
+
+        assert_eq!(
+            home_assistant.entity("heater"),
+            Some(&json!({"uniq_id": "heater", "~": "home/heater", "cmd_t": "~/set"}))
+        );
+        assert_eq!(home_assistant.state("home/heater/state"), Some("ON"));
+        assert_eq!(
+            home_assistant.state("homeassistant/switch/heater/config"),
+            None
+        );
+    }
+}
@@ -0,0 +1,72 @@
+use anyhow::{anyhow, Result};
+use regex::Regex;
+
+use crate::mqtt::text::Text;
+
+/// The maximum size of a text supported by Home Assistant.
+pub const MAX_TEXT_LENGTH: i32 = 255;
+
+impl Text {
+    /// Checks that a value has a size between `min` and `max` characters and starts with a match of `pattern`,
+    /// like Home Assistant does.
+    pub fn check_value(&self, value: &str) -> Result<()> {
+        let length = value.chars().count() as i64;
+        let min = self.min.unwrap_or(0);
+        let max = self.max.unwrap_or(MAX_TEXT_LENGTH);
+        if !(i64::from(min)..=i64::from(max)).contains(&length) {
+            return Err(anyhow!(
+                "text '{value}' should have between {min} and {max} characters"
+            ));
+        }
+        if let Some(pattern) = &self.pattern {
+            let regex = Regex::new(&format!("^(?:{pattern})"))?;
+            if !regex.is_match(value) {
+                return Err(anyhow!(
+                    "text '{value}' doesn't match the pattern {pattern}"
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "client"))]
+mod tests {
+    use crate::{testing::MockClient, validation::ValidationError, Entity, HomeAssistantMqtt};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn can_publish_text_states() {
+        let client = MockClient::default();
+        let ha = HomeAssistantMqtt::new(client.clone(), "homeassistant");
+        let text = Text::new("home/code/set")
+            .state_topic("home/code")
+            .min(4)
+            .max(6)
+            .pattern("[0-9]+");
+
+        ha.publish_text_state(&text, "1234").await.unwrap();
+        assert!(ha.publish_text_state(&text, "123").await.is_err());
+        assert!(ha.publish_text_state(&text, "abcd").await.is_err());
+
+        assert_eq!(client.published().len(), 1);
+        assert_eq!(
+            client.last_published("home/code").unwrap().payload_str(),
+            "1234"
+        );
+        assert_eq!(
+            Entity::from(Text::new("home/code/set").max(300).pattern("[0-9"))
+                .validate()
+                .unwrap_err()
+                .len(),
+            2
+        );
+        assert!(matches!(
+            Entity::from(Text::new("home/code/set").min(10).max(5))
+                .validate()
+                .unwrap_err()[..],
+            [ValidationError::InvalidRange { .. }]
+        ));
+    }
+}
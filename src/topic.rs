@@ -0,0 +1,324 @@
+use std::{fmt::Display, ops::Deref};
+
+use serde_derive::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// The maximum length of an MQTT topic, in bytes.
+pub const MAX_TOPIC_LENGTH: usize = 65535;
+
+/// The discovery prefix Home Assistant subscribes to unless configured otherwise.
+pub const DEFAULT_DISCOVERY_PREFIX: &str = "homeassistant";
+
+/// The reason why a topic is rejected.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TopicError {
+    /// The topic is empty.
+    Empty,
+    /// The topic is longer than `MAX_TOPIC_LENGTH` bytes.
+    TooLong,
+    /// The topic starts with `$`, such topics are reserved for the broker.
+    Reserved,
+    /// The topic contains a `+` or `#` wildcard.
+    Wildcard,
+    /// The topic contains the null character.
+    NullCharacter,
+}
+
+impl Display for TopicError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TopicError::Empty => write!(f, "topic is empty"),
+            TopicError::TooLong => write!(f, "topic is longer than {MAX_TOPIC_LENGTH} bytes"),
+            TopicError::Reserved => write!(f, "topic starts with the reserved character '$'"),
+            TopicError::Wildcard => write!(f, "topic contains a wildcard character '+' or '#'"),
+            TopicError::NullCharacter => write!(f, "topic contains the null character"),
+        }
+    }
+}
+
+impl std::error::Error for TopicError {}
+
+/// A topic attribute of a discovery payload is rejected.
+#[derive(Clone, Debug, PartialEq)]
+pub struct InvalidTopic {
+    /// The path of the attribute in the discovery payload, eg. `stat_t` or `avty[0].t`.
+    pub field: String,
+    /// The rejected topic.
+    pub topic: String,
+    /// The reason why the topic is rejected.
+    pub error: TopicError,
+}
+
+impl Display for InvalidTopic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid topic '{}' for '{}': {}",
+            self.topic, self.field, self.error
+        )
+    }
+}
+
+impl std::error::Error for InvalidTopic {}
+
+/// An MQTT topic, possibly relative to the entity base topic with a leading or trailing `~`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(transparent)]
+pub struct Topic(String);
+
+impl Topic {
+    pub fn new<S: Into<String>>(topic: S) -> Self {
+        Self(topic.into())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Appends a level to the topic, `home/` and `home` both give `home/level`.
+    pub fn join(&self, level: &str) -> Topic {
+        Topic(format!(
+            "{}/{}",
+            self.0.trim_end_matches('/'),
+            level.trim_start_matches('/')
+        ))
+    }
+
+    /// Checks the topic can be used to publish messages.
+    pub fn validate(&self) -> Result<(), TopicError> {
+        validate_topic(&self.0)
+    }
+
+    /// Whether the topic uses the `~` base topic placeholder at its start or its end.
+    pub fn is_relative(&self) -> bool {
+        self.0.starts_with('~') || self.0.ends_with('~')
+    }
+
+    /// Replaces the `~` placeholder at the start or the end of the topic with the base topic.
+    pub fn resolve(&self, base_topic: &str) -> Topic {
+        if let Some(suffix) = self.0.strip_prefix('~') {
+            Topic(format!("{base_topic}{suffix}"))
+        } else if let Some(start) = self.0.strip_suffix('~') {
+            Topic(format!("{start}{base_topic}"))
+        } else {
+            self.clone()
+        }
+    }
+
+    /// Replaces the base topic at the start of the topic with the `~` placeholder, the opposite of `Topic::resolve`.
+    pub fn relative_to(&self, base_topic: &str) -> Topic {
+        match self.0.strip_prefix(base_topic) {
+            Some(suffix) if suffix.is_empty() || suffix.starts_with('/') => {
+                Topic(format!("~{suffix}"))
+            }
+            _ => self.clone(),
+        }
+    }
+}
+
+impl Display for Topic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl Deref for Topic {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for Topic {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl PartialEq<str> for Topic {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for Topic {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+impl From<&str> for Topic {
+    fn from(topic: &str) -> Self {
+        Topic(topic.to_string())
+    }
+}
+
+impl From<String> for Topic {
+    fn from(topic: String) -> Self {
+        Topic(topic)
+    }
+}
+
+impl From<&String> for Topic {
+    fn from(topic: &String) -> Self {
+        Topic(topic.clone())
+    }
+}
+
+impl From<Topic> for String {
+    fn from(topic: Topic) -> Self {
+        topic.0
+    }
+}
+
+/// Resolves the `~` placeholder of a topic with the topic prefix of its entity, the topic is kept as is without prefix.
+pub(crate) fn resolve(topic: &Topic, topic_prefix: Option<&str>) -> Topic {
+    match topic_prefix {
+        Some(prefix) => topic.resolve(prefix),
+        None => topic.clone(),
+    }
+}
+
+/// Checks a topic can be used to publish messages.
+pub fn validate_topic(topic: &str) -> Result<(), TopicError> {
+    if topic.is_empty() {
+        Err(TopicError::Empty)
+    } else if topic.len() > MAX_TOPIC_LENGTH {
+        Err(TopicError::TooLong)
+    } else if topic.starts_with('$') {
+        Err(TopicError::Reserved)
+    } else if topic.contains(['+', '#']) {
+        Err(TopicError::Wildcard)
+    } else if topic.contains('\0') {
+        Err(TopicError::NullCharacter)
+    } else {
+        Ok(())
+    }
+}
+
+/// The discovery prefix without its trailing slashes, rejected when it isn't a valid topic, eg. empty or with a
+/// wildcard.
+///
+/// ```
+/// # use ha_mqtt_discovery::topic::{normalize_discovery_prefix, TopicError};
+/// assert_eq!(normalize_discovery_prefix("homeassistant//"), Ok("homeassistant"));
+/// assert_eq!(normalize_discovery_prefix("home/+"), Err(TopicError::Wildcard));
+/// assert_eq!(normalize_discovery_prefix("/"), Err(TopicError::Empty));
+/// ```
+pub fn normalize_discovery_prefix(discovery_prefix: &str) -> Result<&str, TopicError> {
+    let discovery_prefix = discovery_prefix.trim_end_matches('/');
+    validate_topic(discovery_prefix)?;
+    Ok(discovery_prefix)
+}
+
+/// Checks every topic attribute of a serialized discovery payload, including the `~` base topic and availability topics.
+pub fn validate_payload_topics(payload: &Value) -> Result<(), InvalidTopic> {
+    validate_object_topics("", payload)
+}
+
+fn validate_object_topics(path: &str, value: &Value) -> Result<(), InvalidTopic> {
+    match value {
+        Value::Object(attributes) => {
+            for (key, value) in attributes {
+                let field = if path.is_empty() {
+                    key.to_string()
+                } else {
+                    format!("{path}.{key}")
+                };
+                match value {
+                    Value::String(topic) if is_topic_attribute(key) => validate_topic(topic)
+                        .map_err(|error| InvalidTopic {
+                            field,
+                            topic: topic.to_string(),
+                            error,
+                        })?,
+                    _ => validate_object_topics(&field, value)?,
+                }
+            }
+            Ok(())
+        }
+        Value::Array(items) => {
+            for (index, item) in items.iter().enumerate() {
+                validate_object_topics(&format!("{path}[{index}]"), item)?;
+            }
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
+fn is_topic_attribute(key: &str) -> bool {
+    key == "~" || key == "t" || key.ends_with("_t") || key.ends_with("_topic")
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn can_validate_topics() {
+        assert_eq!(validate_topic("home/sensor/state"), Ok(()));
+        assert_eq!(validate_topic(""), Err(TopicError::Empty));
+        assert_eq!(validate_topic("$SYS/state"), Err(TopicError::Reserved));
+        assert_eq!(validate_topic("home/+/state"), Err(TopicError::Wildcard));
+        assert_eq!(validate_topic("home/#"), Err(TopicError::Wildcard));
+        assert_eq!(validate_topic("home\0"), Err(TopicError::NullCharacter));
+        assert_eq!(
+            validate_topic(&"a".repeat(MAX_TOPIC_LENGTH + 1)),
+            Err(TopicError::TooLong)
+        );
+    }
+
+    #[test]
+    fn can_manipulate_topics() {
+        let base_topic = Topic::new("home/heater/");
+        assert_eq!(base_topic.join("state"), "home/heater/state");
+        assert_eq!(
+            Topic::new("home/heater").join("/state"),
+            "home/heater/state"
+        );
+        assert_eq!(
+            Topic::new("~/state").resolve("home/heater"),
+            "home/heater/state"
+        );
+        assert_eq!(Topic::new("state/~").resolve("home"), "state/home");
+        assert_eq!(
+            Topic::new("home/heater/state").relative_to("home/heater"),
+            "~/state"
+        );
+        assert_eq!(
+            Topic::new("home/heaters/state").relative_to("home/heater"),
+            "home/heaters/state"
+        );
+        assert!(Topic::new("~/state").is_relative());
+        assert_eq!(Topic::new("home/+").validate(), Err(TopicError::Wildcard));
+    }
+
+    #[test]
+    fn can_validate_payload_topics() {
+        assert_eq!(
+            validate_payload_topics(&json!({
+                "~": "home/sensor",
+                "stat_t": "~/state",
+                "avty": [{"t": "~/availability"}],
+                "val_tpl": "{{ value_json.temperature }}"
+            })),
+            Ok(())
+        );
+        assert_eq!(
+            validate_payload_topics(&json!({
+                "stat_t": "~/state",
+                "avty": [{"t": "~/availability"}, {"t": "home/#"}]
+            })),
+            Err(InvalidTopic {
+                field: "avty[1].t".to_string(),
+                topic: "home/#".to_string(),
+                error: TopicError::Wildcard,
+            })
+        );
+    }
+}
@@ -0,0 +1,324 @@
+use std::{collections::HashSet, fmt::Display};
+
+#[cfg(feature = "client")]
+use anyhow::anyhow;
+use anyhow::Result;
+#[cfg(feature = "client")]
+use rumqttc::v5::mqttbytes::QoS;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+#[cfg(feature = "client")]
+use crate::{
+    client::{MqttPublisher, PublishOptions},
+    error::PublishError,
+    HomeAssistantMqtt,
+};
+use crate::{
+    mqtt::{common::Device, device_trigger::DeviceTrigger},
+    topic::Topic,
+    validation::ValidationError,
+    Entity,
+};
+
+macro_rules! trigger_enum {
+    ($(#[$meta:meta])* $name:ident { $($variant:ident => $value:literal),* $(,)? }) => {
+        $(#[$meta])*
+        #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+        pub enum $name {
+            $($variant,)*
+            /// A value unsupported by the frontend, rendered as is.
+            Custom(String),
+        }
+
+        impl $name {
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $($name::$variant => $value,)*
+                    $name::Custom(value) => value,
+                }
+            }
+        }
+
+        impl From<&str> for $name {
+            fn from(value: &str) -> Self {
+                match value {
+                    $($value => $name::$variant,)*
+                    value => $name::Custom(value.to_string()),
+                }
+            }
+        }
+
+        impl Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                self.as_str().fmt(f)
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(self.as_str())
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                Ok(String::deserialize(deserializer)?.as_str().into())
+            }
+        }
+    };
+}
+
+trigger_enum! {
+    /// The type of a device trigger, the frontend renders the supported ones.
+    TriggerType {
+        ButtonShortPress => "button_short_press",
+        ButtonShortRelease => "button_short_release",
+        ButtonLongPress => "button_long_press",
+        ButtonLongRelease => "button_long_release",
+        ButtonDoublePress => "button_double_press",
+        ButtonTriplePress => "button_triple_press",
+        ButtonQuadruplePress => "button_quadruple_press",
+        ButtonQuintuplePress => "button_quintuple_press",
+    }
+}
+
+trigger_enum! {
+    /// The subtype of a device trigger, the frontend renders the supported ones.
+    TriggerSubtype {
+        TurnOn => "turn_on",
+        TurnOff => "turn_off",
+        Button1 => "button_1",
+        Button2 => "button_2",
+        Button3 => "button_3",
+        Button4 => "button_4",
+        Button5 => "button_5",
+        Button6 => "button_6",
+    }
+}
+
+/// The only automation type supported by device triggers.
+pub const AUTOMATION_TYPE_TRIGGER: &str = "trigger";
+
+impl DeviceTrigger {
+    /// A device trigger fired by the messages received on the topic, with the `trigger` automation type.
+    pub fn trigger<T: Into<Topic>>(
+        topic: T,
+        trigger_type: TriggerType,
+        trigger_subtype: TriggerSubtype,
+    ) -> Self {
+        DeviceTrigger::new(
+            AUTOMATION_TYPE_TRIGGER,
+            topic,
+            trigger_type.as_str(),
+            trigger_subtype.as_str(),
+        )
+    }
+
+    /// The type of the trigger.
+    pub fn trigger_type(&self) -> TriggerType {
+        self.r#type.as_str().into()
+    }
+
+    /// The subtype of the trigger.
+    pub fn trigger_subtype(&self) -> TriggerSubtype {
+        self.subtype.as_str().into()
+    }
+
+    /// The `<object_id>` of the discovery topic, built from the device identifier, the subtype and the type since
+    /// device triggers have no `unique_id`.
+    pub fn discovery_object_id(&self) -> String {
+        let device_id = self.device.identifiers.first().map(String::as_str).or(self
+            .device
+            .connections
+            .first()
+            .map(|c| c.identifier.as_str()));
+        let object_id = match device_id {
+            Some(device_id) => format!("{device_id}_{}_{}", self.subtype, self.r#type),
+            None => format!("{}_{}", self.subtype, self.r#type),
+        };
+        object_id
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect()
+    }
+}
+
+/// The triggers of a device sharing the same topic, each one fired by its own payload `<subtype>_<type>`.
+#[derive(Clone, Debug)]
+pub struct TriggerGroup {
+    topic: Topic,
+    device: Device,
+    triggers: Vec<DeviceTrigger>,
+}
+
+impl TriggerGroup {
+    pub fn new<T: Into<Topic>>(topic: T, device: Device) -> Self {
+        Self {
+            topic: topic.into(),
+            device,
+            triggers: vec![],
+        }
+    }
+
+    /// Registers a trigger, the combination of type and subtype must be unique within the device.
+    pub fn trigger(mut self, trigger_type: TriggerType, trigger_subtype: TriggerSubtype) -> Self {
+        let payload = Self::payload(&trigger_type, &trigger_subtype);
+        let trigger = DeviceTrigger::trigger(self.topic.clone(), trigger_type, trigger_subtype)
+            .device(self.device.clone())
+            .payload(payload);
+        self.triggers.push(trigger);
+        self
+    }
+
+    /// The registered triggers.
+    pub fn triggers(&self) -> &[DeviceTrigger] {
+        &self.triggers
+    }
+
+    /// Publishes the discovery configuration of every registered trigger.
+    #[cfg(feature = "client")]
+    pub async fn publish<C: MqttPublisher>(
+        &self,
+        ha: &HomeAssistantMqtt<C>,
+    ) -> Result<(), PublishError> {
+        for trigger in &self.triggers {
+            ha.publish_entity(trigger.clone().into()).await?;
+        }
+        Ok(())
+    }
+
+    /// Fires a registered trigger by publishing its payload on the topic of the group.
+    #[cfg(feature = "client")]
+    pub async fn fire<C: MqttPublisher>(
+        &self,
+        ha: &HomeAssistantMqtt<C>,
+        trigger_type: TriggerType,
+        trigger_subtype: TriggerSubtype,
+    ) -> Result<()> {
+        let trigger = self
+            .triggers
+            .iter()
+            .find(|t| t.trigger_type() == trigger_type && t.trigger_subtype() == trigger_subtype)
+            .ok_or(anyhow!(
+                "trigger {trigger_type} {trigger_subtype} is not registered"
+            ))?;
+        let payload = trigger.payload.clone().unwrap_or_default();
+        ha.client
+            .publish(
+                self.topic.to_string(),
+                QoS::AtLeastOnce,
+                false,
+                payload.into_bytes(),
+                ha.tagged(PublishOptions::default()),
+            )
+            .await?;
+        Ok(())
+    }
+
+    fn payload(trigger_type: &TriggerType, trigger_subtype: &TriggerSubtype) -> String {
+        format!("{trigger_subtype}_{trigger_type}")
+    }
+}
+
+/// Checks the `type` and `subtype` combination of the device triggers is unique for each device.
+pub fn validate_triggers<'a, I>(entities: I) -> Result<(), Vec<ValidationError>>
+where
+    I: IntoIterator<Item = &'a Entity>,
+{
+    let mut known_triggers = HashSet::new();
+    let errors: Vec<ValidationError> = entities
+        .into_iter()
+        .filter_map(|entity| match entity {
+            Entity::DeviceTrigger(trigger) => Some(trigger),
+            _ => None,
+        })
+        .filter(|trigger| {
+            !known_triggers.insert((
+                trigger.device.identifiers.clone(),
+                trigger.r#type.clone(),
+                trigger.subtype.clone(),
+            ))
+        })
+        .map(|trigger| ValidationError::DuplicateTrigger {
+            trigger_type: trigger.trigger_type(),
+            trigger_subtype: trigger.trigger_subtype(),
+        })
+        .collect();
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+#[cfg(all(test, feature = "client"))]
+mod tests {
+    use crate::testing::MockClient;
+
+    use super::*;
+
+    #[test]
+    fn can_build_device_triggers() {
+        let trigger = DeviceTrigger::trigger(
+            "zigbee2mqtt/remote/action",
+            TriggerType::ButtonShortPress,
+            TriggerSubtype::Custom("left button".to_string()),
+        )
+        .device(Device::default().add_identifier("0x90fd9ffffedf1266"));
+
+        assert_eq!(trigger.automation_type, "trigger");
+        assert_eq!(trigger.r#type, "button_short_press");
+        assert_eq!(
+            TriggerType::from("button_short_press"),
+            TriggerType::ButtonShortPress
+        );
+        assert_eq!(
+            trigger.discovery_object_id(),
+            "0x90fd9ffffedf1266_left_button_button_short_press"
+        );
+        assert_eq!(
+            validate_triggers(&[Entity::from(trigger.clone()), Entity::from(trigger)]),
+            Err(vec![ValidationError::DuplicateTrigger {
+                trigger_type: TriggerType::ButtonShortPress,
+                trigger_subtype: TriggerSubtype::Custom("left button".to_string())
+            }])
+        );
+    }
+
+    #[tokio::test]
+    async fn can_fire_grouped_triggers() {
+        let client = MockClient::default();
+        let ha = HomeAssistantMqtt::new(client.clone(), "homeassistant");
+        let group = TriggerGroup::new(
+            "home/remote/action",
+            Device::default().add_identifier("remote"),
+        )
+        .trigger(TriggerType::ButtonShortPress, TriggerSubtype::TurnOn)
+        .trigger(TriggerType::ButtonLongPress, TriggerSubtype::TurnOn);
+
+        group.publish(&ha).await.unwrap();
+        group
+            .fire(&ha, TriggerType::ButtonLongPress, TriggerSubtype::TurnOn)
+            .await
+            .unwrap();
+        assert!(group
+            .fire(&ha, TriggerType::ButtonShortPress, TriggerSubtype::TurnOff)
+            .await
+            .is_err());
+
+        let published = client.published();
+        assert_eq!(published.len(), 3);
+        assert_eq!(
+            published[0].topic,
+            "homeassistant/device_automation/remote_turn_on_button_short_press/config"
+        );
+        assert_eq!(published[2].topic, "home/remote/action");
+        assert_eq!(published[2].payload_str(), "turn_on_button_long_press");
+    }
+}
@@ -0,0 +1,140 @@
+use rust_decimal::Decimal;
+use serde_derive::{Deserialize, Serialize};
+
+use crate::decimal::{DecimalError, IntoDecimal, TryIntoDecimal};
+
+/// The JSON state of an update entity, published on its `state_topic`.
+///
+/// Home Assistant reads these attributes from the payload when no `value_template` is configured.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct UpdateState {
+    /// The software version currently installed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub installed_version: Option<String>,
+    /// The latest software version available.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latest_version: Option<String>,
+    /// The title of the software or firmware update.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// A summary of the release notes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub release_summary: Option<String>,
+    /// The URL of the full release notes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub release_url: Option<String>,
+    /// The picture shown for the entity.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub entity_picture: Option<String>,
+    /// Whether an update is being installed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub in_progress: Option<bool>,
+    /// The progress of the update installation, from 0 to 100.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub update_percentage: Option<Decimal>,
+}
+
+impl UpdateState {
+    /// The software version currently installed.
+    pub fn new<S: Into<String>>(installed_version: S) -> Self {
+        Self {
+            installed_version: Some(installed_version.into()),
+            ..Default::default()
+        }
+    }
+
+    /// The latest software version available.
+    pub fn latest_version<S: Into<String>>(mut self, latest_version: S) -> Self {
+        self.latest_version = Some(latest_version.into());
+        self
+    }
+
+    /// The title of the software or firmware update.
+    pub fn title<S: Into<String>>(mut self, title: S) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// A summary of the release notes.
+    pub fn release_summary<S: Into<String>>(mut self, release_summary: S) -> Self {
+        self.release_summary = Some(release_summary.into());
+        self
+    }
+
+    /// The URL of the full release notes.
+    pub fn release_url<S: Into<String>>(mut self, release_url: S) -> Self {
+        self.release_url = Some(release_url.into());
+        self
+    }
+
+    /// The picture shown for the entity.
+    pub fn entity_picture<S: Into<String>>(mut self, entity_picture: S) -> Self {
+        self.entity_picture = Some(entity_picture.into());
+        self
+    }
+
+    /// Whether an update is being installed.
+    pub fn in_progress(mut self, in_progress: bool) -> Self {
+        self.in_progress = Some(in_progress);
+        self
+    }
+
+    /// The progress of the update installation, from 0 to 100. Also marks the update as in progress.
+    pub fn progress<D: IntoDecimal>(mut self, update_percentage: D) -> Self {
+        self.in_progress = Some(true);
+        self.update_percentage = Some(update_percentage.into_decimal());
+        self
+    }
+
+    /// Same as [`Self::progress`] for the floats, fails when the value is NaN, infinite or out of the `Decimal` range.
+    pub fn try_progress<D: TryIntoDecimal>(
+        self,
+        update_percentage: D,
+    ) -> Result<Self, DecimalError> {
+        Ok(self.progress(update_percentage.try_into_decimal()?))
+    }
+}
+
+#[cfg(all(test, feature = "client"))]
+mod tests {
+    use serde_json::{json, Value};
+
+    use crate::{mqtt::update::Update, testing::MockClient, HomeAssistantMqtt};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn can_publish_update_states() {
+        let client = MockClient::default();
+        let ha = HomeAssistantMqtt::new(client.clone(), "homeassistant");
+        let update = Update::default()
+            .topic_prefix("home/firmware")
+            .state_topic("~/state");
+
+        ha.publish_update_state(
+            &update,
+            &UpdateState::new("1.0.0")
+                .latest_version("1.1.0")
+                .title("Firmware")
+                .progress(25),
+        )
+        .await
+        .unwrap();
+
+        let published = client.last_published("home/firmware/state").unwrap();
+        assert_eq!(
+            serde_json::from_slice::<Value>(&published.payload).unwrap(),
+            json!({
+                "installed_version": "1.0.0",
+                "latest_version": "1.1.0",
+                "title": "Firmware",
+                "in_progress": true,
+                "update_percentage": 25.0
+            })
+        );
+        assert!(ha
+            .publish_update_state(&Update::default(), &UpdateState::default())
+            .await
+            .is_err());
+    }
+}
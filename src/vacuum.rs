@@ -0,0 +1,87 @@
+use serde_derive::{Deserialize, Serialize};
+
+use crate::variant::variant_name;
+
+/// The states a vacuum can report.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VacuumActivity {
+    Cleaning,
+    Docked,
+    Paused,
+    Idle,
+    Returning,
+    Error,
+}
+
+impl VacuumActivity {
+    /// The activity published in the `state` of the JSON state, eg. `cleaning`.
+    pub fn as_str(&self) -> &'static str {
+        variant_name(self)
+    }
+}
+
+/// The JSON state of a vacuum, published on its `state_topic`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct VacuumState {
+    pub state: VacuumActivity,
+    /// The battery level, from 0 to 100.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub battery_level: Option<u8>,
+    /// One of the `fan_speed_list` speeds.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub fan_speed: Option<String>,
+}
+
+impl VacuumState {
+    pub fn new(state: VacuumActivity) -> Self {
+        Self {
+            state,
+            battery_level: None,
+            fan_speed: None,
+        }
+    }
+
+    /// The battery level, from 0 to 100.
+    pub fn battery_level(mut self, battery_level: u8) -> Self {
+        self.battery_level = Some(battery_level);
+        self
+    }
+
+    /// One of the `fan_speed_list` speeds.
+    pub fn fan_speed<S: Into<String>>(mut self, fan_speed: S) -> Self {
+        self.fan_speed = Some(fan_speed.into());
+        self
+    }
+}
+
+#[cfg(all(test, feature = "client"))]
+mod tests {
+    use serde_json::{json, Value};
+
+    use crate::{mqtt::vacuum::Vacuum, testing::MockClient, HomeAssistantMqtt};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn can_publish_vacuum_states() {
+        let client = MockClient::default();
+        let ha = HomeAssistantMqtt::new(client.clone(), "homeassistant");
+        let vacuum = Vacuum::default().state_topic("home/vacuum/state");
+
+        ha.publish_vacuum_state(
+            &vacuum,
+            &VacuumState::new(VacuumActivity::Cleaning)
+                .battery_level(61)
+                .fan_speed("max"),
+        )
+        .await
+        .unwrap();
+
+        let published = client.last_published("home/vacuum/state").unwrap();
+        assert_eq!(
+            serde_json::from_slice::<Value>(&published.payload).unwrap(),
+            json!({"state": "cleaning", "battery_level": 61, "fan_speed": "max"})
+        );
+    }
+}
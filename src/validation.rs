@@ -0,0 +1,379 @@
+use std::fmt::Display;
+
+#[cfg(any(feature = "entity-lock", feature = "entity-text"))]
+use regex::Regex;
+use rust_decimal::Decimal;
+
+#[cfg(any(feature = "entity-climate", feature = "entity-water_heater"))]
+use crate::temperature::TemperatureRange;
+#[cfg(feature = "entity-text")]
+use crate::text::MAX_TEXT_LENGTH;
+#[cfg(feature = "entity-device_trigger")]
+use crate::trigger::{TriggerSubtype, TriggerType};
+use crate::{
+    mqtt::{common::Device, device_classes::SensorDeviceClass},
+    topic::InvalidTopic,
+    Entity,
+};
+
+/// A Home Assistant configuration rule violated by an entity.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ValidationError {
+    /// A topic attribute can't be used to publish messages.
+    InvalidTopic(InvalidTopic),
+    /// The device information only works when `unique_id` is set.
+    MissingUniqueId,
+    /// At least one of identifiers or connections must be present to identify the device.
+    MissingDeviceIdentifiers,
+    /// An attribute must be configured together with another one.
+    MissingAttribute {
+        attribute: &'static str,
+        required_by: &'static str,
+    },
+    /// Two attributes must not be configured together.
+    ConflictingAttributes {
+        attribute: &'static str,
+        conflicts_with: &'static str,
+    },
+    /// A state class is only valid for sensors with a numeric device class.
+    StateClassNotSupported { device_class: SensorDeviceClass },
+    /// The minimum value of a number must be lower than its maximum value.
+    InvalidRange { min: Decimal, max: Decimal },
+    /// The step of a number must be positive.
+    InvalidStep { step: Decimal },
+    /// The `expire_after` delay must be positive.
+    InvalidExpireAfter,
+    /// The minimum and maximum sizes of a text must be between 0 and 255.
+    #[cfg(feature = "entity-text")]
+    InvalidTextLength {
+        attribute: &'static str,
+        length: i32,
+    },
+    /// A temperature must be plausible in the `temperature_unit` and the initial temperature must be between the
+    /// minimum and maximum ones.
+    #[cfg(any(feature = "entity-climate", feature = "entity-water_heater"))]
+    TemperatureOutOfRange {
+        attribute: &'static str,
+        value: Decimal,
+        lowest: Decimal,
+        highest: Decimal,
+    },
+    /// The `pattern` of a text and the `code_format` of a lock must be valid regular expressions.
+    InvalidPattern {
+        attribute: &'static str,
+        pattern: String,
+        error: String,
+    },
+    /// Two components of a device share the same `unique_id` or `object_id`.
+    DuplicateComponent {
+        attribute: &'static str,
+        value: String,
+    },
+    /// A rule violated by the component published under the key of a device.
+    Component {
+        key: String,
+        error: Box<ValidationError>,
+    },
+    /// The `type` and `subtype` combination of a device trigger must be unique for a device.
+    #[cfg(feature = "entity-device_trigger")]
+    DuplicateTrigger {
+        trigger_type: TriggerType,
+        trigger_subtype: TriggerSubtype,
+    },
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValidationError::InvalidTopic(invalid_topic) => invalid_topic.fmt(f),
+            ValidationError::MissingUniqueId => {
+                write!(f, "'device' only works when 'unique_id' is set")
+            }
+            ValidationError::MissingDeviceIdentifiers => write!(
+                f,
+                "at least one of 'identifiers' or 'connections' must be present to identify the device"
+            ),
+            ValidationError::MissingAttribute {
+                attribute,
+                required_by,
+            } => write!(f, "'{attribute}' is required by '{required_by}'"),
+            ValidationError::ConflictingAttributes {
+                attribute,
+                conflicts_with,
+            } => write!(
+                f,
+                "'{attribute}' must not be used together with '{conflicts_with}'"
+            ),
+            ValidationError::StateClassNotSupported { device_class } => write!(
+                f,
+                "'state_class' is not supported by the non numeric device class {device_class:?}"
+            ),
+            ValidationError::InvalidRange { min, max } => {
+                write!(f, "'min' {min} must be lower than 'max' {max}")
+            }
+            ValidationError::InvalidStep { step } => {
+                write!(f, "'step' {step} must be greater than 0")
+            }
+            ValidationError::InvalidExpireAfter => {
+                write!(f, "'expire_after' must be greater than 0")
+            }
+            #[cfg(feature = "entity-text")]
+            ValidationError::InvalidTextLength { attribute, length } => write!(
+                f,
+                "'{attribute}' {length} must be between 0 and {MAX_TEXT_LENGTH}"
+            ),
+            #[cfg(any(feature = "entity-climate", feature = "entity-water_heater"))]
+            ValidationError::TemperatureOutOfRange {
+                attribute,
+                value,
+                lowest,
+                highest,
+            } => write!(
+                f,
+                "'{attribute}' {value} must be between {lowest} and {highest}"
+            ),
+            ValidationError::InvalidPattern {
+                attribute,
+                pattern,
+                error,
+            } => write!(
+                f,
+                "'{attribute}' {pattern} is not a valid regular expression: {error}"
+            ),
+            ValidationError::DuplicateComponent { attribute, value } => write!(
+                f,
+                "device components should have distinct '{attribute}', '{value}' is used more than once"
+            ),
+            ValidationError::Component { key, error } => write!(f, "component '{key}': {error}"),
+            #[cfg(feature = "entity-device_trigger")]
+            ValidationError::DuplicateTrigger {
+                trigger_type,
+                trigger_subtype,
+            } => write!(
+                f,
+                "device trigger '{trigger_subtype} {trigger_type}' is defined more than once for the device"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+impl Entity {
+    /// Checks the entity configuration against the Home Assistant rules, returns every violated rule.
+    pub fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        let mut errors = vec![];
+        if let Err(invalid_topic) = self.validate_topics() {
+            errors.push(ValidationError::InvalidTopic(invalid_topic));
+        }
+        if *self.device() != Device::default() {
+            if self.unique_id().is_none() && !matches!(self.platform(), "device_automation" | "tag")
+            {
+                errors.push(ValidationError::MissingUniqueId);
+            }
+            if let Err(error) = self.device().validate() {
+                errors.push(error);
+            }
+        }
+        if self.availability().expire_after == Some(0) {
+            errors.push(ValidationError::InvalidExpireAfter);
+        }
+        match self {
+            #[cfg(feature = "entity-image")]
+            Entity::Image(image) => {
+                match (image.image_topic.is_none(), image.url_topic.is_none()) {
+                    (true, true) => errors.push(ValidationError::MissingAttribute {
+                        attribute: "image_topic",
+                        required_by: "image",
+                    }),
+                    (false, false) => errors.push(ValidationError::ConflictingAttributes {
+                        attribute: "image_topic",
+                        conflicts_with: "url_topic",
+                    }),
+                    _ => {}
+                }
+            }
+            #[cfg(feature = "entity-humidifier")]
+            Entity::Humidifier(humidifier) => {
+                match (&humidifier.modes, &humidifier.mode_command_topic) {
+                    (Some(_), None) => errors.push(ValidationError::MissingAttribute {
+                        attribute: "mode_command_topic",
+                        required_by: "modes",
+                    }),
+                    (None, Some(_)) => errors.push(ValidationError::MissingAttribute {
+                        attribute: "modes",
+                        required_by: "mode_command_topic",
+                    }),
+                    _ => {}
+                }
+            }
+            #[cfg(feature = "entity-sensor")]
+            Entity::Sensor(sensor) => {
+                if let (Some(_), Some(device_class)) = (&sensor.state_class, &sensor.device_class) {
+                    if matches!(
+                        device_class,
+                        SensorDeviceClass::Date
+                            | SensorDeviceClass::Enum
+                            | SensorDeviceClass::Timestamp
+                    ) {
+                        errors.push(ValidationError::StateClassNotSupported {
+                            device_class: *device_class,
+                        });
+                    }
+                }
+            }
+            #[cfg(feature = "entity-number")]
+            Entity::Number(number) => {
+                // the Home Assistant defaults are a 1 to 100 range with a step of 1
+                let min = number.min.unwrap_or(Decimal::ONE);
+                let max = number.max.unwrap_or(Decimal::ONE_HUNDRED);
+                if min >= max {
+                    errors.push(ValidationError::InvalidRange { min, max });
+                }
+                if let Some(step) = number.step.filter(|step| *step <= Decimal::ZERO) {
+                    errors.push(ValidationError::InvalidStep { step });
+                }
+            }
+            #[cfg(feature = "entity-climate")]
+            Entity::Climate(climate) => TemperatureRange::validate(
+                TemperatureRange::climate,
+                climate.temperature_unit,
+                climate.min_temp,
+                climate.max_temp,
+                climate.initial,
+                &mut errors,
+            ),
+            #[cfg(feature = "entity-water_heater")]
+            Entity::WaterHeater(water_heater) => TemperatureRange::validate(
+                TemperatureRange::water_heater,
+                water_heater.temperature_unit,
+                water_heater.min_temp,
+                water_heater.max_temp,
+                water_heater.initial.map(Decimal::from),
+                &mut errors,
+            ),
+            #[cfg(feature = "entity-lock")]
+            Entity::Lock(lock) => {
+                if let Some(Err(error)) = lock.code_format.as_deref().map(Regex::new) {
+                    errors.push(ValidationError::InvalidPattern {
+                        attribute: "code_format",
+                        pattern: lock.code_format.clone().unwrap_or_default(),
+                        error: error.to_string(),
+                    });
+                }
+            }
+            #[cfg(feature = "entity-text")]
+            Entity::Text(text) => {
+                for (attribute, length) in [("min", text.min), ("max", text.max)] {
+                    if let Some(length) = length.filter(|l| !(0..=MAX_TEXT_LENGTH).contains(l)) {
+                        errors.push(ValidationError::InvalidTextLength { attribute, length });
+                    }
+                }
+                let min = text.min.unwrap_or(0);
+                let max = text.max.unwrap_or(MAX_TEXT_LENGTH);
+                if min > max {
+                    errors.push(ValidationError::InvalidRange {
+                        min: min.into(),
+                        max: max.into(),
+                    });
+                }
+                if let Some(Err(error)) = text.pattern.as_deref().map(Regex::new) {
+                    errors.push(ValidationError::InvalidPattern {
+                        attribute: "pattern",
+                        pattern: text.pattern.clone().unwrap_or_default(),
+                        error: error.to_string(),
+                    });
+                }
+            }
+            _ => {}
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+#[cfg(all(
+    test,
+    feature = "entity-humidifier",
+    feature = "entity-image",
+    feature = "entity-number",
+    feature = "entity-sensor"
+))]
+mod tests {
+    use crate::mqtt::{
+        common::SensorStateClass, humidifier::Humidifier, image::Image, number::Number,
+        sensor::Sensor,
+    };
+
+    use super::*;
+
+    #[test]
+    fn can_validate_entities() {
+        let sensor = Sensor::new("home/timestamp")
+            .unique_id("timestamp")
+            .device(Device::default().add_identifier("home"));
+        assert_eq!(Entity::from(sensor.clone()).validate(), Ok(()));
+        assert_eq!(
+            Entity::from(
+                sensor
+                    .device_class(SensorDeviceClass::Timestamp)
+                    .state_class(SensorStateClass::Measurement)
+            )
+            .validate(),
+            Err(vec![ValidationError::StateClassNotSupported {
+                device_class: SensorDeviceClass::Timestamp
+            }])
+        );
+        assert_eq!(
+            Entity::from(Sensor::new("home/temperature").device(Device::default().name("home")))
+                .validate(),
+            Err(vec![
+                ValidationError::MissingUniqueId,
+                ValidationError::MissingDeviceIdentifiers
+            ])
+        );
+        assert_eq!(
+            Entity::from(
+                Image::default()
+                    .image_topic("home/image")
+                    .url_topic("home/url")
+            )
+            .validate(),
+            Err(vec![ValidationError::ConflictingAttributes {
+                attribute: "image_topic",
+                conflicts_with: "url_topic"
+            }])
+        );
+        assert_eq!(
+            Entity::from(
+                Number::new("home/number/set")
+                    .min(Decimal::from(150))
+                    .step(Decimal::ZERO)
+            )
+            .validate(),
+            Err(vec![
+                ValidationError::InvalidRange {
+                    min: Decimal::from(150),
+                    max: Decimal::ONE_HUNDRED
+                },
+                ValidationError::InvalidStep {
+                    step: Decimal::ZERO
+                }
+            ])
+        );
+        assert_eq!(
+            Entity::from(
+                Humidifier::new("home/humidifier/set", "home/humidifier/humidity/set")
+                    .modes(vec!["eco"])
+            )
+            .validate(),
+            Err(vec![ValidationError::MissingAttribute {
+                attribute: "mode_command_topic",
+                required_by: "modes"
+            }])
+        );
+    }
+}
@@ -0,0 +1,132 @@
+use anyhow::{anyhow, Result};
+
+use crate::mqtt::valve::Valve;
+
+/// The state of a valve, published on its `state_topic`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ValveState {
+    Open,
+    Opening,
+    Closed,
+    Closing,
+    /// The position of a valve reporting its position, between `position_closed` and `position_open`.
+    Position(i32),
+}
+
+/// A command sent by Home Assistant to the `command_topic` of a valve.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ValveCommand {
+    Open,
+    Close,
+    Stop,
+    /// The position requested to a valve reporting its position.
+    Position(i32),
+}
+
+impl Valve {
+    /// The payload of the state, according to the `state_*` payloads of the valve.
+    ///
+    /// The valves reporting their position publish the `position_open` and `position_closed` positions instead of
+    /// the open and closed states, the other valves can't report a position.
+    pub fn state_payload(&self, state: &ValveState) -> Result<String> {
+        let reports_position = self.reports_position.unwrap_or(false);
+        let position_closed = self.position_closed.unwrap_or(0);
+        let position_open = self.position_open.unwrap_or(100);
+        let payload = match state {
+            ValveState::Opening => self.state_opening.as_deref().unwrap_or("opening").into(),
+            ValveState::Closing => self.state_closing.as_deref().unwrap_or("closing").into(),
+            ValveState::Open if reports_position => position_open.to_string(),
+            ValveState::Closed if reports_position => position_closed.to_string(),
+            ValveState::Open => self.state_open.as_deref().unwrap_or("open").into(),
+            ValveState::Closed => self.state_closed.as_deref().unwrap_or("closed").into(),
+            ValveState::Position(_) if !reports_position => {
+                return Err(anyhow!("valve doesn't report its position"));
+            }
+            ValveState::Position(position) => {
+                let range = position_closed.min(position_open)..=position_closed.max(position_open);
+                if !range.contains(position) {
+                    return Err(anyhow!(
+                        "valve position {position} is outside of {}..={}",
+                        range.start(),
+                        range.end()
+                    ));
+                }
+                position.to_string()
+            }
+        };
+        Ok(payload)
+    }
+
+    /// The command of a payload received on the `command_topic`, according to the `payload_*` payloads of the valve.
+    ///
+    /// The valves reporting their position receive the requested position instead of the open and close payloads.
+    /// The payloads rendered by a `command_template` aren't recognized.
+    pub fn command(&self, payload: &str) -> Option<ValveCommand> {
+        if payload == self.payload_stop.as_deref().unwrap_or("STOP") {
+            return Some(ValveCommand::Stop);
+        }
+        if self.reports_position.unwrap_or(false) {
+            return payload.trim().parse().ok().map(ValveCommand::Position);
+        }
+        if payload == self.payload_open.as_deref().unwrap_or("OPEN") {
+            Some(ValveCommand::Open)
+        } else if payload == self.payload_close.as_deref().unwrap_or("CLOSE") {
+            Some(ValveCommand::Close)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(all(test, feature = "client"))]
+mod tests {
+    use crate::{testing::MockClient, HomeAssistantMqtt};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn can_drive_valves() {
+        let client = MockClient::default();
+        let ha = HomeAssistantMqtt::new(client.clone(), "homeassistant");
+        let valve = Valve::default()
+            .state_topic("garden/valve/state")
+            .state_open("OPENED");
+        let positioned = Valve::default()
+            .state_topic("garden/valve/state")
+            .reports_position(true);
+
+        ha.publish_valve_state(&valve, &ValveState::Open)
+            .await
+            .unwrap();
+        assert_eq!(
+            client
+                .last_published("garden/valve/state")
+                .unwrap()
+                .payload_str(),
+            "OPENED"
+        );
+        ha.publish_valve_state(&positioned, &ValveState::Open)
+            .await
+            .unwrap();
+        assert_eq!(
+            client
+                .last_published("garden/valve/state")
+                .unwrap()
+                .payload_str(),
+            "100"
+        );
+
+        assert!(valve.state_payload(&ValveState::Position(10)).is_err());
+        assert!(positioned
+            .state_payload(&ValveState::Position(120))
+            .is_err());
+        assert_eq!(
+            positioned.state_payload(&ValveState::Closing).unwrap(),
+            "closing"
+        );
+        assert_eq!(valve.command("CLOSE"), Some(ValveCommand::Close));
+        assert_eq!(positioned.command("42"), Some(ValveCommand::Position(42)));
+        assert_eq!(positioned.command("STOP"), Some(ValveCommand::Stop));
+        assert_eq!(positioned.command("OPEN"), None);
+    }
+}
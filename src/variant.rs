@@ -0,0 +1,187 @@
+use std::fmt::{self, Display, Formatter};
+
+use serde::{
+    ser::{self, Impossible},
+    Serialize, Serializer,
+};
+
+/// The name a unit variant is serialized as, eg. `armed_home` for `AlarmState::ArmedHome`, so that the `as_str`
+/// methods and serde share the `rename_all` mapping.
+///
+/// Panics when the value isn't serialized as a unit variant.
+pub(crate) fn variant_name<T: Serialize>(value: &T) -> &'static str {
+    value
+        .serialize(VariantName)
+        .expect("value is serialized as a unit variant")
+}
+
+#[derive(Debug)]
+struct NotUnitVariant;
+
+impl Display for NotUnitVariant {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("not a unit variant")
+    }
+}
+
+impl std::error::Error for NotUnitVariant {}
+
+impl ser::Error for NotUnitVariant {
+    fn custom<T: Display>(_msg: T) -> Self {
+        NotUnitVariant
+    }
+}
+
+/// Captures the name of a unit variant, any other value is rejected.
+struct VariantName;
+
+macro_rules! reject {
+    ($($method:ident($($arg:ty),*);)*) => {
+        $(
+            fn $method(self, $(_: $arg),*) -> Result<Self::Ok, Self::Error> {
+                Err(NotUnitVariant)
+            }
+        )*
+    };
+}
+
+impl Serializer for VariantName {
+    type Ok = &'static str;
+    type Error = NotUnitVariant;
+    type SerializeSeq = Impossible<Self::Ok, Self::Error>;
+    type SerializeTuple = Impossible<Self::Ok, Self::Error>;
+    type SerializeTupleStruct = Impossible<Self::Ok, Self::Error>;
+    type SerializeTupleVariant = Impossible<Self::Ok, Self::Error>;
+    type SerializeMap = Impossible<Self::Ok, Self::Error>;
+    type SerializeStruct = Impossible<Self::Ok, Self::Error>;
+    type SerializeStructVariant = Impossible<Self::Ok, Self::Error>;
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Self::Ok, Self::Error> {
+        Ok(variant)
+    }
+
+    reject! {
+        serialize_bool(bool);
+        serialize_i8(i8);
+        serialize_i16(i16);
+        serialize_i32(i32);
+        serialize_i64(i64);
+        serialize_u8(u8);
+        serialize_u16(u16);
+        serialize_u32(u32);
+        serialize_u64(u64);
+        serialize_f32(f32);
+        serialize_f64(f64);
+        serialize_char(char);
+        serialize_str(&str);
+        serialize_bytes(&[u8]);
+        serialize_none();
+        serialize_unit();
+        serialize_unit_struct(&'static str);
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, _: &T) -> Result<Self::Ok, Self::Error> {
+        Err(NotUnitVariant)
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _: &'static str,
+        _: &T,
+    ) -> Result<Self::Ok, Self::Error> {
+        Err(NotUnitVariant)
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> Result<Self::Ok, Self::Error> {
+        Err(NotUnitVariant)
+    }
+
+    fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Err(NotUnitVariant)
+    }
+
+    fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        Err(NotUnitVariant)
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        Err(NotUnitVariant)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        Err(NotUnitVariant)
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Err(NotUnitVariant)
+    }
+
+    fn serialize_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        Err(NotUnitVariant)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        Err(NotUnitVariant)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_derive::Serialize;
+
+    use super::*;
+
+    #[derive(Serialize)]
+    #[serde(rename_all = "snake_case")]
+    enum Mode {
+        FanOnly,
+        #[allow(dead_code)]
+        Custom(String),
+    }
+
+    #[test]
+    fn can_name_unit_variants() {
+        assert_eq!(variant_name(&Mode::FanOnly), "fan_only");
+        assert_eq!(
+            serde_json::to_value(Mode::FanOnly).unwrap(),
+            variant_name(&Mode::FanOnly)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "unit variant")]
+    fn can_reject_other_values() {
+        variant_name(&Mode::Custom("eco".to_string()));
+    }
+}
@@ -0,0 +1,211 @@
+use anyhow::{anyhow, Result};
+use serde_json::Value;
+
+use crate::{mqtt::abbreviations, Entity};
+
+impl Entity {
+    /// Parses the long-form YAML configuration of an MQTT entity, as written in `configuration.yaml`.
+    ///
+    /// Either the entity attributes or a whole `mqtt:` block holding a single entity of the component are accepted,
+    /// like the examples of the Home Assistant documentation:
+    ///
+    /// ```yaml
+    /// mqtt:
+    ///   - switch:
+    ///       command_topic: "home/bedroom/switch1/set"
+    /// ```
+    ///
+    /// The legacy `availability_topic`, `payload_available`, `payload_not_available` and `availability_template`
+    /// attributes are converted to an availability check.
+    pub fn from_ha_yaml(component: &str, yaml: &str) -> Result<Entity> {
+        let document: Value = serde_yaml::from_str(yaml)?;
+        let attributes = match document.get("mqtt") {
+            Some(mqtt) => component_entry(component, mqtt)?,
+            None => document,
+        };
+        Entity::from_platform(component, attributes)
+    }
+
+    /// Renders the entity attributes as written in `configuration.yaml`, under a `- <component>:` item of the `mqtt:`
+    /// block.
+    ///
+    /// The attributes are written with their full names, the `~` placeholders are resolved and the discovery-only
+    /// `platform`, `origin` and `device` attributes are left out.
+    pub fn to_yaml_config(&self) -> Result<String> {
+        let mut attributes = self.get_attributes()?;
+        if let Value::Object(attributes) = &mut attributes {
+            for discovery_only in ["p", "o", "dev", "~"] {
+                attributes.remove(discovery_only);
+            }
+            if attributes
+                .get("avty")
+                .and_then(Value::as_array)
+                .is_some_and(Vec::is_empty)
+            {
+                attributes.remove("avty");
+                attributes.remove("avty_mode");
+            }
+        }
+        let attributes = expand(self, attributes);
+        Ok(serde_yaml::to_string(&attributes)?)
+    }
+}
+
+/// Replaces the abbreviated attribute names with their full names, recursively.
+fn expand(entity: &Entity, value: Value) -> Value {
+    match value {
+        Value::Object(attributes) => Value::Object(
+            attributes
+                .into_iter()
+                .map(|(key, value)| {
+                    let name = abbreviations::expand(&key).map_or(key, str::to_string);
+                    let value = match value {
+                        Value::String(topic) if name == "topic" || name.ends_with("_topic") => {
+                            Value::String(entity.resolve_topic(&topic))
+                        }
+                        value => expand(entity, value),
+                    };
+                    (name, value)
+                })
+                .collect(),
+        ),
+        Value::Array(items) => {
+            Value::Array(items.into_iter().map(|item| expand(entity, item)).collect())
+        }
+        value => value,
+    }
+}
+
+/// Finds the entity in a `mqtt:` block, written either as a list of `- <component>: {...}` items or as a
+/// `<component>:` list of entities.
+fn component_entry(component: &str, mqtt: &Value) -> Result<Value> {
+    let entries: Vec<&Value> = match mqtt {
+        Value::Array(items) => items
+            .iter()
+            .filter_map(|item| item.get(component))
+            .flat_map(|entry| match entry {
+                Value::Array(entries) => entries.iter().collect(),
+                entry => vec![entry],
+            })
+            .collect(),
+        Value::Object(components) => match components.get(component) {
+            Some(Value::Array(entries)) => entries.iter().collect(),
+            Some(entry) => vec![entry],
+            None => vec![],
+        },
+        _ => vec![],
+    };
+    match entries[..] {
+        [entry] => Ok(entry.clone()),
+        [] => Err(anyhow!("no '{component}' entity in the 'mqtt' block")),
+        _ => Err(anyhow!(
+            "more than one '{component}' entity in the 'mqtt' block"
+        )),
+    }
+}
+
+#[cfg(all(test, feature = "entity-sensor", feature = "entity-switch"))]
+mod tests {
+    use crate::mqtt::{
+        common::{Availability, AvailabilityCheck, Qos},
+        sensor::Sensor,
+        switch::Switch,
+    };
+
+    use super::*;
+
+    #[test]
+    fn can_parse_configuration_yaml_entities() {
+        let Entity::Switch(switch) = Entity::from_ha_yaml(
+            "switch",
+            r#"
+                # Example configuration.yaml entry
+                mqtt:
+                  - switch:
+                      unique_id: bedroom_switch
+                      name: "Bedroom Switch"
+                      state_topic: "home/bedroom/switch1"
+                      command_topic: "home/bedroom/switch1/set"
+                      availability:
+                        - topic: "home/bedroom/switch1/available"
+                      payload_on: "ON"
+                      payload_off: "OFF"
+                      qos: 0
+                      retain: true
+                "#,
+        )
+        .unwrap() else {
+            panic!("expected a switch");
+        };
+        assert_eq!(
+            switch,
+            Switch::new("home/bedroom/switch1/set")
+                .unique_id("bedroom_switch")
+                .name("Bedroom Switch")
+                .state_topic("home/bedroom/switch1")
+                .availability(Availability {
+                    availability: vec![AvailabilityCheck::topic("home/bedroom/switch1/available")],
+                    ..Default::default()
+                })
+                .payload_on("ON")
+                .payload_off("OFF")
+                .qos(Qos::AtMostOnce)
+                .retain(true)
+        );
+        let Entity::Sensor(sensor) = Entity::from_ha_yaml(
+            "sensor",
+            r#"
+                state_topic: "home/temperature"
+                availability_topic: "home/available"
+                payload_available: "1"
+                device:
+                  identifiers: ["home"]
+                "#,
+        )
+        .unwrap() else {
+            panic!("expected a sensor");
+        };
+        assert_eq!(
+            sensor,
+            Sensor::new("home/temperature")
+                .availability(Availability {
+                    availability: vec![
+                        AvailabilityCheck::topic("home/available").payload_available("1")
+                    ],
+                    ..Default::default()
+                })
+                .device(crate::mqtt::common::Device::default().add_identifier("home"))
+        );
+        assert!(Entity::from_ha_yaml("light", "command_topic: home/light/set").is_err());
+        assert!(
+            Entity::from_ha_yaml("sensor", "mqtt:\n  - switch:\n      command_topic: a").is_err()
+        );
+    }
+
+    #[test]
+    fn can_render_configuration_yaml_entities() {
+        let switch = Switch::new("~/set")
+            .topic_prefix("home/bedroom/switch1")
+            .unique_id("bedroom_switch")
+            .state_topic("~")
+            .availability(Availability::single("home/bedroom/switch1/available"))
+            .device(crate::mqtt::common::Device::default().add_identifier("home"));
+
+        let yaml = Entity::from(switch).to_yaml_config().unwrap();
+
+        assert_eq!(
+            serde_yaml::from_str::<Value>(&yaml).unwrap(),
+            serde_json::json!({
+                "availability": [{ "topic": "home/bedroom/switch1/available" }],
+                "availability_mode": "all",
+                "command_topic": "home/bedroom/switch1/set",
+                "state_topic": "home/bedroom/switch1",
+                "unique_id": "bedroom_switch",
+            })
+        );
+        let Entity::Switch(parsed) = Entity::from_ha_yaml("switch", &yaml).unwrap() else {
+            panic!("expected a switch");
+        };
+        assert_eq!(parsed.command_topic, "home/bedroom/switch1/set");
+    }
+}
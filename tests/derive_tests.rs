@@ -0,0 +1,129 @@
+use std::collections::HashMap;
+
+use assert_json_diff::assert_json_include;
+use ha_mqtt_discovery::HaDevice;
+use serde_json::json;
+
+#[derive(HaDevice)]
+#[ha(base_topic = "home", name = "Barometer", manufacturer = "Awesome corp")]
+struct Barometer {
+    #[ha(identifier)]
+    serial_number: String,
+    #[ha(
+        sensor,
+        device_class = "temperature",
+        unit = "°C",
+        state_class = "measurement"
+    )]
+    temperature: f32,
+    #[ha(binary_sensor, device_class = "door")]
+    door: bool,
+    #[ha(switch, name = "Heater", on_command = "heater_switched")]
+    heater: bool,
+    heater_commands: usize,
+}
+
+impl Barometer {
+    fn heater_switched(&mut self, _on: bool) {
+        self.heater_commands += 1;
+    }
+}
+
+fn barometer() -> Barometer {
+    Barometer {
+        serial_number: "barometer-09AF".to_string(),
+        temperature: 21.5,
+        door: true,
+        heater: false,
+        heater_commands: 0,
+    }
+}
+
+#[test]
+fn can_derive_device_entities() {
+    let entities = barometer().entities();
+
+    let payloads: Vec<_> = entities
+        .iter()
+        .map(|entity| (entity.platform(), serde_json::to_value(entity).unwrap()))
+        .collect();
+    assert_eq!(payloads.len(), 3);
+    assert_eq!(payloads[0].0, "sensor");
+    assert_json_include!(
+        actual: &payloads[0].1,
+        expected: json!({
+            "dev": {
+                "name": "Barometer",
+                "ids": ["barometer-09AF"],
+                "mf": "Awesome corp"
+            },
+            "uniq_id": "barometer-09AF_temperature",
+            "name": "Temperature",
+            "stat_t": "home/barometer-09AF/temperature",
+            "dev_cla": "temperature",
+            "stat_cla": "measurement",
+            "unit_of_meas": "°C"
+        })
+    );
+    assert_eq!(payloads[1].0, "binary_sensor");
+    assert_json_include!(
+        actual: &payloads[1].1,
+        expected: json!({
+            "uniq_id": "barometer-09AF_door",
+            "stat_t": "home/barometer-09AF/door",
+            "dev_cla": "door"
+        })
+    );
+    assert_eq!(payloads[2].0, "switch");
+    assert_json_include!(
+        actual: &payloads[2].1,
+        expected: json!({
+            "uniq_id": "barometer-09AF_heater",
+            "name": "Heater",
+            "stat_t": "home/barometer-09AF/heater",
+            "cmd_t": "home/barometer-09AF/heater/set"
+        })
+    );
+}
+
+#[test]
+fn can_derive_localized_device_entities() {
+    let names = HashMap::from([("temperature".to_string(), "Température".to_string())]);
+    let entities = barometer().localized_entities(&names);
+
+    let names: Vec<_> = entities.iter().map(|entity| entity.name()).collect();
+    assert_eq!(
+        names,
+        vec![Some("Température"), Some("Door"), Some("Heater")]
+    );
+}
+
+#[test]
+fn can_derive_device_states() {
+    assert_eq!(
+        barometer().states(),
+        vec![
+            (
+                "home/barometer-09AF/temperature".to_string(),
+                "21.5".to_string()
+            ),
+            ("home/barometer-09AF/door".to_string(), "ON".to_string()),
+            ("home/barometer-09AF/heater".to_string(), "OFF".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn can_handle_device_commands() {
+    let mut barometer = barometer();
+
+    assert!(barometer.handle_command("home/barometer-09AF/heater/set", "ON"));
+    assert!(barometer.heater);
+    assert_eq!(barometer.heater_commands, 1);
+    assert!(!barometer.handle_command("home/barometer-09AF/door/set", "ON"));
+}
+
+#[test]
+fn can_reject_unknown_units_at_compile_time() {
+    trybuild::TestCases::new().compile_fail("tests/ui/*.rs");
+}
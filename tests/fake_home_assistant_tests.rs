@@ -0,0 +1,56 @@
+use std::time::Duration;
+
+use ha_mqtt_discovery::{
+    command::CommandRouter, mqtt::switch::Switch, testing::FakeHomeAssistant, Entity,
+    HomeAssistantMqtt,
+};
+use rumqttc::v5::{mqttbytes::QoS, AsyncClient, MqttOptions};
+use testcontainers_modules::{mosquitto, testcontainers::runners::AsyncRunner};
+
+#[tokio::test]
+async fn can_switch_a_bridged_entity() {
+    let mosquitto_container = mosquitto::Mosquitto::default().start().await.unwrap();
+    let port = mosquitto_container.get_host_port_ipv4(1883).await.unwrap();
+    let mut home_assistant = FakeHomeAssistant::new(
+        MqttOptions::new("home-assistant", "127.0.0.1", port),
+        "homeassistant",
+    );
+
+    // a bridge echoing the switch commands as states
+    let (client, mut eventloop) =
+        AsyncClient::new(MqttOptions::new("bridge", "127.0.0.1", port), 10);
+    let heater = Entity::from(
+        Switch::new("~/set")
+            .topic_prefix("home/heater")
+            .state_topic("~/state")
+            .unique_id("heater"),
+    );
+    let mut router = CommandRouter::default();
+    let state_client = client.clone();
+    router
+        .on_command(&heater, move |command| {
+            let payload = command.payload.to_vec();
+            let client = state_client.clone();
+            tokio::spawn(async move {
+                client
+                    .publish("home/heater/state", QoS::AtLeastOnce, true, payload)
+                    .await
+            });
+        })
+        .unwrap();
+    HomeAssistantMqtt::new(client.clone(), "homeassistant")
+        .publish_entity(heater)
+        .await
+        .unwrap();
+    tokio::spawn(async move { router.run(&client, &mut eventloop).await });
+
+    home_assistant
+        .wait_for_entity("heater", Duration::from_secs(5))
+        .await
+        .unwrap();
+    home_assistant.send_command("heater", "ON").await.unwrap();
+    home_assistant
+        .wait_for_state("home/heater/state", "ON", Duration::from_secs(5))
+        .await
+        .unwrap();
+}
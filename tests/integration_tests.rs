@@ -2,7 +2,7 @@ use assert_json_diff::assert_json_eq;
 use ha_mqtt_discovery::{
     mqtt::{
         binary_sensor::BinarySensor,
-        common::{Availability, Device, DeviceConnection, Origin, SensorStateClass},
+        common::{Availability, Device, DeviceConnection, NumberMode, Origin, SensorStateClass},
         device_classes::{BinarySensorDeviceClass, NumberDeviceClass, SensorDeviceClass},
         number::Number,
         sensor::Sensor,
@@ -76,16 +76,13 @@ async fn do_with_mosquitto(callback: fn(AsyncClient) -> ()) -> (Publish, Value)
     });
     // wait for a text message
     while let Ok(message) = eventloop.poll().await {
-        match message {
-            Incoming(Packet::Publish(content)) => {
-                let payload_string =
-                    String::from_utf8(content.payload.to_vec()).expect("a valid UTF-8 string");
-                return (
-                    content,
-                    serde_json::from_str(&payload_string).expect("a valid json"),
-                );
-            }
-            _ => {}
+        if let Incoming(Packet::Publish(content)) = message {
+            let payload_string =
+                String::from_utf8(content.payload.to_vec()).expect("a valid UTF-8 string");
+            return (
+                content,
+                serde_json::from_str(&payload_string).expect("a valid json"),
+            );
         }
     }
     // or panic
@@ -99,7 +96,7 @@ async fn can_publish_a_binary_sensor_configuration() {
         tokio::spawn(async move {
             registry
                 .publish_entity(Entity::BinarySensor(
-                    BinarySensor::default()
+                    BinarySensor::new("~/state")
                         .topic_prefix("temperature_devices/barometer-09AF")
                         .origin(origin())
                         .device(device())
@@ -108,7 +105,6 @@ async fn can_publish_a_binary_sensor_configuration() {
                         .availability(
                             Availability::single_topic("~/availability").expire_after(120),
                         )
-                        .state_topic("~/state")
                         .value_template("{{ json_value.state }}")
                         .device_class(BinarySensorDeviceClass::Door)
                         .force_update(true)
@@ -185,7 +181,7 @@ async fn can_publish_a_number_configuration() {
         tokio::spawn(async move {
             registry
                 .publish_entity(Entity::Number(
-                    Number::default()
+                    Number::new("~/command")
                         .topic_prefix("temperature_devices/barometer-09AF")
                         .origin(origin())
                         .device(device())
@@ -196,7 +192,6 @@ async fn can_publish_a_number_configuration() {
                         )
                         .state_topic("~/state")
                         .value_template("{{ json_value.temperature }}")
-                        .command_topic("~/command".to_string())
                         .command_template("{{ json_value.command }}".to_string())
                         .optimistic(false)
                         .retain(true)
@@ -204,7 +199,7 @@ async fn can_publish_a_number_configuration() {
                         .name("Temperature drift")
                         .min(dec!(-10.0))
                         .max(dec!(10.0))
-                        .mode("slider")
+                        .mode(NumberMode::Slider)
                         .payload_reset("NaN")
                         .step(dec!(0.1))
                         .unit_of_measurement(Unit::Temperature(Celsius)),
@@ -283,7 +278,7 @@ async fn can_publish_a_sensor_configuration() {
         tokio::spawn(async move {
             registry
                 .publish_entity(Entity::Sensor(
-                    Sensor::default()
+                    Sensor::new("~/state")
                         .topic_prefix("temperature_devices/barometer-09AF")
                         .origin(origin())
                         .device(device())
@@ -292,7 +287,6 @@ async fn can_publish_a_sensor_configuration() {
                         .availability(
                             Availability::single_topic("~/availability").expire_after(120),
                         )
-                        .state_topic("~/state")
                         .value_template("{{ json_value.temperature }}")
                         .device_class(SensorDeviceClass::Temperature)
                         .force_update(true)
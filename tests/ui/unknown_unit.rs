@@ -0,0 +1,12 @@
+use ha_mqtt_discovery::HaDevice;
+
+#[derive(HaDevice)]
+#[ha(base_topic = "home", name = "Barometer")]
+struct Barometer {
+    #[ha(identifier)]
+    serial_number: String,
+    #[ha(sensor, device_class = "temperature", unit = "°X")]
+    temperature: f32,
+}
+
+fn main() {}
@@ -0,0 +1,188 @@
+//! The maintenance tasks of the repository, run with `cargo xtask <task>`.
+//!
+//! - `import-docs <home-assistant.io checkout>` copies the MQTT integration pages and the pages documenting device
+//!   classes into `generator/input`.
+//! - `codegen [--check]` regenerates the entities, device classes and abbreviations of `src/mqtt` from
+//!   `generator/input` with the `generator` project, then formats them. `--check` fails when the generated files are
+//!   outdated and leaves them untouched.
+
+use std::{
+    collections::BTreeMap,
+    env, fs,
+    path::{Path, PathBuf},
+    process::{Command, ExitCode},
+};
+
+type Result<T> = std::result::Result<T, String>;
+
+const USAGE: &str =
+    "usage: cargo xtask import-docs <home-assistant.io checkout> | codegen [--check]";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    let result = match args[..] {
+        ["import-docs", docs] => import_docs(Path::new(docs)),
+        ["codegen"] => codegen(false),
+        ["codegen", "--check"] => codegen(true),
+        _ => Err(USAGE.to_string()),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("{error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("xtask should be a member of the workspace")
+        .to_path_buf()
+}
+
+/// Replaces `generator/input` with the pages of a checkout of the Home Assistant documentation repository.
+fn import_docs(docs: &Path) -> Result<()> {
+    let integrations = docs.join("source/_integrations");
+    let pages = fs::read_dir(&integrations)
+        .map_err(|error| format!("can't read {}: {error}", integrations.display()))?;
+    let input = root().join("generator/input");
+    let device_classes = input.join("device_classes");
+    if input.exists() {
+        fs::remove_dir_all(&input).map_err(|error| format!("can't clear the input: {error}"))?;
+    }
+    fs::create_dir_all(&device_classes)
+        .map_err(|error| format!("can't create {}: {error}", device_classes.display()))?;
+
+    let (mut mqtt_pages, mut device_class_pages) = (0, 0);
+    for page in pages.flatten() {
+        let path = page.path();
+        let name = page.file_name().to_string_lossy().to_string();
+        if name.ends_with(".mqtt.markdown") {
+            copy(&path, &input.join(&name))?;
+            mqtt_pages += 1;
+        }
+        let content = fs::read_to_string(&path).unwrap_or_default();
+        if has_device_class_section(&content) {
+            copy(&path, &device_classes.join(&name))?;
+            device_class_pages += 1;
+        }
+    }
+    println!(
+        "imported {mqtt_pages} MQTT integration pages and {device_class_pages} device class pages"
+    );
+    Ok(())
+}
+
+/// Copies a file, writable even when copied from a read-only store.
+fn copy(from: &Path, to: &Path) -> Result<()> {
+    let copied = || -> std::io::Result<()> {
+        fs::copy(from, to)?;
+        let mut permissions = fs::metadata(to)?.permissions();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            permissions.set_mode(permissions.mode() | 0o200);
+        }
+        #[cfg(not(unix))]
+        #[allow(clippy::permissions_set_readonly_false)]
+        permissions.set_readonly(false);
+        fs::set_permissions(to, permissions)
+    };
+    copied().map_err(|error| format!("can't copy {}: {error}", from.display()))
+}
+
+/// Whether the page has a `Device class` heading of level 2 or more, whatever the case.
+fn has_device_class_section(page: &str) -> bool {
+    page.lines().any(|line| {
+        let heading = line.trim_start_matches('#');
+        line.len() - heading.len() >= 2 && heading.to_lowercase().starts_with(" device class")
+    })
+}
+
+/// Runs the generator then formats the generated files.
+fn codegen(check: bool) -> Result<()> {
+    let root = root();
+    let generated = root.join("src/mqtt");
+    let before = snapshot(&generated)?;
+
+    run(Command::new("bun")
+        .args(["run", "generator/src/index.ts"])
+        .env("DEVENV_ROOT", &root)
+        .current_dir(&root))?;
+    let files: Vec<PathBuf> = snapshot(&generated)?.into_keys().collect();
+    run(Command::new("rustfmt")
+        .args(["--edition", "2021"])
+        .args(&files)
+        .current_dir(&root))?;
+
+    let after = snapshot(&generated)?;
+    let outdated: Vec<String> = after
+        .iter()
+        .filter(|(path, content)| before.get(*path) != Some(content))
+        .map(|(path, _)| path.display().to_string())
+        .collect();
+    if !check {
+        println!("regenerated {} files", outdated.len());
+        return Ok(());
+    }
+    for (path, content) in &before {
+        fs::write(path, content)
+            .map_err(|error| format!("can't restore {}: {error}", path.display()))?;
+    }
+    for path in after.keys().filter(|path| !before.contains_key(*path)) {
+        fs::remove_file(path)
+            .map_err(|error| format!("can't remove {}: {error}", path.display()))?;
+    }
+    if outdated.is_empty() {
+        Ok(())
+    } else {
+        Err(format!(
+            "the generated files are outdated, run `cargo xtask codegen`:\n{}",
+            outdated.join("\n")
+        ))
+    }
+}
+
+fn snapshot(dir: &Path) -> Result<BTreeMap<PathBuf, Vec<u8>>> {
+    let entries =
+        fs::read_dir(dir).map_err(|error| format!("can't read {}: {error}", dir.display()))?;
+    let mut files = BTreeMap::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().is_some_and(|extension| extension == "rs") {
+            let content = fs::read(&path)
+                .map_err(|error| format!("can't read {}: {error}", path.display()))?;
+            files.insert(path, content);
+        }
+    }
+    Ok(files)
+}
+
+fn run(command: &mut Command) -> Result<()> {
+    let program = command.get_program().to_string_lossy().to_string();
+    let status = command
+        .status()
+        .map_err(|error| format!("can't run {program}: {error}"))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("{program} failed with {status}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_find_device_class_sections() {
+        assert!(has_device_class_section("intro\n\n### Device class\n\n..."));
+        assert!(has_device_class_section("## Device Class\n"));
+        assert!(has_device_class_section("#### Device classes\n"));
+        assert!(!has_device_class_section("The device class of the sensor."));
+        assert!(!has_device_class_section("# Device class\n"));
+    }
+}